@@ -0,0 +1,329 @@
+use crate::address::{from_hex_address, to_hex_address};
+use crate::errors::AccountError;
+use crate::grpc::{self, ProtoCoin};
+use crate::keys::ImportSource;
+use crate::sequence::AccountState;
+use bip32::{DerivationPath, XPrv};
+use bip39::{Language, Mnemonic};
+use k256::ecdsa::SigningKey;
+use prost::Message;
+use prost_types::Any;
+use sha3::{Digest, Keccak256};
+
+/// The HD path of Ethereum keys, which is also used by the `eth_secp256k1` keys on Evmos.
+const ETH_HD_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// The type URL of the public keys of `eth_secp256k1` accounts.
+pub const ETH_SECP256K1_PUB_KEY: &str = "/ethermint.crypto.v1.ethsecp256k1.PubKey";
+
+/// The type URL of the Ethereum accounts on Evmos, which embed the base account.
+const ETH_ACCOUNT: &str = "/ethermint.types.v1.EthAccount";
+
+/// The type URL of the base accounts of the Cosmos SDK.
+const BASE_ACCOUNT: &str = "/cosmos.auth.v1beta1.BaseAccount";
+
+/// Represents an `eth_secp256k1` account, whose key is held in memory instead of
+/// an Evmos keyring, so that it can be derived and sign without the Evmos CLI.
+pub struct Account {
+    key: SigningKey,
+    /// The Bech32 address of the account.
+    pub address: String,
+}
+
+impl Account {
+    /// Derives the account from the given secret in the given import format.
+    pub fn from_secret(
+        source: ImportSource,
+        secret: &str,
+        prefix: &str,
+    ) -> Result<Account, AccountError> {
+        match source {
+            ImportSource::Mnemonic => Account::from_mnemonic(secret, prefix),
+            ImportSource::Hex => Account::from_private_key(secret, prefix),
+        }
+    }
+
+    /// Derives the account from the given BIP-39 mnemonic using the Ethereum HD path.
+    pub fn from_mnemonic(mnemonic: &str, prefix: &str) -> Result<Account, AccountError> {
+        let seed = Mnemonic::parse_in(Language::English, mnemonic.trim())?.to_seed("");
+        let path: DerivationPath = ETH_HD_PATH.parse()?;
+        let key = XPrv::derive_from_path(seed, &path)?.private_key().clone();
+
+        Account::from_signing_key(key, prefix)
+    }
+
+    /// Derives the account from the given hex encoded private key, with or without `0x` prefix.
+    pub fn from_private_key(key: &str, prefix: &str) -> Result<Account, AccountError> {
+        let bytes = hex::decode(key.trim().trim_start_matches("0x"))?;
+        let key = SigningKey::from_slice(&bytes)?;
+
+        Account::from_signing_key(key, prefix)
+    }
+
+    /// Creates the account for the given key.
+    /// The address is derived like on Ethereum from the last 20 bytes of the Keccak-256 hash
+    /// of the uncompressed public key.
+    fn from_signing_key(key: SigningKey, prefix: &str) -> Result<Account, AccountError> {
+        let public_key = key.verifying_key().to_encoded_point(false);
+        let hash = Keccak256::digest(&public_key.as_bytes()[1..]);
+        let address = from_hex_address(&hex::encode(&hash[12..]), prefix)?;
+
+        Ok(Account { key, address })
+    }
+
+    /// Returns the EIP-55 checksummed hex address of the account.
+    pub fn hex_address(&self) -> Result<String, AccountError> {
+        Ok(to_hex_address(&self.address)?)
+    }
+
+    /// Returns the compressed public key of the account, as it is contained in the signer infos.
+    pub fn public_key(&self) -> Vec<u8> {
+        self.key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec()
+    }
+
+    /// Signs the given sign bytes like the `eth_secp256k1` keys on Evmos, which sign
+    /// the Keccak-256 hash and append the recovery ID to the signature.
+    pub fn sign(&self, sign_bytes: &[u8]) -> Result<Vec<u8>, AccountError> {
+        let digest = Keccak256::digest(sign_bytes);
+        let (signature, recovery_id) = self.key.sign_prehash_recoverable(&digest)?;
+
+        let mut bytes = signature.to_vec();
+        bytes.push(recovery_id.to_byte());
+        Ok(bytes)
+    }
+
+    /// Queries the account number, sequence and public key of the account through the given gRPC endpoint.
+    pub async fn query(&self, grpc_url: &str) -> Result<AccountState, AccountError> {
+        let response: QueryAccountResponse = grpc::call(
+            grpc_url,
+            "/cosmos.auth.v1beta1.Query/Account",
+            QueryAccountRequest {
+                address: self.address.clone(),
+            },
+        )
+        .await?;
+        let account = response
+            .account
+            .ok_or(AccountError::NotFound(self.address.clone()))?;
+
+        account_state_from_any(&account)
+    }
+
+    /// Queries the balance of the account in the given denomination through the given gRPC endpoint.
+    pub async fn balance(&self, denom: &str, grpc_url: &str) -> Result<u128, AccountError> {
+        let response: QueryBalanceResponse = grpc::call(
+            grpc_url,
+            "/cosmos.bank.v1beta1.Query/Balance",
+            QueryBalanceRequest {
+                address: self.address.clone(),
+                denom: denom.to_string(),
+            },
+        )
+        .await?;
+
+        // NOTE: accounts without funds in the denomination have no balance in the response
+        match response.balance {
+            Some(coin) => Ok(coin.amount.parse()?),
+            None => Ok(0),
+        }
+    }
+}
+
+/// Returns the state of the given account from the auth module.
+/// Ethereum accounts on Evmos embed the base account, while plain base accounts don't.
+fn account_state_from_any(account: &Any) -> Result<AccountState, AccountError> {
+    let base_account = match account.type_url.as_str() {
+        ETH_ACCOUNT => EthAccount::decode(account.value.as_slice())?
+            .base_account
+            .ok_or(AccountError::MissingBaseAccount)?,
+        BASE_ACCOUNT => BaseAccount::decode(account.value.as_slice())?,
+        type_url => return Err(AccountError::UnsupportedType(type_url.to_string())),
+    };
+    let pub_key = base_account
+        .pub_key
+        .map(|pub_key| PubKey::decode(pub_key.value.as_slice()))
+        .transpose()?;
+
+    Ok(AccountState {
+        account_number: base_account.account_number,
+        sequence: base_account.sequence,
+        pub_key: pub_key.map(|pub_key| pub_key.key),
+    })
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct PubKey {
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryAccountRequest {
+    #[prost(string, tag = "1")]
+    address: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryAccountResponse {
+    #[prost(message, optional, tag = "1")]
+    account: Option<Any>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct BaseAccount {
+    #[prost(string, tag = "1")]
+    address: String,
+    #[prost(message, optional, tag = "2")]
+    pub_key: Option<Any>,
+    #[prost(uint64, tag = "3")]
+    account_number: u64,
+    #[prost(uint64, tag = "4")]
+    sequence: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct EthAccount {
+    #[prost(message, optional, tag = "1")]
+    base_account: Option<BaseAccount>,
+    #[prost(string, tag = "2")]
+    code_hash: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryBalanceRequest {
+    #[prost(string, tag = "1")]
+    address: String,
+    #[prost(string, tag = "2")]
+    denom: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryBalanceResponse {
+    #[prost(message, optional, tag = "1")]
+    balance: Option<ProtoCoin>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+    /// The mnemonic of the BIP-39 test vectors, whose first Ethereum account is well known.
+    const TEST_MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    /// The first Ethereum account of the test mnemonic.
+    const TEST_HEX_ADDRESS: &str = "0x9858EfFD232B4033E47d90003D41EC34EcaEda94";
+
+    /// The private key of the first Ethereum account of the test mnemonic.
+    const TEST_PRIVATE_KEY: &str =
+        "0x1ab42cc412b618bdea3a599e3c9bae199ebf030895b039e9db1e30dafb12b727";
+
+    #[test]
+    fn test_from_mnemonic() {
+        let account =
+            Account::from_mnemonic(TEST_MNEMONIC, "evmos").expect("failed to derive account");
+        assert_eq!(account.hex_address().unwrap(), TEST_HEX_ADDRESS);
+        assert_eq!(
+            account.address,
+            from_hex_address(TEST_HEX_ADDRESS, "evmos").unwrap()
+        );
+        assert!(account.address.starts_with("evmos1"));
+    }
+
+    #[test]
+    fn test_from_mnemonic_invalid() {
+        let mnemonic = ["abandon"; 12].join(" ");
+        assert!(matches!(
+            Account::from_mnemonic(&mnemonic, "evmos"),
+            Err(AccountError::Mnemonic(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_private_key() {
+        let account =
+            Account::from_private_key(TEST_PRIVATE_KEY, "evmos").expect("failed to derive account");
+        assert_eq!(account.hex_address().unwrap(), TEST_HEX_ADDRESS);
+
+        assert!(Account::from_private_key("0xabcd", "evmos").is_err());
+        assert!(Account::from_private_key(&"zz".repeat(32), "evmos").is_err());
+    }
+
+    #[test]
+    fn test_sign() {
+        let account = Account::from_private_key(TEST_PRIVATE_KEY, "evmos").unwrap();
+        let signature = account.sign(b"sign bytes").expect("failed to sign");
+        assert_eq!(signature.len(), 65);
+
+        let recovered = VerifyingKey::recover_from_prehash(
+            &Keccak256::digest(b"sign bytes"),
+            &Signature::from_slice(&signature[..64]).unwrap(),
+            RecoveryId::from_byte(signature[64]).unwrap(),
+        )
+        .expect("failed to recover public key");
+        assert_eq!(
+            recovered.to_encoded_point(true).as_bytes(),
+            account.public_key().as_slice()
+        );
+    }
+
+    #[test]
+    fn test_account_state_from_any() {
+        let account = Account::from_private_key(TEST_PRIVATE_KEY, "evmos").unwrap();
+        let base_account = BaseAccount {
+            address: account.address.clone(),
+            pub_key: Some(Any {
+                type_url: ETH_SECP256K1_PUB_KEY.to_string(),
+                value: PubKey {
+                    key: account.public_key(),
+                }
+                .encode_to_vec(),
+            }),
+            account_number: 7,
+            sequence: 12,
+        };
+        let eth_account = Any {
+            type_url: ETH_ACCOUNT.to_string(),
+            value: EthAccount {
+                base_account: Some(base_account.clone()),
+                code_hash: "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+                    .to_string(),
+            }
+            .encode_to_vec(),
+        };
+        assert_eq!(
+            account_state_from_any(&eth_account).unwrap(),
+            AccountState {
+                account_number: 7,
+                sequence: 12,
+                pub_key: Some(account.public_key()),
+            }
+        );
+
+        let base_account = Any {
+            type_url: BASE_ACCOUNT.to_string(),
+            value: BaseAccount {
+                pub_key: None,
+                ..base_account
+            }
+            .encode_to_vec(),
+        };
+        let state = account_state_from_any(&base_account).unwrap();
+        assert_eq!(state.account_number, 7);
+        assert_eq!(state.pub_key, None);
+
+        let module_account = Any {
+            type_url: "/cosmos.auth.v1beta1.ModuleAccount".to_string(),
+            value: Vec::new(),
+        };
+        assert!(matches!(
+            account_state_from_any(&module_account),
+            Err(AccountError::UnsupportedType(_))
+        ));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::errors::AddressError;
+use bech32::{FromBase32, ToBase32, Variant};
+use sha3::{Digest, Keccak256};
+
+/// The length of account addresses in bytes.
+const ADDRESS_LENGTH: usize = 20;
+
+/// Checks that the given address is a valid Bech32 account address with the given prefix.
+pub fn validate_address(address: &str, prefix: &str) -> Result<(), AddressError> {
+    let (hrp, data, _) = bech32::decode(address)?;
+    if hrp != prefix {
+        return Err(AddressError::Prefix(prefix.to_string(), hrp));
+    }
+
+    let bytes = Vec::<u8>::from_base32(&data)?;
+    if bytes.len() != ADDRESS_LENGTH {
+        return Err(AddressError::Length(bytes.len()));
+    }
+
+    Ok(())
+}
+
+/// Converts the given Bech32 address to the hex address used on the EVM,
+/// e.g. `evmos1...` to `0x...`, which is checksummed according to EIP-55.
+pub fn to_hex_address(address: &str) -> Result<String, AddressError> {
+    let (_, data, _) = bech32::decode(address)?;
+    let bytes = Vec::<u8>::from_base32(&data)?;
+    if bytes.len() != ADDRESS_LENGTH {
+        return Err(AddressError::Length(bytes.len()));
+    }
+
+    Ok(to_checksum_address(&hex::encode(bytes)))
+}
+
+/// Converts the given hex address to the Bech32 address with the given prefix,
+/// e.g. `0x...` to `evmos1...`.
+pub fn from_hex_address(address: &str, prefix: &str) -> Result<String, AddressError> {
+    let bytes = hex::decode(address.trim_start_matches("0x"))?;
+    if bytes.len() != ADDRESS_LENGTH {
+        return Err(AddressError::Length(bytes.len()));
+    }
+
+    Ok(bech32::encode(prefix, bytes.to_base32(), Variant::Bech32)?)
+}
+
+/// Returns the EIP-55 checksummed representation of the given lowercase hex address without prefix.
+fn to_checksum_address(address: &str) -> String {
+    let hash = hex::encode(Keccak256::digest(address.as_bytes()));
+
+    let checksummed: String = address
+        .chars()
+        .zip(hash.chars())
+        .map(|(c, h)| match h.to_digit(16) {
+            Some(n) if n >= 8 => c.to_ascii_uppercase(),
+            _ => c,
+        })
+        .collect();
+
+    format!("0x{}", checksummed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_conversion() {
+        let address = from_hex_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", "evmos")
+            .expect("failed to convert hex address");
+        assert!(address.starts_with("evmos1"));
+        assert!(validate_address(&address, "evmos").is_ok());
+        assert!(matches!(
+            validate_address(&address, "cosmos"),
+            Err(AddressError::Prefix(_, _))
+        ));
+        assert!(validate_address("evmos1invalid", "evmos").is_err());
+
+        // NOTE: this is one of the test vectors of EIP-55
+        assert_eq!(
+            to_hex_address(&address).expect("failed to convert to hex address"),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use crate::errors::AnswersError;
+use crate::inputs::InputProvider;
+use clap::ValueEnum;
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The keys of the prompts, which can be answered in the answers file.
+pub const PROMPT_KEYS: [&str; 27] = [
+    "commonwealth_link",
+    "config",
+    "description",
+    "description_file",
+    "description_source",
+    "existing_artifacts",
+    "fee_granter",
+    "home",
+    "key",
+    "mainnet_previous_version",
+    "mainnet_target_version",
+    "manual_summary",
+    "message_files",
+    "metadata",
+    "network",
+    "plan_info",
+    "plan_info_text",
+    "plan_info_url",
+    "previous_version",
+    "proposer_address",
+    "summary_fallback",
+    "target_version",
+    "testnet_previous_version",
+    "testnet_target_version",
+    "title",
+    "upgrade_date",
+    "upgrade_hour",
+];
+
+/// Contains the answers to the prompts, which are keyed by the prompt keys.
+///
+/// Recurring flows (e.g. the same network, key and plan info for every release) can be run
+/// with an answers file, so that only the values, which change per release, are prompted.
+/// The answers are used as the input provider of the context, so that unanswered prompts
+/// are still shown in the terminal.
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(BTreeMap<String, Value>);
+
+impl Answers {
+    /// Loads the answers from the given YAML (or JSON) file.
+    /// Unknown keys are rejected, so that typos do not silently lead to prompts.
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        Answers::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Parses the answers from the given YAML contents.
+    fn parse(contents: &str) -> Result<Answers, AnswersError> {
+        let answers: BTreeMap<String, Value> = serde_yaml::from_str(contents)?;
+        if let Some(key) = answers.keys().find(|key| !is_prompt_key(key)) {
+            return Err(AnswersError::UnknownKey(key.clone()));
+        }
+
+        Ok(Answers(answers))
+    }
+}
+
+impl InputProvider for Answers {
+    /// Returns the answer of the given prompt as text, if it is answered.
+    /// Numbers and booleans are converted to text, e.g. for versions like `16`.
+    fn get_text(&self, key: &str) -> Result<Option<String>, AnswersError> {
+        debug_assert!(is_prompt_key(key), "unknown prompt key {}", key);
+
+        match self.0.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value.clone())),
+            Some(Value::Number(value)) => Ok(Some(value.to_string())),
+            Some(Value::Bool(value)) => Ok(Some(value.to_string())),
+            Some(_) => Err(AnswersError::InvalidValue(
+                key.to_string(),
+                "expected a single value".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the answer of the given prompt as a list of texts, if it is answered.
+    fn get_list(&self, key: &str) -> Result<Option<Vec<String>>, AnswersError> {
+        debug_assert!(is_prompt_key(key), "unknown prompt key {}", key);
+
+        match self.0.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => serde_yaml::from_value(value.clone())
+                .map(Some)
+                .map_err(|e| AnswersError::InvalidValue(key.to_string(), e.to_string())),
+        }
+    }
+}
+
+/// Returns whether the given key belongs to a prompt, which can be answered.
+fn is_prompt_key(key: &str) -> bool {
+    PROMPT_KEYS.contains(&key)
+}
+
+/// Returns the answer of the given prompt from the given input provider as text, if it is answered.
+pub fn get_text(inputs: &dyn InputProvider, key: &str) -> Result<Option<String>, AnswersError> {
+    debug_assert!(is_prompt_key(key), "unknown prompt key {}", key);
+
+    let answer = inputs.get_text(key)?;
+    if let Some(answer) = &answer {
+        println!("Using answer for {}: {}", key, answer);
+    }
+
+    Ok(answer)
+}
+
+/// Returns the parsed answer of the given prompt from the given input provider, if it is answered.
+pub fn get_parsed<T>(inputs: &dyn InputProvider, key: &str) -> Result<Option<T>, AnswersError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    get_text(inputs, key)?
+        .map(|answer| {
+            answer
+                .trim()
+                .parse::<T>()
+                .map_err(|e| AnswersError::InvalidValue(key.to_string(), e.to_string()))
+        })
+        .transpose()
+}
+
+/// Returns the chosen option of the given prompt from the given input provider, which is answered with the name of the option,
+/// e.g. `release-notes`, if it is answered.
+pub fn get_choice<T: ValueEnum>(
+    inputs: &dyn InputProvider,
+    key: &str,
+) -> Result<Option<T>, AnswersError> {
+    get_text(inputs, key)?
+        .map(|answer| parse_choice(key, &answer))
+        .transpose()
+}
+
+/// Returns the hour of the upgrade in UTC from the given input provider, if it is answered.
+pub fn get_upgrade_hour(inputs: &dyn InputProvider) -> Result<Option<u32>, AnswersError> {
+    match get_parsed::<u32>(inputs, "upgrade_hour")? {
+        Some(hour) if hour > 23 => Err(AnswersError::InvalidValue(
+            "upgrade_hour".to_string(),
+            format!("{} is not an hour of the day", hour),
+        )),
+        hour => Ok(hour),
+    }
+}
+
+/// Returns the answer of the given prompt from the given input provider as a list of texts, if it is answered.
+pub fn get_list(
+    inputs: &dyn InputProvider,
+    key: &str,
+) -> Result<Option<Vec<String>>, AnswersError> {
+    debug_assert!(is_prompt_key(key), "unknown prompt key {}", key);
+
+    let answer = inputs.get_list(key)?;
+    if let Some(answer) = &answer {
+        println!("Using answer for {}: {}", key, answer.join(", "));
+    }
+
+    Ok(answer)
+}
+
+/// Parses the given answer as the name of one of the options.
+fn parse_choice<T: ValueEnum>(key: &str, answer: &str) -> Result<T, AnswersError> {
+    T::from_str(answer.trim(), true).map_err(|_| {
+        let options: Vec<String> = T::value_variants()
+            .iter()
+            .filter_map(|variant| variant.to_possible_value())
+            .map(|value| value.get_name().to_string())
+            .collect();
+        AnswersError::InvalidValue(
+            key.to_string(),
+            format!("expected one of {}", options.join(", ")),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = Answers::parse(
+            "network: mainnet\nprevious_version: 16\nmessage_files:\n  - params.json\nfee_granter: ~\n",
+        )
+        .expect("failed to parse answers");
+
+        assert_eq!(
+            answers.get_text("network").unwrap(),
+            Some("mainnet".to_string())
+        );
+        assert_eq!(
+            answers.get_text("previous_version").unwrap(),
+            Some("16".to_string())
+        );
+        assert_eq!(
+            answers.get_list("message_files").unwrap(),
+            Some(vec!["params.json".to_string()])
+        );
+        assert_eq!(answers.get_text("fee_granter").unwrap(), None);
+        assert_eq!(answers.get_text("title").unwrap(), None);
+        assert!(answers.get_text("message_files").is_err());
+    }
+
+    #[test]
+    fn test_answers_as_input_provider() {
+        let answers = Answers::parse("network: testnet\nupgrade_hour: 25\n")
+            .expect("failed to parse answers");
+
+        assert_eq!(
+            get_choice::<Network>(&answers, "network").unwrap(),
+            Some(Network::Testnet)
+        );
+        assert_eq!(get_text(&answers, "title").unwrap(), None);
+        assert!(get_upgrade_hour(&answers).is_err());
+    }
+
+    #[test]
+    fn test_unknown_key() {
+        match Answers::parse("netwrok: mainnet\n") {
+            Err(AnswersError::UnknownKey(key)) => assert_eq!(key, "netwrok"),
+            res => panic!("expected unknown key, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_parse_choice() {
+        assert_eq!(
+            parse_choice::<Network>("network", "Local-Node").unwrap(),
+            Network::LocalNode
+        );
+        match parse_choice::<Network>("network", "devnet") {
+            Err(AnswersError::InvalidValue(_, message)) => {
+                assert_eq!(message, "expected one of local-node, testnet, mainnet")
+            }
+            res => panic!("expected invalid value, got {:?}", res),
+        }
+    }
+}
@@ -0,0 +1,445 @@
+use crate::errors::ApprovalError;
+use crate::manifest::{hash_file, Manifest, MANIFEST_FILE};
+use crate::utils::write_atomically;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// The name of the file next to the manifest, which contains the approvals of the reviewers.
+pub const APPROVALS_FILE: &str = "approvals.json";
+
+/// The namespace of the SSH signatures, which prevents reusing signatures made for other purposes.
+const SIGNATURE_NAMESPACE: &str = "proposer";
+
+/// Represents the approval of a generated file by a reviewer,
+/// who signed the SHA256 hash of the file with their SSH key.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Approval {
+    /// The name of the reviewer, which is used as the principal in the allowed signers file.
+    pub reviewer: String,
+    /// The approved file.
+    pub file: String,
+    /// The SHA256 hash of the approved file, which is the signed message.
+    pub sha256: String,
+    /// The public key of the reviewer.
+    pub public_key: String,
+    /// The armored SSH signature of the hash.
+    pub signature: String,
+    /// The time at which the file was approved.
+    pub approved_at: DateTime<Utc>,
+}
+
+/// The result of verifying an approval, which is the fingerprint of the signing key
+/// or the reason, why the approval is not valid.
+pub type Verification = Result<String, String>;
+
+/// Contains the approvals of all files in the working directory.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Approvals {
+    pub approvals: Vec<Approval>,
+}
+
+impl Approvals {
+    /// Loads the approvals from the given file.
+    /// If the file does not exist yet, no approvals are returned.
+    pub fn load(path: &Path) -> Result<Approvals, ApprovalError> {
+        if !path.exists() {
+            return Ok(Approvals::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the approvals to the given file.
+    pub fn save(&self, path: &Path) -> Result<(), ApprovalError> {
+        Ok(write_atomically(
+            path,
+            serde_json::to_string_pretty(self)?.as_bytes(),
+        )?)
+    }
+}
+
+/// Returns the current hash of the given file in the given directory after checking
+/// that it matches the hash recorded in the manifest, so that only generated files are approved.
+fn get_manifest_hash(dir: &Path, file: &str) -> Result<String, ApprovalError> {
+    let manifest = Manifest::load(&dir.join(MANIFEST_FILE))?;
+    let artifact = manifest
+        .artifacts
+        .get(file)
+        .ok_or_else(|| ApprovalError::NotInManifest(file.to_string()))?;
+
+    let sha256 = hash_file(&dir.join(file))?;
+    if sha256 != artifact.sha256 {
+        return Err(ApprovalError::Modified(file.to_string()));
+    }
+
+    Ok(sha256)
+}
+
+/// Returns the path of the public key for the given SSH key, which is expected next to the private key.
+fn get_public_key_path(key: &Path) -> PathBuf {
+    match key.extension().is_some_and(|extension| extension == "pub") {
+        true => key.to_path_buf(),
+        false => PathBuf::from(format!("{}.pub", key.display())),
+    }
+}
+
+/// Returns a temporary directory for the files of the given SSH signature operation.
+fn get_temp_dir(operation: &str) -> Result<PathBuf, ApprovalError> {
+    let dir =
+        std::env::temp_dir().join(format!("proposer-approval-{}-{}", operation, process::id()));
+    fs::create_dir_all(&dir)?;
+
+    Ok(dir)
+}
+
+/// Runs `ssh-keygen` with the given arguments and the optional stdin file and returns its output.
+/// If it does not succeed, an error containing its error output is returned.
+fn run_ssh_keygen(args: &[String], stdin: Option<&Path>) -> Result<String, ApprovalError> {
+    let mut command = process::Command::new("ssh-keygen");
+    command.args(args);
+    if let Some(stdin) = stdin {
+        command.stdin(fs::File::open(stdin)?);
+    }
+
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(ApprovalError::Signature(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Signs the given message with the given SSH key and returns the armored signature.
+fn sign(key: &Path, message: &str) -> Result<String, ApprovalError> {
+    let dir = get_temp_dir("sign")?;
+    let message_path = dir.join("message");
+    fs::write(&message_path, message)?;
+
+    let res = run_ssh_keygen(
+        &[
+            "-Y".to_string(),
+            "sign".to_string(),
+            "-f".to_string(),
+            key.display().to_string(),
+            "-n".to_string(),
+            SIGNATURE_NAMESPACE.to_string(),
+            message_path.display().to_string(),
+        ],
+        None,
+    )
+    .and_then(|_| Ok(fs::read_to_string(dir.join("message.sig"))?));
+    fs::remove_dir_all(&dir)?;
+
+    res
+}
+
+/// Verifies the signature of the given approval and returns the fingerprint of the signing key.
+///
+/// The reviewer has to be listed in the given allowed signers file with the key used for the signature.
+/// The public key stored with the approval is not trusted, because it can be replaced with any key.
+fn verify_signature(approval: &Approval, allowed_signers: &Path) -> Result<String, ApprovalError> {
+    let dir = get_temp_dir("verify")?;
+    let message_path = dir.join("message");
+    let signature_path = dir.join("message.sig");
+    fs::write(&message_path, &approval.sha256)?;
+    fs::write(&signature_path, &approval.signature)?;
+
+    let res = run_ssh_keygen(
+        &[
+            "-Y".to_string(),
+            "verify".to_string(),
+            "-f".to_string(),
+            allowed_signers.display().to_string(),
+            "-I".to_string(),
+            approval.reviewer.clone(),
+            "-n".to_string(),
+            SIGNATURE_NAMESPACE.to_string(),
+            "-s".to_string(),
+            signature_path.display().to_string(),
+        ],
+        Some(&message_path),
+    );
+    fs::remove_dir_all(&dir)?;
+
+    // NOTE: the output has the form `Good "<namespace>" signature for <principal> with <type> key <fingerprint>`
+    let output = res?;
+    output
+        .split_whitespace()
+        .last()
+        .filter(|fingerprint| fingerprint.starts_with("SHA256:"))
+        .map(str::to_string)
+        .ok_or_else(|| ApprovalError::Signature(format!("unexpected output: {}", output)))
+}
+
+/// Approves the given file in the given directory by signing its hash with the given SSH key.
+///
+/// The file has to be unchanged since it was recorded in the manifest. A previous approval
+/// of the same reviewer for the file is replaced.
+pub fn approve(
+    dir: &Path,
+    file: &str,
+    reviewer: &str,
+    key: &Path,
+    now: DateTime<Utc>,
+) -> Result<Approval, ApprovalError> {
+    let sha256 = get_manifest_hash(dir, file)?;
+    let public_key = fs::read_to_string(get_public_key_path(key))?
+        .trim()
+        .to_string();
+
+    let approval = Approval {
+        reviewer: reviewer.to_string(),
+        file: file.to_string(),
+        signature: sign(key, &sha256)?,
+        sha256,
+        public_key,
+        approved_at: now,
+    };
+
+    let approvals_path = dir.join(APPROVALS_FILE);
+    let mut approvals = Approvals::load(&approvals_path)?;
+    approvals
+        .approvals
+        .retain(|existing| existing.file != file || existing.reviewer != reviewer);
+    approvals.approvals.push(approval.clone());
+    approvals.save(&approvals_path)?;
+
+    Ok(approval)
+}
+
+/// Checks the approvals of the given file in the given directory and returns them together with
+/// the fingerprint of the verified signing key or the reason, why they are not valid for the current
+/// contents of the file. Without an allowed signers file, no approval is valid.
+pub fn check_approvals(
+    dir: &Path,
+    file: &str,
+    allowed_signers: Option<&Path>,
+) -> Result<Vec<(Approval, Verification)>, ApprovalError> {
+    let sha256 = hash_file(&dir.join(file))?;
+    let approvals = Approvals::load(&dir.join(APPROVALS_FILE))?;
+
+    Ok(approvals
+        .approvals
+        .into_iter()
+        .filter(|approval| approval.file == file)
+        .map(|approval| {
+            let result = match (approval.sha256 == sha256, allowed_signers) {
+                (false, _) => Err("file was modified after the approval".to_string()),
+                (true, None) => Err("no allowed signers file to verify the key".to_string()),
+                (true, Some(allowed_signers)) => {
+                    verify_signature(&approval, allowed_signers).map_err(|e| e.to_string())
+                }
+            };
+            (approval, result)
+        })
+        .collect())
+}
+
+/// Checks that the given file in the given directory has at least the required number of valid approvals.
+///
+/// The approvals are verified against the keys in the given allowed signers file, which is required
+/// if any approvals are required. Each reviewer and each signing key is only counted once,
+/// so that duplicated entries or reviewers sharing a key do not add up.
+pub fn require_approvals(
+    dir: &Path,
+    file: &str,
+    required: usize,
+    allowed_signers: Option<&Path>,
+) -> Result<(), ApprovalError> {
+    if required == 0 {
+        return Ok(());
+    }
+    let allowed_signers = allowed_signers.ok_or(ApprovalError::NoAllowedSigners)?;
+
+    let mut reviewers = HashSet::new();
+    let mut keys = HashSet::new();
+    for (approval, result) in check_approvals(dir, file, Some(allowed_signers))? {
+        if let Ok(fingerprint) = result {
+            if !reviewers.contains(&approval.reviewer) && !keys.contains(&fingerprint) {
+                reviewers.insert(approval.reviewer);
+                keys.insert(fingerprint);
+            }
+        }
+    }
+
+    let valid = reviewers.len();
+    if valid < required {
+        return Err(ApprovalError::Missing(file.to_string(), valid, required));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest;
+    use test_dir::{DirBuilder, TestDir};
+
+    /// Creates an SSH key without passphrase in the given directory.
+    fn create_key(dir: &Path, name: &str) -> PathBuf {
+        let key = dir.join(name);
+        run_ssh_keygen(
+            &[
+                "-q".to_string(),
+                "-t".to_string(),
+                "ed25519".to_string(),
+                "-N".to_string(),
+                "".to_string(),
+                "-f".to_string(),
+                key.display().to_string(),
+            ],
+            None,
+        )
+        .expect("failed to create key");
+
+        key
+    }
+
+    #[test]
+    fn test_get_public_key_path() {
+        assert_eq!(
+            get_public_key_path(Path::new("/keys/id_ed25519")),
+            PathBuf::from("/keys/id_ed25519.pub")
+        );
+        assert_eq!(
+            get_public_key_path(Path::new("/keys/id_ed25519.pub")),
+            PathBuf::from("/keys/id_ed25519.pub")
+        );
+    }
+
+    /// Writes an allowed signers file in the given directory, which lists the given reviewers
+    /// with the public keys of the given SSH keys.
+    fn write_allowed_signers(dir: &Path, signers: &[(&str, &Path)]) -> PathBuf {
+        let path = dir.join("allowed_signers");
+        let contents: String = signers
+            .iter()
+            .map(|(reviewer, key)| {
+                let public_key = fs::read_to_string(get_public_key_path(key)).unwrap();
+                format!("{} {}\n", reviewer, public_key.trim())
+            })
+            .collect();
+        fs::write(&path, contents).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn test_approve_and_check_approvals() {
+        let test_dir = TestDir::temp();
+        let dir = test_dir.root();
+        let file = "proposal-Mainnet-v17.0.0.md";
+        fs::write(dir.join(file), "# Description").unwrap();
+
+        let key = create_key(dir, "reviewer");
+        let allowed_signers = write_allowed_signers(dir, &[("alice", &key)]);
+        assert!(matches!(
+            approve(dir, file, "alice", &key, Utc::now()),
+            Err(ApprovalError::NotInManifest(_))
+        ));
+
+        manifest::record(dir, None, &[file.to_string()], Utc::now()).unwrap();
+        approve(dir, file, "alice", &key, Utc::now()).expect("failed to approve");
+        approve(dir, file, "alice", &key, Utc::now()).expect("failed to approve");
+
+        let approvals = check_approvals(dir, file, Some(&allowed_signers)).unwrap();
+        assert_eq!(approvals.len(), 1);
+        assert!(approvals[0].1.is_ok(), "{:?}", approvals[0].1);
+        assert!(require_approvals(dir, file, 1, Some(&allowed_signers)).is_ok());
+        assert!(matches!(
+            require_approvals(dir, file, 2, Some(&allowed_signers)),
+            Err(ApprovalError::Missing(_, 1, 2))
+        ));
+
+        // NOTE: without an allowed signers file, the approvals cannot be verified
+        assert!(check_approvals(dir, file, None).unwrap()[0].1.is_err());
+        assert!(matches!(
+            require_approvals(dir, file, 1, None),
+            Err(ApprovalError::NoAllowedSigners)
+        ));
+        assert!(require_approvals(dir, file, 0, None).is_ok());
+
+        // NOTE: modifying the file invalidates the approval
+        fs::write(dir.join(file), "# Modified").unwrap();
+        let approvals = check_approvals(dir, file, Some(&allowed_signers)).unwrap();
+        assert!(approvals[0].1.is_err());
+        assert!(require_approvals(dir, file, 1, Some(&allowed_signers)).is_err());
+    }
+
+    #[test]
+    fn test_forged_key_is_rejected() {
+        let test_dir = TestDir::temp();
+        let dir = test_dir.root();
+        let file = "proposal-Mainnet-v17.0.0.md";
+        fs::write(dir.join(file), "# Description").unwrap();
+        manifest::record(dir, None, &[file.to_string()], Utc::now()).unwrap();
+
+        let alice_key = create_key(dir, "alice");
+        let mallory_key = create_key(dir, "mallory");
+        let allowed_signers = write_allowed_signers(dir, &[("alice", &alice_key)]);
+
+        // NOTE: the approval claims to be from alice, but is signed with a key that is not allowed
+        let mut approval = approve(dir, file, "alice", &mallory_key, Utc::now()).unwrap();
+        let approvals = check_approvals(dir, file, Some(&allowed_signers)).unwrap();
+        assert!(approvals[0].1.is_err());
+        assert!(matches!(
+            require_approvals(dir, file, 1, Some(&allowed_signers)),
+            Err(ApprovalError::Missing(_, 0, 1))
+        ));
+
+        // NOTE: replacing the stored public key with the allowed key does not help either
+        approval.public_key = fs::read_to_string(get_public_key_path(&alice_key))
+            .unwrap()
+            .trim()
+            .to_string();
+        Approvals {
+            approvals: vec![approval],
+        }
+        .save(&dir.join(APPROVALS_FILE))
+        .unwrap();
+        assert!(require_approvals(dir, file, 1, Some(&allowed_signers)).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_approvals_are_counted_once() {
+        let test_dir = TestDir::temp();
+        let dir = test_dir.root();
+        let file = "proposal-Mainnet-v17.0.0.md";
+        fs::write(dir.join(file), "# Description").unwrap();
+        manifest::record(dir, None, &[file.to_string()], Utc::now()).unwrap();
+
+        let key = create_key(dir, "alice");
+        let allowed_signers = write_allowed_signers(dir, &[("alice", &key), ("bob", &key)]);
+
+        // NOTE: duplicating the entry of the same reviewer and key does not count twice
+        let approval = approve(dir, file, "alice", &key, Utc::now()).unwrap();
+        Approvals {
+            approvals: vec![approval.clone(), approval],
+        }
+        .save(&dir.join(APPROVALS_FILE))
+        .unwrap();
+        assert_eq!(
+            check_approvals(dir, file, Some(&allowed_signers))
+                .unwrap()
+                .len(),
+            2
+        );
+        assert!(matches!(
+            require_approvals(dir, file, 2, Some(&allowed_signers)),
+            Err(ApprovalError::Missing(_, 1, 2))
+        ));
+
+        // NOTE: the same key approving under a second reviewer name does not count twice either
+        approve(dir, file, "bob", &key, Utc::now()).unwrap();
+        assert!(matches!(
+            require_approvals(dir, file, 2, Some(&allowed_signers)),
+            Err(ApprovalError::Missing(_, 1, 2))
+        ));
+    }
+}
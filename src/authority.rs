@@ -0,0 +1,110 @@
+use crate::errors::AuthorityError;
+use crate::http::get_json;
+use bech32::{ToBase32, Variant};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use url::Url;
+
+const MODULE_ACCOUNTS_ENDPOINT: &str = "cosmos/auth/v1beta1/module_accounts/";
+
+/// The name of the governance module account, which is the authority for the executed messages.
+const GOV_MODULE_NAME: &str = "gov";
+
+/// Returns the address of the governance module account, which has to be used as the authority
+/// of messages like `MsgSoftwareUpgrade`.
+///
+/// The address is queried from the network and, if the query fails, derived from the module name
+/// and the given Bech32 prefix of the chain.
+pub async fn get_gov_authority(base_url: &Url, prefix: &str) -> Result<String, AuthorityError> {
+    match query_module_address(base_url, GOV_MODULE_NAME).await {
+        Ok(address) => Ok(address),
+        Err(e) => {
+            println!(
+                "WARNING: failed to query governance module account, deriving address instead: {}",
+                e
+            );
+            get_module_address(GOV_MODULE_NAME, prefix)
+        }
+    }
+}
+
+/// Queries the address of the module account with the given name.
+async fn query_module_address(base_url: &Url, module: &str) -> Result<String, AuthorityError> {
+    let response: Value = get_json(base_url.join(MODULE_ACCOUNTS_ENDPOINT)?.join(module)?).await?;
+
+    // NOTE: module accounts contain their address in the embedded base account
+    response["account"]["base_account"]["address"]
+        .as_str()
+        .or(response["account"]["address"].as_str())
+        .map(str::to_string)
+        .ok_or(AuthorityError::MissingAddress(module.to_string()))
+}
+
+/// Derives the address of the module account with the given name, which is the
+/// Bech32 encoding of the first 20 bytes of the SHA-256 hash of the module name.
+pub fn get_module_address(module: &str, prefix: &str) -> Result<String, AuthorityError> {
+    let hash = Sha256::digest(module.as_bytes());
+
+    Ok(bech32::encode(
+        prefix,
+        (&hash[..20]).to_base32(),
+        Variant::Bech32,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_get_module_address() {
+        assert_eq!(
+            get_module_address("gov", "evmos").unwrap(),
+            "evmos10d07y265gmmuvt4z0w9aw880jnsr700jcrztvm"
+        );
+        assert_eq!(
+            get_module_address("gov", "cosmos").unwrap(),
+            "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_gov_authority() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/auth/v1beta1/module_accounts/gov"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "account": {
+                    "@type": "/cosmos.auth.v1beta1.ModuleAccount",
+                    "base_account": {
+                        "address": "evmos10d07y265gmmuvt4z0w9aw880jnsr700jcrztvm",
+                        "pub_key": null,
+                        "account_number": "7",
+                        "sequence": "0"
+                    },
+                    "name": "gov",
+                    "permissions": ["burner"]
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let base_url =
+            Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
+        assert_eq!(
+            get_gov_authority(&base_url, "evmos").await.unwrap(),
+            "evmos10d07y265gmmuvt4z0w9aw880jnsr700jcrztvm"
+        );
+
+        // NOTE: the address is derived if the query fails
+        let base_url = base_url.join("/unavailable/").unwrap();
+        assert_eq!(
+            get_gov_authority(&base_url, "cosmos").await.unwrap(),
+            "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn"
+        );
+    }
+}
@@ -1,11 +1,16 @@
 use crate::errors::KeysError;
-use crate::http::get_body;
-use crate::network::{get_denom, Network};
+use crate::http::get_json;
+use crate::network::{format_coin, Network};
+use indicatif::ProgressBar;
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 use url::Url;
 
 const BALANCES_ENDPOINT: &str = "cosmos/bank/v1beta1/balances/";
 
+/// The interval, in which the balance is queried while waiting for funds.
+pub const FUNDS_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
 /// Represents the response from a successful balance query for a specific denomination.
 #[derive(Serialize, Deserialize)]
 struct BalanceResponse {
@@ -19,21 +24,62 @@ struct Balance {
     amount: String,
 }
 
-/// Checks if a given address has a non-zero balance on the given network.
-pub async fn has_balance(
+/// Returns the balance of the given address in the given denomination.
+pub async fn get_balance(address: &str, denom: &str, base_url: &Url) -> Result<u128, KeysError> {
+    let balances_endpoint = base_url
+        .join(BALANCES_ENDPOINT)?
+        .join(format!("{}/by_denom?denom={}", address, denom).as_str())?;
+
+    let balance: BalanceResponse = get_json(balances_endpoint).await?;
+
+    Ok(balance.balance.amount.parse()?)
+}
+
+/// Polls the balance of the given address in the given denomination in the given interval
+/// until it covers the required amount and returns the funded balance.
+/// A spinner shows the current balance while waiting.
+///
+/// Fails if the account is not funded before the given timeout elapses.
+pub async fn wait_for_funds(
     address: &str,
+    denom: &str,
     network: &Network,
     base_url: &Url,
-) -> Result<bool, KeysError> {
-    let native_denom = get_denom(*network);
-    let balances_endpoint = base_url
-        .join(BALANCES_ENDPOINT)?
-        .join(format!("{}/by_denom?denom={}", address, native_denom).as_str())?;
+    required: u128,
+    interval: Duration,
+    timeout: Duration,
+) -> Result<u128, KeysError> {
+    let spinner = ProgressBar::new_spinner();
+    spinner.enable_steady_tick(Duration::from_millis(100));
+
+    let start = Instant::now();
+    loop {
+        let balance = get_balance(address, denom, base_url).await?;
+        if balance >= required {
+            spinner.finish_with_message(format!(
+                "{} is funded with {}",
+                address,
+                format_coin(&balance.to_string(), denom, *network)
+            ));
+            return Ok(balance);
+        }
 
-    let balance: BalanceResponse =
-        serde_json::from_str(get_body(balances_endpoint).await?.as_str())?;
+        if start.elapsed() >= timeout {
+            spinner.abandon();
+            return Err(KeysError::FundsTimeout(
+                address.to_string(),
+                timeout.as_secs(),
+            ));
+        }
 
-    Ok(balance.balance.amount != "0")
+        spinner.set_message(format!(
+            "Waiting for funds on {}: {} of {}",
+            address,
+            format_coin(&balance.to_string(), denom, *network),
+            format_coin(&required.to_string(), denom, *network)
+        ));
+        tokio::time::sleep(interval.min(timeout.saturating_sub(start.elapsed()))).await;
+    }
 }
 
 #[cfg(test)]
@@ -71,17 +117,55 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_has_balance() {
+    async fn test_wait_for_funds() {
         let network = Network::LocalNode;
+        let mock_server = setup_mock_api().await;
+        let mock_path =
+            Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
+        let balance = get_balance(TEST_ADDRESS, "aevmos", &mock_path)
+            .await
+            .expect("failed to get balance");
+
+        let funded = wait_for_funds(
+            TEST_ADDRESS,
+            "aevmos",
+            &network,
+            &mock_path,
+            balance,
+            Duration::from_millis(10),
+            Duration::from_secs(1),
+        )
+        .await
+        .expect("failed to wait for funds");
+        assert_eq!(funded, balance);
+
+        assert!(matches!(
+            wait_for_funds(
+                TEST_ADDRESS,
+                "aevmos",
+                &network,
+                &mock_path,
+                balance + 1,
+                Duration::from_millis(10),
+                Duration::from_millis(50),
+            )
+            .await,
+            Err(KeysError::FundsTimeout(_, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_balance() {
         let mock_server = setup_mock_api().await;
         let mock_path =
             Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
 
-        assert!(
-            has_balance(TEST_ADDRESS, &network, &mock_path)
+        assert_eq!(
+            get_balance(TEST_ADDRESS, "aevmos", &mock_path)
                 .await
                 .unwrap(),
-            "expected a non-zero balance"
+            150,
+            "expected different balance"
         );
     }
 }
@@ -1,8 +1,18 @@
+use crate::cache::DiskCache;
+use crate::chain::ChainConfig;
 use crate::errors::BlockError;
-use crate::{http::get_body, network::Network};
-use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use crate::{
+    endpoints,
+    http::{get_body, get_json},
+    network::Network,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Duration, NaiveDateTime, TimeZone, Utc};
+use num_format::ToFormattedString;
 use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use url::Url;
 
 /// The number of blocks to use for the block time estimation.
@@ -14,20 +24,52 @@ const BLOCKS_ENDPOINT: &str = "/cosmos/base/tendermint/v1beta1/blocks/";
 /// The REST endpoint for querying the latest block.
 const LATEST_BLOCK_ENDPOINT: &str = "/cosmos/base/tendermint/v1beta1/blocks/latest";
 
+/// The Tendermint RPC endpoint for querying the node status, which contains the latest block height.
+const RPC_STATUS_ENDPOINT: &str = "/status";
+
+/// The Tendermint RPC endpoint for querying blocks.
+const RPC_BLOCK_ENDPOINT: &str = "/block";
+
 /// Represents a block from the Evmos network.
 #[derive(Debug)]
 pub struct Block {
-    height: u64,
-    time: DateTime<Utc>,
+    /// The block height.
+    pub height: u64,
+    /// The block time.
+    pub time: DateTime<Utc>,
+    /// The hex-encoded block hash.
+    pub hash: String,
+    /// The hex-encoded address of the block proposer.
+    pub proposer: String,
+}
+
+impl fmt::Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Height:   {}",
+            self.height.to_formatted_string(&num_format::Locale::en)
+        )?;
+        writeln!(f, "Time:     {}", self.time.format("%Y-%m-%d %H:%M:%S %Z"))?;
+        writeln!(f, "Proposer: {}", self.proposer)?;
+        write!(f, "Hash:     {}", self.hash)
+    }
 }
 
 /// Represents the relevant information from the block query response
 /// that is used for further processing.
 #[derive(Serialize, Deserialize)]
 struct BlockResponse {
+    block_id: BlockId,
     block: BlockInfo,
 }
 
+/// Represents the block ID information from the block query response.
+#[derive(Serialize, Deserialize)]
+struct BlockId {
+    hash: String,
+}
+
 /// Represents the block information from the block query response.
 #[derive(Serialize, Deserialize)]
 struct BlockInfo {
@@ -39,22 +81,148 @@ struct BlockInfo {
 struct Header {
     height: String,
     time: String,
+    proposer_address: String,
 }
 
-/// Gets the estimated block height for the given upgrade time.
-pub async fn get_estimated_height(
-    base_url: &Url,
-    upgrade_time: DateTime<Utc>,
-) -> Result<u64, BlockError> {
-    let block = get_latest_block(base_url).await?;
-    let block_minus_n = get_block(base_url, block.height - N_BLOCKS).await?;
+/// Represents a JSON-RPC response from the Tendermint RPC.
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: T,
+}
+
+/// Represents the relevant information from the Tendermint RPC status response.
+#[derive(Deserialize)]
+struct StatusResult {
+    sync_info: SyncInfo,
+}
+
+/// Represents the sync information from the Tendermint RPC status response.
+#[derive(Deserialize)]
+struct SyncInfo {
+    latest_block_height: String,
+}
+
+/// Contains the endpoints that are used to query blocks.
+///
+/// The REST API is queried first. If it is unavailable and a Tendermint RPC
+/// endpoint is configured, the block information is queried from there instead.
+#[derive(Clone, Debug)]
+pub struct BlockProvider {
+    /// The base URL of the REST API.
+    pub rest: Url,
+    /// The URL of the Tendermint RPC, which is used as a fallback.
+    pub rpc: Option<Url>,
+    /// The cache, which stores the recent failures of the endpoints across runs, so that
+    /// a repeatedly failing REST API is not queried first. Failures are not stored without it.
+    pub cache: Option<DiskCache>,
+}
+
+impl BlockProvider {
+    /// Returns the block provider for the given network, which uses the REST endpoint from the
+    /// given chain configuration or the default provider of the network and falls back to the
+    /// Tendermint RPC from the chain configuration or the default RPC of the network.
+    pub fn for_network(
+        network: Network,
+        chain_config: &ChainConfig,
+    ) -> Result<BlockProvider, BlockError> {
+        let rest = get_rest_endpoint(network, chain_config)?;
+        let tm_rpc = chain_config
+            .tm_rpc
+            .clone()
+            .unwrap_or_else(|| get_rpc_url(network));
+
+        Ok(BlockProvider {
+            rest,
+            rpc: Some(Url::parse(&tm_rpc)?),
+            cache: None,
+        })
+    }
+
+    /// Returns the block provider, which stores the failures of its endpoints in the given cache.
+    pub fn with_cache(self, cache: &DiskCache) -> BlockProvider {
+        BlockProvider {
+            cache: Some(cache.clone()),
+            ..self
+        }
+    }
+
+    /// Records the outcome of a query to the given endpoint and returns the given result.
+    fn track<T, E>(&self, endpoint: &Url, result: Result<T, E>) -> Result<T, E> {
+        endpoints::track(self.cache.as_ref(), endpoint, result)
+    }
+
+    /// Returns the Tendermint RPC if the REST API failed repeatedly and is deprioritized
+    /// during its cooldown, so that it is not queried first.
+    fn get_deprioritized_fallback(&self) -> Option<&Url> {
+        let rpc = self.rpc.as_ref()?;
+        let cache = self.cache.as_ref()?;
+        if !endpoints::is_deprioritized(cache, &self.rest) {
+            return None;
+        }
+
+        println!(
+            "WARNING: REST API {} failed repeatedly; using Tendermint RPC",
+            self.rest
+        );
+        Some(rpc)
+    }
+}
+
+/// Contains the measured block time and the assumptions used for the estimation
+/// of block heights and times.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BlockTimeEstimate {
+    /// The average number of seconds per block.
+    pub seconds_per_block: f32,
+    /// The number of blocks that were sampled to measure the block time.
+    pub n_blocks: u64,
+    /// The height of the latest block at the time of the estimation.
+    pub latest_height: u64,
+    /// The time of the latest block at the time of the estimation.
+    pub latest_time: DateTime<Utc>,
+    /// The time at which the estimation was made.
+    pub estimated_at: DateTime<Utc>,
+}
+
+impl BlockTimeEstimate {
+    /// Returns the estimated block height at the given time.
+    pub fn height_at(&self, time: DateTime<Utc>) -> u64 {
+        let seconds_to_time = (time - self.latest_time).num_seconds() as f32;
+        let blocks_to_time = (seconds_to_time / self.seconds_per_block) as u64;
+
+        blocks_to_time + self.latest_height
+    }
+
+    /// Returns the estimated time at which the given block height will be reached.
+    pub fn time_at(&self, height: u64) -> Result<DateTime<Utc>, BlockError> {
+        if height <= self.latest_height {
+            return Err(BlockError::PastHeight(height, self.latest_height));
+        }
+
+        let seconds_to_height =
+            ((height - self.latest_height) as f32 * self.seconds_per_block) as i64;
+
+        Ok(self.latest_time + Duration::seconds(seconds_to_height))
+    }
+}
+
+/// Measures the average block time over the last N_BLOCKS blocks, which is used
+/// to estimate block heights and times.
+pub async fn get_block_time_estimate(
+    provider: &BlockProvider,
+) -> Result<BlockTimeEstimate, BlockError> {
+    let block = get_latest_block(provider).await?;
+    let block_minus_n = get_block(provider, block.height - N_BLOCKS).await?;
     let seconds_per_block: f32 =
         (block.time - block_minus_n.time).num_seconds() as f32 / N_BLOCKS as f32;
 
-    let seconds_to_upgrade = (upgrade_time - block.time).num_seconds() as f32;
-    let blocks_to_upgrade = (seconds_to_upgrade / seconds_per_block) as u64;
-
-    Ok(blocks_to_upgrade + block.height)
+    Ok(BlockTimeEstimate {
+        seconds_per_block,
+        n_blocks: N_BLOCKS,
+        latest_height: block.height,
+        latest_time: block.time,
+        estimated_at: Utc::now(),
+    })
 }
 
 /// Returns the block height rounded to the nearest 500.
@@ -63,25 +231,84 @@ pub fn round_to_nearest_500(height: u64) -> u64 {
 }
 
 /// Gets the latest block from the Evmos network.
-async fn get_latest_block(base_url: &Url) -> Result<Block, BlockError> {
-    process_block_body(
-        get_body(
-            base_url.join(LATEST_BLOCK_ENDPOINT)?
-        ).await?
-    )
+pub async fn get_latest_block(provider: &BlockProvider) -> Result<Block, BlockError> {
+    if let Some(rpc) = provider.get_deprioritized_fallback() {
+        return provider.track(rpc, get_latest_block_rpc(rpc).await);
+    }
+
+    match (
+        provider.track(&provider.rest, get_latest_block_rest(&provider.rest).await),
+        &provider.rpc,
+    ) {
+        (Err(BlockError::GetBlock(e)), Some(rpc)) => {
+            println!(
+                "WARNING: REST API unavailable ({}); falling back to Tendermint RPC",
+                e
+            );
+            provider.track(rpc, get_latest_block_rpc(rpc).await)
+        }
+        (res, _) => res,
+    }
 }
 
 /// Gets the block at the given height from the Evmos network.
-async fn get_block(base_url: &Url, height: u64) -> Result<Block, BlockError> {
+pub async fn get_block(provider: &BlockProvider, height: u64) -> Result<Block, BlockError> {
+    if let Some(rpc) = provider.get_deprioritized_fallback() {
+        return provider.track(rpc, get_block_rpc(rpc, height).await);
+    }
+
+    match (
+        provider.track(&provider.rest, get_block_rest(&provider.rest, height).await),
+        &provider.rpc,
+    ) {
+        (Err(BlockError::GetBlock(e)), Some(rpc)) => {
+            println!(
+                "WARNING: REST API unavailable ({}); falling back to Tendermint RPC",
+                e
+            );
+            provider.track(rpc, get_block_rpc(rpc, height).await)
+        }
+        (res, _) => res,
+    }
+}
+
+/// Gets the latest block from the REST API.
+async fn get_latest_block_rest(base_url: &Url) -> Result<Block, BlockError> {
+    process_block_body(get_body(base_url.join(LATEST_BLOCK_ENDPOINT)?).await?)
+}
+
+/// Gets the block at the given height from the REST API.
+async fn get_block_rest(base_url: &Url, height: u64) -> Result<Block, BlockError> {
     process_block_body(
         get_body(
             base_url
                 .join(BLOCKS_ENDPOINT)?
-                .join(height.to_string().as_str())?
-        ).await?
+                .join(height.to_string().as_str())?,
+        )
+        .await?,
     )
 }
 
+/// Gets the latest block from the Tendermint RPC.
+async fn get_latest_block_rpc(rpc_url: &Url) -> Result<Block, BlockError> {
+    let status: RpcResponse<StatusResult> = get_json(rpc_url.join(RPC_STATUS_ENDPOINT)?).await?;
+    let height = status.result.sync_info.latest_block_height.parse::<u64>()?;
+
+    get_block_rpc(rpc_url, height).await
+}
+
+/// Gets the block at the given height from the Tendermint RPC.
+async fn get_block_rpc(rpc_url: &Url, height: u64) -> Result<Block, BlockError> {
+    let mut url = rpc_url.join(RPC_BLOCK_ENDPOINT)?;
+    url.query_pairs_mut()
+        .append_pair("height", &height.to_string());
+
+    let response: RpcResponse<BlockResponse> = get_json(url).await?;
+
+    // NOTE: the Tendermint RPC already returns hex-encoded hashes and addresses
+    block_from_response(response.result, |hex| Ok(hex.to_uppercase()))
+}
+
 /// Returns the appropriate REST provider for the given network.
 pub fn get_rest_provider(network: Network) -> Url {
     let base_url = match network {
@@ -93,10 +320,37 @@ pub fn get_rest_provider(network: Network) -> Url {
     Url::parse(base_url).unwrap()
 }
 
-/// Processes the block body.
+/// Returns the REST endpoint from the given chain configuration or the default provider of the network.
+pub fn get_rest_endpoint(
+    network: Network,
+    chain_config: &ChainConfig,
+) -> Result<Url, url::ParseError> {
+    match &chain_config.rest_url {
+        Some(rest_url) => Url::parse(rest_url),
+        None => Ok(get_rest_provider(network)),
+    }
+}
+
+/// Returns the appropriate Tendermint RPC endpoint for the given network.
+pub fn get_rpc_url(network: Network) -> String {
+    match network {
+        Network::Mainnet => "https://tm.evmos.lava.build:443".to_string(),
+        Network::Testnet => "https://tm.evmos-testnet.lava.build:443".to_string(),
+        Network::LocalNode => "http://localhost:26657".to_string(),
+    }
+}
+
+/// Processes the block body returned from the REST API.
 fn process_block_body(body: String) -> Result<Block, BlockError> {
-    let body: BlockResponse = serde_json::from_str(&body)?;
+    block_from_response(serde_json::from_str(&body)?, base64_to_hex)
+}
 
+/// Builds the block from the given response, using the given function to convert
+/// the encoded hash and proposer address into the upper-case hex representation.
+fn block_from_response(
+    body: BlockResponse,
+    to_hex: fn(&str) -> Result<String, BlockError>,
+) -> Result<Block, BlockError> {
     let height = body.block.header.height.parse::<u64>()?;
 
     let captured_time = Regex::new(r"[T0-9\-:]+")?
@@ -108,7 +362,21 @@ fn process_block_body(body: String) -> Result<Block, BlockError> {
     let naive_date_time = NaiveDateTime::parse_from_str(captured_time, TIME_FORMAT)?;
     let time = Utc.from_utc_datetime(&naive_date_time);
 
-    Ok(Block { height, time })
+    let hash = to_hex(&body.block_id.hash)?;
+    let proposer = to_hex(&body.block.header.proposer_address)?;
+
+    Ok(Block {
+        height,
+        time,
+        hash,
+        proposer,
+    })
+}
+
+/// Converts the base64 encoded bytes from the REST responses into the upper-case hex representation,
+/// which is used by Tendermint for hashes and addresses.
+fn base64_to_hex(encoded: &str) -> Result<String, BlockError> {
+    Ok(hex::encode_upper(STANDARD.decode(encoded)?))
 }
 
 #[cfg(test)]
@@ -118,7 +386,7 @@ mod tests {
     use chrono::{Days, TimeZone};
     use serde_json::Value;
     use std::str::FromStr;
-    use wiremock::matchers::{method, path};
+    use wiremock::matchers::{method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     /// Sets up a mock server to return the given response template
@@ -151,19 +419,65 @@ mod tests {
         mock_server
     }
 
+    /// Returns a block provider that only queries the REST API at the given URL.
+    fn rest_only(rest: Url) -> BlockProvider {
+        BlockProvider {
+            rest,
+            rpc: None,
+            cache: None,
+        }
+    }
+
     #[tokio::test]
-    async fn test_get_estimated_height() {
+    async fn test_get_block_time_estimate() {
         let mock_server = setup_mock_api().await;
         let mock_path =
             Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
 
-        let now = Utc::now();
-        let upgrade_time = now.checked_add_days(Days::new(5)).unwrap();
-        let res = get_estimated_height(&mock_path, upgrade_time).await;
+        let res = get_block_time_estimate(&rest_only(mock_path)).await;
         assert!(res.is_ok(), "expected no error; got: {}", res.unwrap_err());
 
-        let height = res.unwrap();
-        assert!(height > 18798834, "expected a different block height");
+        // NOTE: the mocked blocks are 2_692_048 seconds apart
+        let estimate = res.unwrap();
+        assert_eq!(estimate.n_blocks, N_BLOCKS);
+        assert_eq!(estimate.latest_height, 18798834);
+        assert!(
+            (estimate.seconds_per_block - 53.84096).abs() < 0.001,
+            "expected different block time; got: {}",
+            estimate.seconds_per_block
+        );
+
+        let upgrade_time = Utc::now().checked_add_days(Days::new(5)).unwrap();
+        assert!(
+            estimate.height_at(upgrade_time) > 18798834,
+            "expected a different block height"
+        );
+    }
+
+    #[test]
+    fn test_time_at() {
+        let estimate = BlockTimeEstimate {
+            seconds_per_block: 2.0,
+            n_blocks: N_BLOCKS,
+            latest_height: 1_000,
+            latest_time: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            estimated_at: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 1).unwrap(),
+        };
+
+        assert_eq!(
+            estimate.time_at(1_900).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 0, 30, 0).unwrap(),
+            "expected a different time"
+        );
+        assert_eq!(
+            estimate.height_at(Utc.with_ymd_and_hms(2024, 1, 1, 0, 30, 0).unwrap()),
+            1_900,
+            "expected a different height"
+        );
+        assert!(
+            estimate.time_at(1_000).is_err(),
+            "expected error for height that is not in the future"
+        );
     }
 
     #[test]
@@ -180,20 +494,21 @@ mod tests {
         let mock_path =
             Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
 
-        let res = get_latest_block(&mock_path).await;
+        let res = get_latest_block(&rest_only(mock_path)).await;
         assert!(res.is_ok(), "expected no error; got: {}", res.unwrap_err());
 
         let block = res.unwrap();
         assert_eq!(block.height, 18798834, "expected different height");
     }
 
+    #[allow(clippy::zero_prefixed_literal)]
     #[tokio::test]
     async fn test_get_block_pass() {
         let mock_server = setup_mock_api().await;
         let mock_path =
             Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
 
-        let res = get_block(&mock_path, 18748834).await;
+        let res = get_block(&rest_only(mock_path), 18748834).await;
         assert!(res.is_ok(), "expected no error; got: {}", res.unwrap_err());
 
         let block = res.unwrap();
@@ -205,6 +520,7 @@ mod tests {
         );
     }
 
+    #[allow(clippy::zero_prefixed_literal)]
     #[test]
     fn test_process_block_body_pass() {
         let block_response = include_str!("testdata/block_testnet.json");
@@ -219,5 +535,120 @@ mod tests {
             Utc.with_ymd_and_hms(2023, 11, 07, 02, 41, 36).unwrap(),
             "expected a different block time",
         );
+        assert_eq!(
+            block.hash, "BB30668E65EADBF1AD47380B87756EE5721A06FEC3967F424893E555551E7831",
+            "expected a different block hash",
+        );
+        assert_eq!(
+            block.proposer, "7E3019B09942C202D545FA185CC65FF8FE36101D",
+            "expected a different proposer address",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_block_rpc_fallback() {
+        let rest_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(502))
+            .mount(&rest_server)
+            .await;
+
+        let status = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": -1,
+            "result": { "sync_info": { "latest_block_height": "18798834" } }
+        });
+        let latest_block: Value =
+            serde_json::from_str(include_str!("testdata/block_rpc_mainnet_18798834.json"))
+                .expect("failed to parse block JSON");
+
+        let rpc_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(RPC_STATUS_ENDPOINT))
+            .respond_with(ResponseTemplate::new(200).set_body_json(status))
+            .mount(&rpc_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(RPC_BLOCK_ENDPOINT))
+            .and(query_param("height", "18798834"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(latest_block))
+            .mount(&rpc_server)
+            .await;
+
+        let provider = BlockProvider {
+            rest: Url::from_str(rest_server.uri().as_str())
+                .expect("failed to parse mock server uri"),
+            rpc: Some(
+                Url::from_str(rpc_server.uri().as_str()).expect("failed to parse mock server uri"),
+            ),
+            cache: None,
+        };
+
+        let res = get_latest_block(&provider).await;
+        assert!(res.is_ok(), "expected no error; got: {}", res.unwrap_err());
+
+        let block = res.unwrap();
+        assert_eq!(block.height, 18798834, "expected a different block height");
+        assert_eq!(
+            block.hash, "0470722011E8CFD83403CA5DB729FCC1E44B4C032700F589E6E2795D1207D8B6",
+            "expected a different block hash",
+        );
+        assert_eq!(
+            block.proposer, "8336788E5AE5DC5650F21734AD8093AFA376B84B",
+            "expected a different proposer address",
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_block_without_rpc_fail() {
+        let rest_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(502))
+            .mount(&rest_server)
+            .await;
+
+        let mock_path =
+            Url::from_str(rest_server.uri().as_str()).expect("failed to parse mock server uri");
+        let res = get_block(&rest_only(mock_path), 18748834).await;
+        assert!(res.is_err(), "expected an error without RPC fallback");
+    }
+
+    #[test]
+    fn test_get_rpc_url() {
+        let rpc = get_rpc_url(Network::Mainnet);
+        assert_eq!(rpc, "https://tm.evmos.lava.build:443", "rpc does not match");
+
+        let rpc = get_rpc_url(Network::Testnet);
+        assert_eq!(
+            rpc, "https://tm.evmos-testnet.lava.build:443",
+            "rpc does not match"
+        );
+
+        let rpc = get_rpc_url(Network::LocalNode);
+        assert_eq!(rpc, "http://localhost:26657", "rpc does not match");
+    }
+
+    #[test]
+    fn test_get_rest_endpoint() {
+        let chain_config = ChainConfig {
+            rest_url: Some("https://rest.example.com".to_string()),
+            ..ChainConfig::default()
+        };
+        assert_eq!(
+            get_rest_endpoint(Network::Mainnet, &chain_config)
+                .unwrap()
+                .as_str(),
+            "https://rest.example.com/"
+        );
+        assert_eq!(
+            get_rest_endpoint(Network::Mainnet, &ChainConfig::default()).unwrap(),
+            get_rest_provider(Network::Mainnet)
+        );
+
+        let chain_config = ChainConfig {
+            rest_url: Some("not a url".to_string()),
+            ..ChainConfig::default()
+        };
+        assert!(get_rest_endpoint(Network::Mainnet, &chain_config).is_err());
     }
 }
@@ -0,0 +1,315 @@
+use octocrab::{Octocrab, Result};
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// The label of pull requests, which contain state-breaking changes.
+pub const STATE_BREAKING_LABEL: &str = "state-breaking";
+
+/// The label of pull requests, which contain API-breaking changes.
+pub const API_BREAKING_LABEL: &str = "api-breaking";
+
+/// Contains the numbers of the pull requests included in a release, which are labeled as breaking.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct BreakingChanges {
+    /// The pull requests labeled as state-breaking.
+    pub state_breaking: Vec<u64>,
+    /// The pull requests labeled as API-breaking.
+    pub api_breaking: Vec<u64>,
+}
+
+impl BreakingChanges {
+    /// Prints a warning for every kind of breaking change contained in the release.
+    pub fn warn(&self) {
+        for (label, pull_requests) in [
+            (STATE_BREAKING_LABEL, &self.state_breaking),
+            (API_BREAKING_LABEL, &self.api_breaking),
+        ] {
+            if !pull_requests.is_empty() {
+                println!(
+                    "WARNING: the release contains {} changes: {}",
+                    label,
+                    pull_requests
+                        .iter()
+                        .map(|number| format!("#{}", number))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+    }
+}
+
+/// Represents a pull request, which is included in a release.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PullRequest {
+    /// The number of the pull request.
+    pub number: u64,
+    /// The title of the pull request, which is taken from the first line of the commit message.
+    pub title: String,
+}
+
+/// Returns the commits that are included between the given tags from the compare API.
+async fn get_commits(instance: &Octocrab, base: &str, head: &str) -> Result<Vec<Value>> {
+    let comparison: Value = instance
+        .get(
+            format!("/repos/evmos/evmos/compare/{}...{}", base, head),
+            None::<&()>,
+        )
+        .await?;
+
+    Ok(comparison["commits"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Returns the pull requests that are included between the given tags,
+/// which are detected from the commit messages returned by the compare API.
+pub async fn get_pull_requests(
+    instance: &Octocrab,
+    base: &str,
+    head: &str,
+) -> Result<Vec<PullRequest>> {
+    let pr_reference = Regex::new(r"\s*\(#\d+\)").expect("invalid regex");
+
+    Ok(get_commits(instance, base, head)
+        .await?
+        .iter()
+        .filter_map(|commit| commit["commit"]["message"].as_str())
+        .flat_map(|message| {
+            let first_line = message.lines().next().unwrap_or_default();
+            let title = pr_reference.replace_all(first_line, "").trim().to_string();
+            get_pr_numbers(message)
+                .into_iter()
+                .map(move |number| PullRequest {
+                    number,
+                    title: title.clone(),
+                })
+        })
+        .collect())
+}
+
+/// Returns the messages of the commits that are included between the given tags.
+pub async fn get_commit_messages(
+    instance: &Octocrab,
+    base: &str,
+    head: &str,
+) -> Result<Vec<String>> {
+    Ok(get_commits(instance, base, head)
+        .await?
+        .iter()
+        .filter_map(|commit| commit["commit"]["message"].as_str())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Returns the pull requests that are included between the given tags and labeled as breaking.
+///
+/// The pull requests are detected from the commit messages returned by the compare API
+/// and cross-referenced with the labeled pull requests, which were merged since the first commit.
+pub async fn get_breaking_changes(
+    instance: &Octocrab,
+    base: &str,
+    head: &str,
+) -> Result<BreakingChanges> {
+    let commits = get_commits(instance, base, head).await?;
+
+    let included: HashSet<u64> = commits
+        .iter()
+        .filter_map(|commit| commit["commit"]["message"].as_str())
+        .flat_map(get_pr_numbers)
+        .collect();
+    if included.is_empty() {
+        return Ok(BreakingChanges::default());
+    }
+
+    // NOTE: the dates are in RFC 3339 format, so that the first ten characters contain the date
+    let since = commits
+        .iter()
+        .filter_map(|commit| commit["commit"]["committer"]["date"].as_str())
+        .min()
+        .map(|date| date.chars().take(10).collect::<String>());
+
+    Ok(BreakingChanges {
+        state_breaking: get_labeled_prs(
+            instance,
+            STATE_BREAKING_LABEL,
+            since.as_deref(),
+            &included,
+        )
+        .await?,
+        api_breaking: get_labeled_prs(instance, API_BREAKING_LABEL, since.as_deref(), &included)
+            .await?,
+    })
+}
+
+/// Returns the sorted numbers of the included pull requests, which have the given label.
+async fn get_labeled_prs(
+    instance: &Octocrab,
+    label: &str,
+    since: Option<&str>,
+    included: &HashSet<u64>,
+) -> Result<Vec<u64>> {
+    let mut query = format!("repo:evmos/evmos is:pr is:merged label:{}", label);
+    if let Some(since) = since {
+        query.push_str(&format!(" merged:>={}", since));
+    }
+
+    let response: Value = instance
+        .get(
+            "/search/issues",
+            Some(&[("q", query.as_str()), ("per_page", "100")]),
+        )
+        .await?;
+
+    let mut numbers: Vec<u64> = response["items"]
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item["number"].as_u64())
+                .filter(|number| included.contains(number))
+                .collect()
+        })
+        .unwrap_or_default();
+    numbers.sort_unstable();
+
+    Ok(numbers)
+}
+
+/// Returns the pull request numbers referenced in the first line of the given commit message,
+/// e.g. `feat(evm): add precompile (#1234)` or `Merge pull request #1234 from evmos/branch`.
+fn get_pr_numbers(message: &str) -> Vec<u64> {
+    let first_line = message.lines().next().unwrap_or_default();
+
+    Regex::new(r"#(\d+)")
+        .expect("invalid regex")
+        .captures_iter(first_line)
+        .filter_map(|captures| captures[1].parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_get_breaking_changes() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/evmos/evmos/compare/v15.0.0...v16.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "commits": [
+                    {"commit": {
+                        "message": "feat(evm): add precompile (#1234)\n\nlong description",
+                        "committer": {"date": "2024-01-10T12:00:00Z"}
+                    }},
+                    {"commit": {
+                        "message": "fix(api): change response (#1240)",
+                        "committer": {"date": "2024-01-03T12:00:00Z"}
+                    }},
+                    {"commit": {
+                        "message": "chore: bump version",
+                        "committer": {"date": "2024-01-12T12:00:00Z"}
+                    }}
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/search/issues"))
+            .and(query_param(
+                "q",
+                "repo:evmos/evmos is:pr is:merged label:state-breaking merged:>=2024-01-03",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "total_count": 2,
+                "items": [{"number": 1234}, {"number": 999}]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/search/issues"))
+            .and(query_param(
+                "q",
+                "repo:evmos/evmos is:pr is:merged label:api-breaking merged:>=2024-01-03",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "total_count": 1,
+                "items": [{"number": 1240}]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let instance = Octocrab::builder()
+            .base_uri(mock_server.uri())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let breaking_changes = get_breaking_changes(&instance, "v15.0.0", "v16.0.0")
+            .await
+            .expect("failed to get breaking changes");
+        assert_eq!(
+            breaking_changes,
+            BreakingChanges {
+                state_breaking: vec![1234],
+                api_breaking: vec![1240],
+            },
+            "expected only the included pull requests"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_pull_requests() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/evmos/evmos/compare/v15.0.0...v16.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "commits": [
+                    {"commit": {"message": "feat(evm): add precompile (#1234)\n\nlong description"}},
+                    {"commit": {"message": "chore: bump version"}}
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let instance = Octocrab::builder()
+            .base_uri(mock_server.uri())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let pull_requests = get_pull_requests(&instance, "v15.0.0", "v16.0.0")
+            .await
+            .expect("failed to get pull requests");
+        assert_eq!(
+            pull_requests,
+            vec![PullRequest {
+                number: 1234,
+                title: "feat(evm): add precompile".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_get_pr_numbers() {
+        assert_eq!(
+            get_pr_numbers("feat(evm): add precompile (#1234)"),
+            vec![1234]
+        );
+        assert_eq!(
+            get_pr_numbers("Merge pull request #12 from evmos/branch\n\nfixes #13"),
+            vec![12]
+        );
+        assert!(get_pr_numbers("chore: bump version").is_empty());
+    }
+}
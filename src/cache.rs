@@ -0,0 +1,171 @@
+use crate::errors::HttpError;
+use crate::utils::write_atomically;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+/// The environment variable containing the number of seconds, for which successful responses are cached.
+/// Setting it to zero disables the cache.
+const CACHE_TTL_VAR: &str = "PROPOSER_CACHE_TTL";
+
+/// The default number of seconds, for which successful responses are cached.
+const DEFAULT_CACHE_TTL_SECONDS: i64 = 600;
+
+/// Represents a cached response body.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// The key of the entry, e.g. the requested URL.
+    key: String,
+    /// The time at which the response was received.
+    fetched_at: DateTime<Utc>,
+    /// The response body.
+    body: String,
+}
+
+/// Stores response bodies on disk for a short time, so that repeated runs
+/// do not send the same requests to external services.
+///
+/// Other state, which is kept across runs with its own expiry (e.g. the health of the endpoints),
+/// is stored as entries of the cache as well.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiskCache {
+    /// The directory containing the cached entries.
+    dir: PathBuf,
+    /// The duration, for which the entries are valid.
+    ttl: Duration,
+}
+
+impl DiskCache {
+    /// Creates a new cache in the given directory.
+    pub fn new(dir: PathBuf, ttl: Duration) -> DiskCache {
+        DiskCache { dir, ttl }
+    }
+
+    /// Creates the cache in the user's cache directory with the TTL from the environment.
+    pub fn from_env() -> Result<DiskCache, HttpError> {
+        let ttl = match std::env::var(CACHE_TTL_VAR) {
+            Ok(ttl) => ttl
+                .parse::<i64>()
+                .map_err(|_| HttpError::Config(format!("invalid {}: {}", CACHE_TTL_VAR, ttl)))?,
+            Err(_) => DEFAULT_CACHE_TTL_SECONDS,
+        };
+        let dir = dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("proposer")
+            .join("http");
+
+        Ok(DiskCache::new(dir, Duration::seconds(ttl)))
+    }
+
+    /// Returns the cached body for the given key, if it was stored within the TTL before the given time.
+    pub fn get(&self, key: &str, now: DateTime<Utc>) -> Option<String> {
+        if self.ttl <= Duration::zero() {
+            return None;
+        }
+
+        self.read_entry(key, self.ttl, now)
+    }
+
+    /// Stores the given body for the given key.
+    pub fn put(&self, key: &str, body: &str, now: DateTime<Utc>) -> Result<(), std::io::Error> {
+        if self.ttl <= Duration::zero() {
+            return Ok(());
+        }
+
+        self.write_entry(key, body, now)
+    }
+
+    /// Returns the stored body for the given key, if it was stored within the given maximum age
+    /// before the given time. In contrast to `get`, the TTL of the cache is not applied.
+    pub fn read_entry(&self, key: &str, max_age: Duration, now: DateTime<Utc>) -> Option<String> {
+        let contents = fs::read_to_string(self.get_path(key)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&contents).ok()?;
+
+        (entry.key == key && now - entry.fetched_at < max_age).then_some(entry.body)
+    }
+
+    /// Stores the given body for the given key, even if caching responses is disabled.
+    pub fn write_entry(
+        &self,
+        key: &str,
+        body: &str,
+        now: DateTime<Utc>,
+    ) -> Result<(), std::io::Error> {
+        let entry = CacheEntry {
+            key: key.to_string(),
+            fetched_at: now,
+            body: body.to_string(),
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        write_atomically(
+            &self.get_path(key),
+            serde_json::to_string(&entry)?.as_bytes(),
+        )
+    }
+
+    /// Returns the path of the entry for the given key.
+    fn get_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!(
+            "{}.json",
+            hex::encode(Sha256::digest(key.as_bytes()))
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[test]
+    fn test_get_and_put() {
+        let test_dir = TestDir::temp();
+        let cache = DiskCache::new(test_dir.path("cache"), Duration::minutes(10));
+        let now = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+
+        assert_eq!(cache.get("https://example.com", now), None);
+
+        cache
+            .put("https://example.com", "body", now)
+            .expect("failed to store entry");
+        assert_eq!(
+            cache.get("https://example.com", now + Duration::minutes(9)),
+            Some("body".to_string())
+        );
+        assert_eq!(
+            cache.get("https://example.com", now + Duration::minutes(10)),
+            None,
+            "expected expired entry to be ignored"
+        );
+        assert_eq!(cache.get("https://example.org", now), None);
+    }
+
+    #[test]
+    fn test_disabled_cache() {
+        let test_dir = TestDir::temp();
+        let cache = DiskCache::new(test_dir.path("cache"), Duration::zero());
+        let now = Utc::now();
+
+        cache
+            .put("https://example.com", "body", now)
+            .expect("failed to store entry");
+        assert_eq!(cache.get("https://example.com", now), None);
+        assert!(!test_dir.path("cache").exists());
+
+        cache
+            .write_entry("state", "value", now)
+            .expect("failed to store entry");
+        assert_eq!(
+            cache.read_entry("state", Duration::minutes(15), now + Duration::minutes(14)),
+            Some("value".to_string())
+        );
+        assert_eq!(
+            cache.read_entry("state", Duration::minutes(15), now + Duration::minutes(15)),
+            None
+        );
+    }
+}
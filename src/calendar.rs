@@ -0,0 +1,245 @@
+use crate::helper::UpgradeHelper;
+use chrono::{DateTime, Duration, Utc};
+
+/// The maximum length of a content line in octets, after which it is folded (RFC 5545, section 3.1).
+const MAX_LINE_LENGTH: usize = 75;
+
+/// The duration of the events for the start and end of the voting period in minutes.
+const VOTING_EVENT_MINUTES: i64 = 30;
+
+/// The duration of the upgrade event in minutes, if there is no estimate of the downtime.
+const DEFAULT_UPGRADE_MINUTES: i64 = 60;
+
+/// Represents an event of the upgrade schedule in the calendar.
+#[derive(Debug, PartialEq)]
+pub struct CalendarEvent {
+    /// The unique ID of the event, which stays the same when regenerating the calendar,
+    /// so that imported events are updated instead of duplicated.
+    pub uid: String,
+    /// The title of the event.
+    pub summary: String,
+    /// The description of the event.
+    pub description: String,
+    /// The start of the event.
+    pub start: DateTime<Utc>,
+    /// The duration of the event.
+    pub duration: Duration,
+    /// The offsets before the start of the event, at which a reminder is shown.
+    pub alarms: Vec<Duration>,
+}
+
+/// Returns the file name of the calendar for the given helper.
+pub fn get_calendar_file_name(helper: &UpgradeHelper) -> String {
+    helper.proposal_file_name.replace(".md", ".ics")
+}
+
+/// Returns the events of the upgrade schedule of the given helper, i.e. the start and end
+/// of the voting period (if known) and the upgrade.
+pub fn get_events(helper: &UpgradeHelper) -> Vec<CalendarEvent> {
+    let uid_prefix = format!(
+        "{}-{}",
+        helper.network.to_string().to_lowercase().replace(' ', "-"),
+        helper.target_version
+    );
+    let proposal = match helper.proposal_id {
+        Some(proposal_id) => format!("{} ({})", helper.proposal_name, proposal_id),
+        None => helper.proposal_name.clone(),
+    };
+    let mut events = Vec::new();
+
+    if let Some(start) = helper.voting_start_time {
+        events.push(CalendarEvent {
+            uid: format!("{}-voting-start@proposer", uid_prefix),
+            summary: format!("Voting starts: {}", helper.proposal_name),
+            description: format!(
+                "The deposit period of {} ends and the voting period starts.",
+                proposal
+            ),
+            start,
+            duration: Duration::minutes(VOTING_EVENT_MINUTES),
+            alarms: vec![Duration::hours(1)],
+        });
+    }
+    if let Some(end) = helper.voting_end_time {
+        events.push(CalendarEvent {
+            uid: format!("{}-voting-end@proposer", uid_prefix),
+            summary: format!("Voting ends: {}", helper.proposal_name),
+            description: format!(
+                "The voting period of {} ends. Make sure that the validators have voted.",
+                proposal
+            ),
+            start: end,
+            duration: Duration::minutes(VOTING_EVENT_MINUTES),
+            alarms: vec![Duration::days(1), Duration::hours(1)],
+        });
+    }
+
+    let upgrade_minutes = helper
+        .downtime_estimate
+        .as_ref()
+        .map_or(DEFAULT_UPGRADE_MINUTES, |estimate| {
+            estimate.expected_minutes as i64
+        });
+    events.push(CalendarEvent {
+        uid: format!("{}-upgrade@proposer", uid_prefix),
+        summary: format!("Upgrade: {}", helper.proposal_name),
+        description: format!(
+            "The Evmos {} upgrade from {} to {} is estimated to happen at block height {}. \
+             Validators should be available until block production has resumed.",
+            helper.network, helper.previous_version, helper.target_version, helper.upgrade_height
+        ),
+        start: helper.upgrade_time,
+        duration: Duration::minutes(upgrade_minutes.max(1)),
+        alarms: vec![Duration::days(1), Duration::minutes(30)],
+    });
+
+    events
+}
+
+/// Renders the given events as an iCalendar file, which can be imported into calendar applications.
+///
+/// The given time is used as the time stamp of the events, i.e. the time at which they were created.
+pub fn render_calendar(events: &[CalendarEvent], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//proposer//Upgrade Schedule//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+
+    for event in events {
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}", event.uid),
+            format!("DTSTAMP:{}", format_time(now)),
+            format!("DTSTART:{}", format_time(event.start)),
+            format!("DTEND:{}", format_time(event.start + event.duration)),
+            format!("SUMMARY:{}", escape_text(&event.summary)),
+            format!("DESCRIPTION:{}", escape_text(&event.description)),
+        ]);
+        for alarm in event.alarms.iter() {
+            lines.extend([
+                "BEGIN:VALARM".to_string(),
+                "ACTION:DISPLAY".to_string(),
+                format!("DESCRIPTION:{}", escape_text(&event.summary)),
+                format!("TRIGGER:-PT{}M", alarm.num_minutes()),
+                "END:VALARM".to_string(),
+            ]);
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    // NOTE: iCalendar requires CRLF line breaks, including after the last line
+    lines
+        .iter()
+        .map(|line| format!("{}\r\n", fold_line(line)))
+        .collect()
+}
+
+/// Returns the given time in the UTC format of iCalendar, e.g. `20240117T160000Z`.
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes the special characters of the given text value.
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Folds the given content line into lines of at most 75 octets,
+/// where continuation lines start with a space. Characters are not split.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            // NOTE: the leading space counts towards the length of the continuation line
+            length = 1;
+        }
+        folded.push(c);
+        length += c.len_utf8();
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+    use chrono::TimeZone;
+    use std::path::PathBuf;
+
+    fn get_helper() -> UpgradeHelper {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v15.0.0",
+            "v16.0.0",
+            Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap(),
+            18_798_000,
+            "- Add feature",
+        );
+        helper.set_voting_times(
+            Some(Utc.with_ymd_and_hms(2024, 1, 8, 15, 4, 12).unwrap()),
+            Some(Utc.with_ymd_and_hms(2024, 1, 13, 15, 4, 12).unwrap()),
+        );
+
+        helper
+    }
+
+    #[test]
+    fn test_get_events() {
+        let mut helper = get_helper();
+        let events = get_events(&helper);
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].uid, "mainnet-v16.0.0-voting-start@proposer");
+        assert_eq!(
+            events[1].summary,
+            "Voting ends: Evmos Mainnet v16.0.0 Upgrade"
+        );
+        assert_eq!(events[2].start, helper.upgrade_time);
+        assert_eq!(events[2].duration, Duration::minutes(60));
+
+        helper.voting_start_time = None;
+        helper.voting_end_time = None;
+        assert_eq!(get_events(&helper).len(), 1);
+    }
+
+    #[test]
+    fn test_render_calendar() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 5, 12, 0, 0).unwrap();
+        let calendar = render_calendar(&get_events(&get_helper()), now);
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 3);
+        assert_eq!(calendar.matches("BEGIN:VALARM").count(), 5);
+        assert!(calendar.contains("DTSTART:20240117T160000Z\r\nDTEND:20240117T170000Z\r\n"));
+        assert!(calendar.contains("TRIGGER:-PT1440M\r\n"));
+        assert!(
+            calendar.lines().all(|line| line.len() <= MAX_LINE_LENGTH),
+            "expected all lines to be folded"
+        );
+    }
+
+    #[test]
+    fn test_escape_and_fold() {
+        assert_eq!(escape_text("a, b; c\\d\ne"), "a\\, b\\; c\\\\d\\ne");
+
+        let line = format!("DESCRIPTION:{}", "a".repeat(100));
+        let folded = fold_line(&line);
+        let lines: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), MAX_LINE_LENGTH);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+}
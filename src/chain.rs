@@ -0,0 +1,384 @@
+use crate::errors::ConfigError;
+use crate::network::{self, get_denom, Network};
+use crate::schedule::TimezoneShare;
+use crate::schema::SdkVersion;
+use crate::secret;
+use crate::signature::ChecksumSignature;
+use crate::tracking::TrackingRepo;
+use crate::upload::UpgradeInfoHost;
+use crate::version::VersionPolicy;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// The name of the file inside of the proposer configuration directory,
+/// which contains the chain configurations per network.
+const CHAINS_FILE: &str = "chains.toml";
+
+/// Contains the chain-specific configuration that is used to render the submission command.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChainConfig {
+    /// The name of the daemon binary.
+    #[serde(default = "default_daemon")]
+    pub daemon: String,
+    /// The Bech32 prefix of the account addresses on the chain.
+    #[serde(default = "default_bech32_prefix")]
+    pub bech32_prefix: String,
+    /// The chain ID, which overrides the default chain ID of the network (optional).
+    #[serde(default)]
+    pub chain_id: Option<String>,
+    /// The REST endpoint of the network, which overrides the default provider (optional).
+    #[serde(default)]
+    pub rest_url: Option<String>,
+    /// The base URL of the block explorer, which is linked in the proposal (optional),
+    /// e.g. `https://www.mintscan.io/evmos`.
+    #[serde(default)]
+    pub explorer_url: Option<String>,
+    /// Additional keyring flags, e.g. `--keyring-dir <DIR>`.
+    #[serde(default)]
+    pub keyring_flags: Vec<String>,
+    /// Additional flags to add to the submission command.
+    #[serde(default = "default_extra_flags")]
+    pub extra_flags: Vec<String>,
+    /// The durations of previous upgrades in minutes, which are used to estimate the downtime
+    /// of the upgrade. If empty, no downtime section is added to the proposal.
+    #[serde(default)]
+    pub upgrade_durations: Vec<u64>,
+    /// The transport that is used to interact with the governance module.
+    #[serde(default)]
+    pub gov_transport: GovTransport,
+    /// The gRPC endpoint of the network, which is required when using the gRPC transport
+    /// for networks other than the local node.
+    #[serde(default)]
+    pub grpc_url: Option<String>,
+    /// The Tendermint RPC endpoint of the network, which is used for the submission command
+    /// and as a fallback for block queries if the REST API is unavailable.
+    #[serde(default)]
+    pub tm_rpc: Option<String>,
+    /// The signature of the release checksum file, which is verified before trusting the checksums.
+    #[serde(default)]
+    pub checksum_signature: Option<ChecksumSignature>,
+    /// The base URL, at which the upgrade info file is hosted if the binaries JSON
+    /// exceeds the size limits of the upgrade plan info.
+    #[serde(default)]
+    pub upgrade_info_url: Option<String>,
+    /// The host, to which the upgrade info file is uploaded, so that the upgrade plan links to it.
+    #[serde(default)]
+    pub upgrade_info_host: Option<UpgradeInfoHost>,
+    /// Whether the creation and update times and the creator of the configuration
+    /// are rendered into the proposal header for audit purposes.
+    #[serde(default)]
+    pub audit_header: bool,
+    /// The version of the Cosmos SDK used by the chain, whose schema the gov v1 proposal JSON
+    /// is validated against before submission.
+    #[serde(default)]
+    pub sdk_version: SdkVersion,
+    /// The chains, whose block heights are estimated for the same time as the upgrade,
+    /// e.g. for coordinated multi-chain events.
+    #[serde(default)]
+    pub reference_chains: Vec<ReferenceChain>,
+    /// The number of reviewer approvals of the proposal, which are required before
+    /// the command to submit it is generated.
+    #[serde(default)]
+    pub required_approvals: usize,
+    /// The path to the SSH allowed signers file, which lists the trusted reviewer keys.
+    /// It is required to verify approvals, which are all rejected without it.
+    #[serde(default)]
+    pub allowed_signers: Option<PathBuf>,
+    /// The repository, in which the upgrades are tracked with one issue per version (optional).
+    #[serde(default)]
+    pub tracking_repo: Option<TrackingRepo>,
+    /// The distribution of the validator operators across timezones, which is used to suggest
+    /// upgrade times when most operators are awake. Without it, the upgrade is scheduled at 16:00 UTC.
+    #[serde(default)]
+    pub validator_timezones: Vec<TimezoneShare>,
+    /// The optional checks of the version bumps, which are enforced when generating a proposal.
+    #[serde(default)]
+    pub version_policy: VersionPolicy,
+    /// The denomination of the transaction fees, which is also the gas token of the EVM,
+    /// if it differs from the native denomination of the network (optional).
+    #[serde(default)]
+    pub fee_denom: Option<String>,
+    /// The denomination, which is staked and used for governance deposits,
+    /// if it differs from the native denomination of the network (optional).
+    #[serde(default)]
+    pub stake_denom: Option<String>,
+}
+
+impl ChainConfig {
+    /// Returns the chain ID on the given network, unless it is overridden in the configuration.
+    pub fn get_chain_id(&self, network: Network) -> String {
+        self.chain_id
+            .clone()
+            .unwrap_or_else(|| network::get_chain_id(network))
+    }
+
+    /// Returns the denomination of the transaction fees on the given network.
+    pub fn get_fee_denom(&self, network: Network) -> String {
+        self.fee_denom.clone().unwrap_or_else(|| get_denom(network))
+    }
+
+    /// Returns the staking denomination on the given network, which is used for governance deposits.
+    pub fn get_stake_denom(&self, network: Network) -> String {
+        self.stake_denom
+            .clone()
+            .unwrap_or_else(|| get_denom(network))
+    }
+}
+
+/// Contains the endpoints of a chain, whose block height is estimated for the same time
+/// as events on the configured network, e.g. IBC-timed upgrades of counterparty chains.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceChain {
+    /// The name of the chain, which is shown in the table of estimated heights.
+    pub name: String,
+    /// The base URL of the REST API of the chain.
+    pub rest: String,
+    /// The Tendermint RPC endpoint of the chain, which is used as a fallback for the REST API.
+    #[serde(default)]
+    pub tm_rpc: Option<String>,
+}
+
+/// The transports that can be used to interact with the governance module.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GovTransport {
+    /// The REST API of the node.
+    #[default]
+    Rest,
+    /// The gRPC API of the node.
+    Grpc,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        ChainConfig {
+            daemon: default_daemon(),
+            bech32_prefix: default_bech32_prefix(),
+            chain_id: None,
+            rest_url: None,
+            explorer_url: None,
+            keyring_flags: Vec::new(),
+            extra_flags: default_extra_flags(),
+            upgrade_durations: Vec::new(),
+            gov_transport: GovTransport::default(),
+            grpc_url: None,
+            tm_rpc: None,
+            checksum_signature: None,
+            upgrade_info_url: None,
+            upgrade_info_host: None,
+            audit_header: false,
+            sdk_version: SdkVersion::default(),
+            reference_chains: Vec::new(),
+            required_approvals: 0,
+            allowed_signers: None,
+            tracking_repo: None,
+            validator_timezones: Vec::new(),
+            version_policy: VersionPolicy::default(),
+            fee_denom: None,
+            stake_denom: None,
+        }
+    }
+}
+
+/// Returns the default daemon binary name.
+fn default_daemon() -> String {
+    "evmosd".to_string()
+}
+
+/// Returns the default Bech32 prefix of the account addresses.
+fn default_bech32_prefix() -> String {
+    "evmos".to_string()
+}
+
+/// Returns the default extra flags for the submission command.
+fn default_extra_flags() -> Vec<String> {
+    vec!["--gas auto".to_string()]
+}
+
+/// Returns the directory containing the configuration files for the proposer.
+pub fn get_config_dir() -> PathBuf {
+    dirs::config_dir()
+        .expect("failed to get config directory")
+        .join("proposer")
+}
+
+/// Returns the chain configuration for the given network.
+///
+/// The default configuration can be overridden per network in the `chains.toml` file
+/// inside of the proposer configuration directory, e.g.:
+///
+/// ```toml
+/// [mainnet]
+/// daemon = "evmosd"
+/// extra_flags = ["--gas auto", "--gas-adjustment 1.3"]
+/// ```
+pub fn get_chain_config(network: Network) -> Result<ChainConfig, ConfigError> {
+    let path = get_config_dir().join(CHAINS_FILE);
+    if !path.exists() {
+        return Ok(ChainConfig::default());
+    }
+
+    parse_chain_config(&fs::read_to_string(path)?, network)
+}
+
+/// Parses the chain configuration for the given network from the contents of the chains file.
+/// If the network is not configured, the default configuration is returned.
+/// Encrypted values are decrypted with the configured passphrase.
+fn parse_chain_config(contents: &str, network: Network) -> Result<ChainConfig, ConfigError> {
+    let mut chain_configs: HashMap<String, ChainConfig> = if secret::contains_encrypted(contents) {
+        let mut value: serde_json::Value = toml::from_str(contents)?;
+        secret::decrypt_values(&mut value, &secret::get_passphrase()?)?;
+        serde_json::from_value(value)?
+    } else {
+        toml::from_str(contents)?
+    };
+
+    Ok(chain_configs
+        .remove(&get_network_key(network))
+        .unwrap_or_default())
+}
+
+/// Sets the given values in the chain configuration of the given network in the `chains.toml` file,
+/// keeping the other values and networks.
+pub fn update_chain_config(network: Network, values: toml::Table) -> Result<PathBuf, ConfigError> {
+    let path = get_config_dir().join(CHAINS_FILE);
+    let contents = match path.exists() {
+        true => fs::read_to_string(&path)?,
+        false => String::new(),
+    };
+
+    fs::create_dir_all(get_config_dir())?;
+    fs::write(&path, merge_chain_config(&contents, network, values)?)?;
+
+    Ok(path)
+}
+
+/// Merges the given values into the chain configuration of the given network in the contents of the chains file.
+/// Encrypted values are kept as they are.
+fn merge_chain_config(
+    contents: &str,
+    network: Network,
+    values: toml::Table,
+) -> Result<String, ConfigError> {
+    let mut chain_configs: toml::Table = toml::from_str(contents)?;
+    let entry = chain_configs
+        .entry(get_network_key(network))
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    if let toml::Value::Table(table) = entry {
+        table.extend(values);
+    }
+
+    let merged = toml::to_string_pretty(&chain_configs)?;
+    // NOTE: the merged configuration is parsed again so that invalid values are not written
+    parse_chain_config(&merged, network)?;
+
+    Ok(merged)
+}
+
+/// Returns the key that is used for the given network in the configuration files.
+/// This is the same value that is used for the network on the command line.
+pub fn get_network_key(network: Network) -> String {
+    network
+        .to_possible_value()
+        .expect("network variants are not skipped")
+        .get_name()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_chain_config() {
+        let contents = r#"
+            [mainnet]
+            daemon = "evmosd-v16"
+            keyring_flags = ["--keyring-dir /tmp/keyring"]
+            upgrade_durations = [10, 25, 12]
+
+            [mainnet.checksum_signature]
+            method = "gpg"
+            public_key = "/keys/evmos.asc"
+
+            [local-node]
+            extra_flags = []
+            gov_transport = "grpc"
+            fee_denom = "agas"
+        "#;
+
+        let mainnet = parse_chain_config(contents, Network::Mainnet).unwrap();
+        assert_eq!(mainnet.daemon, "evmosd-v16");
+        assert_eq!(mainnet.keyring_flags, vec!["--keyring-dir /tmp/keyring"]);
+        assert_eq!(mainnet.extra_flags, vec!["--gas auto"]);
+        assert_eq!(mainnet.upgrade_durations, vec![10, 25, 12]);
+        assert_eq!(
+            mainnet.checksum_signature,
+            Some(ChecksumSignature::Gpg {
+                public_key: PathBuf::from("/keys/evmos.asc")
+            })
+        );
+
+        let local_node = parse_chain_config(contents, Network::LocalNode).unwrap();
+        assert_eq!(local_node.daemon, "evmosd");
+        assert!(local_node.extra_flags.is_empty());
+        assert_eq!(local_node.gov_transport, GovTransport::Grpc);
+        assert_eq!(mainnet.gov_transport, GovTransport::Rest);
+        assert_eq!(local_node.get_fee_denom(Network::LocalNode), "agas");
+        assert_eq!(local_node.get_stake_denom(Network::LocalNode), "aevmos");
+        assert_eq!(mainnet.get_fee_denom(Network::Mainnet), "aevmos");
+        assert_eq!(local_node.get_chain_id(Network::LocalNode), "evmos_9000-4");
+
+        let testnet = parse_chain_config(contents, Network::Testnet).unwrap();
+        assert_eq!(testnet, ChainConfig::default());
+    }
+
+    #[test]
+    fn test_merge_chain_config() {
+        let contents = r#"
+            [mainnet]
+            daemon = "evmosd-v16"
+            upgrade_durations = [10, 25, 12]
+
+            [testnet]
+            bech32_prefix = "evmos"
+        "#;
+
+        let mut values = toml::Table::new();
+        values.insert("chain_id".to_string(), "evmos_9001-2".into());
+        values.insert("daemon".to_string(), "evmosd".into());
+        let merged = merge_chain_config(contents, Network::Mainnet, values).unwrap();
+
+        let mainnet = parse_chain_config(&merged, Network::Mainnet).unwrap();
+        assert_eq!(mainnet.chain_id.as_deref(), Some("evmos_9001-2"));
+        assert_eq!(mainnet.get_chain_id(Network::Mainnet), "evmos_9001-2");
+        assert_eq!(mainnet.daemon, "evmosd");
+        assert_eq!(mainnet.upgrade_durations, vec![10, 25, 12]);
+        assert!(merged.contains("[testnet]"));
+
+        let mut values = toml::Table::new();
+        values.insert("tm_rpc".to_string(), "https://rpc.example.com".into());
+        let merged = merge_chain_config("", Network::LocalNode, values).unwrap();
+        assert_eq!(
+            parse_chain_config(&merged, Network::LocalNode)
+                .unwrap()
+                .tm_rpc,
+            Some("https://rpc.example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_chain_config_invalid() {
+        assert!(parse_chain_config("[mainnet]\ndaemon = 1", Network::Mainnet).is_err());
+    }
+
+    #[test]
+    fn test_get_network_key() {
+        assert_eq!(get_network_key(Network::LocalNode), "local-node");
+        assert_eq!(get_network_key(Network::Testnet), "testnet");
+        assert_eq!(get_network_key(Network::Mainnet), "mainnet");
+    }
+}
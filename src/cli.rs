@@ -1,20 +1,87 @@
-use crate::evmosd::get_client_config;
+use crate::evmosd::{get_client_config, ClientConfig};
 use crate::{
-    command,
-    commonwealth::check_commonwealth_link,
-    errors::{CommandError, ProposalError},
-    helper::{get_helper_from_inputs, get_helper_from_json},
-    inputs, keys,
-    llm::OpenAIModel,
-    network::Network,
-    proposal, utils,
+    account::Account,
+    address::from_hex_address,
+    approval, balance, block, calendar, chain,
+    color::ColorChoice,
+    command::{self, Signer},
+    commonwealth::{check_commonwealth_link, render_commonwealth_text},
+    context::Context,
+    diff,
+    digest::Digest,
+    errors::{
+        ApprovalError, BlockError, CalendarError, CommandError, CompareError, DigestError,
+        ExportError, HelperError, HistoryError, ImportError, InstructionsError, KeysError,
+        LintError, LocalnetError, ProposalError, QueryError, RegistryError, ReminderError,
+        RunbookError, SecretError, ServeError, StatusError, TelegramError, TemplateTestError,
+        ValidationError, ValidatorsError, VerifyError,
+    },
+    export::{self, ExportFormat},
+    feegrant, gov, heights,
+    helper::{
+        finalize_helper, from_file, get_estimated_upgrade_time, get_helper_from_file,
+        get_helper_from_inputs, get_pair_from_inputs, ConfigFormat, HelperOutcome, InputOptions,
+        UpgradeHelper,
+    },
+    history::{self, get_history_path, History},
+    import::{get_helper_from_plan, ImportOptions},
+    inputs::{self, DescriptionSource},
+    instructions,
+    keys::{self, ImportSource},
+    links,
+    llm::{self, LlmBackend, OpenAIModel},
+    locale::Locale,
+    localnet, manifest,
+    messages::V1Proposal,
+    network::{format_coin, Network},
+    notes::NotesSource,
+    notifier::{self, Notification, Severity},
+    preflight::{self, Flow},
+    profile,
+    proposal::{self, get_proposal_file_name, RenderTarget},
+    readability, registry, release, reminder, resolve, revision, runbook, schema, secret, sequence,
+    serve, status,
+    telegram::{Bot, TelegramClient},
+    telemetry::{self, Event},
+    template,
+    tracking::{self, TrackingRepo},
+    tx, utils, validators,
+    watch::FileWatcher,
 };
+use chrono::{DateTime, Duration, Utc};
 use clap::{Args, Parser, Subcommand};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use url::Url;
 
 /// Utility to help with preparing software upgrades for the Evmos Core Team.
 #[derive(Debug, Parser)]
 pub struct Cli {
+    /// Report how long each step (e.g. GitHub queries, checksum downloads, block sampling,
+    /// LLM calls and rendering) took, to identify slow providers.
+    #[clap(long, global = true)]
+    pub profile: bool,
+    /// The network to use for any sub-command instead of prompting for it.
+    /// Can also be set with the `PROPOSER_NETWORK` environment variable.
+    #[clap(long, global = true, value_enum)]
+    pub network: Option<Network>,
+    /// The home directory of the Evmos binary to use for any sub-command instead of the one
+    /// of the configuration or prompting for it. Can also be set with the `PROPOSER_HOME` environment variable.
+    #[clap(long, global = true)]
+    pub home: Option<PathBuf>,
+    /// The backend, which generates the summaries and other texts. The mock backend returns
+    /// deterministic texts without tokens or network access, e.g. for development and CI.
+    #[clap(long, global = true, value_enum, default_value_t = LlmBackend::OpenAI)]
+    pub llm_backend: LlmBackend,
+    /// Whether to color the output, e.g. the rendered diffs and the prompts. In automatic mode,
+    /// the output is only colored in a terminal and if the `NO_COLOR` environment variable is not set.
+    #[clap(long, global = true, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+    /// The YAML file, which answers prompts by their keys (e.g. `network`, `key` or `plan_info`),
+    /// so that recurring flows only prompt for the values that change per release.
+    #[clap(long, global = true)]
+    pub answers: Option<PathBuf>,
     /// The sub-command to execute.
     #[clap(subcommand)]
     pub subcmd: SubCommand,
@@ -29,6 +96,67 @@ pub enum SubCommand {
     GenerateProposal(GenerateProposalArgs),
     /// The `generate-command` sub-command, which has an optional file path argument.
     GenerateCommand(GenerateCommandArgs),
+    /// The `text-proposal` sub-command, which generates a generic text proposal and the command to submit it.
+    TextProposal(TextProposalArgs),
+    /// The `block` sub-command, which prints the information of a block on the given network.
+    Block(BlockArgs),
+    /// The `localnet` sub-command, which contains helpers to set up a local node for testing.
+    Localnet(LocalnetArgs),
+    /// The `diff` sub-command, which compares two exported upgrade configurations.
+    Diff(DiffArgs),
+    /// The `schema` sub-command, which exports the JSON schema of the upgrade configuration.
+    Schema(SchemaArgs),
+    /// The `verify` sub-command, which compares an on-chain proposal with the local configuration.
+    Verify(VerifyArgs),
+    /// The `compare-previous` sub-command, which compares the proposal with the one of the previous upgrade.
+    ComparePrevious(ComparePreviousArgs),
+    /// The `history` sub-command, which prints the record of generated upgrades and their statistics.
+    History(HistoryArgs),
+    /// The `status` sub-command, which prints the governance parameters and the proposals in voting period.
+    Status(StatusArgs),
+    /// The `export-status` sub-command, which exports the status of all tracked proposals as CSV or JSON.
+    ExportStatus(ExportStatusArgs),
+    /// The `digest` sub-command, which prints a Markdown summary of the pending governance work.
+    Digest(DigestArgs),
+    /// The `lint` sub-command, which reviews a proposal against the governance guidelines using an LLM.
+    Lint(LintArgs),
+    /// The `validators` sub-command, which exports the bonded validators with their voting power and contacts as CSV.
+    Validators(ValidatorsArgs),
+    /// The `nag` sub-command, which lists the validators with the highest voting power that have not voted yet.
+    Nag(NagArgs),
+    /// The `runbook` sub-command, which generates the runbook with the timeline of the upgrade day.
+    Runbook(RunbookArgs),
+    /// The `calendar` sub-command, which generates the calendar with the voting period and the upgrade.
+    Calendar(CalendarArgs),
+    /// The `reminder` sub-command, which generates the reminder for validators to vote on the proposal.
+    Reminder(ReminderArgs),
+    /// The `import` sub-command, which reconstructs the configuration from an on-chain proposal.
+    Import(ImportArgs),
+    /// The `instructions` sub-command, which generates the upgrade instructions for validators.
+    Instructions(InstructionsArgs),
+    /// The `serve` sub-command, which exposes the height estimation and the rendering of the proposal
+    /// and the command as a REST API with JSON payloads.
+    Serve(ServeArgs),
+    /// The `telegram` sub-command, which runs a Telegram bot to follow the upgrade of a configuration.
+    Telegram(TelegramArgs),
+    /// The `encrypt` sub-command, which encrypts a sensitive value to store it in the configuration files.
+    Encrypt(EncryptArgs),
+    /// The `promote` sub-command, which marks a draft revision of the proposal as final.
+    Promote(PromoteArgs),
+    /// The `finalize` sub-command, which fills in the placeholders of a draft proposal.
+    Finalize(FinalizeArgs),
+    /// The `approve` sub-command, which signs the hash of the proposal as a reviewer.
+    Approve(ApproveArgs),
+    /// The `verify-approvals` sub-command, which verifies the approvals of the proposal.
+    VerifyApprovals(VerifyApprovalsArgs),
+    /// The `keys` sub-command, which manages the keys used to submit proposals.
+    Keys(KeysArgs),
+    /// The `chain` sub-command, which manages the chain configurations.
+    Chain(ChainArgs),
+    /// The `heights` sub-command, which estimates the block heights of multiple chains at the same time.
+    Heights(HeightsArgs),
+    /// The `template` sub-command, which helps with writing custom templates.
+    Template(TemplateArgs),
 }
 
 /// This struct defines the pattern of the arguments for the `generate-proposal` sub-command.
@@ -37,6 +165,85 @@ pub struct GenerateProposalArgs {
     /// The LLM model to use for summarizing the release notes.
     #[clap(short, long, default_value_t = OpenAIModel::Gpt4o)]
     model: OpenAIModel,
+    /// The upgrade height to use instead of estimating it from the chosen upgrade time.
+    #[clap(long)]
+    upgrade_height: Option<u64>,
+    /// The intended mainnet version, for which the latest release candidate is detected
+    /// and suggested as the target version for non-mainnet upgrades.
+    #[clap(long)]
+    mainnet_version: Option<String>,
+    /// Generate a plain-language summary for the community in addition to the technical summary.
+    #[clap(long)]
+    dual_summary: bool,
+    /// The source of the notes, which are summarized for the proposal: the description of the GitHub release,
+    /// the titles of the included pull requests or the commit messages between the versions.
+    #[clap(long, value_enum, default_value_t = NotesSource::Release)]
+    notes_source: NotesSource,
+    /// The format of the exported configuration file.
+    #[clap(long, value_enum, default_value_t = ConfigFormat::Json)]
+    format: ConfigFormat,
+    /// Watch the exported configuration and the template for changes and re-render the proposal.
+    #[clap(short, long)]
+    watch: bool,
+    /// Generate the proposals for the testnet release candidate and the mainnet release
+    /// of the same version line in one pass.
+    #[clap(long, conflicts_with_all = ["upgrade_height", "watch"])]
+    pair: bool,
+    /// The number of days, by which the mainnet upgrade is scheduled after the testnet upgrade in pair mode.
+    #[clap(long, default_value_t = 7, requires = "pair")]
+    pair_lag_days: i64,
+    /// The target to render the proposal for. The canonical Markdown file is always written.
+    #[clap(short, long, value_enum, default_value_t = RenderTarget::Markdown)]
+    target: RenderTarget,
+    /// Render the templates leniently, replacing unknown placeholders with empty values
+    /// and only warning about them instead of failing.
+    #[clap(long)]
+    no_strict: bool,
+    /// Skip the connectivity checks of the needed endpoints before starting the wizard.
+    #[clap(long)]
+    skip_preflight: bool,
+    /// Skip the checks of the version policy in the chain configuration, e.g. for hotfix releases.
+    #[clap(long)]
+    skip_version_policy: bool,
+    /// The locale, in which numbers and dates are rendered in the proposal.
+    #[clap(long, value_enum, default_value_t = Locale::En)]
+    locale: Locale,
+    /// Generate a draft before the release is cut, which contains placeholders for the upgrade height,
+    /// time and release information until it is completed with the `finalize` sub-command.
+    #[clap(long, conflicts_with_all = ["upgrade_height", "pair"])]
+    draft: bool,
+}
+
+/// This struct defines the pattern of the arguments for the `finalize` sub-command.
+/// If no path is provided, the user is prompted to choose a configuration file.
+#[derive(Debug, Clone, Args)]
+pub struct FinalizeArgs {
+    /// The path to the configuration file of the draft.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// The LLM model to use for summarizing the release notes.
+    #[clap(short, long, default_value_t = OpenAIModel::Gpt4o)]
+    model: OpenAIModel,
+    /// The upgrade height to use instead of estimating it from the chosen upgrade time.
+    #[clap(long)]
+    upgrade_height: Option<u64>,
+    /// Generate a plain-language summary for the community in addition to the technical summary.
+    #[clap(long)]
+    dual_summary: bool,
+    /// The source of the notes, which are summarized for the proposal: the description of the GitHub release,
+    /// the titles of the included pull requests or the commit messages between the versions.
+    #[clap(long, value_enum, default_value_t = NotesSource::Release)]
+    notes_source: NotesSource,
+    /// The target to render the proposal for. The canonical Markdown file is always written.
+    #[clap(short, long, value_enum, default_value_t = RenderTarget::Markdown)]
+    target: RenderTarget,
+    /// Render the templates leniently, replacing unknown placeholders with empty values
+    /// and only warning about them instead of failing.
+    #[clap(long)]
+    no_strict: bool,
+    /// Skip the checks of the version policy in the chain configuration, e.g. for hotfix releases.
+    #[clap(long)]
+    skip_version_policy: bool,
 }
 
 /// This struct defines the pattern of the arguments for the `generate-command` sub-command.
@@ -47,67 +254,2457 @@ pub struct GenerateCommandArgs {
     /// The path to the configuration file.
     #[clap(short, long)]
     config: Option<PathBuf>,
+    /// Watch the configuration, the proposal and the templates for changes and re-render the command.
+    #[clap(short, long)]
+    watch: bool,
+    /// Generate a gov v1 proposal JSON file, which can combine the upgrade with additional messages,
+    /// instead of the legacy software upgrade proposal command.
+    #[clap(long)]
+    proposal_json: bool,
+    /// The address of the account that pays the fees from its fee allowance to the selected key.
+    /// If not provided, the existing fee allowances of the selected key are detected.
+    #[clap(long)]
+    fee_granter: Option<String>,
+    /// The regular expression, which the names of the keys to select from have to match.
+    #[clap(long)]
+    key_filter: Option<regex::Regex>,
+    /// If the balance of the selected key does not cover the fees, wait for it to be funded
+    /// for up to the given number of seconds before continuing.
+    #[clap(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "1800")]
+    wait_for_funds: Option<u64>,
+    /// Export the unsigned transaction to submit the proposal as Proto JSON (not Amino JSON)
+    /// to the given path for external signers instead of generating the command.
+    /// The local keyring and client configuration are not used.
+    #[clap(long)]
+    export_unsigned: Option<PathBuf>,
+    /// The address of the proposer, which signs the exported unsigned transaction.
+    /// Hex addresses (`0x...`) are converted to the Bech32 address of the chain.
+    #[clap(long, requires = "export_unsigned")]
+    proposer: Option<String>,
+    /// The initial deposit in the smallest unit of the staking denomination of the chain, e.g. `10000000000`.
+    /// Overrides the deposit in the configuration.
+    #[clap(long)]
+    deposit: Option<u128>,
+    /// Sign the transaction to submit the proposal natively with the key from the secret in `--secret-env`
+    /// and broadcast it instead of generating the command. The local keyring and the Evmos CLI are not used.
+    #[clap(long, conflicts_with_all = ["export_unsigned", "proposal_json", "watch"])]
+    submit: bool,
+    /// The format of the secret, which signs the direct submission.
+    #[clap(long, value_enum, default_value_t = ImportSource::Mnemonic)]
+    secret_source: ImportSource,
+    /// The environment variable, which contains the secret to sign the direct submission.
+    #[clap(long, default_value = "PROPOSER_KEY_SECRET")]
+    secret_env: String,
+    /// Render the templates leniently, replacing unknown placeholders with empty values
+    /// and only warning about them instead of failing.
+    #[clap(long)]
+    no_strict: bool,
+    /// Skip the connectivity checks of the needed endpoints before asking for further inputs.
+    #[clap(long)]
+    skip_preflight: bool,
 }
 
-/// Runs the logic for the `generate-command` sub-command.
-pub async fn generate_command(args: GenerateCommandArgs) -> Result<(), CommandError> {
-    let helper_config_path = match args.config {
-        Some(config_file_name) => config_file_name,
-        None => inputs::choose_config()?, // NOTE: if no config file is provided, prompt the user to choose one
+/// This struct defines the pattern of the arguments for the `text-proposal` sub-command.
+/// If no network is provided, the user is prompted to select one.
+#[derive(Debug, Clone, Args)]
+pub struct TextProposalArgs {
+    /// The network to submit the proposal on.
+    #[clap(short, long)]
+    network: Option<Network>,
+    /// The LLM model to use for drafting the description from bullet points.
+    #[clap(short, long, default_value_t = OpenAIModel::Gpt4o)]
+    model: OpenAIModel,
+    /// The address of the account that pays the fees from its fee allowance to the selected key.
+    /// If not provided, the existing fee allowances of the selected key are detected.
+    #[clap(long)]
+    fee_granter: Option<String>,
+    /// The regular expression, which the names of the keys to select from have to match.
+    #[clap(long)]
+    key_filter: Option<regex::Regex>,
+    /// If the balance of the selected key does not cover the fees, wait for it to be funded
+    /// for up to the given number of seconds before continuing.
+    #[clap(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "1800")]
+    wait_for_funds: Option<u64>,
+    /// Render the templates leniently, replacing unknown placeholders with empty values
+    /// and only warning about them instead of failing.
+    #[clap(long)]
+    no_strict: bool,
+}
+
+/// This struct defines the pattern of the arguments for the `block` sub-command.
+/// If no height is provided, the latest block is queried.
+/// If no network is provided, the user is prompted to select one.
+#[derive(Debug, Clone, Args)]
+pub struct BlockArgs {
+    /// The height of the block to query or `latest`.
+    #[clap(default_value = "latest")]
+    height: String,
+    /// The network to query the block from.
+    #[clap(short, long)]
+    network: Option<Network>,
+}
+
+/// This struct defines the pattern of the arguments for the `localnet` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct LocalnetArgs {
+    /// The `localnet` sub-command to execute.
+    #[clap(subcommand)]
+    pub subcmd: LocalnetSubCommand,
+}
+
+/// This enum defines the sub-commands of the `localnet` sub-command.
+#[derive(Debug, Clone, Subcommand)]
+pub enum LocalnetSubCommand {
+    /// Initializes the home directory for a local node with funded test keys.
+    Init(LocalnetInitArgs),
+}
+
+/// This struct defines the pattern of the arguments for the `localnet init` sub-command.
+/// If no home directory is provided, the default home for the local node is used.
+#[derive(Debug, Clone, Args)]
+pub struct LocalnetInitArgs {
+    /// The home directory to initialize.
+    #[clap(long)]
+    home: Option<PathBuf>,
+    /// Overwrite the home directory if it already exists.
+    #[clap(long)]
+    overwrite: bool,
+}
+
+/// This struct defines the pattern of the arguments for the `diff` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct DiffArgs {
+    /// The path to the old configuration file.
+    old: PathBuf,
+    /// The path to the new configuration file.
+    new: PathBuf,
+}
+
+/// This struct defines the pattern of the arguments for the `schema` sub-command.
+/// If no output path is provided, the schema is printed to stdout.
+#[derive(Debug, Clone, Args)]
+pub struct SchemaArgs {
+    /// The path to write the JSON schema to.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// This struct defines the pattern of the arguments for the `verify` sub-command.
+/// If no configuration file is provided, the user is prompted to choose one.
+#[derive(Debug, Clone, Args)]
+pub struct VerifyArgs {
+    /// The ID of the on-chain proposal.
+    #[clap(short, long)]
+    proposal_id: u64,
+    /// The path to the configuration file.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+}
+
+/// This struct defines the pattern of the arguments for the `compare-previous` sub-command.
+/// If no configuration file is provided, the user is prompted to choose one.
+#[derive(Debug, Clone, Args)]
+pub struct ComparePreviousArgs {
+    /// The path to the configuration file.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// The ID of the on-chain proposal of the previous upgrade, which is used instead of
+    /// looking up the proposal in the workspace and history.
+    #[clap(short, long)]
+    proposal_id: Option<u64>,
+}
+
+/// This struct defines the pattern of the arguments for the `history` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct HistoryArgs {
+    /// Query the execution times of recorded upgrades, whose upgrade height was reached since the last sync.
+    #[clap(long)]
+    sync: bool,
+}
+
+/// This struct defines the pattern of the arguments for the `status` sub-command.
+/// If no network is provided, the user is prompted to select one.
+#[derive(Debug, Clone, Args)]
+pub struct StatusArgs {
+    /// The network to query the governance status from.
+    #[clap(short, long)]
+    network: Option<Network>,
+    /// Show an overview of testnet and mainnet side by side with the latest block,
+    /// the scheduled upgrade plan and the newest proposals in deposit or voting period.
+    #[clap(short, long, conflicts_with = "network")]
+    all: bool,
+}
+
+/// This struct defines the pattern of the arguments for the `export-status` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct ExportStatusArgs {
+    /// The networks to export the tracked proposals from.
+    #[clap(short, long, value_delimiter = ',', default_values_t = [Network::Testnet, Network::Mainnet])]
+    networks: Vec<Network>,
+    /// The format of the exported file.
+    #[clap(long, value_enum, default_value_t = ExportFormat::Csv)]
+    format: ExportFormat,
+    /// The path of the file to write. Defaults to `proposal-status.<format>`.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// This struct defines the pattern of the arguments for the `validators` sub-command.
+/// If no network is provided, the user is prompted to select one.
+#[derive(Debug, Clone, Args)]
+pub struct ValidatorsArgs {
+    /// The network to query the validator set from.
+    #[clap(short, long)]
+    network: Option<Network>,
+    /// The path of the CSV file to write. Defaults to `validators-<network>.csv`.
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// This struct defines the pattern of the arguments for the `nag` sub-command.
+/// If no network is provided, the user is prompted to select one.
+#[derive(Debug, Clone, Args)]
+pub struct NagArgs {
+    /// The ID of the proposal in voting period.
+    proposal_id: u64,
+    /// The network, on which the proposal was submitted.
+    #[clap(short, long)]
+    network: Option<Network>,
+    /// The number of validators with the highest voting power to list.
+    #[clap(short, long, default_value_t = 20)]
+    top: usize,
+}
+
+/// This struct defines the pattern of the arguments for the `runbook` sub-command.
+/// If no path is provided, the user is prompted to choose a configuration file.
+#[derive(Debug, Clone, Args)]
+pub struct RunbookArgs {
+    /// The path to the configuration file.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+}
+
+/// This struct defines the pattern of the arguments for the `calendar` sub-command.
+/// If no path is provided, the user is prompted to choose a configuration file.
+#[derive(Debug, Clone, Args)]
+pub struct CalendarArgs {
+    /// The path to the configuration file.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+}
+
+/// This struct defines the pattern of the arguments for the `reminder` sub-command.
+/// If no path is provided, the user is prompted to choose a configuration file.
+#[derive(Debug, Clone, Args)]
+pub struct ReminderArgs {
+    /// The path to the configuration file.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// The ID of the proposal, which overrides the proposal ID stored in the configuration.
+    #[clap(short, long)]
+    proposal_id: Option<u64>,
+    /// Send the reminder to the configured notification channels.
+    #[clap(long)]
+    notify: bool,
+}
+
+/// This struct defines the pattern of the arguments for the `import` sub-command.
+/// If no network is provided, the user is prompted to select one.
+#[derive(Debug, Clone, Args)]
+pub struct ImportArgs {
+    /// The ID of the on-chain proposal.
+    #[clap(short, long)]
+    proposal_id: u64,
+    /// The network, on which the proposal was submitted.
+    #[clap(short, long)]
+    network: Option<Network>,
+    /// The version to upgrade from, if it cannot be detected from the proposal description.
+    #[clap(long)]
+    previous_version: Option<String>,
+    /// The home directory of the Evmos binary. Defaults to the home directory of the network.
+    #[clap(long)]
+    home: Option<PathBuf>,
+    /// The format of the exported configuration file.
+    #[clap(long, value_enum, default_value_t = ConfigFormat::Json)]
+    format: ConfigFormat,
+}
+
+/// This struct defines the pattern of the arguments for the `instructions` sub-command.
+/// If no path is provided, the user is prompted to choose a configuration file.
+#[derive(Debug, Clone, Args)]
+pub struct InstructionsArgs {
+    /// The path to the configuration file.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// Render the template leniently, replacing unknown placeholders with empty values
+    /// and only warning about them instead of failing.
+    #[clap(long)]
+    no_strict: bool,
+}
+
+/// This struct defines the pattern of the arguments for the `serve` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct ServeArgs {
+    /// The address, on which the API server listens.
+    #[clap(short, long, default_value = serve::DEFAULT_ADDRESS)]
+    address: String,
+    /// Render the templates leniently, replacing unknown placeholders with empty values
+    /// and only warning about them instead of failing.
+    #[clap(long)]
+    no_strict: bool,
+}
+
+/// This struct defines the pattern of the arguments for the `encrypt` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct EncryptArgs {
+    /// The value to encrypt. If not provided, the value is prompted for without echoing it.
+    #[clap(long)]
+    value: Option<String>,
+}
+
+/// This struct defines the pattern of the arguments for the `keys` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct KeysArgs {
+    /// The `keys` sub-command to execute.
+    #[clap(subcommand)]
+    pub subcmd: KeysSubCommand,
+}
+
+/// This enum defines the sub-commands of the `keys` sub-command.
+#[derive(Debug, Clone, Subcommand)]
+pub enum KeysSubCommand {
+    /// Imports a submission key into a dedicated `test` keyring, e.g. in ephemeral CI pipelines.
+    Import(KeysImportArgs),
+}
+
+/// This struct defines the pattern of the arguments for the `keys import` sub-command.
+/// The secret is read from the given environment variable or, if it is not set, from the standard input.
+#[derive(Debug, Clone, Args)]
+pub struct KeysImportArgs {
+    /// The name of the imported key.
+    name: String,
+    /// The dedicated home directory of the keyring. The default home directories cannot be used.
+    #[clap(long)]
+    home: PathBuf,
+    /// The format of the secret.
+    #[clap(long, value_enum, default_value_t = ImportSource::Mnemonic)]
+    source: ImportSource,
+    /// The network, for which the client configuration is written if the home directory has none.
+    #[clap(short, long, value_enum, default_value_t = Network::Testnet)]
+    network: Network,
+    /// The environment variable, which contains the secret.
+    #[clap(long, default_value = "PROPOSER_KEY_SECRET")]
+    secret_env: String,
+}
+
+/// This struct defines the pattern of the arguments for the `chain` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct ChainArgs {
+    /// The `chain` sub-command to execute.
+    #[clap(subcommand)]
+    pub subcmd: ChainSubCommand,
+}
+
+/// This enum defines the sub-commands of the `chain` sub-command.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ChainSubCommand {
+    /// Adds the endpoints, chain ID, Bech32 prefix and explorer of a chain from the Cosmos chain registry
+    /// to the chain configuration of a network.
+    Add(ChainAddArgs),
+}
+
+/// This struct defines the pattern of the arguments for the `chain add` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct ChainAddArgs {
+    /// The name of the chain in the chain registry, e.g. `evmos` or `evmostestnet`.
+    name: String,
+    /// The network, whose chain configuration is populated. Testnets are looked up in the `testnets` directory of the registry.
+    #[clap(short, long, value_enum, default_value_t = Network::Mainnet)]
+    network: Network,
+    /// The base URL of the chain registry.
+    #[clap(long, default_value = registry::CHAIN_REGISTRY_URL)]
+    registry_url: String,
+}
+
+/// This struct defines the pattern of the arguments for the `heights` sub-command.
+/// If no time is provided, the upgrade time and network of the chosen configuration are used.
+#[derive(Debug, Clone, Args)]
+pub struct HeightsArgs {
+    /// The time, for which the heights are estimated, e.g. `2024-03-01T14:00:00Z`.
+    #[clap(short, long)]
+    time: Option<DateTime<Utc>>,
+    /// The network, whose reference chains are used if a time is given.
+    #[clap(short, long, requires = "time")]
+    network: Option<Network>,
+    /// The path to the configuration file, whose upgrade time and network are used.
+    #[clap(short, long, conflicts_with = "time")]
+    config: Option<PathBuf>,
+}
+
+/// This struct defines the pattern of the arguments for the `template` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct TemplateArgs {
+    /// The `template` sub-command to execute.
+    #[clap(subcommand)]
+    pub subcmd: TemplateSubCommand,
+}
+
+/// This enum defines the sub-commands of the `template` sub-command.
+#[derive(Debug, Clone, Subcommand)]
+pub enum TemplateSubCommand {
+    /// Renders a proposal template and re-renders it whenever the template or the configuration changes.
+    Test(TemplateTestArgs),
+}
+
+/// This struct defines the pattern of the arguments for the `template test` sub-command.
+/// If no configuration is provided, the template is rendered against a sample configuration.
+#[derive(Debug, Clone, Args)]
+pub struct TemplateTestArgs {
+    /// The path to the Handlebars template to render.
+    template: PathBuf,
+    /// The path to the configuration file, whose data is rendered.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// Render the template once instead of re-rendering it on changes.
+    #[clap(long)]
+    once: bool,
+    /// Render unknown placeholders as empty values instead of failing.
+    #[clap(long)]
+    no_strict: bool,
+}
+
+/// This struct defines the pattern of the arguments for the `promote` sub-command.
+/// If no path is provided, the user is prompted to choose a configuration file.
+#[derive(Debug, Clone, Args)]
+pub struct PromoteArgs {
+    /// The path to the configuration file of the proposal.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// The revision of the proposal to promote as final.
+    #[clap(short, long)]
+    revision: u32,
+}
+
+/// This struct defines the pattern of the arguments for the `approve` sub-command.
+/// If no path is provided, the user is prompted to choose a configuration file.
+#[derive(Debug, Clone, Args)]
+pub struct ApproveArgs {
+    /// The path to the configuration file of the proposal.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// The name of the reviewer, which has to match the principal in the allowed signers file.
+    #[clap(short, long)]
+    reviewer: String,
+    /// The path to the SSH key to sign the approval with.
+    /// If the private key is held by the SSH agent, the public key can be passed instead.
+    #[clap(short, long)]
+    key: PathBuf,
+}
+
+/// This struct defines the pattern of the arguments for the `verify-approvals` sub-command.
+/// If no path is provided, the user is prompted to choose a configuration file.
+#[derive(Debug, Clone, Args)]
+pub struct VerifyApprovalsArgs {
+    /// The path to the configuration file of the proposal.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// The path to the SSH allowed signers file with the trusted reviewer keys,
+    /// which overrides the file configured for the network.
+    #[clap(long)]
+    allowed_signers: Option<PathBuf>,
+}
+
+/// This struct defines the pattern of the arguments for the `telegram` sub-command.
+/// If no path is provided, the user is prompted to choose a configuration file.
+#[derive(Debug, Clone, Args)]
+pub struct TelegramArgs {
+    /// The path to the configuration file of the upgrade to follow.
+    #[clap(short, long)]
+    config: Option<PathBuf>,
+    /// The IDs of the Telegram users, which are allowed to use the bot.
+    #[clap(short, long, value_delimiter = ',', required = true)]
+    allowed_users: Vec<i64>,
+    /// The ID of the chat, to which the milestone alerts are sent.
+    #[clap(long)]
+    alert_chat: Option<i64>,
+}
+
+/// This struct defines the pattern of the arguments for the `digest` sub-command.
+#[derive(Debug, Clone, Args)]
+pub struct DigestArgs {
+    /// The networks to query the proposals in voting period from.
+    #[clap(short, long, value_delimiter = ',', default_values_t = [Network::Testnet, Network::Mainnet])]
+    networks: Vec<Network>,
+    /// The number of days to look ahead for scheduled upgrades.
+    #[clap(short, long, default_value_t = 7)]
+    days: i64,
+}
+
+/// This struct defines the pattern of the arguments for the `lint` sub-command.
+/// If no guidelines are provided, the default guidelines for software upgrades are used.
+#[derive(Debug, Clone, Args)]
+pub struct LintArgs {
+    /// The path to the rendered proposal.
+    proposal: PathBuf,
+    /// The path to the governance guidelines to check the proposal against.
+    #[clap(short, long, default_value = proposal::GUIDELINES_FILE)]
+    guidelines: PathBuf,
+    /// The LLM model to use for the review.
+    #[clap(short, long, default_value_t = OpenAIModel::Gpt4o)]
+    model: OpenAIModel,
+}
+
+/// Prompts the user to select the key to submit the proposal with from the keyring in the given home directory
+/// and the fee granter, whose allowance pays the fees, unless one is given.
+/// Returns the client configuration of the home directory, the selected key and the fee granter.
+/// A warning is printed if the selected key has transactions in flight.
+///
+/// If no fee granter pays the fees and the balance of the selected key does not cover them,
+/// the balance is polled until the key is funded, when waiting for funds is enabled.
+async fn choose_key_and_fee_granter(
+    ctx: &Context,
+    network: Network,
+    evmosd_home: &Path,
+    key_filter: Option<regex::Regex>,
+    fee_granter: Option<String>,
+    wait_for_funds: Option<u64>,
+) -> Result<(ClientConfig, String, Option<String>), CommandError> {
+    let client_config = get_client_config(evmosd_home.join("config/client.toml").as_path())?;
+    let chain_config = ctx.chain_config(network)?;
+    let fee_denom = chain_config.get_fee_denom(network);
+    let rest_url = block::get_rest_endpoint(network, &chain_config)?;
+
+    let filter_keys_config = keys::FilterKeysConfig {
+        config: client_config.clone(),
+        home: evmosd_home.to_path_buf(),
+        bech32_prefix: chain_config.bech32_prefix,
+        fee_denom: fee_denom.clone(),
+        name_filter: key_filter,
+        rest_url: rest_url.clone(),
+    };
+    let keys_with_balances = keys::get_keys_with_balances(filter_keys_config.clone()).await?;
+    let balances: HashMap<String, u128> = keys_with_balances
+        .iter()
+        .map(|key| (key.name.clone(), key.balance))
+        .collect();
+    let key = inputs::get_key(ctx.inputs.as_ref(), keys_with_balances, &fee_denom, network)?;
+    let address = keys::get_key_address(&filter_keys_config, &key)?;
+    warn_on_sequence_conflict(ctx, network, &address).await;
+
+    let fee_granter = match fee_granter {
+        Some(fee_granter) => Some(fee_granter),
+        None => {
+            let fee_granters = feegrant::get_fee_granters(&address, &rest_url, Utc::now()).await?;
+            inputs::get_fee_granter(ctx.inputs.as_ref(), fee_granters)?
+        }
+    };
+
+    let fees: u128 = command::FEE_AMOUNT.parse().unwrap_or_default();
+    let balance = balances.get(&key).copied().unwrap_or_default();
+    if fee_granter.is_none() && balance < fees {
+        match wait_for_funds {
+            Some(timeout) => {
+                balance::wait_for_funds(
+                    &address,
+                    &fee_denom,
+                    &network,
+                    &rest_url,
+                    fees,
+                    balance::FUNDS_POLL_INTERVAL,
+                    std::time::Duration::from_secs(timeout),
+                )
+                .await?;
+            }
+            None => println!(
+                "WARNING: the balance of {} does not cover the fees of {}; use --wait-for-funds to wait until it is funded",
+                key,
+                format_coin(command::FEE_AMOUNT, &fee_denom, network)
+            ),
+        }
+    }
+
+    Ok((client_config, key, fee_granter))
+}
+
+/// Checks right before the submission whether another transaction of the given signer
+/// is still in flight on the given network and prints a warning if so.
+///
+/// The check is best effort, so that failed queries only result in a warning as well.
+async fn warn_on_sequence_conflict(ctx: &Context, network: Network, address: &str) {
+    let res = match ctx.block_provider(network) {
+        Ok(provider) => sequence::check_sequence_conflict(&provider, address)
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
     };
 
-    let mut upgrade_helper = get_helper_from_json(&helper_config_path)?;
-    let client_config = get_client_config(
-        upgrade_helper
-            .evmosd_home
-            .join("config/client.toml")
-            .as_path(),
+    match res {
+        Ok(None) => {}
+        Ok(Some(conflict)) => println!("WARNING: {}", conflict),
+        Err(e) => println!("WARNING: failed to check account sequence: {}", e),
+    }
+}
+
+/// Runs the logic for the `generate-command` sub-command.
+pub async fn generate_command(
+    ctx: &Context,
+    args: GenerateCommandArgs,
+) -> Result<(), CommandError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+
+    // NOTE: the home directory is not validated for the unsigned export and the direct submission,
+    // which need no client configuration
+    let mut upgrade_helper = match args.export_unsigned.is_some() || args.submit {
+        true => from_file(&helper_config_path)?,
+        false => get_helper_from_file(ctx, &helper_config_path)?,
+    };
+    upgrade_helper
+        .check_not_draft()
+        .map_err(HelperError::from)?;
+    if let Some(deposit) = args.deposit {
+        upgrade_helper.deposit = Some(deposit.to_string());
+    }
+    let chain_config = ctx.chain_config(upgrade_helper.network)?;
+    approval::require_approvals(
+        Path::new("."),
+        &upgrade_helper.proposal_file_name,
+        chain_config.required_approvals,
+        chain_config.allowed_signers.as_deref(),
     )?;
+    if !args.skip_preflight {
+        preflight::run(ctx, Flow::Command(upgrade_helper.network)).await?;
+    }
 
     if upgrade_helper.network == Network::Mainnet {
-        let commonwealth_link = inputs::choose_commonwealth_link().await?;
+        let commonwealth_link = inputs::choose_commonwealth_link(ctx.inputs.as_ref()).await?;
         check_commonwealth_link(&commonwealth_link, &upgrade_helper).await?;
         upgrade_helper.commonwealth_link = Some(commonwealth_link.clone());
     }
 
-    let keys_with_balances = keys::get_keys_with_balances(keys::FilterKeysConfig {
-        config: client_config.clone(),
-        home: upgrade_helper.evmosd_home.clone(),
-        network: upgrade_helper.network,
-    })
+    // NOTE: the unsigned transaction is built without accessing the local keyring or client configuration
+    if let Some(path) = args.export_unsigned {
+        let mut proposer = match args.proposer {
+            Some(proposer) => proposer,
+            None => inputs::get_text(
+                ctx.inputs.as_ref(),
+                "proposer_address",
+                "Address of the proposer:",
+            )?,
+        };
+        if proposer.starts_with("0x") {
+            proposer = from_hex_address(&proposer, &chain_config.bech32_prefix)?;
+        }
+        warn_on_sequence_conflict(ctx, upgrade_helper.network, &proposer).await;
+        return export_unsigned_tx(
+            ctx,
+            &helper_config_path,
+            &upgrade_helper,
+            &proposer,
+            args.fee_granter.as_deref(),
+            &inputs::get_message_files(ctx.inputs.as_ref())?,
+            &path,
+        )
+        .await;
+    }
+
+    if args.submit {
+        let secret = std::env::var(&args.secret_env)
+            .map_err(|_| CommandError::MissingSecret(args.secret_env.clone()))?;
+        let account =
+            Account::from_secret(args.secret_source, &secret, &chain_config.bech32_prefix)?;
+        warn_on_sequence_conflict(ctx, upgrade_helper.network, &account.address).await;
+        return submit_proposal(
+            ctx,
+            &upgrade_helper,
+            &account,
+            args.fee_granter.as_deref(),
+            &inputs::get_message_files(ctx.inputs.as_ref())?,
+        )
+        .await;
+    }
+
+    let (client_config, key, fee_granter) = choose_key_and_fee_granter(
+        ctx,
+        upgrade_helper.network,
+        &upgrade_helper.evmosd_home,
+        args.key_filter,
+        args.fee_granter,
+        args.wait_for_funds,
+    )
+    .await?;
+
+    // NOTE: additional messages are only supported in gov v1 proposals
+    let message_files = match args.proposal_json {
+        true => Some(inputs::get_message_files(ctx.inputs.as_ref())?),
+        false => None,
+    };
+
+    let signer = Signer {
+        client_config: &client_config,
+        key: &key,
+        fee_granter: fee_granter.as_deref(),
+    };
+
+    // Prepare command to submit proposal and write it to file
+    let command_file_name = write_command(
+        ctx,
+        &helper_config_path,
+        &upgrade_helper,
+        &signer,
+        message_files.as_deref(),
+        !args.no_strict,
+    )
+    .await?;
+    post_to_tracking_issue(
+        ctx,
+        &upgrade_helper,
+        std::slice::from_ref(&command_file_name),
+    )
+    .await;
+
+    if args.watch {
+        let mut watched_files = vec![
+            helper_config_path.clone(),
+            PathBuf::from(&upgrade_helper.proposal_file_name),
+            PathBuf::from(command::COMMAND_TEMPLATE),
+            PathBuf::from(command::COMMONWEALTH_TEMPLATE),
+            PathBuf::from(command::COMMAND_V1_TEMPLATE),
+        ];
+        watched_files.extend(message_files.iter().flatten().cloned());
+        let mut watcher = FileWatcher::new(watched_files);
+
+        loop {
+            let changed = watcher.wait_for_change().await?;
+            println!(
+                "Detected change in {}; re-rendering command",
+                changed.display()
+            );
+
+            // NOTE: errors are only printed so that watching continues after e.g. invalid edits
+            match rerender_command(
+                ctx,
+                &helper_config_path,
+                &upgrade_helper,
+                &signer,
+                message_files.as_deref(),
+                !args.no_strict,
+            )
+            .await
+            {
+                Ok(_) => println!("Wrote command to {}", command_file_name),
+                Err(e) => println!("Error re-rendering command: {}", e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-renders the command from the configuration at the given path, using the same key, fee granter
+/// and client configuration as the initial run. The Commonwealth link and the deposit of the initial run
+/// are used, unless the configuration contains them.
+async fn rerender_command(
+    ctx: &Context,
+    helper_config_path: &Path,
+    initial_helper: &UpgradeHelper,
+    signer: &Signer<'_>,
+    message_files: Option<&[PathBuf]>,
+    strict: bool,
+) -> Result<(), CommandError> {
+    let mut upgrade_helper = get_helper_from_file(ctx, helper_config_path)?;
+    if upgrade_helper.commonwealth_link.is_none() {
+        upgrade_helper.commonwealth_link = initial_helper.commonwealth_link.clone();
+    }
+    if upgrade_helper.deposit.is_none() {
+        upgrade_helper.deposit = initial_helper.deposit.clone();
+    }
+
+    write_command(
+        ctx,
+        helper_config_path,
+        &upgrade_helper,
+        signer,
+        message_files,
+        strict,
+    )
     .await?;
-    let key = inputs::get_key(keys_with_balances)?;
 
-    // Prepare command to submit proposal
-    let command = command::prepare_command(&upgrade_helper, &client_config, &key).await?;
+    Ok(())
+}
+
+/// Prepares the gov v1 proposal containing the upgrade and the messages in the given files
+/// and writes the unsigned transaction to submit it to the given path.
+async fn export_unsigned_tx(
+    ctx: &Context,
+    helper_config_path: &Path,
+    helper: &UpgradeHelper,
+    proposer: &str,
+    fee_granter: Option<&str>,
+    message_files: &[PathBuf],
+    path: &Path,
+) -> Result<(), CommandError> {
+    let proposal = command::prepare_v1_proposal(ctx, helper, message_files).await?;
+    let unsigned_tx = tx::build_unsigned_tx(
+        &proposal,
+        proposer,
+        command::FEE_AMOUNT,
+        &ctx.chain_config(helper.network)?
+            .get_fee_denom(helper.network),
+        tx::DEFAULT_GAS_LIMIT,
+        fee_granter,
+    )?;
+    utils::write_atomically(path, serde_json::to_string_pretty(&unsigned_tx)?.as_bytes())?;
+    manifest::record(
+        Path::new("."),
+        Some(helper_config_path),
+        &[path.to_string_lossy().to_string()],
+        Utc::now(),
+    )?;
+
+    println!("Wrote unsigned transaction to {}", path.display());
 
-    // Write command to file
-    Ok(utils::write_content_to_file(
-        &command,
-        &upgrade_helper.proposal_file_name.replace(".md", ".sh"),
-    )?)
+    Ok(())
 }
 
-/// Runs the logic for the `generate-proposal` sub-command.
+/// Prepares the gov v1 proposal containing the upgrade and the messages in the given files,
+/// signs the transaction to submit it with the given account and broadcasts it.
+async fn submit_proposal(
+    ctx: &Context,
+    helper: &UpgradeHelper,
+    account: &Account,
+    fee_granter: Option<&str>,
+    message_files: &[PathBuf],
+) -> Result<(), CommandError> {
+    let proposal = command::prepare_v1_proposal(ctx, helper, message_files).await?;
+    let chain_config = ctx.chain_config(helper.network)?;
+    let grpc_url = gov::get_grpc_url(helper.network, &chain_config)?;
+    let fee_denom = chain_config.get_fee_denom(helper.network);
+
+    let balance = account.balance(&fee_denom, &grpc_url).await?;
+    if fee_granter.is_none() && balance < command::FEE_AMOUNT.parse().unwrap_or_default() {
+        println!(
+            "WARNING: the balance of {} does not cover the fees of {}",
+            account.address,
+            format_coin(command::FEE_AMOUNT, &fee_denom, helper.network)
+        );
+    }
+
+    let state = account.query(&grpc_url).await?;
+    let tx_bytes = tx::build_signed_tx(
+        &proposal,
+        account,
+        &state,
+        &helper.chain_id,
+        &tx::Fee {
+            amount: command::FEE_AMOUNT,
+            denom: &fee_denom,
+            gas_limit: tx::DEFAULT_GAS_LIMIT,
+            granter: fee_granter,
+        },
+    )?;
+    let tx_hash = ctx.gov_client(helper.network)?.submit(&tx_bytes).await?;
+
+    println!(
+        "Submitted proposal from {} ({}) in transaction {}",
+        account.address,
+        account.hex_address()?,
+        tx_hash
+    );
+
+    Ok(())
+}
+
+/// Prepares the command to submit the proposal and writes it to file.
+/// If message files are given, the gov v1 proposal JSON containing the upgrade and the additional
+/// messages is written as well. The written files are recorded in the manifest together with
+/// the hash of the given configuration. Returns the name of the command file.
+async fn write_command(
+    ctx: &Context,
+    helper_config_path: &Path,
+    helper: &UpgradeHelper,
+    signer: &Signer<'_>,
+    message_files: Option<&[PathBuf]>,
+    strict: bool,
+) -> Result<String, CommandError> {
+    let mut files = Vec::new();
+    let command = match message_files {
+        Some(message_files) => {
+            let (proposal, command) =
+                command::prepare_v1_command(ctx, helper, signer, message_files, strict).await?;
+            let proposal_file_name = command::get_v1_proposal_file_name(helper);
+            utils::write_content_to_file(
+                &serde_json::to_string_pretty(&proposal)?,
+                &proposal_file_name,
+            )?;
+            files.push(proposal_file_name);
+
+            command
+        }
+        None => command::prepare_command(ctx, helper, signer, strict).await?,
+    };
+
+    let command_file_name = helper.proposal_file_name.replace(".md", ".sh");
+    utils::write_content_to_file(&command, &command_file_name)?;
+    files.push(command_file_name.clone());
+    manifest::record(Path::new("."), Some(helper_config_path), &files, Utc::now())?;
+
+    Ok(command_file_name)
+}
+
+/// Runs the logic for the `text-proposal` sub-command.
 ///
-/// This sub-command queries the user for the necessary information to prepare the proposal description
-/// for a standard Evmos software upgrade.
-pub async fn generate_proposal(args: GenerateProposalArgs) -> Result<(), ProposalError> {
-    // Create an instance of the helper
-    let upgrade_helper = get_helper_from_inputs(args.model).await?;
+/// This sub-command queries the user for the title and description of a generic text proposal,
+/// which contains no messages, and writes the gov v1 proposal JSON and the command to submit it.
+/// The description can be drafted from bullet points with the LLM. The key and the fee granter
+/// are selected in the same way as for the `generate-command` sub-command.
+pub async fn generate_text_proposal(
+    ctx: &Context,
+    args: TextProposalArgs,
+) -> Result<(), CommandError> {
+    let network = ctx.resolve_network(args.network)?;
+    let evmosd_home = ctx.resolve_home(network, None)?;
 
-    // Validate the helper configuration
-    upgrade_helper.validate()?;
+    let title = inputs::get_text(ctx.inputs.as_ref(), "title", "Title of the proposal:")?;
+    let description = match inputs::choose_description_source(ctx.inputs.as_ref())? {
+        DescriptionSource::Draft => {
+            let bullet_points = inputs::get_manual_summary(ctx.inputs.as_ref())?;
+            llm::draft_description(ctx.llm().as_ref(), &title, &bullet_points, args.model).await?
+        }
+        DescriptionSource::File => std::fs::read_to_string(inputs::get_text(
+            ctx.inputs.as_ref(),
+            "description_file",
+            "Path to the Markdown file:",
+        )?)?,
+        DescriptionSource::Manual => inputs::get_text(
+            ctx.inputs.as_ref(),
+            "description",
+            "Description of the proposal:",
+        )?,
+    };
+    let metadata = inputs::get_text(
+        ctx.inputs.as_ref(),
+        "metadata",
+        "Link to the forum discussion (leave empty to skip):",
+    )?;
 
-    // Export the configuration
-    upgrade_helper.write_to_json()?;
+    let proposal = V1Proposal::text(&title, &description, &metadata);
+    proposal.validate()?;
+    schema::validate_proposal(&proposal, ctx.chain_config(network)?.sdk_version)?;
+
+    let (client_config, key, fee_granter) = choose_key_and_fee_granter(
+        ctx,
+        network,
+        &evmosd_home,
+        args.key_filter,
+        args.fee_granter,
+        args.wait_for_funds,
+    )
+    .await?;
 
-    // Render the proposal description
-    let description = proposal::render_proposal(&upgrade_helper)?;
+    let proposal_file_name = command::get_text_proposal_file_name(network, &title);
+    utils::write_content_to_file(
+        &serde_json::to_string_pretty(&proposal)?,
+        &proposal_file_name,
+    )?;
 
-    // Write the proposal description to file
-    Ok(utils::write_content_to_file(
-        &description,
-        &upgrade_helper.proposal_file_name,
-    )?)
+    let signer = Signer {
+        client_config: &client_config,
+        key: &key,
+        fee_granter: fee_granter.as_deref(),
+    };
+    let command = command::render_v1_command(
+        &ctx.chain_config(network)?,
+        network,
+        &evmosd_home,
+        &signer,
+        &proposal_file_name,
+        !args.no_strict,
+    )?;
+    let command_file_name = proposal_file_name.replace(".json", ".sh");
+    utils::write_content_to_file(&command, &command_file_name)?;
+    manifest::record(
+        Path::new("."),
+        None,
+        &[proposal_file_name.clone(), command_file_name.clone()],
+        Utc::now(),
+    )?;
+
+    println!(
+        "Wrote text proposal to {} and the command to submit it to {}",
+        proposal_file_name, command_file_name
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `generate-proposal` sub-command.
+///
+/// This sub-command queries the user for the necessary information to prepare the proposal description
+/// for a standard Evmos software upgrade. In pair mode, the proposals for the testnet release candidate
+/// and the mainnet release of the same version line are generated in one pass.
+pub async fn generate_proposal(
+    ctx: &Context,
+    args: GenerateProposalArgs,
+) -> Result<(), ProposalError> {
+    if !args.skip_preflight {
+        preflight::run(ctx, Flow::Proposal).await?;
+    }
+
+    let options = InputOptions {
+        model: args.model,
+        upgrade_height: args.upgrade_height,
+        mainnet_version: args.mainnet_version.clone(),
+        dual_summary: args.dual_summary,
+        notes_source: args.notes_source,
+        draft: args.draft,
+    };
+
+    if args.pair {
+        let (testnet_helper, mainnet_helper) =
+            get_pair_from_inputs(ctx, options, Duration::days(args.pair_lag_days)).await?;
+        for mut upgrade_helper in [testnet_helper, mainnet_helper] {
+            upgrade_helper.set_config_format(args.format);
+            upgrade_helper.locale = args.locale;
+            export_proposal(
+                ctx,
+                &mut upgrade_helper,
+                args.target,
+                !args.no_strict,
+                !args.skip_version_policy,
+            )
+            .await?;
+        }
+
+        return Ok(());
+    }
+
+    // Create an instance of the helper or update the files of a previous run
+    let mut upgrade_helper = match get_helper_from_inputs(ctx, options).await? {
+        HelperOutcome::Created(mut upgrade_helper) => {
+            upgrade_helper.set_config_format(args.format);
+            upgrade_helper.locale = args.locale;
+            upgrade_helper
+        }
+        HelperOutcome::Existing(upgrade_helper) => upgrade_helper,
+        HelperOutcome::Kept => {
+            println!("Kept the existing files");
+            return Ok(());
+        }
+    };
+    export_proposal(
+        ctx,
+        &mut upgrade_helper,
+        args.target,
+        !args.no_strict,
+        !args.skip_version_policy,
+    )
+    .await?;
+
+    if args.watch {
+        let config_path = PathBuf::from(&upgrade_helper.config_file_name);
+        let mut watcher = FileWatcher::new(vec![
+            config_path.clone(),
+            PathBuf::from(proposal::PROPOSAL_TEMPLATE),
+        ]);
+
+        loop {
+            let changed = watcher.wait_for_change().await?;
+            println!(
+                "Detected change in {}; re-rendering proposal",
+                changed.display()
+            );
+
+            // NOTE: errors are only printed so that watching continues after e.g. invalid edits
+            match get_helper_from_file(ctx, &config_path)
+                .map_err(ProposalError::from)
+                .and_then(|helper| write_proposal(ctx, &helper, args.target, !args.no_strict))
+            {
+                Ok(_) => println!(
+                    "Wrote proposal to {}",
+                    get_proposal_file_name(&upgrade_helper, args.target)
+                ),
+                Err(e) => println!("Error re-rendering proposal: {}", e),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the logic for the `finalize` sub-command.
+///
+/// This sub-command completes a draft proposal once the release is cut and the upgrade schedule is fixed.
+/// The upgrade time is queried from the user, the placeholders of the draft are filled in
+/// and the configuration and proposal are written to the files of the draft.
+pub async fn finalize(ctx: &Context, args: FinalizeArgs) -> Result<(), ProposalError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let draft = get_helper_from_file(ctx, &helper_config_path)?;
+
+    let options = InputOptions {
+        model: args.model,
+        upgrade_height: args.upgrade_height,
+        mainnet_version: None,
+        dual_summary: args.dual_summary,
+        notes_source: args.notes_source,
+        draft: false,
+    };
+    let mut upgrade_helper = finalize_helper(ctx, &draft, options).await?;
+    export_proposal(
+        ctx,
+        &mut upgrade_helper,
+        args.target,
+        !args.no_strict,
+        !args.skip_version_policy,
+    )
+    .await?;
+
+    println!(
+        "Finalized draft and wrote proposal to {}",
+        get_proposal_file_name(&upgrade_helper, args.target)
+    );
+
+    Ok(())
+}
+
+/// Checks the version bump of the given helper against the version policy of its network.
+/// Drafts are not checked for released release candidates, because they are prepared before the release is cut.
+async fn check_version_policy(
+    ctx: &Context,
+    upgrade_helper: &UpgradeHelper,
+) -> Result<(), ProposalError> {
+    let policy = ctx.chain_config(upgrade_helper.network)?.version_policy;
+    upgrade_helper.check_version_policy(&policy)?;
+
+    if policy.mainnet_requires_rc
+        && upgrade_helper.network == Network::Mainnet
+        && !upgrade_helper.draft
+        && release::get_latest_rc(ctx.github.as_ref(), &upgrade_helper.target_version)
+            .await?
+            .is_none()
+    {
+        return Err(ValidationError::MissingReleaseCandidate(
+            upgrade_helper.target_version.clone(),
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Validates and exports the configuration of the given helper, writes the proposal
+/// and records the generated upgrade in the history. Drafts are not recorded in the history
+/// until they are finalized.
+async fn export_proposal(
+    ctx: &Context,
+    upgrade_helper: &mut UpgradeHelper,
+    target: RenderTarget,
+    strict: bool,
+    enforce_version_policy: bool,
+) -> Result<(), ProposalError> {
+    // Validate the helper configuration
+    upgrade_helper.validate()?;
+    if enforce_version_policy {
+        check_version_policy(ctx, upgrade_helper).await?;
+    }
+
+    // Link the tracking issue of the upgrade, so that it is referenced in the proposal
+    link_tracking_issue(ctx, upgrade_helper).await;
+
+    // Export the configuration
+    upgrade_helper.write_to_file(Utc::now())?;
+
+    // Render the proposal description and write it to file
+    write_proposal(ctx, upgrade_helper, target, strict)?;
+    post_to_tracking_issue(
+        ctx,
+        upgrade_helper,
+        &[
+            upgrade_helper.config_file_name.clone(),
+            upgrade_helper.proposal_file_name.clone(),
+        ],
+    )
+    .await;
+    if upgrade_helper.draft {
+        return Ok(());
+    }
+
+    // Record the generated upgrade in the history
+    let history_path = get_history_path();
+    let mut history = History::load(&history_path)?;
+    history.record_generated(upgrade_helper, Utc::now());
+    history.save(&history_path)?;
+    telemetry::record(Event::ProposalGenerated {
+        network: upgrade_helper.network,
+    })
+    .await;
+
+    Ok(())
+}
+
+/// Returns the repository, in which the upgrades on the given network are tracked, if any is configured.
+fn get_tracking_repo(ctx: &Context, network: Network) -> Option<TrackingRepo> {
+    match ctx.chain_config(network) {
+        Ok(chain_config) => chain_config.tracking_repo,
+        Err(e) => {
+            println!("WARNING: failed to get chain configuration: {}", e);
+            None
+        }
+    }
+}
+
+/// Looks up or creates the tracking issue of the upgrade in the configured tracking repository
+/// and links it in the given helper, unless it is linked already.
+///
+/// Failures are only printed as warnings, so that GitHub being unavailable does not block the proposal.
+async fn link_tracking_issue(ctx: &Context, upgrade_helper: &mut UpgradeHelper) {
+    let Some(repo) = get_tracking_repo(ctx, upgrade_helper.network) else {
+        return;
+    };
+    if upgrade_helper.tracking_issue.is_some() {
+        return;
+    }
+
+    match tracking::get_or_create_issue(&ctx.github, &repo, &upgrade_helper.target_version).await {
+        Ok(issue) => {
+            println!("Linked tracking issue {}", issue.url);
+            upgrade_helper.tracking_issue = Some(issue);
+        }
+        Err(e) => println!("WARNING: failed to get tracking issue: {}", e),
+    }
+}
+
+/// Posts the given generated files as a comment on the tracking issue of the upgrade, if it is linked.
+/// Failures are only printed as warnings.
+async fn post_to_tracking_issue(ctx: &Context, upgrade_helper: &UpgradeHelper, files: &[String]) {
+    let (Some(repo), Some(issue)) = (
+        get_tracking_repo(ctx, upgrade_helper.network),
+        &upgrade_helper.tracking_issue,
+    ) else {
+        return;
+    };
+
+    match tracking::post_artifacts(&ctx.github, &repo, issue, files).await {
+        Ok(()) => println!("Posted generated artifacts to {}", issue.url),
+        Err(e) => println!("WARNING: failed to post artifacts to tracking issue: {}", e),
+    }
+}
+
+/// Prints the word count, the estimated reading time and the reading ease of the given proposal
+/// with a warning for each limit of the community guidelines, which is exceeded.
+fn print_metrics(description: &str) {
+    let metrics = readability::get_metrics(description);
+    println!("Proposal metrics: {}", metrics);
+    match readability::get_readability_limits() {
+        Ok(limits) => {
+            for warning in limits.check(&metrics) {
+                println!("WARNING: {}", warning);
+            }
+        }
+        Err(e) => println!("WARNING: failed to check readability: {}", e),
+    }
+}
+
+/// Renders the proposal description and writes it as a new draft revision to the proposal file.
+/// For the Commonwealth target, the converted description is written to a separate file,
+/// so that the canonical proposal file is left untouched. The written files are recorded in the manifest.
+fn write_proposal(
+    ctx: &Context,
+    upgrade_helper: &UpgradeHelper,
+    target: RenderTarget,
+    strict: bool,
+) -> Result<(), ProposalError> {
+    let chain_config = ctx.chain_config(upgrade_helper.network)?;
+    let description = profile::measure("render proposal", || {
+        proposal::render_proposal(upgrade_helper, &chain_config, strict)
+    })?;
+    let revision = revision::write_revision(
+        Path::new("."),
+        &upgrade_helper.proposal_file_name,
+        &description,
+        Utc::now(),
+    )?;
+    println!(
+        "Wrote revision {} of {}",
+        revision, upgrade_helper.proposal_file_name
+    );
+    print_metrics(&description);
+
+    let mut files = vec![
+        upgrade_helper.proposal_file_name.clone(),
+        revision::get_revision_file_name(&upgrade_helper.proposal_file_name, revision),
+    ];
+    if target == RenderTarget::Commonwealth {
+        let commonwealth_file_name = get_proposal_file_name(upgrade_helper, target);
+        utils::write_content_to_file(
+            &render_commonwealth_text(&description, upgrade_helper.proposal_id),
+            &commonwealth_file_name,
+        )?;
+        files.push(commonwealth_file_name);
+    }
+
+    manifest::record(
+        Path::new("."),
+        Some(Path::new(&upgrade_helper.config_file_name)),
+        &files,
+        Utc::now(),
+    )?;
+
+    Ok(())
+}
+
+/// Runs the logic for the `block` sub-command.
+///
+/// This sub-command queries the block at the given height (or the latest block)
+/// and prints its height, time, proposer and hash.
+pub async fn query_block(ctx: &Context, args: BlockArgs) -> Result<(), QueryError> {
+    let network = ctx.resolve_network(args.network)?;
+    let provider = ctx.block_provider(network)?;
+
+    let block = match args.height.as_str() {
+        "latest" => block::get_latest_block(&provider).await?,
+        height => {
+            let height = height.parse::<u64>().map_err(BlockError::from)?;
+            block::get_block(&provider, height).await?
+        }
+    };
+
+    println!("Network:  {}\n{}", network, block);
+
+    Ok(())
+}
+
+/// Runs the logic for the `heights` sub-command.
+///
+/// This sub-command estimates the block heights of the network and its reference chains from the
+/// chain configuration at the same wall-clock time, e.g. for coordinated multi-chain events,
+/// and prints them as a Markdown table.
+pub async fn estimate_heights(ctx: &Context, args: HeightsArgs) -> Result<(), QueryError> {
+    let (network, time) = match args.time {
+        Some(time) => {
+            let network = ctx.resolve_network(args.network)?;
+            (network, time)
+        }
+        None => {
+            let helper_config_path = ctx.resolve_config(args.config)?;
+            let helper = from_file(&helper_config_path)?;
+            (helper.network, helper.upgrade_time)
+        }
+    };
+
+    if time <= Utc::now() {
+        println!("WARNING: the given time is in the past; the latest heights are shown instead");
+    }
+
+    let chain_config = ctx.chain_config(network)?;
+    if chain_config.reference_chains.is_empty() {
+        println!(
+            "WARNING: no reference chains configured for {}; only its own height is estimated",
+            network
+        );
+    }
+
+    let providers = heights::get_providers(network, &chain_config, &ctx.cache)?;
+    let estimates = heights::estimate_heights(providers, time).await;
+    println!("{}", heights::render_table(&estimates, time));
+
+    Ok(())
+}
+
+/// Runs the logic for the `localnet init` sub-command.
+///
+/// This sub-command initializes the home directory for a local node, so that the generated
+/// commands for the local node network can be tested.
+pub fn init_localnet(ctx: &Context, args: LocalnetInitArgs) -> Result<(), LocalnetError> {
+    let home = resolve::get_home_override(&ctx.flags, args.home)
+        .unwrap_or_else(|| inputs::get_default_evmosd_home(&Network::LocalNode));
+
+    localnet::init(
+        &home,
+        &ctx.chain_config(Network::LocalNode)?,
+        args.overwrite,
+    )?;
+
+    println!(
+        "Initialized local node in {} with the funded keys: {}",
+        home.display(),
+        localnet::LOCALNET_KEYS.join(", ")
+    );
+    println!(
+        "Start the node with: evmosd start --home {}",
+        home.display()
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `diff` sub-command.
+///
+/// This sub-command prints the differences between two exported upgrade configurations,
+/// ordered by their severity.
+pub fn diff_configs(ctx: &Context, args: DiffArgs) -> Result<(), HelperError> {
+    let old = from_file(&args.old)?;
+    let new = from_file(&args.new)?;
+
+    let diffs = diff::compare_helpers(&old, &new);
+    if diffs.is_empty() {
+        println!("No differences found");
+        return Ok(());
+    }
+
+    print!(
+        "{}",
+        diff::render_diffs(
+            &diffs,
+            &args.old.display().to_string(),
+            &args.new.display().to_string(),
+            ctx.colored,
+        )
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `schema` sub-command.
+///
+/// This sub-command exports the JSON schema of the upgrade configuration, which can be referenced
+/// in editors and external tools to validate and autocomplete the configuration files.
+pub fn export_schema(args: SchemaArgs) -> Result<(), HelperError> {
+    let schema = serde_json::to_string_pretty(&schema::get_helper_schema())?;
+    match args.output {
+        Some(output) => {
+            utils::write_atomically(&output, schema.as_bytes())?;
+            println!("Exported configuration schema to {}", output.display());
+        }
+        None => println!("{}", schema),
+    }
+
+    Ok(())
+}
+
+/// Runs the logic for the `verify` sub-command.
+///
+/// This sub-command queries the given proposal from the network of the local configuration
+/// and checks that the upgrade plan matches the configuration and the proposal description.
+/// An error is returned if any critical mismatches are found.
+pub async fn verify_proposal(ctx: &Context, args: VerifyArgs) -> Result<(), VerifyError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let mut helper = from_file(&helper_config_path)?;
+
+    let gov_client = ctx.gov_client(helper.network)?;
+    let proposal = gov_client.proposal(args.proposal_id).await?;
+    let plan = proposal
+        .get_upgrade_plan()
+        .ok_or(VerifyError::NoUpgradePlan(args.proposal_id))?;
+
+    let description = match Path::new(&helper.proposal_file_name).exists() {
+        true => Some(std::fs::read_to_string(&helper.proposal_file_name)?),
+        false => {
+            println!(
+                "WARNING: proposal file {} not found; skipping description check",
+                helper.proposal_file_name
+            );
+            None
+        }
+    };
+
+    let diffs = diff::compare_upgrade_plan(&helper, &plan, description.as_deref());
+    if diff::count_critical(&diffs) == 0 {
+        let history_path = get_history_path();
+        let mut history = History::load(&history_path)?;
+        if history.set_proposal_id(helper.network, &helper.target_version, args.proposal_id) {
+            history.save(&history_path)?;
+        }
+
+        confirm_proposal_id(
+            &mut helper,
+            &helper_config_path,
+            args.proposal_id,
+            &description,
+        )?;
+
+        // NOTE: the expected voting period is replaced with the actual one of the submitted proposal
+        if helper.set_voting_times(proposal.voting_start_time, proposal.voting_end_time) {
+            helper
+                .write_to_path(&helper_config_path, Utc::now())
+                .map_err(VerifyError::UpdateHelper)?;
+        }
+        if let (Some(start), Some(end)) = (helper.voting_start_time, helper.voting_end_time) {
+            println!(
+                "Voting period: {} to {}",
+                inputs::get_time_string(start),
+                inputs::get_time_string(end)
+            );
+        }
+    }
+
+    if diffs.is_empty() {
+        println!(
+            "Proposal {} matches the local configuration",
+            args.proposal_id
+        );
+        return Ok(());
+    }
+
+    print!(
+        "{}",
+        diff::render_diffs(
+            &diffs,
+            &helper_config_path.display().to_string(),
+            &format!("proposal {}", args.proposal_id),
+            ctx.colored,
+        )
+    );
+
+    match diff::count_critical(&diffs) {
+        0 => Ok(()),
+        n => Err(VerifyError::CriticalMismatch(n)),
+    }
+}
+
+/// Stores the confirmed proposal ID in the helper configuration and updates an existing
+/// Commonwealth text, which contained the expected proposal ID.
+fn confirm_proposal_id(
+    helper: &mut UpgradeHelper,
+    helper_config_path: &Path,
+    proposal_id: u64,
+    description: &Option<String>,
+) -> Result<(), VerifyError> {
+    let confirmed = gov::ProposalId {
+        id: proposal_id,
+        confirmed: true,
+    };
+    if helper.proposal_id == Some(confirmed) {
+        return Ok(());
+    }
+
+    if let Some(expected) = helper
+        .proposal_id
+        .filter(|expected| expected.id != proposal_id)
+    {
+        println!(
+            "WARNING: proposal was submitted with ID {} instead of the expected ID {}",
+            proposal_id, expected.id
+        );
+    }
+    helper.proposal_id = Some(confirmed);
+    helper
+        .write_to_path(helper_config_path, Utc::now())
+        .map_err(VerifyError::UpdateHelper)?;
+
+    let commonwealth_file = get_proposal_file_name(helper, RenderTarget::Commonwealth);
+    if let (true, Some(description)) = (Path::new(&commonwealth_file).exists(), description) {
+        utils::write_content_to_file(
+            &render_commonwealth_text(description, helper.proposal_id),
+            &commonwealth_file,
+        )
+        .map_err(VerifyError::WriteCommonwealth)?;
+        println!("Updated proposal ID in {}", commonwealth_file);
+    }
+
+    Ok(())
+}
+
+/// Runs the logic for the `compare-previous` sub-command.
+///
+/// This sub-command compares the proposal with the proposal of the previous upgrade section by section,
+/// so that the wording and structure stay consistent across upgrades. The previous proposal is read
+/// from the workspace if it exists there and otherwise queried from chain using the recorded proposal ID.
+/// The versions are masked in both proposals, so that only the actual changes are shown.
+pub async fn compare_previous(
+    ctx: &Context,
+    args: ComparePreviousArgs,
+) -> Result<(), CompareError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let helper = from_file(&helper_config_path)?;
+    let current = std::fs::read_to_string(&helper.proposal_file_name)?;
+
+    let history = History::load(&get_history_path())?;
+    let record = history.get(helper.network, &helper.previous_version);
+    let previous_file_name = format!("proposal-{}-{}.md", helper.network, helper.previous_version);
+
+    let local_exists = Path::new(&previous_file_name).exists();
+    let proposal_id = match args.proposal_id {
+        Some(proposal_id) => Some(proposal_id),
+        None if !local_exists => record.and_then(|record| record.proposal_id),
+        None => None,
+    };
+
+    let (source, previous) = match proposal_id {
+        Some(proposal_id) => {
+            let proposal = ctx
+                .gov_client(helper.network)?
+                .proposal(proposal_id)
+                .await?;
+            let plan = proposal
+                .get_upgrade_plan()
+                .ok_or(CompareError::NoUpgradePlan(proposal_id))?;
+            (format!("proposal {}", proposal_id), plan.description)
+        }
+        None if local_exists => (
+            previous_file_name.clone(),
+            std::fs::read_to_string(&previous_file_name)?,
+        ),
+        None => return Err(CompareError::MissingPrevious(helper.previous_version)),
+    };
+
+    // NOTE: the previous upgrade went from an unknown version to the current previous version, unless it was recorded
+    let previous = diff::mask_versions(
+        &previous,
+        &helper.previous_version,
+        record.map_or("", |record| record.previous_version.as_str()),
+    );
+    let current = diff::mask_versions(&current, &helper.target_version, &helper.previous_version);
+
+    let diffs = diff::compare_proposals(&previous, &current);
+    if diffs.is_empty() {
+        println!(
+            "{} is structured and worded like {}",
+            helper.proposal_file_name, source
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        diff::render_diffs(&diffs, &source, &helper.proposal_file_name, ctx.colored)
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `history` sub-command.
+///
+/// This sub-command prints all recorded upgrades with their prediction errors
+/// and the aggregated statistics. If syncing is enabled, the execution times of the upgrades,
+/// whose heights were reached in the meantime, are queried from the respective networks first.
+pub async fn show_history(ctx: &Context, args: HistoryArgs) -> Result<(), HistoryError> {
+    let history_path = get_history_path();
+    let mut history = History::load(&history_path)?;
+
+    if args.sync {
+        for record in history.records.iter_mut() {
+            if record.executed_at.is_some() {
+                continue;
+            }
+
+            // NOTE: errors are only printed so that unreachable networks (e.g. a stopped local node)
+            // don't prevent syncing the other records
+            match get_execution_time(ctx, record.network, record.upgrade_height).await {
+                Ok(executed_at) => {
+                    record.executed_at = executed_at;
+                    if let Some(prediction_error_minutes) = record.prediction_error_minutes() {
+                        telemetry::record(Event::UpgradeExecuted {
+                            network: record.network,
+                            prediction_error_minutes,
+                        })
+                        .await;
+                    }
+                }
+                Err(e) => println!(
+                    "WARNING: failed to sync {} upgrade to {}: {}",
+                    record.network, record.target_version, e
+                ),
+            }
+        }
+
+        history.save(&history_path)?;
+    }
+
+    println!("{}", history.render());
+
+    Ok(())
+}
+
+/// Returns the time of the block at the given upgrade height,
+/// or None if the height was not reached yet.
+async fn get_execution_time(
+    ctx: &Context,
+    network: Network,
+    upgrade_height: u64,
+) -> Result<Option<chrono::DateTime<Utc>>, BlockError> {
+    let provider = ctx.block_provider(network)?;
+    if block::get_latest_block(&provider).await?.height < upgrade_height {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        block::get_block(&provider, upgrade_height).await?.time,
+    ))
+}
+
+/// Runs the logic for the `status` sub-command.
+///
+/// This sub-command prints the governance parameters of the given network
+/// and the proposals that are currently in the voting period.
+/// With `--all`, an overview of testnet and mainnet is printed side by side instead.
+pub async fn show_status(ctx: &Context, args: StatusArgs) -> Result<(), StatusError> {
+    if args.all {
+        let testnet_client = ctx.gov_client(Network::Testnet)?;
+        let mainnet_client = ctx.gov_client(Network::Mainnet)?;
+        let testnet_provider = ctx.block_provider(Network::Testnet)?;
+        let mainnet_provider = ctx.block_provider(Network::Mainnet)?;
+
+        // NOTE: both networks are queried concurrently, so that the overview is quick to print
+        let (testnet, mainnet) = tokio::join!(
+            status::get_overview(testnet_client.as_ref(), &testnet_provider, Network::Testnet),
+            status::get_overview(mainnet_client.as_ref(), &mainnet_provider, Network::Mainnet),
+        );
+        println!("{}", status::render_columns(&[testnet, mainnet]));
+
+        return Ok(());
+    }
+
+    let network = ctx.resolve_network(args.network)?;
+
+    let gov_client = ctx.gov_client(network)?;
+    println!(
+        "{}",
+        status::get_status(gov_client.as_ref(), network, Utc::now()).await?
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `export-status` sub-command.
+///
+/// This sub-command collects the status of all proposals, whose IDs are tracked in the history,
+/// including their tally, turnout, voting deadline and final outcome, and writes them to a CSV or JSON file.
+/// Proposals, which cannot be queried, are skipped with a warning.
+pub async fn export_status(ctx: &Context, args: ExportStatusArgs) -> Result<(), ExportError> {
+    let history = History::load(&get_history_path())?;
+
+    let mut records = Vec::new();
+    for network in args.networks.iter() {
+        let tracked: Vec<(&history::UpgradeRecord, u64)> = history
+            .records
+            .iter()
+            .filter(|record| record.network == *network)
+            .filter_map(|record| Some((record, record.proposal_id?)))
+            .collect();
+        if tracked.is_empty() {
+            continue;
+        }
+
+        let gov_client = match ctx.gov_client(*network) {
+            Ok(gov_client) => gov_client,
+            Err(e) => {
+                println!(
+                    "WARNING: failed to get governance client for {}: {}",
+                    network, e
+                );
+                continue;
+            }
+        };
+        // NOTE: the turnout is left empty if the bonded tokens cannot be queried
+        let bonded_tokens = async {
+            let rest_url = block::get_rest_endpoint(*network, &ctx.chain_config(*network)?)?;
+            export::get_bonded_tokens(&rest_url).await
+        };
+        let bonded_tokens = match bonded_tokens.await {
+            Ok(bonded_tokens) => Some(bonded_tokens),
+            Err(e) => {
+                println!(
+                    "WARNING: failed to query bonded tokens on {}: {}",
+                    network, e
+                );
+                None
+            }
+        };
+
+        for (record, proposal_id) in tracked {
+            match export::get_status_record(gov_client.as_ref(), record, proposal_id, bonded_tokens)
+                .await
+            {
+                Ok(status) => records.push(status),
+                Err(e) => println!(
+                    "WARNING: failed to query proposal {} on {}: {}",
+                    proposal_id, network, e
+                ),
+            }
+        }
+    }
+
+    let output = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(export::get_export_file_name(args.format)));
+    utils::write_atomically(&output, export::render(&records, args.format)?.as_bytes())?;
+
+    println!(
+        "Exported the status of {} proposals to {}",
+        records.len(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `digest` sub-command.
+///
+/// This sub-command summarizes the upgrade configurations in the current directory that were not submitted yet,
+/// the proposals in voting period and the upcoming scheduled upgrades as Markdown.
+pub async fn show_digest(ctx: &Context, args: DigestArgs) -> Result<(), DigestError> {
+    let now = Utc::now();
+    let history = History::load(&get_history_path())?;
+    let digest = Digest::collect(
+        ctx,
+        &std::env::current_dir()?,
+        &history,
+        &args.networks,
+        chrono::Duration::days(args.days),
+        now,
+    )
+    .await?;
+
+    println!("{}", digest.render(now));
+
+    Ok(())
+}
+
+/// Runs the logic for the `lint` sub-command.
+///
+/// This sub-command reviews the rendered proposal against the governance guidelines
+/// and reports the missing items, so that they can be added before publishing the proposal.
+pub async fn lint_proposal(ctx: &Context, args: LintArgs) -> Result<(), LintError> {
+    let proposal = std::fs::read_to_string(&args.proposal)?;
+    let guidelines = std::fs::read_to_string(&args.guidelines)?;
+
+    print_metrics(&proposal);
+
+    let broken_links = links::check_links(&proposal, &ctx.cache).await;
+    for (link, e) in broken_links.iter() {
+        println!("Broken link {}: {}", link, e);
+    }
+
+    let missing_items =
+        llm::lint_proposal(ctx.llm().as_ref(), &proposal, &guidelines, args.model).await?;
+    if !missing_items.is_empty() {
+        println!("Missing guideline items:");
+        for item in missing_items.iter() {
+            println!("- {}", item);
+        }
+
+        return Err(LintError::MissingItems(missing_items.len()));
+    }
+
+    if !broken_links.is_empty() {
+        return Err(LintError::BrokenLinks(broken_links.len()));
+    }
+
+    println!("Proposal covers all guideline items");
+    Ok(())
+}
+
+/// Runs the logic for the `validators` sub-command.
+///
+/// This sub-command exports the bonded validators of the given network, sorted by voting power,
+/// with their on-chain contact information to a CSV file to prioritize the upgrade outreach.
+pub async fn export_validators(ctx: &Context, args: ValidatorsArgs) -> Result<(), ValidatorsError> {
+    let network = ctx.resolve_network(args.network)?;
+
+    let rest_url = block::get_rest_endpoint(network, &ctx.chain_config(network)?)?;
+    let validators = validators::get_validators(&rest_url).await?;
+    let output = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(validators::get_csv_file_name(network)));
+    utils::write_atomically(&output, validators::to_csv(&validators).as_bytes())?;
+
+    println!(
+        "Exported {} validators to {}",
+        validators.len(),
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `nag` sub-command.
+///
+/// This sub-command cross-references the votes cast on the given proposal with the bonded validator set
+/// and prints the validators with the highest voting power that have not voted yet.
+pub async fn show_nag_list(ctx: &Context, args: NagArgs) -> Result<(), ValidatorsError> {
+    let network = ctx.resolve_network(args.network)?;
+
+    let chain_config = ctx.chain_config(network)?;
+    let validators =
+        validators::get_validators(&block::get_rest_endpoint(network, &chain_config)?).await?;
+    let voters = ctx.gov_client(network)?.voters(args.proposal_id).await?;
+    let missing =
+        validators::get_missing_voters(&validators, &voters, &chain_config.bech32_prefix)?;
+
+    print!(
+        "{}",
+        validators::render_nag_list(args.proposal_id, &missing, args.top)
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `runbook` sub-command.
+///
+/// This sub-command writes the runbook for the upgrade day of the given configuration.
+/// The timeline is based on the current estimate of the time to reach the upgrade height,
+/// falling back to the scheduled upgrade time if the estimation fails.
+pub async fn generate_runbook(ctx: &Context, args: RunbookArgs) -> Result<(), RunbookError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let helper = from_file(&helper_config_path)?;
+
+    let estimated_time = match get_estimated_upgrade_time(ctx, &helper).await {
+        Ok(estimated_time) => estimated_time,
+        Err(e) => {
+            println!(
+                "WARNING: failed to estimate the upgrade time ({}); using the scheduled time",
+                e
+            );
+            helper.upgrade_time
+        }
+    };
+
+    let chain_config = ctx.chain_config(helper.network)?;
+    let runbook_file_name = runbook::get_runbook_file_name(&helper);
+    utils::write_content_to_file(
+        &runbook::render_runbook(&helper, &chain_config, estimated_time)?,
+        &runbook_file_name,
+    )?;
+    let skip_upgrade_file_name = runbook::get_skip_upgrade_file_name(&helper);
+    utils::write_content_to_file(
+        &runbook::render_skip_upgrade(&helper, &chain_config, estimated_time)?,
+        &skip_upgrade_file_name,
+    )?;
+    manifest::record(
+        Path::new("."),
+        Some(&helper_config_path),
+        &[runbook_file_name.clone(), skip_upgrade_file_name.clone()],
+        Utc::now(),
+    )?;
+    println!("Wrote runbook to {}", runbook_file_name);
+    println!(
+        "Wrote emergency skip-upgrade instructions to {}",
+        skip_upgrade_file_name
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `calendar` sub-command.
+///
+/// This sub-command writes an iCalendar file with events for the start and end of the voting period
+/// and the upgrade of the given configuration, which contain reminders ahead of time.
+pub async fn generate_calendar(ctx: &Context, args: CalendarArgs) -> Result<(), CalendarError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let helper = from_file(&helper_config_path)?;
+
+    let events = calendar::get_events(&helper);
+    if helper.voting_start_time.is_none() || helper.voting_end_time.is_none() {
+        println!(
+            "WARNING: the voting period is not known; only the upgrade is added to the calendar"
+        );
+    }
+
+    let calendar_file_name = calendar::get_calendar_file_name(&helper);
+    utils::write_content_to_file(
+        &calendar::render_calendar(&events, Utc::now()),
+        &calendar_file_name,
+    )?;
+    manifest::record(
+        Path::new("."),
+        Some(&helper_config_path),
+        std::slice::from_ref(&calendar_file_name),
+        Utc::now(),
+    )?;
+    println!("Wrote {} events to {}", events.len(), calendar_file_name);
+
+    Ok(())
+}
+
+/// Runs the logic for the `reminder` sub-command.
+///
+/// This sub-command generates the reminder for validators to vote on the proposal, which contains
+/// the explorer link and CLI command to vote and the end of the voting period in several timezones.
+/// The end of the voting period is queried from the submitted proposal if possible.
+pub async fn generate_vote_reminder(
+    ctx: &Context,
+    args: ReminderArgs,
+) -> Result<(), ReminderError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let helper = from_file(&helper_config_path)?;
+    let proposal_id = args
+        .proposal_id
+        .or(helper.proposal_id.map(|proposal_id| proposal_id.id))
+        .ok_or(ReminderError::MissingProposalId)?;
+
+    let voting_end_time = match ctx.gov_client(helper.network)?
+        .proposal(proposal_id)
+        .await
+    {
+        Ok(proposal) => proposal.voting_end_time.or(helper.voting_end_time),
+        Err(e) => {
+            println!(
+                "WARNING: failed to query proposal {} ({}); using the expected end of the voting period",
+                proposal_id, e
+            );
+            helper.voting_end_time
+        }
+    }
+    .ok_or(ReminderError::MissingVotingEnd(proposal_id))?;
+
+    let chain_config = ctx.chain_config(helper.network)?;
+    let reminder = reminder::render_reminder(&helper, &chain_config, proposal_id, voting_end_time)?;
+    let reminder_file_name = reminder::get_reminder_file_name(&helper);
+    utils::write_content_to_file(&reminder, &reminder_file_name)?;
+    manifest::record(
+        Path::new("."),
+        Some(&helper_config_path),
+        std::slice::from_ref(&reminder_file_name),
+        Utc::now(),
+    )?;
+    println!("Wrote vote reminder to {}", reminder_file_name);
+
+    if args.notify {
+        notifier::notify(Notification::new(
+            Severity::Info,
+            &format!("Vote on proposal #{}", proposal_id),
+            &reminder,
+        ))
+        .await;
+    }
+
+    Ok(())
+}
+
+/// Runs the logic for the `import` sub-command.
+///
+/// This sub-command reconstructs the configuration from the upgrade plan of an on-chain proposal,
+/// so that the runbook, announcements and commands can be generated for proposals created elsewhere.
+/// The on-chain description is stored as the proposal file, unless the file already exists.
+pub async fn import_proposal(ctx: &Context, args: ImportArgs) -> Result<(), ImportError> {
+    let network = ctx.resolve_network(args.network)?;
+
+    let proposal = ctx.gov_client(network)?.proposal(args.proposal_id).await?;
+    let plan = proposal
+        .get_upgrade_plan()
+        .ok_or(ImportError::NoUpgradePlan(args.proposal_id))?;
+
+    let upgrade_time = match get_upgrade_time(ctx, network, plan.height).await {
+        Ok(upgrade_time) => upgrade_time,
+        Err(e) => {
+            println!(
+                "WARNING: failed to estimate the upgrade time ({}); using the end of the voting period",
+                e
+            );
+            proposal.voting_end_time.unwrap_or_else(Utc::now)
+        }
+    };
+
+    let mut helper = get_helper_from_plan(
+        &plan,
+        ImportOptions {
+            network,
+            proposal_id: args.proposal_id,
+            evmosd_home: resolve::get_home_override(&ctx.flags, args.home)
+                .unwrap_or_else(|| inputs::get_default_evmosd_home(&network)),
+            previous_version: args.previous_version,
+            upgrade_time,
+        },
+    )?;
+    helper.chain_id = ctx.chain_config(network)?.get_chain_id(network);
+    helper.set_config_format(args.format);
+    helper.write_to_file(Utc::now())?;
+    println!("Wrote configuration to {}", helper.config_file_name);
+
+    match Path::new(&helper.proposal_file_name).exists() {
+        true => println!(
+            "WARNING: proposal file {} already exists; skipping",
+            helper.proposal_file_name
+        ),
+        false => {
+            utils::write_content_to_file(&plan.description, &helper.proposal_file_name)?;
+            println!("Wrote proposal to {}", helper.proposal_file_name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the logic for the `instructions` sub-command.
+///
+/// This sub-command writes the upgrade instructions for validators to `instructions-<version>.md`,
+/// which cover both the automatic download with Cosmovisor and building and swapping the binary manually.
+pub async fn generate_instructions(
+    ctx: &Context,
+    args: InstructionsArgs,
+) -> Result<(), InstructionsError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let helper = from_file(&helper_config_path)?;
+
+    // NOTE: the binaries are only listed if Cosmovisor can download them with the upgrade plan info
+    let assets = match helper.plan_info.supports_auto_download() {
+        true => {
+            let release = utils::with_deadline(
+                "query release",
+                release::get_release(&ctx.github, helper.target_version.as_str()),
+            )
+            .await??;
+            let chain_config = ctx.chain_config(helper.network)?;
+            profile::timed(
+                "download checksums",
+                release::get_asset_string(&release, chain_config.checksum_signature.as_ref()),
+            )
+            .await?
+        }
+        false => "{}".to_string(),
+    };
+
+    let instructions_file_name = instructions::get_instructions_file_name(&helper);
+    utils::write_content_to_file(
+        &instructions::render_instructions(
+            &helper,
+            &ctx.chain_config(helper.network)?,
+            &assets,
+            !args.no_strict,
+        )?,
+        &instructions_file_name,
+    )?;
+    manifest::record(
+        Path::new("."),
+        Some(&helper_config_path),
+        std::slice::from_ref(&instructions_file_name),
+        Utc::now(),
+    )?;
+    println!("Wrote instructions to {}", instructions_file_name);
+
+    Ok(())
+}
+
+/// Runs the logic for the `approve` sub-command.
+///
+/// This sub-command signs the SHA256 hash of the proposal file with the SSH key of the reviewer.
+/// The proposal has to be unchanged since it was recorded in the manifest. The approval is stored
+/// in the approvals file next to the manifest.
+pub fn approve(ctx: &Context, args: ApproveArgs) -> Result<(), ApprovalError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let helper = from_file(&helper_config_path)?;
+
+    let approval = approval::approve(
+        Path::new("."),
+        &helper.proposal_file_name,
+        &args.reviewer,
+        &args.key,
+        Utc::now(),
+    )?;
+    println!(
+        "Approved {} (sha256 {}) as {}",
+        approval.file, approval.sha256, approval.reviewer
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `verify-approvals` sub-command.
+///
+/// This sub-command verifies the signatures of the approvals of the proposal file against its current hash
+/// and prints the status of each approval. An error is returned if fewer valid approvals exist
+/// than required for the network.
+pub fn verify_approvals(ctx: &Context, args: VerifyApprovalsArgs) -> Result<(), ApprovalError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let helper = from_file(&helper_config_path)?;
+    let chain_config = ctx.chain_config(helper.network)?;
+    let allowed_signers = args.allowed_signers.or(chain_config.allowed_signers);
+
+    let approvals = approval::check_approvals(
+        Path::new("."),
+        &helper.proposal_file_name,
+        allowed_signers.as_deref(),
+    )?;
+    for (approval, result) in approvals.iter() {
+        match result {
+            Ok(fingerprint) => println!(
+                "{}: valid with key {} (approved at {})",
+                approval.reviewer, fingerprint, approval.approved_at
+            ),
+            Err(reason) => println!("{}: invalid ({})", approval.reviewer, reason),
+        }
+    }
+
+    approval::require_approvals(
+        Path::new("."),
+        &helper.proposal_file_name,
+        chain_config.required_approvals,
+        allowed_signers.as_deref(),
+    )?;
+    println!(
+        "{} has the required {} approvals",
+        helper.proposal_file_name, chain_config.required_approvals
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `serve` sub-command.
+///
+/// This sub-command runs a small REST API, so that a web UI or bots can estimate upgrade heights
+/// and render the proposal and the command without an interactive terminal.
+pub async fn run_server(ctx: Arc<Context>, args: ServeArgs) -> Result<(), ServeError> {
+    let result = serve::serve(ctx, args.address.parse()?, !args.no_strict).await;
+    if let Err(e) = &result {
+        notifier::notify(Notification::new(
+            Severity::Critical,
+            "Server stopped",
+            &format!("The proposer server stopped with an error: {}", e),
+        ))
+        .await;
+    }
+
+    result
+}
+
+/// Runs the logic for the `telegram` sub-command.
+///
+/// This sub-command runs a Telegram bot, which lets authorized team members re-estimate the upgrade time
+/// and query the governance status via chat commands. Alerts are sent to the alert chat when milestones
+/// of the upgrade are reached, e.g. one hour before the upgrade height.
+pub async fn run_telegram_bot(ctx: &Context, args: TelegramArgs) -> Result<(), TelegramError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+
+    let bot = Bot::new(
+        ctx,
+        TelegramClient::from_env(ctx.client.clone())?,
+        helper_config_path,
+        args.allowed_users,
+        args.alert_chat,
+    )?;
+    println!("Running Telegram bot; press Ctrl+C to stop");
+
+    let result = bot.run().await;
+    if let Err(e) = &result {
+        notifier::notify(Notification::new(
+            Severity::Critical,
+            "Telegram bot stopped",
+            &format!("The Telegram bot stopped with an error: {}", e),
+        ))
+        .await;
+    }
+
+    result
+}
+
+/// Returns the time of the block at the given height if it was already reached
+/// or the current estimate of the time to reach it otherwise.
+async fn get_upgrade_time(
+    ctx: &Context,
+    network: Network,
+    height: u64,
+) -> Result<chrono::DateTime<Utc>, BlockError> {
+    let provider = ctx.block_provider(network)?;
+    let estimate = block::get_block_time_estimate(&provider).await?;
+
+    match estimate.time_at(height) {
+        Err(BlockError::PastHeight(..)) => Ok(block::get_block(&provider, height).await?.time),
+        res => res,
+    }
+}
+
+/// Runs the logic for the `encrypt` sub-command.
+///
+/// This sub-command encrypts the given value with the configured passphrase and prints the encrypted value,
+/// which can be used in place of any string value in the upgrade configurations or the chain configuration.
+pub fn encrypt_value(args: EncryptArgs) -> Result<(), SecretError> {
+    let value = match args.value {
+        Some(value) => value,
+        None => inquire::Password::new("Value to encrypt:")
+            .without_confirmation()
+            .prompt()?,
+    };
+
+    println!("{}", secret::encrypt(&value, &secret::get_passphrase()?)?);
+
+    Ok(())
+}
+
+/// Runs the logic for the `chain add` sub-command.
+///
+/// This sub-command queries the chain with the given name from the Cosmos chain registry
+/// and writes the discovered chain ID, Bech32 prefix, daemon, endpoints and explorer
+/// to the chain configuration of the given network. Other configured values are kept.
+pub async fn add_chain(ctx: &Context, args: ChainAddArgs) -> Result<(), RegistryError> {
+    let registry_url = Url::parse(&args.registry_url)?;
+    let chain = registry::get_registry_chain(&registry_url, &args.name, args.network).await?;
+
+    let values = chain.to_config_values();
+    for (key, value) in values.iter() {
+        println!("{} = {}", key, value);
+    }
+    let path = chain::update_chain_config(args.network, values)?;
+    // NOTE: the updated configuration is reloaded, so that invalid values are reported right away
+    ctx.reload_chain_config(args.network)?;
+    println!(
+        "Added {} ({}) to the {} configuration in {}",
+        args.name,
+        chain.chain_id,
+        args.network,
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `keys import` sub-command.
+///
+/// This sub-command imports a key from a mnemonic or a hex private key into the `test` keyring
+/// of a dedicated home directory, so that CI pipelines can submit testnet proposals automatically.
+/// The secret is never passed as a command line argument of the `proposer` binary.
+pub fn import_key(ctx: &Context, args: KeysImportArgs) -> Result<(), KeysError> {
+    let secret = match std::env::var(&args.secret_env) {
+        Ok(secret) => secret,
+        Err(_) => {
+            let mut secret = String::new();
+            std::io::stdin().read_line(&mut secret)?;
+            secret
+        }
+    };
+    let default_homes: Vec<PathBuf> = [Network::LocalNode, Network::Mainnet]
+        .iter()
+        .map(inputs::get_default_evmosd_home)
+        .collect();
+
+    let address = keys::import_key(
+        &args.home,
+        &default_homes,
+        &args.name,
+        args.source,
+        &secret,
+        args.network,
+        &ctx.chain_config(args.network)?,
+    )?;
+    println!(
+        "Imported key {} ({}) into the {} keyring in {}",
+        args.name,
+        address,
+        keys::IMPORT_KEYRING_BACKEND,
+        args.home.display()
+    );
+
+    Ok(())
+}
+
+/// Runs the logic for the `template test` sub-command.
+///
+/// This sub-command renders the given template with the data of the proposal template,
+/// so that custom templates can be written without running the entire pipeline.
+/// Render errors are printed with their line and column. Unless `--once` is given, the template
+/// is re-rendered whenever the template or the configuration changes.
+pub async fn test_template(ctx: &Context, args: TemplateTestArgs) -> Result<(), TemplateTestError> {
+    let mut helper = match &args.config {
+        Some(config) => from_file(config)?,
+        None => get_sample_helper(),
+    };
+    let strict = !args.no_strict;
+
+    let result = render_test_template(ctx, &args.template, &helper, strict);
+    if args.once {
+        return result;
+    }
+    if let Err(e) = result {
+        print_template_error(&e);
+    }
+
+    let mut watched_files = vec![args.template.clone()];
+    watched_files.extend(args.config.clone());
+    let mut watcher = FileWatcher::new(watched_files);
+    println!(
+        "Watching {} for changes; press Ctrl+C to stop",
+        args.template.display()
+    );
+
+    loop {
+        let changed = watcher.wait_for_change().await?;
+        println!(
+            "Detected change in {}; re-rendering template",
+            changed.display()
+        );
+
+        // NOTE: errors are only printed so that watching continues after e.g. invalid edits
+        if let Some(config) = args.config.as_ref().filter(|config| **config == changed) {
+            match from_file(config) {
+                Ok(updated) => helper = updated,
+                Err(e) => {
+                    println!("Error reloading configuration: {}", e);
+                    continue;
+                }
+            }
+        }
+        if let Err(e) = render_test_template(ctx, &args.template, &helper, strict) {
+            print_template_error(&e);
+        }
+    }
+}
+
+/// Returns the sample configuration, which is used to test templates without an exported configuration.
+fn get_sample_helper() -> UpgradeHelper {
+    UpgradeHelper::new(
+        inputs::get_default_evmosd_home(&Network::Testnet),
+        Network::Testnet,
+        "v16.0.0",
+        "v17.0.0",
+        Utc::now() + Duration::days(3),
+        18_500_000,
+        "- Added the sample feature\n- Fixed the sample bug",
+    )
+}
+
+/// Renders the template at the given path with the proposal data of the given configuration
+/// and prints the result.
+fn render_test_template(
+    ctx: &Context,
+    path: &Path,
+    helper: &UpgradeHelper,
+    strict: bool,
+) -> Result<(), TemplateTestError> {
+    let path = path
+        .to_str()
+        .expect("failed to convert template path to string");
+    let data = proposal::get_proposal_data(helper, &ctx.chain_config(helper.network)?)?;
+    let handlebars = template::get_handlebars("template", path, strict, true)?;
+
+    println!(
+        "{}",
+        template::render(&handlebars, "template", path, &data)?
+    );
+
+    Ok(())
+}
+
+/// Prints the given error of rendering a template, including the line and column if they are known.
+fn print_template_error(error: &TemplateTestError) {
+    match error {
+        TemplateTestError::Template(e) => match template::get_error_location(e) {
+            Some((line, column)) => {
+                println!("Error at line {}, column {}: {}", line, column, e)
+            }
+            None => println!("Error: {}", e),
+        },
+        e => println!("Error: {}", e),
+    }
+}
+
+/// Runs the logic for the `promote` sub-command.
+///
+/// This sub-command copies the given draft revision of the proposal to the proposal file,
+/// which is used to generate the command, and marks it as final in the revision index.
+pub fn promote_revision(ctx: &Context, args: PromoteArgs) -> Result<(), ProposalError> {
+    let helper_config_path = ctx.resolve_config(args.config)?;
+    let helper = from_file(&helper_config_path)?;
+
+    revision::promote(Path::new("."), &helper.proposal_file_name, args.revision)?;
+    manifest::record(
+        Path::new("."),
+        Some(&helper_config_path),
+        std::slice::from_ref(&helper.proposal_file_name),
+        Utc::now(),
+    )?;
+    println!(
+        "Promoted revision {} to {}",
+        args.revision, helper.proposal_file_name
+    );
+
+    Ok(())
 }
@@ -0,0 +1,87 @@
+use clap::ValueEnum;
+use inquire::ui::RenderConfig;
+use std::io::IsTerminal;
+
+/// The environment variable, which disables colored output if it is set to a non-empty value.
+/// See https://no-color.org.
+const NO_COLOR_VAR: &str = "NO_COLOR";
+
+/// The choices of the `--color` flag, which controls whether the output is colored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ColorChoice {
+    /// Colors the output if it is written to a terminal, which supports colors, and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    /// Always colors the output, e.g. when piping it into a pager.
+    Always,
+    /// Never colors the output.
+    Never,
+}
+
+/// Returns whether the output should be colored for the given choice.
+///
+/// In automatic mode, the output is not colored if `NO_COLOR` is set to a non-empty value,
+/// if the terminal is `dumb` or if the output is not written to a terminal.
+fn should_colorize(
+    choice: ColorChoice,
+    no_color: Option<&str>,
+    term: Option<&str>,
+    is_terminal: bool,
+) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            no_color.unwrap_or_default().is_empty() && term != Some("dumb") && is_terminal
+        }
+    }
+}
+
+/// Returns whether the output should be colored for the given choice in the current environment
+/// and applies it to the terminal libraries, i.e. the colored text and the interactive prompts.
+///
+/// The result is stored in the context, so that the rendering functions (e.g. of the diffs)
+/// receive it explicitly.
+pub fn apply_color_choice(choice: ColorChoice) -> bool {
+    let enabled = should_colorize(
+        choice,
+        std::env::var(NO_COLOR_VAR).ok().as_deref(),
+        std::env::var("TERM").ok().as_deref(),
+        std::io::stdout().is_terminal(),
+    );
+
+    // NOTE: both libraries only offer process-wide switches, which are set once in `main`
+    colored::control::set_override(enabled);
+    if !enabled {
+        inquire::set_global_render_config(RenderConfig::empty());
+    }
+
+    enabled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_colorize() {
+        assert!(should_colorize(
+            ColorChoice::Auto,
+            None,
+            Some("xterm"),
+            true
+        ));
+        assert!(should_colorize(ColorChoice::Auto, Some(""), None, true));
+        assert!(!should_colorize(ColorChoice::Auto, Some("1"), None, true));
+        assert!(!should_colorize(
+            ColorChoice::Auto,
+            None,
+            Some("dumb"),
+            true
+        ));
+        assert!(!should_colorize(ColorChoice::Auto, None, None, false));
+
+        assert!(should_colorize(ColorChoice::Always, Some("1"), None, false));
+        assert!(!should_colorize(ColorChoice::Never, None, None, true));
+    }
+}
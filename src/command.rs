@@ -1,94 +1,313 @@
-use crate::errors::PrepareError;
+use crate::authority::get_gov_authority;
+use crate::block::{get_rest_endpoint, get_rpc_url};
+use crate::chain::ChainConfig;
+use crate::context::Context;
+use crate::errors::{MessageError, PrepareError};
 use crate::evmosd::ClientConfig;
 use crate::helper::UpgradeHelper;
-use crate::network::{get_denom, Network};
-use crate::release::{get_asset_string, get_instance, get_release};
-use handlebars::{no_escape, Handlebars};
+use crate::messages::{read_message, V1Proposal};
+use crate::network::{format_coin, Network};
+use crate::profile;
+use crate::release::{get_asset_string, get_release};
+use crate::schema::validate_proposal;
+use crate::template;
+use crate::upgrade_info::{get_release_notes_url, get_upgrade_info, PlanInfo};
+use crate::utils::with_deadline;
 use serde_json::json;
 use std::io;
+use std::path::{Path, PathBuf};
+
+/// The amount of the fees, which are paid in the native denomination of the network.
+// TODO: get fees from network conditions?
+pub const FEE_AMOUNT: &str = "10000000000";
+
+/// The path to the command template.
+pub const COMMAND_TEMPLATE: &str = "src/templates/command.hbs";
+
+/// The path to the Commonwealth template.
+pub const COMMONWEALTH_TEMPLATE: &str = "src/templates/commonwealth.hbs";
+
+/// The path to the template of the command to submit a gov v1 proposal JSON.
+pub const COMMAND_V1_TEMPLATE: &str = "src/templates/command_v1.hbs";
+
+/// Contains the key, which signs the submission of a proposal, and the fee granter,
+/// whose allowance pays the fees, if any.
+pub struct Signer<'a> {
+    /// The client configuration of the home directory, which contains the keyring backend.
+    pub client_config: &'a ClientConfig,
+    /// The name of the key in the keyring.
+    pub key: &'a str,
+    /// The address of the fee granter, if any.
+    pub fee_granter: Option<&'a str>,
+}
+
+/// Returns the fees paid to submit the proposal in the display denomination of the given network.
+fn get_fees_display(fee_denom: &str, network: Network) -> String {
+    format_coin(FEE_AMOUNT, fee_denom, network)
+}
+
+/// Returns the initial deposit of the given helper in the staking denomination of its network,
+/// e.g. `10000000000aevmos`, if one is configured.
+pub fn get_deposit(
+    helper: &UpgradeHelper,
+    chain_config: &ChainConfig,
+) -> Result<Option<String>, MessageError> {
+    let Some(amount) = &helper.deposit else {
+        return Ok(None);
+    };
+    if amount.is_empty() || !amount.chars().all(|c| c.is_ascii_digit()) {
+        return Err(MessageError::Deposit(amount.clone()));
+    }
+
+    Ok(Some(format!(
+        "{}{}",
+        amount,
+        chain_config.get_stake_denom(helper.network)
+    )))
+}
 
 /// Prepares the command to submit the proposal using the Evmos CLI.
+/// If a fee granter is given, the fees are paid from its allowance to the key.
 pub async fn prepare_command(
+    ctx: &Context,
     helper: &UpgradeHelper,
-    client_config: &ClientConfig,
-    key: &str,
+    signer: &Signer<'_>,
+    strict: bool,
 ) -> Result<String, PrepareError> {
-    let mut description = get_description_from_md(&helper.proposal_file_name)?;
-    let release = get_release(&get_instance(), helper.target_version.as_str()).await?;
-    let assets = get_asset_string(&release).await?;
-    let denom = get_denom(helper.network);
+    let description = get_description_from_md(&helper.proposal_file_name)?;
 
-    // TODO: get fees from network conditions?
-    let fees = format!("10000000000{}", denom);
-    let tm_rpc = get_rpc_url(helper.network);
+    render_command(ctx, helper, &description, signer, strict).await
+}
 
-    let mut handlebars = Handlebars::new();
-    handlebars.set_strict_mode(true);
-    handlebars.register_escape_fn(no_escape);
+/// Renders the command to submit the proposal with the given description using the Evmos CLI.
+/// If a fee granter is given, the fees are paid from its allowance to the key.
+pub async fn render_command(
+    ctx: &Context,
+    helper: &UpgradeHelper,
+    description: &str,
+    signer: &Signer<'_>,
+    strict: bool,
+) -> Result<String, PrepareError> {
+    let mut description = description.to_string();
+    let chain_config = ctx.chain_config(helper.network)?;
+    let assets = get_plan_info(ctx, helper, &chain_config).await?;
+    let denom = chain_config.get_fee_denom(helper.network);
 
-    handlebars
-        .register_template_file("command", "src/templates/command.hbs")
-        .expect("Failed to register command template file");
+    let fees = format!("{}{}", FEE_AMOUNT, denom);
+    let tm_rpc = chain_config
+        .tm_rpc
+        .clone()
+        .unwrap_or_else(|| get_rpc_url(helper.network));
 
+    let mut handlebars = template::get_handlebars("command", COMMAND_TEMPLATE, strict, false)?;
     handlebars
-        .register_template_file("commonwealth_template", "src/templates/commonwealth.hbs")
+        .register_template_file("commonwealth_template", COMMONWEALTH_TEMPLATE)
         .expect("Failed to register commonwealth template file");
 
     if helper.commonwealth_link.is_some() {
         description = format!(
             "{}{}",
             description.as_str(),
-            handlebars.render(
+            template::render(
+                &handlebars,
                 "commonwealth_template",
+                COMMONWEALTH_TEMPLATE,
                 &json!({"commonwealth": helper.commonwealth_link})
             )?
         );
     }
 
     let data = json!({
-        "assets": assets,
+        "assets": assets.replace('\'', "'\\''"), // NOTE: the upgrade info is passed in single quotes
         "chain_id": helper.chain_id,
         "commonwealth": helper.commonwealth_link,
+        "daemon": chain_config.daemon,
+        "deposit": get_deposit(helper, &chain_config)?,
         "description": description.replace('\n', "\\n"),  // NOTE: this is necessary to not print the actual new lines when rendering the template.
+        "extra_flags": chain_config.extra_flags,
+        "fee_granter": signer.fee_granter,
         "fees": fees,
+        "fees_display": get_fees_display(&denom, helper.network),
         "height": helper.upgrade_height,
         "home": helper.evmosd_home,
-        "key": key,
-        "keyring": client_config.keyring_backend,
+        "key": signer.key,
+        "keyring": signer.client_config.keyring_backend,
+        "keyring_flags": chain_config.keyring_flags,
+        "plan_name": helper.get_plan_name(),
         "title": helper.proposal_name,
         "tm_rpc": tm_rpc,
         "version": helper.target_version,
     });
 
-    let command = handlebars.render("command", &data)?;
+    let command = profile::measure("render command", || {
+        template::render(&handlebars, "command", COMMAND_TEMPLATE, &data)
+    })?;
+
+    Ok(command)
+}
+
+/// Returns the contents of the `info` field of the upgrade plan as configured in the helper.
+///
+/// The binaries JSON is built from the release assets and linked from a hosted file,
+/// if configured in the chain configuration or needed because of its size.
+pub async fn get_plan_info(
+    ctx: &Context,
+    helper: &UpgradeHelper,
+    chain_config: &ChainConfig,
+) -> Result<String, PrepareError> {
+    match &helper.plan_info {
+        PlanInfo::Binaries => {
+            let release = with_deadline(
+                "query release",
+                get_release(&ctx.github, helper.target_version.as_str()),
+            )
+            .await??;
+            let assets = profile::timed(
+                "download checksums",
+                get_asset_string(&release, chain_config.checksum_signature.as_ref()),
+            )
+            .await?;
+
+            Ok(get_upgrade_info(helper, assets, chain_config).await?)
+        }
+        PlanInfo::Url { url } => Ok(url.clone()),
+        PlanInfo::ReleaseNotes => Ok(get_release_notes_url(&helper.target_version)),
+        PlanInfo::Custom { text } => Ok(text.clone()),
+    }
+}
+
+/// Prepares the gov v1 proposal, which contains the software upgrade message followed by the
+/// messages in the given files.
+pub async fn prepare_v1_proposal(
+    ctx: &Context,
+    helper: &UpgradeHelper,
+    message_files: &[PathBuf],
+) -> Result<V1Proposal, PrepareError> {
+    let description = get_description_from_md(&helper.proposal_file_name)?;
+    let chain_config = ctx.chain_config(helper.network)?;
+    let assets = get_plan_info(ctx, helper, &chain_config).await?;
+
+    let additional_messages = message_files
+        .iter()
+        .map(|path| read_message(path))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let authority = get_gov_authority(
+        &get_rest_endpoint(helper.network, &chain_config)?,
+        &chain_config.bech32_prefix,
+    )
+    .await?;
+    let mut proposal = V1Proposal::new(
+        helper,
+        &description,
+        &assets,
+        &authority,
+        additional_messages,
+    );
+    proposal.deposit = get_deposit(helper, &chain_config)?.unwrap_or_default();
+    proposal.validate()?;
+    validate_proposal(&proposal, chain_config.sdk_version)?;
+
+    Ok(proposal)
+}
+
+/// Prepares the gov v1 proposal, which contains the software upgrade message followed by the
+/// messages in the given files, and the command to submit it using the Evmos CLI.
+pub async fn prepare_v1_command(
+    ctx: &Context,
+    helper: &UpgradeHelper,
+    signer: &Signer<'_>,
+    message_files: &[PathBuf],
+    strict: bool,
+) -> Result<(V1Proposal, String), PrepareError> {
+    let proposal = prepare_v1_proposal(ctx, helper, message_files).await?;
+    let command = render_v1_command(
+        &ctx.chain_config(helper.network)?,
+        helper.network,
+        &helper.evmosd_home,
+        signer,
+        &get_v1_proposal_file_name(helper),
+        strict,
+    )?;
+
+    Ok((proposal, command))
+}
+
+/// Renders the command to submit the gov v1 proposal in the given file on the given network
+/// with the given chain configuration using the Evmos CLI. If a fee granter is given,
+/// the fees are paid from its allowance to the key.
+pub fn render_v1_command(
+    chain_config: &ChainConfig,
+    network: Network,
+    evmosd_home: &Path,
+    signer: &Signer<'_>,
+    proposal_file: &str,
+    strict: bool,
+) -> Result<String, PrepareError> {
+    let denom = chain_config.get_fee_denom(network);
+
+    let handlebars = template::get_handlebars("command_v1", COMMAND_V1_TEMPLATE, strict, false)?;
+
+    let data = json!({
+        "chain_id": chain_config.get_chain_id(network),
+        "daemon": chain_config.daemon,
+        "extra_flags": chain_config.extra_flags,
+        "fee_granter": signer.fee_granter,
+        "fees": format!("{}{}", FEE_AMOUNT, denom),
+        "fees_display": get_fees_display(&denom, network),
+        "home": evmosd_home,
+        "key": signer.key,
+        "keyring": signer.client_config.keyring_backend,
+        "keyring_flags": chain_config.keyring_flags,
+        "proposal_file": proposal_file,
+        "tm_rpc": chain_config
+            .tm_rpc
+            .clone()
+            .unwrap_or_else(|| get_rpc_url(network)),
+    });
+
+    let command = profile::measure("render command", || {
+        template::render(&handlebars, "command_v1", COMMAND_V1_TEMPLATE, &data)
+    })?;
 
     Ok(command)
 }
 
+/// Returns the name of the gov v1 proposal JSON file for the given helper.
+pub fn get_v1_proposal_file_name(helper: &UpgradeHelper) -> String {
+    helper.proposal_file_name.replace(".md", "-v1.json")
+}
+
+/// Returns the name of the JSON file of the text proposal with the given title on the given network,
+/// e.g. `proposal-Mainnet-text-community-pool-spend.json`.
+pub fn get_text_proposal_file_name(network: Network, title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    format!("proposal-{}-text-{}.json", network, slug)
+}
+
 /// Returns the description string from the given Markdown file.
 fn get_description_from_md(filename: &str) -> io::Result<String> {
     std::fs::read_to_string(filename)
 }
 
-/// Returns the RPC URL based on the network.
-fn get_rpc_url(network: Network) -> String {
-    match network {
-        Network::Mainnet => "https://tm.evmos.lava.build:443".to_string(),
-        Network::Testnet => "https://tm.evmos-testnet.lava.build:443".to_string(),
-        Network::LocalNode => "http://localhost:26657".to_string(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::network::Network;
     use chrono::Utc;
+    use handlebars::Handlebars;
     use std::path::PathBuf;
+    use test_dir::{DirBuilder, TestDir};
 
     #[tokio::test]
     async fn test_prepare_command() {
-        let helper = UpgradeHelper::new(
+        let mut helper = UpgradeHelper::new(
             PathBuf::from("./.evmosd"),
             Network::Testnet,
             "v13.0.0",
@@ -106,20 +325,28 @@ mod tests {
             broadcast_mode: "sync".to_string(),
         };
 
-        // Write description to file
+        // Write description to file, which is removed with the test directory even if the test fails
+        let test_dir = TestDir::temp();
+        helper.proposal_file_name = test_dir
+            .path(&helper.proposal_file_name)
+            .to_str()
+            .expect("failed to convert path to string")
+            .to_string();
         let description = "This is a test proposal.";
         std::fs::write(&helper.proposal_file_name, description)
             .expect("Unable to write proposal to file");
 
         // Parse the description and prepare exported command
-        let command = prepare_command(&helper, &client_config, "dev0")
+        let ctx = Context::for_tests(test_dir.path("cache"), &[]);
+        let signer = Signer {
+            client_config: &client_config,
+            key: "dev0",
+            fee_granter: None,
+        };
+        let command = prepare_command(&ctx, &helper, &signer, true)
             .await
             .expect("failed to prepare command");
 
-        // Remove description file
-        std::fs::remove_file(&helper.proposal_file_name)
-            .expect("failed to remove description file after test");
-
         assert_eq!(
             command,
             include_str!("testdata/example_command.sh"),
@@ -127,6 +354,143 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_render_v1_command_fee_granter() {
+        let mut handlebars = Handlebars::new();
+        handlebars.set_strict_mode(true);
+        handlebars
+            .register_template_file("command_v1", COMMAND_V1_TEMPLATE)
+            .unwrap();
+
+        let mut data = json!({
+            "chain_id": "evmos_9000-4",
+            "daemon": "evmosd",
+            "extra_flags": [],
+            "fee_granter": null,
+            "fees": "10000000000atevmos",
+            "fees_display": "0.00000001 tEVMOS",
+            "home": "./.evmosd",
+            "key": "dev0",
+            "keyring": "test",
+            "keyring_flags": [],
+            "proposal_file": "proposal-Testnet-v14.0.0-v1.json",
+            "tm_rpc": "http://localhost:26657",
+        });
+
+        let command = handlebars.render("command_v1", &data).unwrap();
+        assert!(!command.contains("--fee-granter"));
+        assert!(command.contains("--fees 10000000000atevmos \\\n--chain-id"));
+
+        data["fee_granter"] = json!("evmos1granter");
+        let command = handlebars.render("command_v1", &data).unwrap();
+        assert!(command.contains("--fees 10000000000atevmos \\\n--fee-granter evmos1granter \\\n"));
+    }
+
+    #[test]
+    fn test_render_command_deposit() {
+        let mut handlebars = Handlebars::new();
+        handlebars.set_strict_mode(true);
+        handlebars
+            .register_template_file("command", COMMAND_TEMPLATE)
+            .unwrap();
+
+        let mut data = json!({
+            "assets": "{}",
+            "chain_id": "evmos_9000-4",
+            "daemon": "evmosd",
+            "deposit": null,
+            "description": "This is a test proposal.",
+            "extra_flags": [],
+            "fee_granter": null,
+            "fees": "10000000000atevmos",
+            "fees_display": "0.00000001 tEVMOS",
+            "height": 60,
+            "home": "./.evmosd",
+            "key": "dev0",
+            "keyring": "test",
+            "keyring_flags": [],
+            "plan_name": "v14.0.0",
+            "title": "Evmos Testnet v14.0.0 Upgrade",
+            "tm_rpc": "http://localhost:26657",
+        });
+
+        let command = handlebars.render("command", &data).unwrap();
+        assert!(!command.contains("--deposit"));
+
+        data["deposit"] = json!("10000000000astake");
+        let command = handlebars.render("command", &data).unwrap();
+        assert!(command.contains("--fees 10000000000atevmos \\\n--deposit 10000000000astake \\\n"));
+    }
+
+    #[test]
+    fn test_get_deposit() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v13.0.0",
+            "v14.0.0",
+            Utc::now(),
+            60,
+            "",
+        );
+        let chain_config = ChainConfig {
+            fee_denom: Some("atevmos".to_string()),
+            stake_denom: Some("astake".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(get_deposit(&helper, &chain_config).unwrap(), None);
+
+        helper.deposit = Some("10000000000".to_string());
+        assert_eq!(
+            get_deposit(&helper, &chain_config).unwrap(),
+            Some("10000000000astake".to_string())
+        );
+        assert_eq!(
+            get_deposit(&helper, &ChainConfig::default()).unwrap(),
+            Some("10000000000atevmos".to_string())
+        );
+
+        helper.deposit = Some("10evmos".to_string());
+        assert!(matches!(
+            get_deposit(&helper, &chain_config),
+            Err(MessageError::Deposit(_))
+        ));
+    }
+
+    #[test]
+    fn test_render_text_proposal_command() {
+        let client_config = ClientConfig {
+            chain_id: "evmos_9000-4".to_string(),
+            keyring_backend: "test".to_string(),
+            output: "text".to_string(),
+            node: "https://tm.evmos-testnet.lava.build:443".to_string(),
+            broadcast_mode: "sync".to_string(),
+        };
+        let proposal_file = get_text_proposal_file_name(Network::Testnet, "Community Pool: Spend!");
+        assert_eq!(
+            proposal_file,
+            "proposal-Testnet-text-community-pool-spend.json"
+        );
+
+        let command = render_v1_command(
+            &ChainConfig::default(),
+            Network::Testnet,
+            Path::new("./.evmosd"),
+            &Signer {
+                client_config: &client_config,
+                key: "dev0",
+                fee_granter: None,
+            },
+            &proposal_file,
+            true,
+        )
+        .expect("failed to render command");
+        assert!(command.contains(
+            "tx gov submit-proposal proposal-Testnet-text-community-pool-spend.json \\\n"
+        ));
+        assert!(command.contains("--chain-id evmos_9000-4 \\\n"));
+    }
+
     #[test]
     fn test_get_description_from_md() {
         let description = get_description_from_md("src/templates/command.hbs");
@@ -141,19 +505,4 @@ mod tests {
             "description should be err, but is not"
         );
     }
-
-    #[test]
-    fn test_get_rpc_url() {
-        let rpc = get_rpc_url(Network::Mainnet);
-        assert_eq!(rpc, "https://tm.evmos.lava.build:443", "rpc does not match");
-
-        let rpc = get_rpc_url(Network::Testnet);
-        assert_eq!(
-            rpc, "https://tm.evmos-testnet.lava.build:443",
-            "rpc does not match"
-        );
-
-        let rpc = get_rpc_url(Network::LocalNode);
-        assert_eq!(rpc, "http://localhost:26657", "rpc does not match");
-    }
 }
@@ -1,4 +1,6 @@
-use crate::{errors::CommonwealthError, helper::UpgradeHelper, http::get_body};
+use crate::{
+    errors::CommonwealthError, gov::ProposalId, helper::UpgradeHelper, http::get_page_cached,
+};
 use url::Url;
 
 /// Check if the page body can be retrieved (i.e. the link is valid) and do some basic
@@ -8,7 +10,7 @@ pub async fn check_commonwealth_link(
     _: &UpgradeHelper, // TODO: use when accessing the Commonwealth API
 ) -> Result<(), CommonwealthError> {
     // NOTE: for now we just check that the contents at the given URL can be retrieved
-    let _ = get_body(Url::parse(commonwealth_link)?).await?;
+    let _ = get_page_cached(Url::parse(commonwealth_link)?).await?;
     Ok(())
 
     // TODO: This is not working as expected because the returned contents in the body are not reliably containing the
@@ -22,6 +24,116 @@ pub async fn check_commonwealth_link(
     // }
 }
 
+/// Returns the text of the proposal to post on Commonwealth, which starts with the proposal ID
+/// if it is known. Until the proposal is submitted, the ID is marked as expected.
+pub fn render_commonwealth_text(description: &str, proposal_id: Option<ProposalId>) -> String {
+    let text = to_commonwealth_markdown(description);
+
+    match proposal_id {
+        Some(proposal_id) => format!("**{}**\n\n{}", proposal_id, text),
+        None => text,
+    }
+}
+
+/// Converts the given proposal Markdown into a format that is rendered correctly on Commonwealth.
+///
+/// Commonwealth does not support tables and nested lists and joins consecutive lines,
+/// so that tables are flattened into lists, nested list items are moved to the top level
+/// and line breaks inside of paragraphs are made explicit. Code blocks are left untouched.
+pub fn to_commonwealth_markdown(markdown: &str) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut converted = Vec::new();
+    let mut table_header: Option<Vec<String>> = None;
+    let mut in_code_block = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            converted.push(line.to_string());
+            continue;
+        }
+        if in_code_block {
+            converted.push(line.to_string());
+            continue;
+        }
+
+        if trimmed.starts_with('|') {
+            let cells = get_table_cells(trimmed);
+            match &table_header {
+                None => table_header = Some(cells),
+                Some(_) if is_table_separator(&cells) => {}
+                Some(header) => converted.push(flatten_table_row(header, &cells)),
+            }
+            continue;
+        }
+        table_header = None;
+
+        if is_list_item(trimmed) {
+            converted.push(trimmed.to_string());
+            continue;
+        }
+
+        // NOTE: two trailing spaces force a line break in Markdown
+        let next = lines.get(i + 1).map_or("", |next| next.trim());
+        if !line.trim().is_empty() && !trimmed.starts_with('#') && !next.is_empty() {
+            converted.push(format!("{}  ", line.trim_end()));
+        } else {
+            converted.push(line.to_string());
+        }
+    }
+
+    let mut converted = converted.join("\n");
+    if markdown.ends_with('\n') {
+        converted.push('\n');
+    }
+
+    converted
+}
+
+/// Returns the trimmed cells of the given Markdown table row.
+fn get_table_cells(row: &str) -> Vec<String> {
+    row.trim()
+        .trim_matches('|')
+        .split('|')
+        .map(|cell| cell.trim().to_string())
+        .collect()
+}
+
+/// Checks if the given table cells are the separator between the table header and the rows.
+fn is_table_separator(cells: &[String]) -> bool {
+    cells
+        .iter()
+        .all(|cell| !cell.is_empty() && cell.chars().all(|c| c == '-' || c == ':'))
+}
+
+/// Converts a table row into a list item. Rows of two-column tables are rendered as `first: second`,
+/// other rows list every cell together with its column header.
+fn flatten_table_row(header: &[String], cells: &[String]) -> String {
+    if cells.len() == 2 {
+        return format!("- {}: {}", cells[0], cells[1]);
+    }
+
+    let entries: Vec<String> = header
+        .iter()
+        .zip(cells.iter())
+        .map(|(column, cell)| format!("{}: {}", column, cell))
+        .collect();
+
+    format!("- {}", entries.join(", "))
+}
+
+/// Checks if the given line (without leading whitespace) is a list item.
+fn is_list_item(line: &str) -> bool {
+    if line.starts_with("- ") || line.starts_with("* ") {
+        return true;
+    }
+
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    digits > 0 && line[digits..].starts_with(". ")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -72,4 +184,76 @@ mod tests {
             .await
             .is_ok());
     }
+
+    #[test]
+    fn test_to_commonwealth_markdown() {
+        let markdown = "## Upgrade
+
+This is the first line
+and this is the second line.
+
+| Timezone | Estimated Upgrade Time |
+|----------|------------------------|
+| UTC | 4PM UTC on Mon., October 23., 2023 |
+| KST | 1AM KST on Tue., October 24., 2023 |
+
+- Features
+  - Nested feature
+    1. Deeply nested
+
+```
+evmosd tx gov submit-legacy-proposal \\
+  --title test
+```";
+
+        let expected = "## Upgrade
+
+This is the first line  
+and this is the second line.
+
+- UTC: 4PM UTC on Mon., October 23., 2023
+- KST: 1AM KST on Tue., October 24., 2023
+
+- Features
+- Nested feature
+1. Deeply nested
+
+```
+evmosd tx gov submit-legacy-proposal \\
+  --title test
+```";
+
+        assert_eq!(to_commonwealth_markdown(markdown), expected);
+    }
+
+    #[test]
+    fn test_flatten_table_row() {
+        let header = vec!["Name".to_string(), "Height".to_string(), "Time".to_string()];
+        let cells = vec!["v16.0.0".to_string(), "100".to_string(), "4PM".to_string()];
+        assert_eq!(
+            flatten_table_row(&header, &cells),
+            "- Name: v16.0.0, Height: 100, Time: 4PM"
+        );
+    }
+
+    #[test]
+    fn test_render_commonwealth_text() {
+        let description = "# Description\n\nUpgrade to v16.0.0.";
+        assert_eq!(render_commonwealth_text(description, None), description);
+
+        let mut proposal_id = ProposalId {
+            id: 252,
+            confirmed: false,
+        };
+        assert_eq!(
+            render_commonwealth_text(description, Some(proposal_id)),
+            format!("**Proposal #252 (expected)**\n\n{}", description)
+        );
+
+        proposal_id.confirmed = true;
+        assert_eq!(
+            render_commonwealth_text(description, Some(proposal_id)),
+            format!("**Proposal #252**\n\n{}", description)
+        );
+    }
 }
@@ -0,0 +1,260 @@
+use crate::block::BlockProvider;
+use crate::cache::DiskCache;
+use crate::chain::{get_chain_config, ChainConfig};
+use crate::errors::{BlockError, ConfigError, ContextError, GovError, InputError};
+use crate::gov::{get_gov_client, GovClient};
+use crate::http::get_client;
+use crate::inputs::{self, InputProvider};
+use crate::llm::{self, LlmBackend, LlmProvider};
+use crate::network::Network;
+use crate::resolve::{get_home_override, get_network_override, GlobalFlags};
+use crate::upload::GITHUB_TOKEN_VAR;
+use octocrab::Octocrab;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Contains the shared state of the application, which is built once in `main`
+/// and passed to the sub-command handlers.
+///
+/// Replacing its parts (e.g. the GitHub client or the input provider) allows to run
+/// the handlers against other endpoints or with predefined inputs.
+pub struct Context {
+    /// The HTTP client, which is used to query external services.
+    pub client: Client,
+    /// The GitHub client, which is authenticated if a token is set in the environment.
+    pub github: Arc<Octocrab>,
+    /// The disk cache for responses, which are repeatedly queried across runs.
+    pub cache: DiskCache,
+    /// Provides the values, which are otherwise prompted from the user.
+    pub inputs: Box<dyn InputProvider>,
+    /// The global flags, which override the network and home directory for any sub-command.
+    pub flags: GlobalFlags,
+    /// The backend, which answers the prompts to the LLM.
+    pub llm_backend: LlmBackend,
+    /// Whether the output is colored.
+    pub colored: bool,
+    /// The chain configurations, which were loaded so far.
+    chain_configs: Mutex<HashMap<Network, ChainConfig>>,
+}
+
+impl Context {
+    /// Creates a new context from the given parts.
+    pub fn new(
+        client: Client,
+        github: Arc<Octocrab>,
+        cache: DiskCache,
+        inputs: Box<dyn InputProvider>,
+    ) -> Context {
+        Context {
+            client,
+            github,
+            cache,
+            inputs,
+            flags: GlobalFlags::default(),
+            llm_backend: LlmBackend::default(),
+            colored: false,
+            chain_configs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates the context from the environment with the given input provider, global flags,
+    /// LLM backend and color setting of this run.
+    pub fn from_env(
+        inputs: Box<dyn InputProvider>,
+        flags: GlobalFlags,
+        llm_backend: LlmBackend,
+        colored: bool,
+    ) -> Result<Context, ContextError> {
+        let github = match std::env::var(GITHUB_TOKEN_VAR) {
+            Ok(token) => Arc::new(Octocrab::builder().personal_token(token).build()?),
+            Err(_) => octocrab::instance(),
+        };
+
+        Ok(Context {
+            flags,
+            llm_backend,
+            colored,
+            ..Context::new(
+                get_client()?.clone(),
+                github,
+                DiskCache::from_env()?,
+                inputs,
+            )
+        })
+    }
+
+    /// Returns the chain configuration for the given network.
+    ///
+    /// The configuration is only loaded once per network, so that encrypted values
+    /// do not have to be decrypted again for every use.
+    pub fn chain_config(&self, network: Network) -> Result<ChainConfig, ConfigError> {
+        let mut chain_configs = self
+            .chain_configs
+            .lock()
+            .expect("failed to lock chain configurations");
+        if let Some(chain_config) = chain_configs.get(&network) {
+            return Ok(chain_config.clone());
+        }
+
+        let chain_config = get_chain_config(network)?;
+        chain_configs.insert(network, chain_config.clone());
+
+        Ok(chain_config)
+    }
+
+    /// Drops the loaded chain configuration for the given network and loads it again,
+    /// e.g. after it was updated on disk.
+    pub fn reload_chain_config(&self, network: Network) -> Result<ChainConfig, ConfigError> {
+        self.chain_configs
+            .lock()
+            .expect("failed to lock chain configurations")
+            .remove(&network);
+
+        self.chain_config(network)
+    }
+
+    /// Returns the provider of the selected LLM backend.
+    pub fn llm(&self) -> Box<dyn LlmProvider> {
+        llm::get_provider(self.llm_backend)
+    }
+
+    /// Returns the block provider for the given network from its chain configuration,
+    /// which stores the failures of its endpoints in the cache.
+    pub fn block_provider(&self, network: Network) -> Result<BlockProvider, BlockError> {
+        Ok(
+            BlockProvider::for_network(network, &self.chain_config(network)?)?
+                .with_cache(&self.cache),
+        )
+    }
+
+    /// Returns the governance client for the given network from its chain configuration.
+    pub fn gov_client(&self, network: Network) -> Result<Box<dyn GovClient>, GovError> {
+        get_gov_client(network, &self.chain_config(network)?)
+    }
+
+    /// Returns the given path of the upgrade configuration or gets it from the input provider,
+    /// if none is given.
+    pub fn resolve_config(&self, config: Option<PathBuf>) -> Result<PathBuf, InputError> {
+        match config {
+            Some(config) => Ok(config),
+            None => inputs::choose_config(self.inputs.as_ref()),
+        }
+    }
+
+    /// Returns the network to use with the precedence flag > environment > input provider.
+    pub fn resolve_network(&self, flag: Option<Network>) -> Result<Network, InputError> {
+        match get_network_override(&self.flags, flag)? {
+            Some(network) => Ok(network),
+            None => inputs::get_used_network(self.inputs.as_ref(), |network| {
+                Ok(self.chain_config(network)?.get_chain_id(network))
+            }),
+        }
+    }
+
+    /// Returns the home directory of the Evmos binary for the given network
+    /// with the precedence flag > environment > configuration > input provider.
+    pub fn resolve_home(
+        &self,
+        network: Network,
+        config: Option<PathBuf>,
+    ) -> Result<PathBuf, InputError> {
+        match get_home_override(&self.flags, None).or(config) {
+            Some(home) => Ok(home),
+            None => inputs::get_evmosd_home(self.inputs.as_ref(), &network),
+        }
+    }
+}
+
+#[cfg(test)]
+impl Context {
+    /// Returns the context for tests, which caches the responses in the given directory
+    /// and answers the prompts with the given answers.
+    pub fn for_tests(cache_dir: PathBuf, answers: &[(&str, &str)]) -> Context {
+        Context::new(
+            Client::new(),
+            Arc::new(Octocrab::default()),
+            DiskCache::new(cache_dir, chrono::Duration::minutes(10)),
+            Box::new(FixedInputs(
+                answers
+                    .iter()
+                    .map(|(key, answer)| (key.to_string(), answer.to_string()))
+                    .collect(),
+            )),
+        )
+    }
+
+    /// Returns the context with the given chain configuration for the given network,
+    /// so that tests do not depend on the chains file in the configuration directory.
+    pub fn with_chain_config(self, network: Network, chain_config: ChainConfig) -> Context {
+        self.chain_configs
+            .lock()
+            .expect("failed to lock chain configurations")
+            .insert(network, chain_config);
+
+        self
+    }
+}
+
+/// Answers the prompts with fixed texts by their keys in tests.
+#[cfg(test)]
+struct FixedInputs(HashMap<String, String>);
+
+#[cfg(test)]
+impl InputProvider for FixedInputs {
+    fn get_text(&self, key: &str) -> Result<Option<String>, crate::errors::AnswersError> {
+        Ok(self.0.get(key).cloned())
+    }
+
+    fn get_list(&self, key: &str) -> Result<Option<Vec<String>>, crate::errors::AnswersError> {
+        Ok(self
+            .0
+            .get(key)
+            .map(|answer| answer.split(',').map(str::to_string).collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    fn get_context(test_dir: &TestDir) -> Context {
+        Context::for_tests(
+            test_dir.path("cache"),
+            &[("config", "config_testnet.json"), ("network", "testnet")],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resolve_config() {
+        let test_dir = TestDir::temp();
+        let ctx = get_context(&test_dir);
+
+        assert_eq!(
+            ctx.resolve_config(None).unwrap(),
+            PathBuf::from("config_testnet.json")
+        );
+        assert_eq!(
+            ctx.resolve_config(Some(PathBuf::from("config_mainnet.json")))
+                .unwrap(),
+            PathBuf::from("config_mainnet.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_network() {
+        let test_dir = TestDir::temp();
+        let ctx = get_context(&test_dir);
+
+        assert_eq!(
+            ctx.resolve_network(Some(Network::Mainnet)).unwrap(),
+            Network::Mainnet
+        );
+        // NOTE: the environment variable takes precedence over the input provider
+        if std::env::var(crate::resolve::NETWORK_VAR).is_err() {
+            assert_eq!(ctx.resolve_network(None).unwrap(), Network::Testnet);
+        }
+    }
+}
@@ -0,0 +1,499 @@
+use crate::gov::UpgradePlan;
+use crate::helper::UpgradeHelper;
+use colored::{ColoredString, Colorize};
+use serde_json::Value;
+use similar::TextDiff;
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// The number of unchanged lines to show around the changes in multi-line values.
+const CONTEXT_LINES: usize = 3;
+
+/// Represents how relevant a mismatch of a given field is for the upgrade.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Mismatches that would lead to a wrong upgrade, e.g. a different height or version.
+    Critical,
+    /// Mismatches that should be double-checked, e.g. a different previous version.
+    Warning,
+    /// Mismatches that are informational only, e.g. changes in the summary.
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Severity::Critical => write!(f, "CRITICAL"),
+            Severity::Warning => write!(f, "WARNING"),
+            Severity::Info => write!(f, "INFO"),
+        }
+    }
+}
+
+/// Represents a mismatch of a single field between the expected and the actual value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldDiff {
+    /// The name of the field.
+    pub field: String,
+    /// The severity of the mismatch.
+    pub severity: Severity,
+    /// The expected value.
+    pub expected: String,
+    /// The actual value.
+    pub actual: String,
+}
+
+/// Returns the severity of a change in the given field of the upgrade helper configuration.
+fn get_field_severity(field: &str) -> Severity {
+    match field {
+        "chain_id" | "network" | "plan_name" | "target_version" | "upgrade_height"
+        | "upgrade_time" => Severity::Critical,
+        "commonwealth_link" | "evmosd_home" | "previous_version" | "voting_period" => {
+            Severity::Warning
+        }
+        _ => Severity::Info,
+    }
+}
+
+/// Returns the string representation of a JSON value that is used in the diff output.
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        value => serde_json::to_string_pretty(value).expect("failed to serialize JSON value"),
+    }
+}
+
+/// Returns the field-by-field differences between two upgrade helper configurations.
+pub fn compare_helpers(old: &UpgradeHelper, new: &UpgradeHelper) -> Vec<FieldDiff> {
+    let old = serde_json::to_value(old).expect("failed to serialize helper");
+    let new = serde_json::to_value(new).expect("failed to serialize helper");
+
+    let fields: BTreeSet<&String> = old
+        .as_object()
+        .expect("helper is serialized as an object")
+        .keys()
+        .chain(
+            new.as_object()
+                .expect("helper is serialized as an object")
+                .keys(),
+        )
+        .collect();
+
+    fields
+        .into_iter()
+        .filter(|field| old[field.as_str()] != new[field.as_str()])
+        .map(|field| FieldDiff {
+            field: field.clone(),
+            severity: get_field_severity(field),
+            expected: value_to_string(&old[field.as_str()]),
+            actual: value_to_string(&new[field.as_str()]),
+        })
+        .collect()
+}
+
+/// Returns the differences between the local upgrade helper configuration and the
+/// upgrade plan of an on-chain proposal.
+///
+/// If the local proposal description is given, it is expected to be contained at the start
+/// of the on-chain description, because the Commonwealth link might be appended to it.
+pub fn compare_upgrade_plan(
+    helper: &UpgradeHelper,
+    plan: &UpgradePlan,
+    description: Option<&str>,
+) -> Vec<FieldDiff> {
+    let mut diffs = Vec::new();
+
+    let mut check = |field: &str, severity: Severity, expected: String, actual: String| {
+        if expected != actual {
+            diffs.push(FieldDiff {
+                field: field.to_string(),
+                severity,
+                expected,
+                actual,
+            });
+        }
+    };
+
+    check(
+        "plan name",
+        Severity::Critical,
+        helper.get_plan_name().to_string(),
+        plan.name.clone(),
+    );
+    check(
+        "upgrade height",
+        Severity::Critical,
+        helper.upgrade_height.to_string(),
+        plan.height.to_string(),
+    );
+    check(
+        "title",
+        Severity::Warning,
+        helper.proposal_name.clone(),
+        plan.title.clone(),
+    );
+
+    if let Some(description) = description {
+        if !plan.description.starts_with(description.trim_end()) {
+            check(
+                "description",
+                Severity::Warning,
+                description.to_string(),
+                plan.description.clone(),
+            );
+        }
+    }
+
+    diffs
+}
+
+/// Splits the given Markdown proposal into its sections, which start with a level two heading.
+/// The text before the first heading is returned as the section with an empty heading.
+pub fn split_sections(text: &str) -> Vec<(String, String)> {
+    let mut sections = vec![(String::new(), String::new())];
+    for line in text.lines() {
+        match line.strip_prefix("## ") {
+            Some(heading) => sections.push((heading.trim().to_string(), String::new())),
+            None => {
+                let (_, content) = sections.last_mut().expect("sections are not empty");
+                content.push_str(line);
+                content.push('\n');
+            }
+        }
+    }
+
+    sections
+        .into_iter()
+        .filter(|(heading, content)| !heading.is_empty() || !content.trim().is_empty())
+        .map(|(heading, content)| (heading, content.trim().to_string()))
+        .collect()
+}
+
+/// Replaces the target and previous version in the given proposal text with placeholders,
+/// so that proposals of different upgrades can be compared without every version mention showing up.
+pub fn mask_versions(text: &str, target_version: &str, previous_version: &str) -> String {
+    let mut replacements = [
+        (target_version, "<target version>"),
+        (previous_version, "<previous version>"),
+    ];
+    // NOTE: the longer version is replaced first, e.g. `v16.0.0-rc2` before `v16.0.0`
+    replacements.sort_by_key(|(version, _)| std::cmp::Reverse(version.len()));
+
+    replacements
+        .iter()
+        .filter(|(version, _)| !version.is_empty())
+        .fold(text.to_string(), |text, (version, placeholder)| {
+            text.replace(version, placeholder)
+        })
+}
+
+/// Returns the structural differences between the proposal of the previous upgrade and the current one.
+///
+/// Missing sections are reported as warnings, because they usually were dropped by accident,
+/// while new sections, a changed order and changed wording within a section are informational.
+pub fn compare_proposals(previous: &str, current: &str) -> Vec<FieldDiff> {
+    let previous = split_sections(previous);
+    let current = split_sections(current);
+    let headings = |sections: &[(String, String)]| -> Vec<String> {
+        sections
+            .iter()
+            .map(|(heading, _)| heading.clone())
+            .collect()
+    };
+    let section_name = |heading: &str| match heading {
+        "" => "header".to_string(),
+        heading => format!("section \"{}\"", heading),
+    };
+
+    let mut diffs = Vec::new();
+    for (heading, content) in previous.iter() {
+        match current.iter().find(|(other, _)| other == heading) {
+            None => diffs.push(FieldDiff {
+                field: section_name(heading),
+                severity: Severity::Warning,
+                expected: content.clone(),
+                actual: String::new(),
+            }),
+            Some((_, other)) if other != content => diffs.push(FieldDiff {
+                field: section_name(heading),
+                severity: Severity::Info,
+                expected: content.clone(),
+                actual: other.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (heading, content) in current.iter() {
+        if !previous.iter().any(|(other, _)| other == heading) {
+            diffs.push(FieldDiff {
+                field: section_name(heading),
+                severity: Severity::Info,
+                expected: String::new(),
+                actual: content.clone(),
+            });
+        }
+    }
+
+    // NOTE: the order is only compared for the sections, which exist in both proposals
+    let common = |sections: &[(String, String)], others: &[(String, String)]| -> Vec<String> {
+        headings(sections)
+            .into_iter()
+            .filter(|heading| others.iter().any(|(other, _)| other == heading))
+            .collect()
+    };
+    let previous_order = common(&previous, &current);
+    let current_order = common(&current, &previous);
+    if previous_order != current_order {
+        diffs.push(FieldDiff {
+            field: "section order".to_string(),
+            severity: Severity::Info,
+            expected: previous_order.join("\n"),
+            actual: current_order.join("\n"),
+        });
+    }
+
+    diffs
+}
+
+/// Renders the given differences in a unified diff style, where the most severe
+/// mismatches are shown first. The output is only colored if enabled.
+pub fn render_diffs(
+    diffs: &[FieldDiff],
+    expected_label: &str,
+    actual_label: &str,
+    colored: bool,
+) -> String {
+    let mut sorted = diffs.to_vec();
+    sorted.sort_by_key(|diff| diff.severity);
+
+    sorted
+        .iter()
+        .map(|diff| render_diff(diff, expected_label, actual_label, colored))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Returns the given text with the given style applied, if coloring is enabled.
+fn paint(text: &str, colored: bool, style: impl Fn(&str) -> ColoredString) -> String {
+    match colored {
+        true => style(text).to_string(),
+        false => text.to_string(),
+    }
+}
+
+/// Renders a single field difference as a unified diff with a colored severity header.
+fn render_diff(
+    diff: &FieldDiff,
+    expected_label: &str,
+    actual_label: &str,
+    colored: bool,
+) -> String {
+    let severity = diff.severity.to_string();
+    let severity = match diff.severity {
+        Severity::Critical => paint(&severity, colored, |text| text.red().bold()),
+        Severity::Warning => paint(&severity, colored, |text| text.yellow().bold()),
+        Severity::Info => paint(&severity, colored, |text| text.blue().bold()),
+    };
+
+    // NOTE: trailing newlines are added so that single-line values are not marked
+    // as missing a newline at the end.
+    let expected = format!("{}\n", diff.expected.trim_end_matches('\n'));
+    let actual = format!("{}\n", diff.actual.trim_end_matches('\n'));
+    let unified = TextDiff::from_lines(&expected, &actual)
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .header(expected_label, actual_label)
+        .to_string();
+
+    let lines: Vec<String> = unified
+        .lines()
+        .map(|line| {
+            if line.starts_with("---") || line.starts_with("+++") {
+                paint(line, colored, |text| text.bold())
+            } else if line.starts_with("@@") {
+                paint(line, colored, |text| text.cyan())
+            } else if line.starts_with('-') {
+                paint(line, colored, |text| text.red())
+            } else if line.starts_with('+') {
+                paint(line, colored, |text| text.green())
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+
+    format!(
+        "{} {}\n{}\n",
+        severity,
+        paint(&diff.field, colored, |text| text.bold()),
+        lines.join("\n")
+    )
+}
+
+/// Returns the number of critical mismatches in the given differences.
+pub fn count_critical(diffs: &[FieldDiff]) -> usize {
+    diffs
+        .iter()
+        .filter(|diff| diff.severity == Severity::Critical)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+    use chrono::{TimeZone, Utc};
+    use std::path::PathBuf;
+
+    /// Returns an upgrade helper for testing.
+    fn get_helper() -> UpgradeHelper {
+        UpgradeHelper::new(
+            PathBuf::from("/root/.evmosd"),
+            Network::Mainnet,
+            "v15.0.0",
+            "v16.0.0",
+            Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap(),
+            18_798_000,
+            "This is a summary.",
+        )
+    }
+
+    #[test]
+    fn test_split_sections() {
+        let sections = split_sections("# Title\n\n## Author\n\nEvmos\n\n## Testing\n\nDone.\n");
+        assert_eq!(
+            sections,
+            vec![
+                ("".to_string(), "# Title".to_string()),
+                ("Author".to_string(), "Evmos".to_string()),
+                ("Testing".to_string(), "Done.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mask_versions() {
+        assert_eq!(
+            mask_versions(
+                "Upgrade from v16.0.0 to v16.0.0-rc2, see v16.0.0-rc2.",
+                "v16.0.0-rc2",
+                "v16.0.0"
+            ),
+            "Upgrade from <previous version> to <target version>, see <target version>."
+        );
+    }
+
+    #[test]
+    fn test_compare_proposals() {
+        let previous =
+            "# Upgrade\n\n## Author\n\nEvmos\n\n## Motivation\n\nFixes.\n\n## Testing\n\nDone.\n";
+        let current = "# Upgrade\n\n## Testing\n\nDone.\n\n## Author\n\nEvmos Core\n\n## Binaries\n\n- linux\n";
+
+        let diffs = compare_proposals(previous, current);
+        let fields: Vec<(&str, Severity)> = diffs
+            .iter()
+            .map(|diff| (diff.field.as_str(), diff.severity))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("section \"Author\"", Severity::Info),
+                ("section \"Motivation\"", Severity::Warning),
+                ("section \"Binaries\"", Severity::Info),
+                ("section order", Severity::Info),
+            ]
+        );
+        assert!(compare_proposals(previous, previous).is_empty());
+    }
+
+    #[test]
+    fn test_compare_helpers() {
+        let old = get_helper();
+        let mut new = get_helper();
+        new.upgrade_height = 18_800_000;
+        new.previous_version = "v15.0.1".to_string();
+        new.summary = "This is an updated summary.".to_string();
+
+        let diffs = compare_helpers(&old, &new);
+        assert_eq!(
+            diffs.len(),
+            3,
+            "expected three differences; got: {:?}",
+            diffs
+        );
+        assert_eq!(
+            diffs[0],
+            FieldDiff {
+                field: "previous_version".to_string(),
+                severity: Severity::Warning,
+                expected: "v15.0.0".to_string(),
+                actual: "v15.0.1".to_string(),
+            }
+        );
+        assert_eq!(diffs[1].field, "summary");
+        assert_eq!(diffs[1].severity, Severity::Info);
+        assert_eq!(diffs[2].field, "upgrade_height");
+        assert_eq!(diffs[2].severity, Severity::Critical);
+        assert_eq!(count_critical(&diffs), 1);
+    }
+
+    #[test]
+    fn test_compare_helpers_no_diff() {
+        assert!(compare_helpers(&get_helper(), &get_helper()).is_empty());
+    }
+
+    #[test]
+    fn test_compare_upgrade_plan() {
+        let helper = get_helper();
+        let mut plan = UpgradePlan {
+            title: helper.proposal_name.clone(),
+            description: "# Upgrade\n\nCommonwealth: https://commonwealth.im/evmos".to_string(),
+            name: "v16.0.0".to_string(),
+            height: 18_798_000,
+            info: "".to_string(),
+        };
+
+        assert!(compare_upgrade_plan(&helper, &plan, Some("# Upgrade\n")).is_empty());
+
+        plan.height = 18_798_500;
+        let diffs = compare_upgrade_plan(&helper, &plan, Some("# Other\n"));
+        assert_eq!(diffs.len(), 2, "expected two differences; got: {:?}", diffs);
+        assert_eq!(diffs[0].field, "upgrade height");
+        assert_eq!(diffs[0].severity, Severity::Critical);
+        assert_eq!(diffs[1].field, "description");
+    }
+
+    #[test]
+    fn test_render_diffs() {
+        let diffs = vec![
+            FieldDiff {
+                field: "summary".to_string(),
+                severity: Severity::Info,
+                expected: "a\nb\n".to_string(),
+                actual: "a\nc\n".to_string(),
+            },
+            FieldDiff {
+                field: "upgrade_height".to_string(),
+                severity: Severity::Critical,
+                expected: "100".to_string(),
+                actual: "200".to_string(),
+            },
+        ];
+
+        let expected = "CRITICAL upgrade_height\n\
+            --- old\n\
+            +++ new\n\
+            @@ -1 +1 @@\n\
+            -100\n\
+            +200\n\
+            \n\
+            INFO summary\n\
+            --- old\n\
+            +++ new\n\
+            @@ -1,2 +1,2 @@\n \
+            a\n\
+            -b\n\
+            +c\n";
+        assert_eq!(render_diffs(&diffs, "old", "new", false), expected);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::context::Context;
+use crate::errors::DigestError;
+use crate::gov::{Proposal, ProposalStatus};
+use crate::helper::{from_file, ConfigFormat, UpgradeHelper};
+use crate::history::{History, UpgradeRecord};
+use crate::network::Network;
+use crate::status::format_time_left;
+use chrono::{DateTime, Duration, Utc};
+use std::fs;
+use std::path::Path;
+
+/// Contains the pending governance work, which is summarized in the digest.
+#[derive(Debug, Default)]
+pub struct Digest {
+    /// The upgrade configurations in the workspace, which have not been submitted yet.
+    pub unsubmitted: Vec<UpgradeHelper>,
+    /// The proposals that are currently in the voting period per network.
+    pub voting: Vec<(Network, Proposal)>,
+    /// The recorded upgrades, which are scheduled within the lookahead window.
+    pub approaching: Vec<UpgradeRecord>,
+    /// The networks that could not be queried, together with the error message.
+    pub failed_networks: Vec<(Network, String)>,
+}
+
+impl Digest {
+    /// Collects the pending governance work from the configurations in the given directory,
+    /// the given history and the governance module of the given networks.
+    pub async fn collect(
+        ctx: &Context,
+        dir: &Path,
+        history: &History,
+        networks: &[Network],
+        lookahead: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<Digest, DigestError> {
+        let mut digest = Digest {
+            unsubmitted: get_unsubmitted_configs(dir, history)?,
+            approaching: get_approaching_upgrades(history, now, lookahead),
+            ..Default::default()
+        };
+
+        // NOTE: failing networks are listed in the digest instead of failing the whole digest
+        for network in networks {
+            let proposals = match ctx.gov_client(*network) {
+                Ok(client) => client.proposals(Some(ProposalStatus::VotingPeriod)).await,
+                Err(e) => Err(e),
+            };
+
+            match proposals {
+                Ok(proposals) => digest
+                    .voting
+                    .extend(proposals.into_iter().map(|proposal| (*network, proposal))),
+                Err(e) => digest.failed_networks.push((*network, e.to_string())),
+            }
+        }
+
+        Ok(digest)
+    }
+
+    /// Renders the digest as Markdown, which can be posted in a team channel.
+    pub fn render(&self, now: DateTime<Utc>) -> String {
+        let mut lines = vec![
+            format!("# Governance Digest {}", now.format("%Y-%m-%d")),
+            String::new(),
+            "## Unsubmitted Configurations".to_string(),
+            String::new(),
+        ];
+
+        match self.unsubmitted.is_empty() {
+            true => lines.push("- None".to_string()),
+            false => lines.extend(self.unsubmitted.iter().map(|helper| {
+                format!(
+                    "- {} {} at height {} (`{}`){}",
+                    helper.network,
+                    helper.target_version,
+                    helper.upgrade_height,
+                    helper.config_file_name,
+                    helper
+                        .proposal_id
+                        .map_or(String::new(), |proposal_id| format!(" - {}", proposal_id))
+                )
+            })),
+        }
+
+        lines.extend([
+            String::new(),
+            "## Proposals in Voting Period".to_string(),
+            String::new(),
+        ]);
+        match self.voting.is_empty() {
+            true => lines.push("- None".to_string()),
+            false => lines.extend(self.voting.iter().map(|(network, proposal)| {
+                format!(
+                    "- {} #{}: {} ({} left)",
+                    network,
+                    proposal.id,
+                    proposal.get_title(),
+                    proposal
+                        .voting_end_time
+                        .map_or("unknown time".to_string(), |end| format_time_left(
+                            end - now
+                        ))
+                )
+            })),
+        }
+        lines.extend(
+            self.failed_networks.iter().map(|(network, error)| {
+                format!("- WARNING: failed to query {}: {}", network, error)
+            }),
+        );
+
+        lines.extend([
+            String::new(),
+            "## Approaching Upgrades".to_string(),
+            String::new(),
+        ]);
+        match self.approaching.is_empty() {
+            true => lines.push("- None".to_string()),
+            false => lines.extend(self.approaching.iter().map(|record| {
+                format!(
+                    "- {} {} at height {} on {} (in {})",
+                    record.network,
+                    record.target_version,
+                    record.upgrade_height,
+                    record.estimated_time.format("%Y-%m-%d %H:%M UTC"),
+                    format_time_left(record.estimated_time - now)
+                )
+            })),
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Returns the upgrade configurations in the given directory, for which no proposal ID
+/// has been recorded in the history. Files that are not upgrade configurations are skipped.
+pub fn get_unsubmitted_configs(
+    dir: &Path,
+    history: &History,
+) -> Result<Vec<UpgradeHelper>, DigestError> {
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| ConfigFormat::from_path(path).is_some())
+        .collect();
+    paths.sort();
+
+    Ok(paths
+        .iter()
+        .filter_map(|path| from_file(path).ok())
+        .filter(|helper| {
+            history
+                .get(helper.network, &helper.target_version)
+                .is_none_or(|record| record.proposal_id.is_none())
+        })
+        .collect())
+}
+
+/// Returns the recorded upgrades, which have not been executed yet
+/// and are scheduled within the given lookahead window.
+pub fn get_approaching_upgrades(
+    history: &History,
+    now: DateTime<Utc>,
+    lookahead: Duration,
+) -> Vec<UpgradeRecord> {
+    let mut approaching: Vec<UpgradeRecord> = history
+        .records
+        .iter()
+        .filter(|record| record.executed_at.is_none())
+        .filter(|record| record.estimated_time > now && record.estimated_time <= now + lookahead)
+        .cloned()
+        .collect();
+    approaching.sort_by_key(|record| record.estimated_time);
+
+    approaching
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::path::PathBuf;
+    use test_dir::{DirBuilder, TestDir};
+
+    /// Returns an upgrade helper for testing with the given network and target version.
+    fn get_helper(
+        network: Network,
+        target_version: &str,
+        upgrade_time: DateTime<Utc>,
+    ) -> UpgradeHelper {
+        UpgradeHelper::new(
+            PathBuf::from("/root/.evmosd"),
+            network,
+            "v15.0.0",
+            target_version,
+            upgrade_time,
+            18_798_000,
+            "",
+        )
+    }
+
+    #[test]
+    fn test_get_unsubmitted_configs() {
+        let test_dir = TestDir::temp();
+        let upgrade_time = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        let submitted = get_helper(Network::Mainnet, "v16.0.0", upgrade_time);
+        let unsubmitted = get_helper(Network::Testnet, "v16.0.0-rc2", upgrade_time);
+
+        for helper in [&submitted, &unsubmitted] {
+            fs::write(
+                test_dir.path(&helper.config_file_name),
+                serde_json::to_string(helper).unwrap(),
+            )
+            .unwrap();
+        }
+        fs::write(test_dir.path("other.json"), "{}").unwrap();
+
+        let mut history = History::default();
+        history.record_generated(&submitted, upgrade_time);
+        history.record_generated(&unsubmitted, upgrade_time);
+        history.set_proposal_id(Network::Mainnet, "v16.0.0", 252);
+
+        let configs = get_unsubmitted_configs(test_dir.root(), &history).unwrap();
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].config_file_name, unsubmitted.config_file_name);
+    }
+
+    #[test]
+    fn test_get_approaching_upgrades() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        let mut history = History::default();
+        for (version, days) in [("v15.0.0", -10), ("v17.0.0", 20), ("v16.0.0", 2)] {
+            let time = now + Duration::days(days);
+            history.record_generated(&get_helper(Network::Mainnet, version, time), now);
+        }
+
+        let approaching = get_approaching_upgrades(&history, now, Duration::days(7));
+        assert_eq!(approaching.len(), 1);
+        assert_eq!(approaching[0].target_version, "v16.0.0");
+    }
+
+    #[test]
+    fn test_render() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 11, 12, 0, 0).unwrap();
+        let mut proposal: Proposal = serde_json::from_value(
+            serde_json::from_str::<serde_json::Value>(include_str!(
+                "testdata/proposal_upgrade.json"
+            ))
+            .unwrap()["proposal"]
+                .clone(),
+        )
+        .unwrap();
+        proposal.status = "PROPOSAL_STATUS_VOTING_PERIOD".to_string();
+
+        let digest = Digest {
+            unsubmitted: vec![get_helper(Network::Testnet, "v16.0.0-rc2", now)],
+            voting: vec![(Network::Mainnet, proposal)],
+            approaching: vec![],
+            failed_networks: vec![(Network::Testnet, "connection refused".to_string())],
+        };
+
+        let rendered = digest.render(now);
+        assert!(rendered.starts_with("# Governance Digest 2024-01-11"));
+        assert!(rendered.contains(
+            "- Testnet v16.0.0-rc2 at height 18798000 (`proposal-Testnet-v16.0.0-rc2.json`)"
+        ));
+        assert!(rendered.contains("- Mainnet #252: Evmos Mainnet v16.0.0 Upgrade (2d 3h 4m left)"));
+        assert!(rendered.contains("- WARNING: failed to query Testnet: connection refused"));
+        assert!(rendered.ends_with("## Approaching Upgrades\n\n- None"));
+    }
+}
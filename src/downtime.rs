@@ -0,0 +1,89 @@
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The number of minutes before the estimated upgrade time, from which users should stop
+/// sending transactions, because the upgrade height might be reached earlier than estimated.
+const STOP_TRANSACTIONS_BUFFER_MINUTES: i64 = 30;
+
+/// Contains the expected duration of the upgrade, which is derived
+/// from the durations of previous upgrades.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct DowntimeEstimate {
+    /// The average duration of the previous upgrades in minutes.
+    pub expected_minutes: u64,
+    /// The maximum duration of the previous upgrades in minutes.
+    pub max_minutes: u64,
+    /// The number of previous upgrades the estimate is based on.
+    pub n_upgrades: usize,
+}
+
+impl DowntimeEstimate {
+    /// Returns the time from which users should stop sending transactions.
+    pub fn stop_transactions_at(&self, upgrade_time: DateTime<Utc>) -> DateTime<Utc> {
+        upgrade_time - Duration::minutes(STOP_TRANSACTIONS_BUFFER_MINUTES)
+    }
+
+    /// Returns the time at which the block production is expected to resume.
+    pub fn expected_resumption(&self, upgrade_time: DateTime<Utc>) -> DateTime<Utc> {
+        upgrade_time + Duration::minutes(self.expected_minutes as i64)
+    }
+
+    /// Returns the time at which the block production should have resumed at the latest,
+    /// based on the longest previous upgrade.
+    pub fn latest_resumption(&self, upgrade_time: DateTime<Utc>) -> DateTime<Utc> {
+        upgrade_time + Duration::minutes(self.max_minutes as i64)
+    }
+}
+
+/// Estimates the downtime of the upgrade from the durations of previous upgrades in minutes.
+/// If no previous durations are given, no estimate is returned.
+pub fn estimate_downtime(durations: &[u64]) -> Option<DowntimeEstimate> {
+    let max_minutes = *durations.iter().max()?;
+    let n_upgrades = durations.len();
+    let expected_minutes = durations.iter().sum::<u64>().div_ceil(n_upgrades as u64);
+
+    Some(DowntimeEstimate {
+        expected_minutes,
+        max_minutes,
+        n_upgrades,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_estimate_downtime() {
+        let estimate = estimate_downtime(&[10, 25, 12]).expect("expected estimate");
+        assert_eq!(
+            estimate,
+            DowntimeEstimate {
+                expected_minutes: 16,
+                max_minutes: 25,
+                n_upgrades: 3,
+            }
+        );
+
+        let upgrade_time = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        assert_eq!(
+            estimate.stop_transactions_at(upgrade_time),
+            Utc.with_ymd_and_hms(2024, 1, 17, 15, 30, 0).unwrap()
+        );
+        assert_eq!(
+            estimate.expected_resumption(upgrade_time),
+            Utc.with_ymd_and_hms(2024, 1, 17, 16, 16, 0).unwrap()
+        );
+        assert_eq!(
+            estimate.latest_resumption(upgrade_time),
+            Utc.with_ymd_and_hms(2024, 1, 17, 16, 25, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_estimate_downtime_no_history() {
+        assert_eq!(estimate_downtime(&[]), None);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::cache::DiskCache;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Mutex;
+use url::Url;
+
+/// The key of the cache entry, which contains the recent failures of the endpoints.
+const FAILURES_KEY: &str = "endpoint-failures";
+
+/// The number of consecutive failures, after which an endpoint is deprioritized.
+const FAILURE_THRESHOLD: u32 = 3;
+
+/// The number of minutes, for which a repeatedly failing endpoint is deprioritized.
+const COOLDOWN_MINUTES: i64 = 15;
+
+/// The endpoints, which successfully answered queries during this run.
+static USED_ENDPOINTS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+
+/// Represents the recent failures of an endpoint.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EndpointFailures {
+    /// The number of consecutive failures.
+    pub count: u32,
+    /// The time of the last failure.
+    pub last_failure: DateTime<Utc>,
+}
+
+/// Contains the recent failures of the endpoints, which are keyed by their URL.
+///
+/// The failures are stored across runs, so that an endpoint, which failed repeatedly,
+/// is not queried first again until its cooldown has passed.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct FailureLog {
+    pub endpoints: BTreeMap<String, EndpointFailures>,
+}
+
+impl FailureLog {
+    /// Loads the failures from the given cache.
+    /// If no failures were stored within the cooldown or they cannot be read, the log is empty.
+    pub fn load(cache: &DiskCache, now: DateTime<Utc>) -> FailureLog {
+        cache
+            .read_entry(FAILURES_KEY, get_cooldown(), now)
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Stores the failures in the given cache, dropping the ones whose cooldown has passed.
+    pub fn save(&mut self, cache: &DiskCache, now: DateTime<Utc>) -> Result<(), std::io::Error> {
+        self.endpoints
+            .retain(|_, failures| now - failures.last_failure < get_cooldown());
+
+        cache.write_entry(FAILURES_KEY, &serde_json::to_string(self)?, now)
+    }
+
+    /// Records a failure of the given endpoint. Failures, which occurred before the cooldown, are not counted.
+    pub fn record_failure(&mut self, endpoint: &str, now: DateTime<Utc>) {
+        let failures = self
+            .endpoints
+            .entry(endpoint.to_string())
+            .or_insert(EndpointFailures {
+                count: 0,
+                last_failure: now,
+            });
+
+        if now - failures.last_failure >= get_cooldown() {
+            failures.count = 0;
+        }
+        failures.count += 1;
+        failures.last_failure = now;
+    }
+
+    /// Clears the failures of the given endpoint after it answered successfully.
+    /// Returns whether any failures were recorded.
+    pub fn record_success(&mut self, endpoint: &str) -> bool {
+        self.endpoints.remove(endpoint).is_some()
+    }
+
+    /// Returns whether the given endpoint failed repeatedly and is still within its cooldown.
+    pub fn is_deprioritized(&self, endpoint: &str, now: DateTime<Utc>) -> bool {
+        self.endpoints.get(endpoint).is_some_and(|failures| {
+            failures.count >= FAILURE_THRESHOLD && now - failures.last_failure < get_cooldown()
+        })
+    }
+}
+
+/// Returns the duration, for which a repeatedly failing endpoint is deprioritized.
+fn get_cooldown() -> Duration {
+    Duration::minutes(COOLDOWN_MINUTES)
+}
+
+/// Returns whether the given endpoint failed repeatedly according to the failures stored
+/// in the given cache and should not be queried first.
+pub fn is_deprioritized(cache: &DiskCache, endpoint: &Url) -> bool {
+    let now = Utc::now();
+    FailureLog::load(cache, now).is_deprioritized(endpoint.as_str(), now)
+}
+
+/// Records the outcome of a query to the given endpoint and returns the given result.
+///
+/// Failures are stored in the given cache, so that the endpoint is deprioritized after repeated
+/// failures. Without a cache, only the successfully queried endpoints are remembered as used during this run.
+pub fn track<T, E>(
+    cache: Option<&DiskCache>,
+    endpoint: &Url,
+    result: Result<T, E>,
+) -> Result<T, E> {
+    if result.is_ok() {
+        USED_ENDPOINTS
+            .lock()
+            .expect("failed to lock used endpoints")
+            .insert(endpoint.to_string());
+    }
+    let Some(cache) = cache else {
+        return result;
+    };
+
+    let now = Utc::now();
+    let mut log = FailureLog::load(cache, now);
+    let changed = match &result {
+        Ok(_) => log.record_success(endpoint.as_str()),
+        Err(_) => {
+            log.record_failure(endpoint.as_str(), now);
+            true
+        }
+    };
+
+    if changed {
+        if let Err(e) = log.save(cache, now) {
+            println!("WARNING: failed to store endpoint failures: {}", e);
+        }
+    }
+
+    result
+}
+
+/// Returns the endpoints, which successfully answered queries during this run.
+pub fn get_used_endpoints() -> Vec<String> {
+    USED_ENDPOINTS
+        .lock()
+        .expect("failed to lock used endpoints")
+        .iter()
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use test_dir::{DirBuilder, TestDir};
+
+    const ENDPOINT: &str = "https://rest.evmos.lava.build/";
+
+    #[test]
+    fn test_deprioritize_after_repeated_failures() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        let mut log = FailureLog::default();
+
+        log.record_failure(ENDPOINT, now);
+        log.record_failure(ENDPOINT, now + Duration::minutes(1));
+        assert!(!log.is_deprioritized(ENDPOINT, now + Duration::minutes(1)));
+
+        log.record_failure(ENDPOINT, now + Duration::minutes(2));
+        assert!(log.is_deprioritized(ENDPOINT, now + Duration::minutes(2)));
+        assert!(
+            !log.is_deprioritized(ENDPOINT, now + Duration::minutes(17)),
+            "expected endpoint to be queried again after the cooldown"
+        );
+
+        assert!(log.record_success(ENDPOINT));
+        assert!(!log.is_deprioritized(ENDPOINT, now + Duration::minutes(2)));
+    }
+
+    #[test]
+    fn test_failures_before_cooldown_are_not_counted() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        let mut log = FailureLog::default();
+
+        log.record_failure(ENDPOINT, now);
+        log.record_failure(ENDPOINT, now + Duration::minutes(1));
+        log.record_failure(ENDPOINT, now + Duration::minutes(30));
+        assert_eq!(log.endpoints[ENDPOINT].count, 1);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let test_dir = TestDir::temp();
+        // NOTE: the failures are stored even if caching responses is disabled
+        let cache = DiskCache::new(test_dir.path("cache"), Duration::zero());
+        let now = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        let mut log = FailureLog::default();
+        log.record_failure(ENDPOINT, now);
+        log.record_failure("http://localhost:1317/", now - Duration::hours(1));
+
+        log.save(&cache, now).expect("failed to save failures");
+
+        let loaded = FailureLog::load(&cache, now + Duration::minutes(1));
+        assert_eq!(
+            loaded.endpoints.len(),
+            1,
+            "expected expired entry to be dropped"
+        );
+        assert_eq!(loaded.endpoints[ENDPOINT].count, 1);
+        assert_eq!(
+            FailureLog::load(&cache, now + Duration::minutes(COOLDOWN_MINUTES)),
+            FailureLog::default(),
+            "expected failures to expire after the cooldown"
+        );
+    }
+
+    #[test]
+    fn test_track() {
+        let test_dir = TestDir::temp();
+        let cache = DiskCache::new(test_dir.path("cache"), Duration::minutes(10));
+        let endpoint = Url::parse(ENDPOINT).unwrap();
+
+        for _ in 0..FAILURE_THRESHOLD {
+            let _ = track(Some(&cache), &endpoint, Err::<(), ()>(()));
+        }
+        assert!(is_deprioritized(&cache, &endpoint));
+
+        let _ = track(Some(&cache), &endpoint, Ok::<(), ()>(()));
+        assert!(!is_deprioritized(&cache, &endpoint));
+        assert!(get_used_endpoints().contains(&ENDPOINT.to_string()));
+    }
+}
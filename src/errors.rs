@@ -1,4 +1,6 @@
 use crate::network::Network;
+use crate::notes::NotesSource;
+use crate::template::TemplateReport;
 use async_openai::error::OpenAIError;
 use chrono::{DateTime, Utc};
 use inquire::InquireError;
@@ -6,11 +8,41 @@ use std::path::PathBuf;
 use std::string::FromUtf8Error;
 use thiserror::Error;
 
+/// Error type for failed loading or invalid values of the answers file
+#[derive(Error, Debug)]
+pub enum AnswersError {
+    #[error("Invalid answer for {0}: {1}")]
+    InvalidValue(String, String),
+    #[error("Failed to read answers file: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to parse answers file: {0}")]
+    Parse(#[from] serde_yaml::Error),
+    #[error("Unknown prompt key in answers file: {0}")]
+    UnknownKey(String),
+}
+
+/// Error type for failures when getting the governance module authority
+#[derive(Error, Debug)]
+pub enum AuthorityError {
+    #[error("Failed to encode address: {0}")]
+    Bech32(#[from] bech32::Error),
+    #[error("Failed to query module account: {0}")]
+    Http(#[from] HttpError),
+    #[error("No address found for module account {0}")]
+    MissingAddress(String),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+}
+
 /// High level error type than can occur when handling the block information
 #[derive(Error, Debug)]
 pub enum BlockError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to decode base64 value: {0}")]
+    Decode(#[from] base64::DecodeError),
     #[error("Failed to get block information: {0}")]
-    GetBlock(#[from] reqwest::Error),
+    GetBlock(#[from] HttpError),
     #[error("Failed to get parse date: {0}")]
     ParseDate(#[from] chrono::ParseError),
     #[error("Failed to parse int: {0}")]
@@ -19,6 +51,8 @@ pub enum BlockError {
     ParseTime,
     #[error("Failed to parse url: {0}")]
     ParseUrl(#[from] url::ParseError),
+    #[error("Height {0} is not after the latest block height {1}")]
+    PastHeight(u64, u64),
     #[error("Failed to build regex: {0}")]
     Regex(#[from] regex::Error),
     #[error("Failed to unpack block response: {0}")]
@@ -28,24 +62,62 @@ pub enum BlockError {
 /// High level error type that can occur when generating the submission command
 #[derive(Error, Debug)]
 pub enum CommandError {
+    #[error("Failed to derive account: {0}")]
+    Account(#[from] AccountError),
+    #[error("Failed to convert address: {0}")]
+    Address(#[from] AddressError),
+    #[error("Missing approvals: {0}")]
+    Approval(#[from] ApprovalError),
+    #[error("Failed to get block provider: {0}")]
+    Block(#[from] BlockError),
     #[error("Failed to get client configuration: {0}")]
     ClientConfig(#[from] ConfigError),
     #[error("Failed to validate Commonwealth link: {0}")]
     Commonwealth(#[from] CommonwealthError),
     #[error("Failed to get helper: {0}")]
     GetHelper(#[from] HelperError),
+    #[error("Failed to broadcast transaction: {0}")]
+    Gov(#[from] GovError),
     #[error("Failed to get user input: {0}")]
     Input(#[from] InputError),
     #[error("Failed to get key: {0}")]
     Key(#[from] KeysError),
+    #[error("Failed to record artifacts in manifest: {0}")]
+    Manifest(#[from] ManifestError),
+    #[error("Failed to validate proposal: {0}")]
+    Message(#[from] MessageError),
+    #[error("No secret found in environment variable {0}")]
+    MissingSecret(String),
+    #[error("Failed to parse REST endpoint: {0}")]
+    ParseUrl(#[from] url::ParseError),
+    #[error("Failed connectivity checks: {0}")]
+    Preflight(#[from] PreflightError),
     #[error("Failed to prepare command: {0}")]
     Prepare(#[from] PrepareError),
     #[error("Failed to render command: {0}")]
     Render(#[from] handlebars::RenderError),
+    #[error("Failed to serialize proposal: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("Failed to draft description: {0}")]
+    Summary(#[from] SummaryError),
     #[error("Failed to write to file: {0}")]
     Write(#[from] std::io::Error),
 }
 
+/// Error type for steps that did not finish before their deadline
+#[derive(Error, Debug)]
+#[error("Step '{0}' did not finish within {1} seconds")]
+pub struct DeadlineError(pub &'static str, pub u64);
+
+/// Error type for failures when collecting the digest of pending governance work
+#[derive(Error, Debug)]
+pub enum DigestError {
+    #[error("Failed to load history: {0}")]
+    History(#[from] HistoryError),
+    #[error("Failed to read configurations: {0}")]
+    IO(#[from] std::io::Error),
+}
+
 /// Error type for failures related to Commonwealth
 #[derive(Error, Debug)]
 pub enum CommonwealthError {
@@ -54,7 +126,47 @@ pub enum CommonwealthError {
     #[error("Failed to parse url: {0}")]
     ParseUrl(#[from] url::ParseError),
     #[error("Failed to get body: {0}")]
-    GetBody(#[from] reqwest::Error),
+    GetBody(#[from] HttpError),
+}
+
+/// Error type for invalid or unconvertible account addresses
+#[derive(Error, Debug)]
+pub enum AddressError {
+    #[error("Invalid Bech32 address: {0}")]
+    Bech32(#[from] bech32::Error),
+    #[error("Invalid hex address: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Invalid address length: expected 20 bytes, got {0}")]
+    Length(usize),
+    #[error("Invalid address prefix: expected {0}, got {1}")]
+    Prefix(String, String),
+}
+
+/// Error type for failed derivations of accounts from secrets
+#[derive(Error, Debug)]
+pub enum AccountError {
+    #[error("Failed to derive address: {0}")]
+    Address(#[from] AddressError),
+    #[error("Failed to derive key: {0}")]
+    Derivation(#[from] bip32::Error),
+    #[error("Invalid private key: {0}")]
+    Key(#[from] k256::ecdsa::Error),
+    #[error("Invalid hex private key: {0}")]
+    Hex(#[from] hex::FromHexError),
+    #[error("Failed to decode account: {0}")]
+    Decode(#[from] prost::DecodeError),
+    #[error("Invalid mnemonic: {0}")]
+    Mnemonic(#[from] bip39::Error),
+    #[error("Ethereum account does not contain a base account")]
+    MissingBaseAccount,
+    #[error("Account {0} not found")]
+    NotFound(String),
+    #[error("Failed to parse balance: {0}")]
+    ParseAmount(#[from] std::num::ParseIntError),
+    #[error("Failed to query account: {0}")]
+    Query(#[from] GovError),
+    #[error("Unsupported account type: {0}")]
+    UnsupportedType(String),
 }
 
 /// Error type for failed parsing of the client configuration
@@ -64,6 +176,41 @@ pub enum ConfigError {
     Read(#[from] std::io::Error),
     #[error("Failed to parse toml: {0}")]
     Parse(#[from] toml::de::Error),
+    #[error("Failed to convert configuration: {0}")]
+    Convert(#[from] serde_json::Error),
+    #[error("Failed to decrypt configuration value: {0}")]
+    Secret(#[from] SecretError),
+    #[error("Failed to serialize toml: {0}")]
+    Serialize(#[from] toml::ser::Error),
+}
+
+/// Error type for failed queries of governance proposals
+#[derive(Error, Debug)]
+pub enum GovError {
+    #[error("Transaction failed with code {0}: {1}")]
+    Broadcast(u32, String),
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("gRPC request failed: {0}")]
+    GrpcStatus(#[from] Box<tonic::Status>),
+    #[error("Failed to connect to gRPC endpoint: {0}")]
+    GrpcTransport(#[from] tonic::transport::Error),
+    #[error("Failed to query REST endpoint: {0}")]
+    Http(#[from] HttpError),
+    #[error("No gRPC endpoint configured for {0}")]
+    MissingGrpcUrl(Network),
+    #[error("Missing field in response: {0}")]
+    MissingField(&'static str),
+    #[error("Failed to parse duration: {0}")]
+    ParseDuration(String),
+    #[error("Failed to parse proposal ID: {0}")]
+    ParseProposalId(String),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+    #[error("Failed to send request: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Failed to unpack response: {0}")]
+    UnpackResponse(#[from] serde_json::Error),
 }
 
 /// Error type for failed helper operations
@@ -73,23 +220,148 @@ pub enum HelperError {
     Read(#[from] std::io::Error),
     #[error("Failed to parse JSON: {0}")]
     Parse(#[from] serde_json::Error),
+    #[error("Failed to parse TOML: {0}")]
+    ParseToml(#[from] toml::de::Error),
+    #[error("Refusing to overwrite encrypted values in {0} with plain text")]
+    PlaintextOverwrite(PathBuf),
+    #[error("Failed to decrypt or encrypt configuration value: {0}")]
+    Secret(#[from] SecretError),
+    #[error("Failed to serialize TOML: {0}")]
+    SerializeToml(#[from] toml::ser::Error),
+    #[error("Failed to parse or serialize YAML: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("Unsupported configuration format: {0}")]
+    UnsupportedFormat(PathBuf),
     #[error("Invalid helper configuration: {0}")]
     Validate(#[from] ValidationError),
 }
 
+/// Error type for failed operations on the upgrade history
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("Failed to get block information: {0}")]
+    Block(#[from] BlockError),
+    #[error("Failed to read or write history file: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to parse history: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+/// Error type for failed HTTP requests, which maps common upstream failures to dedicated variants
+#[derive(Error, Debug)]
+pub enum HttpError {
+    #[error("Invalid HTTP configuration: {0}")]
+    Config(String),
+    #[error("Failed to deserialize response from {0}: {1}; body: {2}")]
+    Deserialize(String, serde_json::Error, String),
+    #[error("Rate limited by {0}; try again later")]
+    RateLimited(String),
+    #[error("Failed to read CA certificate: {0}")]
+    ReadCertificate(std::io::Error),
+    #[error("Failed to send request: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Received status {1} from {0}; body: {2}")]
+    Status(String, reqwest::StatusCode, String),
+    #[error("Expected data from {0}, but received an HTML page: {1}")]
+    UnexpectedHtml(String, String),
+    #[error("Upstream of {0} is unavailable ({1}); try again later")]
+    Upstream(String, reqwest::StatusCode),
+}
+
+/// Error type for failed queries of the IBC client information
+#[derive(Error, Debug)]
+pub enum IbcError {
+    #[error("Failed to get IBC client information: {0}")]
+    GetClients(#[from] HttpError),
+    #[error("Failed to parse date: {0}")]
+    ParseDate(#[from] chrono::ParseError),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+    #[error("Invalid trusting period: {0}")]
+    TrustingPeriod(String),
+    #[error("Failed to unpack IBC response: {0}")]
+    UnpackResponse(#[from] serde_json::Error),
+}
+
+/// High level error type that can occur when importing an on-chain proposal
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Failed to get block information: {0}")]
+    Block(#[from] BlockError),
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to query proposal: {0}")]
+    Gov(#[from] GovError),
+    #[error("Failed to write helper configuration: {0}")]
+    Helper(#[from] HelperError),
+    #[error("Error during user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Invalid version: {0}")]
+    InvalidVersion(String),
+    #[error("Proposal {0} does not contain a software upgrade plan")]
+    NoUpgradePlan(u64),
+    #[error(
+        "Failed to detect previous version of proposal {0}; provide it with --previous-version"
+    )]
+    PreviousVersion(u64),
+    #[error("Failed to write proposal: {0}")]
+    Write(#[from] std::io::Error),
+}
+
+/// High level error type that can occur when generating the upgrade instructions for validators
+#[derive(Error, Debug)]
+pub enum InstructionsError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Deadline exceeded: {0}")]
+    Deadline(#[from] DeadlineError),
+    #[error("Failed to get helper: {0}")]
+    GetHelper(#[from] HelperError),
+    #[error("Failed to get release: {0}")]
+    GetRelease(#[from] ReleaseError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Failed to record artifacts in manifest: {0}")]
+    Manifest(#[from] ManifestError),
+    #[error("Failed to parse release assets: {0}")]
+    ParseAssets(#[from] serde_json::Error),
+    #[error("Failed to prepare release assets: {0}")]
+    Prepare(#[from] PrepareError),
+    #[error("Failed to render instructions: {0}")]
+    Template(#[from] TemplateError),
+    #[error("Failed to write instructions: {0}")]
+    Write(#[from] std::io::Error),
+}
+
 /// Error type for failed user input
 #[derive(Error, Debug)]
 pub enum InputError {
+    #[error("Failed to use answers file: {0}")]
+    Answers(#[from] AnswersError),
     #[error("Error getting block information: {0}")]
     Block(#[from] BlockError),
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Deadline exceeded: {0}")]
+    Deadline(#[from] DeadlineError),
+    #[error("Failed to read existing configuration: {0}")]
+    Existing(#[from] HelperError),
     #[error("Failed to get GitHub data: {0}")]
     GitHub(#[from] octocrab::Error),
-    #[error("Invalid network: {0}")]
-    InvalidNetwork(String),
+    #[error("Failed to check upgrade handler: {0}")]
+    Handler(#[from] HandlerError),
+    #[error("Invalid value of {0}: {1}")]
+    InvalidEnvVar(&'static str, String),
     #[error("Got IO error: {0}")]
     IO(#[from] std::io::Error),
     #[error("No configuration files found in current directory: {0}")]
     NoConfigFiles(PathBuf),
+    #[error("Failed to get notes to summarize: {0}")]
+    Notes(#[from] NotesError),
+    #[error("Failed to get release: {0}")]
+    Release(#[from] ReleaseError),
+    #[error("Failed to suggest upgrade times: {0}")]
+    Schedule(#[from] ScheduleError),
     #[error("Failed to create summary: {0}")]
     Summary(#[from] SummaryError),
     #[error("Error during user input: {0}")]
@@ -98,90 +370,709 @@ pub enum InputError {
     Validate(#[from] ValidationError),
 }
 
+/// Error type for failed approvals of generated files
+#[derive(Error, Debug)]
+pub enum ApprovalError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to get upgrade helper: {0}")]
+    GetHelper(#[from] HelperError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Failed to read or write approvals: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to load manifest: {0}")]
+    Manifest(#[from] ManifestError),
+    #[error("{0} has {1} valid approvals, but {2} are required")]
+    Missing(String, usize, usize),
+    #[error("{0} was modified after it was generated")]
+    Modified(String),
+    #[error("Approvals are required, but no allowed signers file is configured to verify them")]
+    NoAllowedSigners,
+    #[error("{0} is not recorded in the manifest; generate it first")]
+    NotInManifest(String),
+    #[error("Failed to parse or serialize approvals: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("SSH signature operation failed: {0}")]
+    Signature(String),
+}
+
+/// Error type for failed checks of the account sequence before submission
+#[derive(Error, Debug)]
+pub enum SequenceError {
+    #[error("Failed to decode base64 value: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("Failed to query account or mempool: {0}")]
+    Http(#[from] HttpError),
+    #[error("No account number found for account {0}")]
+    MissingAccountNumber(String),
+    #[error("No sequence found for account {0}")]
+    MissingSequence(String),
+    #[error("Failed to parse sequence: {0}")]
+    ParseInt(#[from] std::num::ParseIntError),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+}
+
+/// Error type for failed interactions with the tracking issues on GitHub
+#[derive(Error, Debug)]
+pub enum TrackingError {
+    #[error("Failed to interact with GitHub: {0}")]
+    GitHub(#[from] octocrab::Error),
+    #[error("Failed to read generated file: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to hash generated file: {0}")]
+    Manifest(#[from] ManifestError),
+    #[error("No tracking issue returned for {0}")]
+    MissingIssue(String),
+}
+
+/// Error type for failed loading of the glossary
+#[derive(Error, Debug)]
+pub enum GlossaryError {
+    #[error("Failed to read glossary: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to parse glossary: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// Error type for failed loading of the readability limits
+#[derive(Error, Debug)]
+pub enum ReadabilityError {
+    #[error("Failed to read readability limits: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to parse readability limits: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// Error type for failed loading of the Markdown style
+#[derive(Error, Debug)]
+pub enum MarkdownError {
+    #[error("Invalid list marker {0}; expected one of -, * or +")]
+    InvalidListMarker(char),
+    #[error("Failed to read Markdown style: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to parse Markdown style: {0}")]
+    Parse(#[from] toml::de::Error),
+}
+
+/// Error type for failed checks of the upgrade handler in the release
+#[derive(Error, Debug)]
+pub enum HandlerError {
+    #[error("Upgrade plan {0} was already applied at height {1}; choose a new plan name")]
+    AlreadyApplied(String, u64),
+    #[error("Failed to query repository contents: {0}")]
+    GitHub(#[from] octocrab::Error),
+    #[error("Failed to query applied plan: {0}")]
+    Http(#[from] HttpError),
+    #[error("No upgrade handler for plan {0} found in {1}; available upgrade directories: {}", .2.join(", "))]
+    NotFound(String, String, Vec<String>),
+    #[error("Failed to parse applied height: {0}")]
+    ParseHeight(#[from] std::num::ParseIntError),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+}
+
+/// Error type for failures when setting up the application context
+#[derive(Error, Debug)]
+pub enum ContextError {
+    #[error("Failed to build GitHub client: {0}")]
+    GitHub(#[from] octocrab::Error),
+    #[error("Failed to set up HTTP client: {0}")]
+    Http(#[from] HttpError),
+}
+
+/// Error type for failed schedule suggestions
+#[derive(Error, Debug)]
+pub enum ScheduleError {
+    #[error("The configured validator timezone distribution has no positive shares")]
+    EmptyDistribution,
+    #[error("Invalid timezone in validator timezone distribution: {0}")]
+    InvalidTimezone(String),
+}
+
+/// Error type for failed comparisons with the proposal of the previous upgrade
+#[derive(Error, Debug)]
+pub enum CompareError {
+    #[error("Failed to get helper: {0}")]
+    GetHelper(#[from] HelperError),
+    #[error("Failed to query previous proposal: {0}")]
+    Gov(#[from] GovError),
+    #[error("Failed to load history: {0}")]
+    History(#[from] HistoryError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Failed to read proposal file: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Proposal of the previous upgrade to {0} not found in the workspace or history; pass its ID with --proposal-id")]
+    MissingPrevious(String),
+    #[error("Proposal {0} does not contain a software upgrade plan")]
+    NoUpgradePlan(u64),
+}
+
+/// Error type for failed queries of the chain registry
+#[derive(Error, Debug)]
+pub enum RegistryError {
+    #[error("Failed to update chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to query chain registry: {0}")]
+    Http(#[from] HttpError),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+}
+
+/// Error type for failed operations on the manifest of the generated artifacts
+#[derive(Error, Debug)]
+pub enum ManifestError {
+    #[error("Failed to read or write manifest: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to parse or serialize manifest: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+/// High level error type that can occur when linting a proposal against the governance guidelines
+#[derive(Error, Debug)]
+pub enum LintError {
+    #[error("Proposal contains {0} broken links")]
+    BrokenLinks(usize),
+    #[error("Failed to review proposal: {0}")]
+    Llm(#[from] SummaryError),
+    #[error("Proposal is missing {0} guideline items")]
+    MissingItems(usize),
+    #[error("Failed to read file: {0}")]
+    Read(#[from] std::io::Error),
+}
+
+/// Error type for invalid messages of a gov v1 proposal
+#[derive(Error, Debug)]
+pub enum MessageError {
+    #[error("Messages use different authorities: {0} and {1}")]
+    AuthorityMismatch(String, String),
+    #[error("Invalid deposit: {0}")]
+    Deposit(String),
+    #[error("Proposal field {0} must not be empty")]
+    EmptyField(&'static str),
+    #[error("Cannot encode {0} for direct submission")]
+    Encode(String),
+    #[error("Failed to read message file: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Proposal metadata exceeds the maximum length with {0} characters")]
+    MetadataTooLong(usize),
+    #[error("Message {0} is missing the @type field")]
+    MissingType(usize),
+    #[error("Proposal contains {0} software upgrade messages")]
+    MultipleUpgrades(usize),
+    #[error("Failed to parse message: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("Proposal does not match the schema: {0}")]
+    Schema(String),
+    #[error("Failed to sign transaction: {0}")]
+    Sign(#[from] AccountError),
+}
+
+/// Error type for failed initialization of the local node
+#[derive(Error, Debug)]
+pub enum LocalnetError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to execute CLI command: {0}")]
+    CLICommand(#[from] std::io::Error),
+    #[error("Command 'evmosd {0}' failed: {1}")]
+    Command(String, String),
+    #[error("Failed to handle genesis file: {0}")]
+    Genesis(#[from] serde_json::Error),
+    #[error("Home directory already exists: {0}; use --overwrite to replace it")]
+    HomeExists(PathBuf),
+    #[error("Failed to parse toml: {0}")]
+    ParseToml(#[from] toml::de::Error),
+    #[error("Failed to serialize toml: {0}")]
+    SerializeToml(#[from] toml::ser::Error),
+}
+
 /// Error type for failed operations regarding keys
 #[derive(Error, Debug)]
 pub enum KeysError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
     #[error("Failed to execute CLI command: {0}")]
     CLICommand(#[from] std::io::Error),
+    #[error("{0} was not funded within {1} seconds")]
+    FundsTimeout(String, u64),
     #[error("Failed to get balance: {0}")]
-    GetBalance(#[from] reqwest::Error),
+    GetBalance(#[from] HttpError),
     #[error("Failed to get keys from keyring: {0}")]
     GetKeys(#[from] InputError),
-    #[error("No keys with balance found")]
+    #[error("Failed to import key: {0}")]
+    Import(String),
+    #[error("Cannot import keys into {0} because {1}")]
+    ImportHome(PathBuf, String),
+    #[error("Invalid secret: {0}")]
+    InvalidSecret(String),
+    #[error("Key not found in keyring: {0}")]
+    KeyNotFound(String),
+    #[error("No keys with balance or fee allowance found")]
     NoKeysWithBalance,
     #[error("Failed to convert bytes to string: {0}")]
     OutputConversion(#[from] FromUtf8Error),
+    #[error("Failed to parse balance amount: {0}")]
+    ParseAmount(#[from] std::num::ParseIntError),
     #[error("Failed to parse url: {0}")]
     ParseUrl(#[from] url::ParseError),
     #[error("Failed to unpack balance response: {0}")]
     UnpackResponse(#[from] serde_json::Error),
+    #[error("Failed to write client configuration: {0}")]
+    WriteConfig(#[from] toml::ser::Error),
+}
+
+/// Error type for failed verifications of the checksum file signature
+#[derive(Error, Debug)]
+pub enum SignatureError {
+    #[error("Failed to download signature: {0}")]
+    Download(#[from] HttpError),
+    #[error("Failed to write signature files: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Signature asset {0} not found in release assets")]
+    MissingAsset(String),
+    #[error("Verification with {0} failed: {1}")]
+    Verification(String, String),
+}
+
+/// Error type for failed encryption or decryption of configuration values
+#[derive(Error, Debug)]
+pub enum SecretError {
+    #[error("Failed to decode encrypted value: {0}")]
+    Decode(#[from] base64::DecodeError),
+    #[error("Failed to decrypt value; check the passphrase")]
+    Decrypt,
+    #[error("Failed to encrypt value")]
+    Encrypt,
+    #[error("Invalid encrypted value: {0}")]
+    Format(String),
+    #[error("Error during user input: {0}")]
+    Input(#[from] InquireError),
 }
 
 /// Error type for failed interactions with the LLM to generate the release notes summary
 #[derive(Error, Debug)]
 pub enum SummaryError {
+    #[error("Failed to load glossary: {0}")]
+    Glossary(#[from] GlossaryError),
     #[error("Failed to communicate with LLM: {0}")]
     Llm(#[from] OpenAIError),
     #[error("No summary generated")]
     NoSummary,
+}
+
+/// Error type for failures when building the notes, which are summarized for the proposal
+#[derive(Error, Debug)]
+pub enum NotesError {
+    #[error("No {0} found to summarize; choose another --notes-source")]
+    Empty(NotesSource),
+    #[error("Failed to query commits: {0}")]
+    GitHub(#[from] octocrab::Error),
     #[error("Failed to get release notes: {0}")]
-    ReleaseNotes(#[from] ReleaseError),
+    Release(#[from] ReleaseError),
+}
+
+/// Error type for failed connectivity checks before starting the wizard
+#[derive(Error, Debug)]
+pub enum PreflightError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to build HTTP client: {0}")]
+    Http(#[from] HttpError),
+    #[error("Failed to parse REST endpoint: {0}")]
+    ParseUrl(#[from] url::ParseError),
+    #[error("Failed to reach endpoint: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Required endpoints are unreachable: {0}; use --skip-preflight to continue anyway")]
+    Unreachable(String),
 }
 
 /// Error type for failed preparation of the proposal command
 #[derive(Error, Debug)]
 pub enum PrepareError {
+    #[error("Failed to get governance authority: {0}")]
+    Authority(#[from] AuthorityError),
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Deadline exceeded: {0}")]
+    Deadline(#[from] DeadlineError),
     #[error("Failed to download checksums: {0}")]
-    DownloadChecksums(#[from] reqwest::Error),
+    DownloadChecksums(#[from] HttpError),
     #[error("checksum.txt not found in assets")]
     GetChecksumAsset,
     #[error("Failed to get helper: {0}")]
     GetHelper(#[from] HelperError),
-    #[error("Failed to get release from GitHub: {0}")]
-    GetRelease(#[from] octocrab::Error),
+    #[error("Failed to get release: {0}")]
+    GetRelease(#[from] ReleaseError),
     #[error("Failed user input: {0}")]
     Input(#[from] InputError),
+    #[error("Invalid proposal message: {0}")]
+    Message(#[from] MessageError),
+    #[error("Failed to parse REST endpoint: {0}")]
+    ParseUrl(#[from] url::ParseError),
+    #[error("Failed to verify checksum signature: {0}")]
+    Signature(#[from] SignatureError),
     #[error("Failed to get summary: {0}")]
     Summary(#[from] SummaryError),
     #[error("Failed to read proposal file: {0}")]
     ReadProposal(#[from] std::io::Error),
     #[error("Failed to render command: {0}")]
     RenderCommand(#[from] handlebars::RenderError),
+    #[error("Failed to render command template: {0}")]
+    Template(#[from] TemplateError),
     #[error("Failed to validate helper: {0}")]
     ValidateHelper(#[from] ValidationError),
+    #[error("Failed to host upgrade info: {0}")]
+    UpgradeInfo(#[from] UploadError),
 }
 
 /// High level error type that can occur while preparing the proposal contents
 #[derive(Error, Debug)]
 pub enum ProposalError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to get helper: {0}")]
+    GetHelper(#[from] HelperError),
+    #[error("Failed to query release candidates: {0}")]
+    GetReleaseCandidates(#[from] octocrab::Error),
+    #[error("Failed to record upgrade in history: {0}")]
+    History(#[from] HistoryError),
     #[error("Failed to get user input: {0}")]
     Input(#[from] InputError),
+    #[error("Failed to record artifacts in manifest: {0}")]
+    Manifest(#[from] ManifestError),
+    #[error("Failed to format proposal: {0}")]
+    Markdown(#[from] MarkdownError),
+    #[error("Failed connectivity checks: {0}")]
+    Preflight(#[from] PreflightError),
     #[error("Failed to render proposal: {0}")]
     Render(#[from] handlebars::RenderError),
+    #[error("Failed to write proposal revision: {0}")]
+    Revision(#[from] RevisionError),
+    #[error("Failed to render proposal template: {0}")]
+    Template(#[from] TemplateError),
     #[error("Failed to validate helper: {0}")]
     Validate(#[from] ValidationError),
     #[error("Failed to write to file: {0}")]
     Write(#[from] std::io::Error),
 }
 
+/// Error type for failed operations on the draft revisions of a proposal
+#[derive(Error, Debug)]
+pub enum RevisionError {
+    #[error("Failed to read or write revision: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Revision {1} of {0} not found")]
+    NotFound(String, u32),
+    #[error("Failed to parse or serialize revision index: {0}")]
+    Parse(#[from] serde_json::Error),
+}
+
+/// High level error type that can occur when querying information from the network
+#[derive(Error, Debug)]
+pub enum QueryError {
+    #[error("Failed to get block: {0}")]
+    Block(#[from] BlockError),
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to get helper: {0}")]
+    Helper(#[from] HelperError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+}
+
+/// High level error type that can occur when generating the vote reminder for validators
+#[derive(Error, Debug)]
+pub enum ReminderError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to get helper: {0}")]
+    GetHelper(#[from] HelperError),
+    #[error("Failed to query governance module: {0}")]
+    Gov(#[from] GovError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Failed to record artifacts in manifest: {0}")]
+    Manifest(#[from] ManifestError),
+    #[error("No proposal ID found in the configuration; please provide it with --proposal-id")]
+    MissingProposalId,
+    #[error("No end of the voting period found for proposal {0}")]
+    MissingVotingEnd(u64),
+    #[error("Failed to render vote reminder: {0}")]
+    Template(#[from] TemplateError),
+    #[error("Failed to write vote reminder: {0}")]
+    Write(#[from] std::io::Error),
+}
+
+/// High level error type that can occur when generating the calendar of the upgrade schedule
+#[derive(Error, Debug)]
+pub enum CalendarError {
+    #[error("Failed to get helper: {0}")]
+    GetHelper(#[from] HelperError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Failed to record artifacts in manifest: {0}")]
+    Manifest(#[from] ManifestError),
+    #[error("Failed to write calendar: {0}")]
+    Write(#[from] std::io::Error),
+}
+
+/// High level error type that can occur when generating the runbook for the upgrade day
+#[derive(Error, Debug)]
+pub enum RunbookError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to get helper: {0}")]
+    GetHelper(#[from] HelperError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Failed to record artifacts in manifest: {0}")]
+    Manifest(#[from] ManifestError),
+    #[error("Failed to render runbook: {0}")]
+    Render(#[from] handlebars::RenderError),
+    #[error("Failed to load template partials: {0}")]
+    Template(#[from] TemplateError),
+    #[error("Failed to write runbook: {0}")]
+    Write(#[from] std::io::Error),
+}
+
+/// High level error type that can occur when querying the governance status of a network
+#[derive(Error, Debug)]
+pub enum StatusError {
+    #[error("Failed to get block provider: {0}")]
+    Block(#[from] BlockError),
+    #[error("Failed to query governance module: {0}")]
+    Gov(#[from] GovError),
+    #[error("Failed to query REST endpoint: {0}")]
+    Http(#[from] HttpError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+}
+
+/// Error type for failures when exporting the status of the tracked proposals
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to load history: {0}")]
+    History(#[from] HistoryError),
+    #[error("Failed to query REST endpoint: {0}")]
+    Http(#[from] HttpError),
+    #[error("Failed to serialize status: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Failed to parse bonded tokens: {0}")]
+    ParseAmount(#[from] std::num::ParseIntError),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+    #[error("Failed to write file: {0}")]
+    Write(#[from] std::io::Error),
+}
+
 /// High level error type that can occur when handling the release information
 #[derive(Error, Debug)]
 pub enum ReleaseError {
+    #[error("Failed to get release from GitHub: {0}")]
+    GitHub(octocrab::Error),
+    #[error("Failed to reach GitHub, please check the connection and try again: {0}")]
+    Network(octocrab::Error),
     #[error("No release notes found")]
     NoReleaseNotes,
+    #[error("No release found for tag {0}; the nearest existing tags are: {}", .1.join(", "))]
+    TagNotFound(String, Vec<String>),
+}
+
+/// High level error type that can occur when verifying an on-chain proposal
+#[derive(Error, Debug)]
+pub enum VerifyError {
+    #[error(
+        "Found {0} critical mismatch(es) between the on-chain proposal and the local configuration"
+    )]
+    CriticalMismatch(usize),
+    #[error("Failed to get helper: {0}")]
+    GetHelper(#[from] HelperError),
+    #[error("Failed to query proposal: {0}")]
+    Gov(#[from] GovError),
+    #[error("Failed to record proposal ID in history: {0}")]
+    History(#[from] HistoryError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Proposal {0} does not contain a software upgrade plan")]
+    NoUpgradePlan(u64),
+    #[error("Failed to read proposal file: {0}")]
+    ReadProposal(#[from] std::io::Error),
+    #[error("Failed to update helper configuration: {0}")]
+    UpdateHelper(HelperError),
+    #[error("Failed to write Commonwealth text: {0}")]
+    WriteCommonwealth(std::io::Error),
+}
+
+/// High level error type that can occur when running the Telegram bot
+#[derive(Error, Debug)]
+pub enum TelegramError {
+    #[error("Telegram API returned an error: {0}")]
+    Api(String),
+    #[error("Failed to get helper: {0}")]
+    GetHelper(#[from] HelperError),
+    #[error("Failed to build HTTP client: {0}")]
+    Http(#[from] HttpError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Missing bot token; set the {0} environment variable")]
+    MissingToken(&'static str),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+    #[error("Failed to send request: {0}")]
+    Request(reqwest::Error),
+    #[error("Failed to watch configuration: {0}")]
+    Watch(#[from] std::io::Error),
+}
+
+/// Error type for failed notifications
+#[derive(Error, Debug)]
+pub enum NotifyError {
+    #[error("Failed to build HTTP client: {0}")]
+    Http(#[from] HttpError),
+    #[error("Failed to read notification channels or run sendmail: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to parse notification channels: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Failed to parse decrypted notification channels: {0}")]
+    ParseDecrypted(#[from] serde_json::Error),
+    #[error("Failed to send notification to {0}: {1}")]
+    Request(&'static str, reqwest::Error),
+    #[error("Failed to decrypt notification channels: {0}")]
+    Secret(#[from] SecretError),
+    #[error("sendmail exited with code {0}")]
+    Sendmail(i32),
+    #[error("Notification endpoint returned status {0}")]
+    Status(u16),
+}
+
+/// Error type for failed exports of usage metrics
+#[derive(Error, Debug)]
+pub enum TelemetryError {
+    #[error("Failed to parse decrypted telemetry configuration: {0}")]
+    Decrypted(serde_json::Error),
+    #[error("Failed to build HTTP client: {0}")]
+    Http(#[from] HttpError),
+    #[error("Failed to read telemetry configuration: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Failed to parse telemetry configuration: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Failed to send metrics: {0}")]
+    Request(reqwest::Error),
+    #[error("Failed to decrypt telemetry configuration: {0}")]
+    Secret(#[from] SecretError),
+    #[error("Failed to serialize event: {0}")]
+    Serialize(#[from] serde_json::Error),
+    #[error("Metrics endpoint returned status {0}")]
+    Status(u16),
+}
+
+/// Error type for failed template operations
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    #[error("{0}")]
+    MissingPlaceholders(TemplateReport),
+    #[error("Failed to read template: {0}")]
+    Read(#[from] std::io::Error),
+    #[error("Failed to register template: {0}")]
+    Register(#[from] Box<handlebars::TemplateError>),
+    #[error("Failed to render template: {0}")]
+    Render(#[from] handlebars::RenderError),
+}
+
+/// High level error type that can occur when testing a template
+#[derive(Error, Debug)]
+pub enum TemplateTestError {
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to load configuration: {0}")]
+    Helper(#[from] HelperError),
+    #[error("Failed to prepare proposal data: {0}")]
+    Proposal(#[from] ProposalError),
+    #[error("{0}")]
+    Template(#[from] TemplateError),
+    #[error("Failed to watch files: {0}")]
+    Watch(#[from] std::io::Error),
+}
+
+/// High level error type that can occur when running the API server
+#[derive(Error, Debug)]
+pub enum ServeError {
+    #[error("Invalid address: {0}")]
+    Address(#[from] std::net::AddrParseError),
+    #[error("Failed to run server: {0}")]
+    Server(#[from] hyper::Error),
+}
+
+/// Error type for failed uploads of the upgrade info file
+#[derive(Error, Debug)]
+pub enum UploadError {
+    #[error("Failed to interact with GitHub: {0}")]
+    GitHub(#[from] octocrab::Error),
+    #[error("Failed to build HTTP client: {0}")]
+    Http(#[from] HttpError),
+    #[error("Failed to read or write upgrade info file: {0}")]
+    IO(#[from] std::io::Error),
+    #[error("Missing GitHub token; set the {0} environment variable")]
+    MissingToken(&'static str),
+    #[error("No URL returned for uploaded file {0}")]
+    MissingUrl(String),
+    #[error("Failed to send request: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Upload with {0} failed: {1}")]
+    Upload(String, String),
 }
 
 /// Error type for failed validations
 #[derive(Error, Debug)]
 pub enum ValidationError {
+    #[error("Client configuration uses chain ID {0}, but the {1} network requires {2}")]
+    ChainId(String, Network, String),
     #[error("Failed to validate client configuration: {0}")]
     ClientConfig(#[from] ConfigError),
+    #[error("Configuration {0} is a draft; finalize it first with `proposer finalize`")]
+    Draft(String),
     #[error("Home directory does not exist: {0}")]
     HomeDir(PathBuf),
+    #[error("Configuration {0} is not a draft")]
+    NotDraft(String),
+    #[error("Testnet version {0} is not a release candidate of mainnet version {1}")]
+    PairVersion(String, String),
     #[error("Invalid previous version: {0}")]
     PreviousVersion(String),
     #[error("Invalid target version for {0}: {1}")]
     TargetVersion(Network, String),
     #[error("Invalid upgrade time: {0}")]
     UpgradeTime(DateTime<Utc>),
+    #[error("No release candidate of {0} was released before; the version policy requires one for mainnet")]
+    MissingReleaseCandidate(String),
+    #[error("Version bump from {0} to {1} violates the version policy: {2}")]
+    VersionPolicy(String, String, String),
+}
+
+/// High level error type that can occur when exporting the validator set
+#[derive(Error, Debug)]
+pub enum ValidatorsError {
+    #[error("Failed to convert address: {0}")]
+    Bech32(#[from] bech32::Error),
+    #[error("Failed to get chain configuration: {0}")]
+    ChainConfig(#[from] ConfigError),
+    #[error("Failed to query votes: {0}")]
+    Gov(#[from] GovError),
+    #[error("Failed to query validators: {0}")]
+    Http(#[from] HttpError),
+    #[error("Failed to get user input: {0}")]
+    Input(#[from] InputError),
+    #[error("Failed to parse voting power: {0}")]
+    ParseAmount(#[from] std::num::ParseIntError),
+    #[error("Failed to parse url: {0}")]
+    ParseUrl(#[from] url::ParseError),
+    #[error("Failed to write CSV file: {0}")]
+    Write(#[from] std::io::Error),
 }
@@ -1,9 +1,10 @@
 use crate::errors::ConfigError;
-use serde::Deserialize;
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process;
 
 /// The client configuration for the `evmosd` node.
-#[derive(Clone, Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct ClientConfig {
     #[serde(rename = "chain-id")]
     pub chain_id: String,
@@ -22,6 +23,45 @@ pub fn get_client_config(path: &Path) -> Result<ClientConfig, ConfigError> {
     )?)
 }
 
+/// Returns the home directories of the running `evmosd` processes, which are detected
+/// from the `--home` arguments in the process list. If the process list cannot be queried, e.g.
+/// because `ps` is not available, no home directories are returned.
+pub fn detect_running_homes() -> Vec<PathBuf> {
+    match process::Command::new("ps").args(["-eo", "args"]).output() {
+        Ok(output) if output.status.success() => {
+            parse_home_args(&String::from_utf8_lossy(&output.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Parses the home directories of the `evmosd` processes from the given process list,
+/// which contains the command line of one process per line.
+fn parse_home_args(process_list: &str) -> Vec<PathBuf> {
+    let mut homes: Vec<PathBuf> = Vec::new();
+    for line in process_list.lines() {
+        let mut args = line.split_whitespace();
+        if !args.next().is_some_and(|binary| binary.ends_with("evmosd")) {
+            continue;
+        }
+
+        while let Some(arg) = args.next() {
+            let home = match arg.strip_prefix("--home") {
+                Some("") => args.next(),
+                Some(value) => value.strip_prefix('='),
+                None => None,
+            };
+            if let Some(home) = home.map(PathBuf::from) {
+                if !homes.contains(&home) {
+                    homes.push(home);
+                }
+            }
+        }
+    }
+
+    homes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,4 +91,22 @@ mod tests {
         assert_eq!(config.node, "tcp://localhost:26657");
         assert_eq!(config.broadcast_mode, "sync");
     }
+
+    #[test]
+    fn test_parse_home_args() {
+        let process_list = "COMMAND
+/usr/bin/evmosd start --home /data/evmos --log_level info
+evmosd start --home=/home/user/.tmp-evmosd
+/usr/bin/evmosd start --home /data/evmos
+vim notes.md --home /other
+cosmovisor run start";
+
+        assert_eq!(
+            parse_home_args(process_list),
+            vec![
+                PathBuf::from("/data/evmos"),
+                PathBuf::from("/home/user/.tmp-evmosd")
+            ]
+        );
+    }
 }
@@ -0,0 +1,266 @@
+use crate::errors::{ExportError, GovError};
+use crate::gov::GovClient;
+use crate::history::UpgradeRecord;
+use crate::http::get_json;
+use crate::network::Network;
+use crate::status::get_status_name;
+use crate::validators::escape_csv_field;
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The REST endpoint for querying the bonded and unbonded tokens of the staking module.
+const POOL_ENDPOINT: &str = "/cosmos/staking/v1beta1/pool";
+
+/// The header of the exported CSV file.
+const CSV_HEADER: &str = "network,proposal_id,target_version,title,status,voting_end_time,\
+    yes,no,no_with_veto,abstain,turnout,outcome";
+
+/// The supported file formats of the exported proposal status.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Returns the file extension that is associated with the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Represents the response of the staking pool query.
+#[derive(Deserialize)]
+struct PoolResponse {
+    pool: Pool,
+}
+
+/// Represents the relevant information of the staking pool.
+#[derive(Deserialize)]
+struct Pool {
+    bonded_tokens: String,
+}
+
+/// Contains the status of a tracked proposal, which is exported for spreadsheets or dashboards.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ProposalStatusRecord {
+    /// The network, on which the proposal was submitted.
+    pub network: Network,
+    /// The ID of the on-chain proposal.
+    pub proposal_id: u64,
+    /// The version that is upgraded to.
+    pub target_version: String,
+    /// The title of the proposal.
+    pub title: String,
+    /// The short name of the proposal status, e.g. `voting`.
+    pub status: String,
+    /// The end of the voting period, which is the deadline to vote.
+    pub voting_end_time: Option<DateTime<Utc>>,
+    /// The yes votes of the current or final tally.
+    pub yes: String,
+    /// The no votes of the current or final tally.
+    pub no: String,
+    /// The no with veto votes of the current or final tally.
+    pub no_with_veto: String,
+    /// The abstain votes of the current or final tally.
+    pub abstain: String,
+    /// The share of the bonded tokens that voted in percent, if the bonded tokens are known.
+    pub turnout: Option<f64>,
+    /// The final outcome of the proposal (`passed`, `rejected` or `failed`), or `pending` while it is open.
+    pub outcome: String,
+}
+
+/// Returns the amount of bonded tokens of the network, which is used to calculate the turnout.
+pub async fn get_bonded_tokens(base_url: &Url) -> Result<u128, ExportError> {
+    let response: PoolResponse = get_json(base_url.join(POOL_ENDPOINT)?).await?;
+
+    Ok(response.pool.bonded_tokens.parse::<u128>()?)
+}
+
+/// Queries the status of the proposal of the given upgrade record.
+///
+/// The turnout is calculated from the given bonded tokens. For finished proposals, this uses
+/// the current amount of bonded tokens, so that the turnout is an approximation.
+pub async fn get_status_record(
+    client: &dyn GovClient,
+    record: &UpgradeRecord,
+    proposal_id: u64,
+    bonded_tokens: Option<u128>,
+) -> Result<ProposalStatusRecord, GovError> {
+    let proposal = client.proposal(proposal_id).await?;
+    let tally = client.tally(proposal_id).await?;
+
+    let voted: u128 = [
+        &tally.yes_count,
+        &tally.no_count,
+        &tally.no_with_veto_count,
+        &tally.abstain_count,
+    ]
+    .iter()
+    .map(|count| count.parse::<u128>().unwrap_or_default())
+    .sum();
+
+    let status = get_status_name(&proposal.status);
+    let outcome = match status.as_str() {
+        "passed" | "rejected" | "failed" => status.clone(),
+        _ => "pending".to_string(),
+    };
+
+    Ok(ProposalStatusRecord {
+        network: record.network,
+        proposal_id,
+        target_version: record.target_version.clone(),
+        title: proposal.get_title(),
+        status,
+        voting_end_time: proposal.voting_end_time,
+        yes: tally.yes_count,
+        no: tally.no_count,
+        no_with_veto: tally.no_with_veto_count,
+        abstain: tally.abstain_count,
+        turnout: bonded_tokens
+            .filter(|bonded| *bonded > 0)
+            .map(|bonded| voted as f64 / bonded as f64 * 100.0),
+        outcome,
+    })
+}
+
+/// Returns the CSV representation of the given status records.
+pub fn to_csv(records: &[ProposalStatusRecord]) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for record in records {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}\n",
+            escape_csv_field(&record.network.to_string()),
+            record.proposal_id,
+            escape_csv_field(&record.target_version),
+            escape_csv_field(&record.title),
+            record.status,
+            record.voting_end_time.map_or(String::new(), |end| {
+                end.to_rfc3339_opts(SecondsFormat::Secs, true)
+            }),
+            record.yes,
+            record.no,
+            record.no_with_veto,
+            record.abstain,
+            record
+                .turnout
+                .map_or(String::new(), |turnout| format!("{:.2}", turnout)),
+            record.outcome,
+        ));
+    }
+
+    csv
+}
+
+/// Returns the serialized status records in the given format.
+pub fn render(
+    records: &[ProposalStatusRecord],
+    format: ExportFormat,
+) -> Result<String, ExportError> {
+    match format {
+        ExportFormat::Csv => Ok(to_csv(records)),
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(records)?),
+    }
+}
+
+/// Returns the default name of the exported file for the given format.
+pub fn get_export_file_name(format: ExportFormat) -> String {
+    format!("proposal-status.{}", format.extension())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gov::RestGovClient;
+    use chrono::TimeZone;
+    use serde_json::{json, Value};
+    use std::str::FromStr;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Returns a recorded mainnet upgrade to v16.0.0.
+    fn get_record() -> UpgradeRecord {
+        let time = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        UpgradeRecord {
+            network: Network::Mainnet,
+            previous_version: "v15.0.0".to_string(),
+            target_version: "v16.0.0".to_string(),
+            upgrade_height: 18_798_000,
+            estimated_time: time,
+            generated_at: time,
+            proposal_id: Some(252),
+            executed_at: None,
+        }
+    }
+
+    async fn setup_mock_api() -> MockServer {
+        let mut proposal: Value =
+            serde_json::from_str(include_str!("testdata/proposal_upgrade.json"))
+                .expect("failed to parse proposal JSON");
+        proposal["proposal"]["status"] = json!("PROPOSAL_STATUS_PASSED");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/gov/v1/proposals/252"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(proposal))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/gov/v1/proposals/252/tally"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tally": {
+                    "yes_count": "600",
+                    "abstain_count": "50",
+                    "no_count": "40",
+                    "no_with_veto_count": "10"
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/staking/v1beta1/pool"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "pool": {"not_bonded_tokens": "100", "bonded_tokens": "1000"}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_get_status_record() {
+        let mock_server = setup_mock_api().await;
+        let base_url =
+            Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
+        let client = RestGovClient::new(base_url.clone());
+
+        let bonded_tokens = get_bonded_tokens(&base_url)
+            .await
+            .expect("failed to get bonded tokens");
+        assert_eq!(bonded_tokens, 1000);
+
+        let record = get_status_record(&client, &get_record(), 252, Some(bonded_tokens))
+            .await
+            .expect("failed to get status record");
+        assert_eq!(record.title, "Evmos Mainnet v16.0.0 Upgrade");
+        assert_eq!(record.status, "passed");
+        assert_eq!(record.outcome, "passed");
+        assert_eq!(record.turnout, Some(70.0));
+
+        let csv = to_csv(&[record]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            "Mainnet,252,v16.0.0,Evmos Mainnet v16.0.0 Upgrade,passed,\
+            2024-01-13T15:04:12Z,600,40,10,50,70.00,passed"
+        );
+    }
+}
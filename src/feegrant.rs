@@ -0,0 +1,109 @@
+use crate::errors::KeysError;
+use crate::http::get_json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+const ALLOWANCES_ENDPOINT: &str = "cosmos/feegrant/v1beta1/allowances/";
+
+/// Represents the response from the query for the fee allowances granted to an address.
+#[derive(Serialize, Deserialize)]
+struct AllowancesResponse {
+    allowances: Vec<Grant>,
+}
+
+/// Represents a fee allowance, which the granter has granted to the grantee.
+#[derive(Serialize, Deserialize)]
+struct Grant {
+    granter: String,
+    grantee: String,
+    allowance: Value,
+}
+
+/// Returns the addresses of the accounts, which have granted a fee allowance to the given address
+/// that is not expired at the given time.
+pub async fn get_fee_granters(
+    grantee: &str,
+    base_url: &Url,
+    now: DateTime<Utc>,
+) -> Result<Vec<String>, KeysError> {
+    let allowances_endpoint = base_url.join(ALLOWANCES_ENDPOINT)?.join(grantee)?;
+
+    let response: AllowancesResponse = get_json(allowances_endpoint).await?;
+
+    Ok(response
+        .allowances
+        .into_iter()
+        .filter(|grant| !is_expired(&grant.allowance, now))
+        .map(|grant| grant.granter)
+        .collect())
+}
+
+/// Checks if the given allowance is expired at the given time.
+/// The expiration is contained in the basic allowance, which can be wrapped
+/// in a periodic or filtered allowance.
+fn is_expired(allowance: &Value, now: DateTime<Utc>) -> bool {
+    let expiration = [
+        &allowance["expiration"],
+        &allowance["basic"]["expiration"],
+        &allowance["allowance"]["expiration"],
+        &allowance["allowance"]["basic"]["expiration"],
+    ]
+    .into_iter()
+    .find_map(|expiration| expiration.as_str()?.parse::<DateTime<Utc>>().ok());
+
+    expiration.is_some_and(|expiration| expiration <= now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+    use std::str::FromStr;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const TEST_ADDRESS: &str = "evmos1hafptm4zxy7y4fj6j7m6fj5n89v2zjy5l7ltae";
+
+    #[tokio::test]
+    async fn test_get_fee_granters() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{}{}", ALLOWANCES_ENDPOINT, TEST_ADDRESS)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "allowances": [
+                    {
+                        "granter": "evmos1granter",
+                        "grantee": TEST_ADDRESS,
+                        "allowance": {
+                            "@type": "/cosmos.feegrant.v1beta1.BasicAllowance",
+                            "spend_limit": [],
+                            "expiration": null
+                        }
+                    },
+                    {
+                        "granter": "evmos1expired",
+                        "grantee": TEST_ADDRESS,
+                        "allowance": {
+                            "@type": "/cosmos.feegrant.v1beta1.PeriodicAllowance",
+                            "basic": {"spend_limit": [], "expiration": "2023-12-31T00:00:00Z"}
+                        }
+                    }
+                ],
+                "pagination": {"next_key": null, "total": "2"}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let base_url =
+            Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+
+        let granters = get_fee_granters(TEST_ADDRESS, &base_url, now)
+            .await
+            .expect("failed to get fee granters");
+        assert_eq!(granters, vec!["evmos1granter"]);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::chain::get_config_dir;
+use crate::errors::GlossaryError;
+use regex::{Captures, Regex, RegexBuilder};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// The name of the file inside of the proposer configuration directory, which contains the glossary.
+const GLOSSARY_FILE: &str = "glossary.toml";
+
+/// Contains the preferred terminology of the team, which is used in the generated texts.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+pub struct Glossary {
+    /// The preferred terms.
+    #[serde(default)]
+    pub terms: Vec<Term>,
+}
+
+/// Represents a preferred term together with the phrases, which are replaced by it.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Term {
+    /// The preferred phrasing, e.g. `network upgrade`.
+    pub preferred: String,
+    /// The phrases, which should not be used, e.g. `hard fork`.
+    #[serde(default)]
+    pub avoid: Vec<String>,
+}
+
+impl Glossary {
+    /// Loads the glossary from the given file. If the file does not exist, the glossary is empty.
+    pub fn load(path: &Path) -> Result<Glossary, GlossaryError> {
+        if !path.exists() {
+            return Ok(Glossary::default());
+        }
+
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Returns the instructions for the LLM to use the preferred terminology,
+    /// which are appended to the prompts. Empty if the glossary contains no terms.
+    pub fn to_prompt(&self) -> String {
+        if self.terms.is_empty() {
+            return String::new();
+        }
+
+        let terms = self
+            .terms
+            .iter()
+            .map(|term| match term.avoid.is_empty() {
+                true => format!("- \"{}\"", term.preferred),
+                false => format!(
+                    "- \"{}\" instead of {}",
+                    term.preferred,
+                    term.avoid
+                        .iter()
+                        .map(|phrase| format!("\"{}\"", phrase))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        format!(
+            "\nPlease use the following terminology consistently:\n{}",
+            terms
+        )
+    }
+
+    /// Replaces the phrases to avoid in the given text with the preferred terms.
+    /// The phrases are matched as whole words regardless of their case,
+    /// and a capitalized phrase is replaced with the capitalized term.
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for term in self.terms.iter() {
+            for phrase in term.avoid.iter() {
+                let pattern = RegexBuilder::new(&format!(r"\b{}\b", regex::escape(phrase)))
+                    .case_insensitive(true)
+                    .build()
+                    .expect("invalid regex");
+                text = replace_matching_case(&pattern, &text, &term.preferred);
+            }
+        }
+
+        text
+    }
+}
+
+/// Replaces the matches of the given pattern in the given text with the given replacement,
+/// which is capitalized if the match starts with an uppercase letter.
+fn replace_matching_case(pattern: &Regex, text: &str, replacement: &str) -> String {
+    pattern
+        .replace_all(text, |captures: &Captures| {
+            match captures[0].chars().next().is_some_and(char::is_uppercase) {
+                true => capitalize(replacement),
+                false => replacement.to_string(),
+            }
+        })
+        .to_string()
+}
+
+/// Returns the given text with the first letter in uppercase.
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Loads the glossary from the proposer configuration directory.
+pub fn get_glossary() -> Result<Glossary, GlossaryError> {
+    Glossary::load(&get_config_dir().join(GLOSSARY_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_glossary() -> Glossary {
+        toml::from_str(
+            r#"
+            [[terms]]
+            preferred = "network upgrade"
+            avoid = ["hard fork", "chain upgrade"]
+
+            [[terms]]
+            preferred = "EVM"
+            "#,
+        )
+        .expect("failed to parse glossary")
+    }
+
+    #[test]
+    fn test_to_prompt() {
+        assert_eq!(
+            get_glossary().to_prompt(),
+            "\nPlease use the following terminology consistently:\
+            \n- \"network upgrade\" instead of \"hard fork\", \"chain upgrade\"\
+            \n- \"EVM\""
+        );
+        assert_eq!(Glossary::default().to_prompt(), "");
+    }
+
+    #[test]
+    fn test_apply() {
+        let text = "- Hard fork to enable the new fee market\n\
+            - The chain upgrade includes a hard-fork-free migration";
+        assert_eq!(
+            get_glossary().apply(text),
+            "- Network upgrade to enable the new fee market\n\
+            - The network upgrade includes a hard-fork-free migration"
+        );
+    }
+}
@@ -0,0 +1,720 @@
+use crate::block::get_rest_endpoint;
+use crate::chain::{ChainConfig, GovTransport};
+use crate::errors::GovError;
+use crate::grpc::GrpcGovClient;
+use crate::http::{get_client, get_json};
+use crate::network::Network;
+use crate::utils::parse_proto_duration;
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+/// The REST endpoint for querying the governance parameters.
+const PARAMS_ENDPOINT: &str = "/cosmos/gov/v1/params/voting";
+
+/// The REST endpoint for querying governance proposals.
+const PROPOSALS_ENDPOINT: &str = "/cosmos/gov/v1/proposals/";
+
+/// The REST endpoint for broadcasting transactions.
+const TXS_ENDPOINT: &str = "/cosmos/tx/v1beta1/txs";
+
+/// The maximum number of proposals that are queried at once.
+const PROPOSALS_LIMIT: &str = "1000";
+
+/// The maximum number of votes that are queried per page.
+const VOTES_LIMIT: &str = "1000";
+
+/// The message type that wraps legacy proposal contents.
+pub const MSG_EXEC_LEGACY_CONTENT: &str = "/cosmos.gov.v1.MsgExecLegacyContent";
+
+/// The type of the legacy software upgrade proposal contents.
+pub const SOFTWARE_UPGRADE_PROPOSAL: &str = "/cosmos.upgrade.v1beta1.SoftwareUpgradeProposal";
+
+/// The message type of a software upgrade.
+pub const MSG_SOFTWARE_UPGRADE: &str = "/cosmos.upgrade.v1beta1.MsgSoftwareUpgrade";
+
+/// Represents the status of a governance proposal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProposalStatus {
+    DepositPeriod = 1,
+    VotingPeriod = 2,
+    Passed = 3,
+    Rejected = 4,
+    Failed = 5,
+}
+
+impl ProposalStatus {
+    /// Returns the name of the status as used in the Cosmos SDK, e.g. `PROPOSAL_STATUS_PASSED`.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ProposalStatus::DepositPeriod => "PROPOSAL_STATUS_DEPOSIT_PERIOD",
+            ProposalStatus::VotingPeriod => "PROPOSAL_STATUS_VOTING_PERIOD",
+            ProposalStatus::Passed => "PROPOSAL_STATUS_PASSED",
+            ProposalStatus::Rejected => "PROPOSAL_STATUS_REJECTED",
+            ProposalStatus::Failed => "PROPOSAL_STATUS_FAILED",
+        }
+    }
+
+    /// Returns the status for the given protobuf enum value.
+    pub fn from_i32(value: i32) -> Option<ProposalStatus> {
+        match value {
+            1 => Some(ProposalStatus::DepositPeriod),
+            2 => Some(ProposalStatus::VotingPeriod),
+            3 => Some(ProposalStatus::Passed),
+            4 => Some(ProposalStatus::Rejected),
+            5 => Some(ProposalStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Represents an amount of tokens of the given denomination.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Coin {
+    pub denom: String,
+    pub amount: String,
+}
+
+/// Represents the relevant governance parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GovParams {
+    /// The minimum deposit for a proposal to enter the voting period.
+    pub min_deposit: Vec<Coin>,
+    /// The duration of the voting period.
+    pub voting_period: Duration,
+    /// The minimum share of the voting power that needs to vote for a valid result.
+    pub quorum: String,
+    /// The minimum share of yes votes for a proposal to pass.
+    pub threshold: String,
+}
+
+/// Represents the current tally of a governance proposal.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TallyResult {
+    pub yes_count: String,
+    pub abstain_count: String,
+    pub no_count: String,
+    pub no_with_veto_count: String,
+}
+
+/// Represents a governance proposal.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Proposal {
+    /// The proposal ID.
+    pub id: String,
+    /// The messages contained in the proposal in their JSON representation.
+    pub messages: Vec<Value>,
+    /// The status of the proposal, e.g. `PROPOSAL_STATUS_VOTING_PERIOD`.
+    pub status: String,
+    /// The title of the proposal.
+    #[serde(default)]
+    pub title: String,
+    /// The summary of the proposal.
+    #[serde(default)]
+    pub summary: String,
+    /// The start of the voting period.
+    #[serde(default)]
+    pub voting_start_time: Option<DateTime<Utc>>,
+    /// The end of the voting period.
+    #[serde(default)]
+    pub voting_end_time: Option<DateTime<Utc>>,
+}
+
+/// Represents the software upgrade plan of a proposal together with
+/// the title and description of the proposal.
+#[derive(Debug, PartialEq)]
+pub struct UpgradePlan {
+    /// The title of the upgrade proposal.
+    pub title: String,
+    /// The description of the upgrade proposal.
+    pub description: String,
+    /// The name of the upgrade plan.
+    pub name: String,
+    /// The upgrade height.
+    pub height: u64,
+    /// The upgrade info, e.g. containing the binaries.
+    pub info: String,
+}
+
+impl Proposal {
+    /// Returns the title of the proposal. For legacy proposals, the title is
+    /// contained in the proposal content.
+    pub fn get_title(&self) -> String {
+        if !self.title.is_empty() {
+            return self.title.clone();
+        }
+
+        self.messages
+            .iter()
+            .find_map(|message| message["content"]["title"].as_str())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// Returns the software upgrade plan contained in the proposal messages, if any.
+    ///
+    /// Both legacy software upgrade proposals and `MsgSoftwareUpgrade` messages are supported.
+    pub fn get_upgrade_plan(&self) -> Option<UpgradePlan> {
+        self.messages
+            .iter()
+            .find_map(|message| match message["@type"].as_str()? {
+                MSG_EXEC_LEGACY_CONTENT => {
+                    let content = &message["content"];
+                    if content["@type"].as_str()? != SOFTWARE_UPGRADE_PROPOSAL {
+                        return None;
+                    }
+
+                    parse_plan(
+                        &content["plan"],
+                        content["title"].as_str()?,
+                        content["description"].as_str()?,
+                    )
+                }
+                MSG_SOFTWARE_UPGRADE => parse_plan(&message["plan"], &self.title, &self.summary),
+                _ => None,
+            })
+    }
+}
+
+/// Parses the upgrade plan from the given JSON value.
+fn parse_plan(plan: &Value, title: &str, description: &str) -> Option<UpgradePlan> {
+    Some(UpgradePlan {
+        title: title.to_string(),
+        description: description.to_string(),
+        name: plan["name"].as_str()?.to_string(),
+        height: plan["height"].as_str()?.parse().ok()?,
+        info: plan["info"].as_str().unwrap_or_default().to_string(),
+    })
+}
+
+/// The ID of a proposal for the upgrade, which is expected from the next proposal ID of the network
+/// until it is confirmed by verifying the submitted on-chain proposal.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ProposalId {
+    /// The ID of the proposal.
+    pub id: u64,
+    /// Whether the ID was confirmed from the submitted proposal.
+    pub confirmed: bool,
+}
+
+impl std::fmt::Display for ProposalId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.confirmed {
+            true => write!(f, "Proposal #{}", self.id),
+            false => write!(f, "Proposal #{} (expected)", self.id),
+        }
+    }
+}
+
+/// Returns the ID that the next submitted proposal gets, based on the latest existing proposal.
+pub fn get_next_proposal_id(latest: Option<&Proposal>) -> Result<u64, GovError> {
+    match latest {
+        Some(proposal) => proposal
+            .id
+            .parse::<u64>()
+            .map(|id| id + 1)
+            .map_err(|_| GovError::ParseProposalId(proposal.id.clone())),
+        None => Ok(1),
+    }
+}
+
+/// Defines the interactions with the governance module of a network,
+/// so that the used transport can be swapped per network and mocked in tests.
+#[async_trait]
+pub trait GovClient: Send + Sync {
+    /// Returns the governance parameters.
+    async fn params(&self) -> Result<GovParams, GovError>;
+
+    /// Returns the proposals with the given status or all proposals if no status is given.
+    async fn proposals(&self, status: Option<ProposalStatus>) -> Result<Vec<Proposal>, GovError>;
+
+    /// Returns the proposal with the given ID.
+    async fn proposal(&self, proposal_id: u64) -> Result<Proposal, GovError>;
+
+    /// Returns the current tally of the proposal with the given ID.
+    async fn tally(&self, proposal_id: u64) -> Result<TallyResult, GovError>;
+
+    /// Returns the addresses of the accounts that have voted on the proposal with the given ID.
+    async fn voters(&self, proposal_id: u64) -> Result<Vec<String>, GovError>;
+
+    /// Returns the ID that the next submitted proposal gets, without submitting a proposal.
+    async fn next_proposal_id(&self) -> Result<u64, GovError>;
+
+    /// Broadcasts the given signed transaction and returns its hash.
+    async fn submit(&self, tx_bytes: &[u8]) -> Result<String, GovError>;
+}
+
+/// Returns the governance client for the given network, using the transport
+/// that is configured in the given chain configuration.
+pub fn get_gov_client(
+    network: Network,
+    chain_config: &ChainConfig,
+) -> Result<Box<dyn GovClient>, GovError> {
+    match chain_config.gov_transport {
+        GovTransport::Rest => Ok(Box::new(RestGovClient::new(get_rest_endpoint(
+            network,
+            chain_config,
+        )?))),
+        GovTransport::Grpc => Ok(Box::new(GrpcGovClient::new(get_grpc_url(
+            network,
+            chain_config,
+        )?))),
+    }
+}
+
+/// Returns the gRPC endpoint from the given chain configuration.
+/// Only the local node has a default endpoint, which is used if none is configured.
+pub fn get_grpc_url(network: Network, chain_config: &ChainConfig) -> Result<String, GovError> {
+    match (&chain_config.grpc_url, network) {
+        (Some(grpc_url), _) => Ok(grpc_url.clone()),
+        (None, Network::LocalNode) => Ok("http://localhost:9090".to_string()),
+        (None, _) => Err(GovError::MissingGrpcUrl(network)),
+    }
+}
+
+/// Implements the governance client using the REST API of a node.
+pub struct RestGovClient {
+    base_url: Url,
+}
+
+impl RestGovClient {
+    /// Creates a new REST client for the given base URL.
+    pub fn new(base_url: Url) -> RestGovClient {
+        RestGovClient { base_url }
+    }
+}
+
+/// Represents the relevant information from the params query response.
+#[derive(Deserialize)]
+struct ParamsResponse {
+    params: RestParams,
+}
+
+/// Represents the governance parameters as returned from the REST API.
+#[derive(Deserialize)]
+struct RestParams {
+    min_deposit: Vec<Coin>,
+    voting_period: String,
+    quorum: String,
+    threshold: String,
+}
+
+/// Represents the relevant information from the proposal query response.
+#[derive(Deserialize)]
+struct ProposalResponse {
+    proposal: Proposal,
+}
+
+/// Represents the relevant information from the proposals query response.
+#[derive(Deserialize)]
+struct ProposalsResponse {
+    proposals: Vec<Proposal>,
+}
+
+/// Represents the relevant information from the tally query response.
+#[derive(Deserialize)]
+struct TallyResponse {
+    tally: TallyResult,
+}
+
+/// Represents the relevant information from the votes query response.
+#[derive(Deserialize)]
+struct VotesResponse {
+    votes: Vec<Vote>,
+    pagination: Option<PageResponse>,
+}
+
+/// Represents a vote on a governance proposal.
+#[derive(Deserialize)]
+struct Vote {
+    voter: String,
+}
+
+/// Represents the pagination information of a paginated query response.
+#[derive(Deserialize)]
+struct PageResponse {
+    next_key: Option<String>,
+}
+
+/// Represents the relevant information from the broadcast response.
+#[derive(Deserialize)]
+struct BroadcastResponse {
+    tx_response: TxResponse,
+}
+
+/// Represents the relevant information of the broadcast transaction.
+#[derive(Deserialize)]
+struct TxResponse {
+    txhash: String,
+    code: u32,
+    #[serde(default)]
+    raw_log: String,
+}
+
+#[async_trait]
+impl GovClient for RestGovClient {
+    async fn params(&self) -> Result<GovParams, GovError> {
+        let url = self.base_url.join(PARAMS_ENDPOINT)?;
+        let response: ParamsResponse = get_json(url).await?;
+        let params = response.params;
+
+        Ok(GovParams {
+            min_deposit: params.min_deposit,
+            voting_period: parse_proto_duration(&params.voting_period)
+                .ok_or(GovError::ParseDuration(params.voting_period))?,
+            quorum: params.quorum,
+            threshold: params.threshold,
+        })
+    }
+
+    async fn proposals(&self, status: Option<ProposalStatus>) -> Result<Vec<Proposal>, GovError> {
+        let mut url = self.base_url.join(PROPOSALS_ENDPOINT)?;
+        url.query_pairs_mut()
+            .append_pair("pagination.limit", PROPOSALS_LIMIT);
+        if let Some(status) = status {
+            url.query_pairs_mut()
+                .append_pair("proposal_status", status.as_str_name());
+        }
+
+        let response: ProposalsResponse = get_json(url).await?;
+
+        Ok(response.proposals)
+    }
+
+    async fn proposal(&self, proposal_id: u64) -> Result<Proposal, GovError> {
+        let url = self
+            .base_url
+            .join(format!("{}{}", PROPOSALS_ENDPOINT, proposal_id).as_str())?;
+        let response: ProposalResponse = get_json(url).await?;
+
+        Ok(response.proposal)
+    }
+
+    async fn tally(&self, proposal_id: u64) -> Result<TallyResult, GovError> {
+        let url = self
+            .base_url
+            .join(format!("{}{}/tally", PROPOSALS_ENDPOINT, proposal_id).as_str())?;
+        let response: TallyResponse = get_json(url).await?;
+
+        Ok(response.tally)
+    }
+
+    async fn voters(&self, proposal_id: u64) -> Result<Vec<String>, GovError> {
+        let votes_url = self
+            .base_url
+            .join(format!("{}{}/votes", PROPOSALS_ENDPOINT, proposal_id).as_str())?;
+
+        let mut voters = Vec::new();
+        let mut next_key: Option<String> = None;
+        loop {
+            let mut url = votes_url.clone();
+            url.query_pairs_mut()
+                .append_pair("pagination.limit", VOTES_LIMIT);
+            if let Some(key) = &next_key {
+                url.query_pairs_mut().append_pair("pagination.key", key);
+            }
+
+            let response: VotesResponse = get_json(url).await?;
+            voters.extend(response.votes.into_iter().map(|vote| vote.voter));
+
+            next_key = response
+                .pagination
+                .and_then(|pagination| pagination.next_key)
+                .filter(|key| !key.is_empty());
+            if next_key.is_none() {
+                return Ok(voters);
+            }
+        }
+    }
+
+    async fn next_proposal_id(&self) -> Result<u64, GovError> {
+        let mut url = self.base_url.join(PROPOSALS_ENDPOINT)?;
+        url.query_pairs_mut()
+            .append_pair("pagination.limit", "1")
+            .append_pair("pagination.reverse", "true");
+
+        let response: ProposalsResponse = get_json(url).await?;
+
+        get_next_proposal_id(response.proposals.first())
+    }
+
+    async fn submit(&self, tx_bytes: &[u8]) -> Result<String, GovError> {
+        let url = self.base_url.join(TXS_ENDPOINT)?;
+        let body = json!({
+            "tx_bytes": STANDARD.encode(tx_bytes),
+            "mode": "BROADCAST_MODE_SYNC",
+        });
+
+        let response = get_client()?
+            .post(url)
+            .json(&body)
+            .send()
+            .await?
+            .text()
+            .await?;
+        let response: BroadcastResponse = serde_json::from_str(&response)?;
+
+        match response.tx_response.code {
+            0 => Ok(response.tx_response.txhash),
+            code => Err(GovError::Broadcast(code, response.tx_response.raw_log)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Sets up a mock server to return the governance responses.
+    async fn setup_mock_api() -> MockServer {
+        let proposal: Value = serde_json::from_str(include_str!("testdata/proposal_upgrade.json"))
+            .expect("failed to parse proposal JSON");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(PROPOSALS_ENDPOINT.to_owned() + "252"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(proposal.clone()))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(PROPOSALS_ENDPOINT))
+            .and(query_param("proposal_status", "PROPOSAL_STATUS_PASSED"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "proposals": [proposal["proposal"]],
+                "pagination": {"next_key": null, "total": "1"}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(PROPOSALS_ENDPOINT.to_owned() + "252/tally"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tally": {
+                    "yes_count": "100",
+                    "abstain_count": "2",
+                    "no_count": "1",
+                    "no_with_veto_count": "0"
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(PARAMS_ENDPOINT))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "voting_params": null,
+                "deposit_params": null,
+                "tally_params": null,
+                "params": {
+                    "min_deposit": [{"denom": "aevmos", "amount": "1000000000000000000000"}],
+                    "max_deposit_period": "1209600s",
+                    "voting_period": "432000s",
+                    "quorum": "0.334000000000000000",
+                    "threshold": "0.500000000000000000",
+                    "veto_threshold": "0.334000000000000000"
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(PROPOSALS_ENDPOINT.to_owned() + "252/votes"))
+            .and(query_param("pagination.key", "bmV4dA=="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "votes": [{"proposal_id": "252", "voter": "evmos1second", "options": []}],
+                "pagination": {"next_key": null, "total": "0"}
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(PROPOSALS_ENDPOINT.to_owned() + "252/votes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "votes": [{"proposal_id": "252", "voter": "evmos1first", "options": []}],
+                "pagination": {"next_key": "bmV4dA==", "total": "2"}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("POST"))
+            .and(path(TXS_ENDPOINT))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tx_response": {"height": "0", "txhash": "ABCDEF", "code": 0, "raw_log": "[]"}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    /// Returns a REST client for the given mock server.
+    fn get_mock_client(mock_server: &MockServer) -> RestGovClient {
+        RestGovClient::new(
+            Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri"),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_proposal() {
+        let mock_server = setup_mock_api().await;
+        let client = get_mock_client(&mock_server);
+
+        let res = client.proposal(252).await;
+        assert!(res.is_ok(), "expected no error; got: {}", res.unwrap_err());
+
+        let proposal = res.unwrap();
+        assert_eq!(proposal.id, "252");
+        assert_eq!(proposal.status, "PROPOSAL_STATUS_PASSED");
+        assert_eq!(
+            proposal.voting_end_time,
+            Some(
+                DateTime::parse_from_rfc3339("2024-01-13T15:04:12.345678901Z")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )
+        );
+
+        let plan = proposal.get_upgrade_plan().expect("expected upgrade plan");
+        assert_eq!(plan.name, "v16.0.0");
+        assert_eq!(plan.height, 18_798_000);
+        assert_eq!(plan.title, "Evmos Mainnet v16.0.0 Upgrade");
+        assert!(plan.info.contains("binaries"));
+    }
+
+    #[tokio::test]
+    async fn test_get_proposals() {
+        let mock_server = setup_mock_api().await;
+        let client = get_mock_client(&mock_server);
+
+        let proposals = client
+            .proposals(Some(ProposalStatus::Passed))
+            .await
+            .expect("failed to get proposals");
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].id, "252");
+    }
+
+    #[tokio::test]
+    async fn test_get_params_and_tally() {
+        let mock_server = setup_mock_api().await;
+        let client = get_mock_client(&mock_server);
+
+        let params = client.params().await.expect("failed to get params");
+        assert_eq!(params.voting_period, Duration::days(5));
+        assert_eq!(params.min_deposit[0].denom, "aevmos");
+        assert_eq!(params.quorum, "0.334000000000000000");
+
+        let tally = client.tally(252).await.expect("failed to get tally");
+        assert_eq!(tally.yes_count, "100");
+        assert_eq!(tally.no_with_veto_count, "0");
+    }
+
+    #[tokio::test]
+    async fn test_get_voters() {
+        let mock_server = setup_mock_api().await;
+        let client = get_mock_client(&mock_server);
+
+        assert_eq!(
+            client.voters(252).await.expect("failed to get voters"),
+            vec!["evmos1first".to_string(), "evmos1second".to_string()],
+            "expected the voters of all pages"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_next_proposal_id() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(PROPOSALS_ENDPOINT))
+            .and(query_param("pagination.reverse", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "proposals": [{
+                    "id": "252",
+                    "messages": [],
+                    "status": "PROPOSAL_STATUS_PASSED"
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+        let client = get_mock_client(&mock_server);
+
+        assert_eq!(
+            client
+                .next_proposal_id()
+                .await
+                .expect("failed to get next proposal ID"),
+            253
+        );
+        assert_eq!(get_next_proposal_id(None).unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit() {
+        let mock_server = setup_mock_api().await;
+        let client = get_mock_client(&mock_server);
+
+        assert_eq!(
+            client.submit(&[1, 2, 3]).await.expect("failed to submit"),
+            "ABCDEF"
+        );
+    }
+
+    #[test]
+    fn test_get_upgrade_plan_msg_software_upgrade() {
+        let proposal = Proposal {
+            id: "1".to_string(),
+            messages: vec![json!({
+                "@type": MSG_SOFTWARE_UPGRADE,
+                "authority": "evmos10d07y265gmmuvt4z0w9aw880jnsr700jcrztvm",
+                "plan": {"name": "v17.0.0", "height": "100", "info": ""}
+            })],
+            status: "PROPOSAL_STATUS_VOTING_PERIOD".to_string(),
+            title: "Evmos Testnet v17.0.0 Upgrade".to_string(),
+            summary: "Upgrade".to_string(),
+            voting_start_time: None,
+            voting_end_time: None,
+        };
+
+        assert_eq!(
+            proposal.get_upgrade_plan(),
+            Some(UpgradePlan {
+                title: "Evmos Testnet v17.0.0 Upgrade".to_string(),
+                description: "Upgrade".to_string(),
+                name: "v17.0.0".to_string(),
+                height: 100,
+                info: "".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_upgrade_plan_text_proposal() {
+        let proposal = Proposal {
+            id: "1".to_string(),
+            messages: vec![json!({
+                "@type": MSG_EXEC_LEGACY_CONTENT,
+                "content": {
+                    "@type": "/cosmos.gov.v1beta1.TextProposal",
+                    "title": "Text",
+                    "description": "Text"
+                }
+            })],
+            status: "PROPOSAL_STATUS_VOTING_PERIOD".to_string(),
+            title: "".to_string(),
+            summary: "".to_string(),
+            voting_start_time: None,
+            voting_end_time: None,
+        };
+
+        assert_eq!(proposal.get_upgrade_plan(), None);
+    }
+}
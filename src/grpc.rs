@@ -0,0 +1,570 @@
+use crate::errors::GovError;
+use crate::gov::{
+    Coin, GovClient, GovParams, Proposal, ProposalStatus, TallyResult, MSG_EXEC_LEGACY_CONTENT,
+    MSG_SOFTWARE_UPGRADE, SOFTWARE_UPGRADE_PROPOSAL,
+};
+use crate::http::HttpConfig;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use prost::Message;
+use serde_json::{json, Value};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::Endpoint;
+
+/// The maximum number of proposals that are queried at once.
+const PROPOSALS_LIMIT: u64 = 1000;
+
+/// The maximum number of votes that are queried per page.
+const VOTES_LIMIT: u64 = 1000;
+
+/// The broadcast mode to wait for the check of the transaction.
+const BROADCAST_MODE_SYNC: i32 = 2;
+
+/// Implements the governance client using the gRPC API of a node.
+///
+/// NOTE: the used protobuf messages only contain the fields that are relevant for this tool.
+pub struct GrpcGovClient {
+    url: String,
+}
+
+impl GrpcGovClient {
+    /// Creates a new gRPC client for the given URL.
+    pub fn new(url: String) -> GrpcGovClient {
+        GrpcGovClient { url }
+    }
+
+    /// Sends the unary gRPC request to the given method path.
+    async fn call<Req, Res>(&self, path: &'static str, request: Req) -> Result<Res, GovError>
+    where
+        Req: Message + 'static,
+        Res: Message + Default + 'static,
+    {
+        call(&self.url, path, request).await
+    }
+}
+
+/// Sends the unary gRPC request to the given method path of the node at the given URL.
+pub async fn call<Req, Res>(url: &str, path: &'static str, request: Req) -> Result<Res, GovError>
+where
+    Req: Message + 'static,
+    Res: Message + Default + 'static,
+{
+    let timeout = HttpConfig::from_env()?.timeout;
+    let channel = Endpoint::from_shared(url.to_string())?
+        .connect_timeout(timeout)
+        .timeout(timeout)
+        .connect()
+        .await?;
+    let mut client = tonic::client::Grpc::new(channel);
+    client.ready().await?;
+
+    let response = client
+        .unary(
+            tonic::Request::new(request),
+            PathAndQuery::from_static(path),
+            tonic::codec::ProstCodec::default(),
+        )
+        .await
+        .map_err(Box::new)?;
+
+    Ok(response.into_inner())
+}
+
+#[async_trait]
+impl GovClient for GrpcGovClient {
+    async fn params(&self) -> Result<GovParams, GovError> {
+        let response: QueryParamsResponse = self
+            .call(
+                "/cosmos.gov.v1.Query/Params",
+                QueryParamsRequest {
+                    params_type: "voting".to_string(),
+                },
+            )
+            .await?;
+        let params = response.params.ok_or(GovError::MissingField("params"))?;
+        let voting_period = params
+            .voting_period
+            .ok_or(GovError::MissingField("voting_period"))?;
+
+        Ok(GovParams {
+            min_deposit: params.min_deposit.into_iter().map(Coin::from).collect(),
+            voting_period: Duration::seconds(voting_period.seconds)
+                + Duration::nanoseconds(voting_period.nanos as i64),
+            quorum: params.quorum,
+            threshold: params.threshold,
+        })
+    }
+
+    async fn proposals(&self, status: Option<ProposalStatus>) -> Result<Vec<Proposal>, GovError> {
+        let response: QueryProposalsResponse = self
+            .call(
+                "/cosmos.gov.v1.Query/Proposals",
+                QueryProposalsRequest {
+                    proposal_status: status.map_or(0, |status| status as i32),
+                    pagination: Some(PageRequest {
+                        limit: PROPOSALS_LIMIT,
+                        ..Default::default()
+                    }),
+                },
+            )
+            .await?;
+
+        Ok(response.proposals.into_iter().map(Proposal::from).collect())
+    }
+
+    async fn proposal(&self, proposal_id: u64) -> Result<Proposal, GovError> {
+        let response: QueryProposalResponse = self
+            .call(
+                "/cosmos.gov.v1.Query/Proposal",
+                QueryProposalRequest { proposal_id },
+            )
+            .await?;
+
+        Ok(response
+            .proposal
+            .ok_or(GovError::MissingField("proposal"))?
+            .into())
+    }
+
+    async fn tally(&self, proposal_id: u64) -> Result<TallyResult, GovError> {
+        let response: QueryTallyResultResponse = self
+            .call(
+                "/cosmos.gov.v1.Query/TallyResult",
+                QueryTallyResultRequest { proposal_id },
+            )
+            .await?;
+        let tally = response.tally.ok_or(GovError::MissingField("tally"))?;
+
+        Ok(TallyResult {
+            yes_count: tally.yes_count,
+            abstain_count: tally.abstain_count,
+            no_count: tally.no_count,
+            no_with_veto_count: tally.no_with_veto_count,
+        })
+    }
+
+    async fn voters(&self, proposal_id: u64) -> Result<Vec<String>, GovError> {
+        let mut voters = Vec::new();
+        let mut next_key = Vec::new();
+        loop {
+            let response: QueryVotesResponse = self
+                .call(
+                    "/cosmos.gov.v1.Query/Votes",
+                    QueryVotesRequest {
+                        proposal_id,
+                        pagination: Some(PageRequest {
+                            key: next_key,
+                            limit: VOTES_LIMIT,
+                            ..Default::default()
+                        }),
+                    },
+                )
+                .await?;
+            voters.extend(response.votes.into_iter().map(|vote| vote.voter));
+
+            next_key = response
+                .pagination
+                .map(|pagination| pagination.next_key)
+                .unwrap_or_default();
+            if next_key.is_empty() {
+                return Ok(voters);
+            }
+        }
+    }
+
+    async fn next_proposal_id(&self) -> Result<u64, GovError> {
+        let response: QueryProposalsResponse = self
+            .call(
+                "/cosmos.gov.v1.Query/Proposals",
+                QueryProposalsRequest {
+                    proposal_status: 0,
+                    pagination: Some(PageRequest {
+                        limit: 1,
+                        reverse: true,
+                        ..Default::default()
+                    }),
+                },
+            )
+            .await?;
+
+        Ok(response
+            .proposals
+            .first()
+            .map_or(1, |proposal| proposal.id + 1))
+    }
+
+    async fn submit(&self, tx_bytes: &[u8]) -> Result<String, GovError> {
+        let response: BroadcastTxResponse = self
+            .call(
+                "/cosmos.tx.v1beta1.Service/BroadcastTx",
+                BroadcastTxRequest {
+                    tx_bytes: tx_bytes.to_vec(),
+                    mode: BROADCAST_MODE_SYNC,
+                },
+            )
+            .await?;
+        let tx_response = response
+            .tx_response
+            .ok_or(GovError::MissingField("tx_response"))?;
+
+        match tx_response.code {
+            0 => Ok(tx_response.txhash),
+            code => Err(GovError::Broadcast(code, tx_response.raw_log)),
+        }
+    }
+}
+
+impl From<ProtoCoin> for Coin {
+    fn from(coin: ProtoCoin) -> Self {
+        Coin {
+            denom: coin.denom,
+            amount: coin.amount,
+        }
+    }
+}
+
+impl From<ProtoProposal> for Proposal {
+    fn from(proposal: ProtoProposal) -> Self {
+        Proposal {
+            id: proposal.id.to_string(),
+            messages: proposal.messages.iter().map(any_to_json).collect(),
+            status: ProposalStatus::from_i32(proposal.status)
+                .map_or("PROPOSAL_STATUS_UNSPECIFIED", |status| status.as_str_name())
+                .to_string(),
+            title: proposal.title,
+            summary: proposal.summary,
+            voting_start_time: proposal
+                .voting_start_time
+                .and_then(|time| DateTime::<Utc>::from_timestamp(time.seconds, time.nanos as u32)),
+            voting_end_time: proposal
+                .voting_end_time
+                .and_then(|time| DateTime::<Utc>::from_timestamp(time.seconds, time.nanos as u32)),
+        }
+    }
+}
+
+/// Converts the given protobuf message into the JSON representation used by the REST API,
+/// so that the proposal messages can be handled independently of the used transport.
+///
+/// Only the messages relevant for software upgrades are decoded, all others only contain their type.
+fn any_to_json(any: &prost_types::Any) -> Value {
+    let decoded = match any.type_url.as_str() {
+        MSG_SOFTWARE_UPGRADE => MsgSoftwareUpgrade::decode(any.value.as_slice())
+            .ok()
+            .map(|msg| {
+                json!({
+                    "@type": any.type_url,
+                    "authority": msg.authority,
+                    "plan": msg.plan.as_ref().map(plan_to_json),
+                })
+            }),
+        MSG_EXEC_LEGACY_CONTENT => {
+            MsgExecLegacyContent::decode(any.value.as_slice())
+                .ok()
+                .map(|msg| {
+                    json!({
+                        "@type": any.type_url,
+                        "authority": msg.authority,
+                        "content": msg.content.as_ref().map(any_to_json),
+                    })
+                })
+        }
+        SOFTWARE_UPGRADE_PROPOSAL => SoftwareUpgradeProposal::decode(any.value.as_slice())
+            .ok()
+            .map(|content| {
+                json!({
+                    "@type": any.type_url,
+                    "title": content.title,
+                    "description": content.description,
+                    "plan": content.plan.as_ref().map(plan_to_json),
+                })
+            }),
+        _ => None,
+    };
+
+    decoded.unwrap_or_else(|| json!({"@type": any.type_url}))
+}
+
+/// Converts the given upgrade plan into its JSON representation.
+fn plan_to_json(plan: &Plan) -> Value {
+    json!({
+        "name": plan.name,
+        "height": plan.height.to_string(),
+        "info": plan.info,
+    })
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoCoin {
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    #[prost(string, tag = "2")]
+    pub amount: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PageRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    key: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    offset: u64,
+    #[prost(uint64, tag = "3")]
+    limit: u64,
+    #[prost(bool, tag = "4")]
+    count_total: bool,
+    #[prost(bool, tag = "5")]
+    reverse: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PageResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    next_key: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryParamsRequest {
+    #[prost(string, tag = "1")]
+    params_type: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryParamsResponse {
+    #[prost(message, optional, tag = "4")]
+    params: Option<ProtoParams>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ProtoParams {
+    #[prost(message, repeated, tag = "1")]
+    min_deposit: Vec<ProtoCoin>,
+    #[prost(message, optional, tag = "3")]
+    voting_period: Option<prost_types::Duration>,
+    #[prost(string, tag = "4")]
+    quorum: String,
+    #[prost(string, tag = "5")]
+    threshold: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryProposalsRequest {
+    #[prost(int32, tag = "1")]
+    proposal_status: i32,
+    #[prost(message, optional, tag = "4")]
+    pagination: Option<PageRequest>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryProposalsResponse {
+    #[prost(message, repeated, tag = "1")]
+    proposals: Vec<ProtoProposal>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryProposalRequest {
+    #[prost(uint64, tag = "1")]
+    proposal_id: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryProposalResponse {
+    #[prost(message, optional, tag = "1")]
+    proposal: Option<ProtoProposal>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ProtoProposal {
+    #[prost(uint64, tag = "1")]
+    id: u64,
+    #[prost(message, repeated, tag = "2")]
+    messages: Vec<prost_types::Any>,
+    #[prost(int32, tag = "3")]
+    status: i32,
+    #[prost(message, optional, tag = "8")]
+    voting_start_time: Option<prost_types::Timestamp>,
+    #[prost(message, optional, tag = "9")]
+    voting_end_time: Option<prost_types::Timestamp>,
+    #[prost(string, tag = "11")]
+    title: String,
+    #[prost(string, tag = "12")]
+    summary: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryTallyResultRequest {
+    #[prost(uint64, tag = "1")]
+    proposal_id: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryTallyResultResponse {
+    #[prost(message, optional, tag = "1")]
+    tally: Option<ProtoTallyResult>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ProtoTallyResult {
+    #[prost(string, tag = "1")]
+    yes_count: String,
+    #[prost(string, tag = "2")]
+    abstain_count: String,
+    #[prost(string, tag = "3")]
+    no_count: String,
+    #[prost(string, tag = "4")]
+    no_with_veto_count: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryVotesRequest {
+    #[prost(uint64, tag = "1")]
+    proposal_id: u64,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageRequest>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryVotesResponse {
+    #[prost(message, repeated, tag = "1")]
+    votes: Vec<ProtoVote>,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageResponse>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ProtoVote {
+    #[prost(string, tag = "2")]
+    voter: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct BroadcastTxRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    tx_bytes: Vec<u8>,
+    #[prost(int32, tag = "2")]
+    mode: i32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct BroadcastTxResponse {
+    #[prost(message, optional, tag = "1")]
+    tx_response: Option<ProtoTxResponse>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ProtoTxResponse {
+    #[prost(string, tag = "2")]
+    txhash: String,
+    #[prost(uint32, tag = "4")]
+    code: u32,
+    #[prost(string, tag = "6")]
+    raw_log: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Plan {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(int64, tag = "3")]
+    pub height: i64,
+    #[prost(string, tag = "4")]
+    pub info: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgSoftwareUpgrade {
+    #[prost(string, tag = "1")]
+    pub authority: String,
+    #[prost(message, optional, tag = "2")]
+    pub plan: Option<Plan>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct MsgExecLegacyContent {
+    #[prost(message, optional, tag = "1")]
+    content: Option<prost_types::Any>,
+    #[prost(string, tag = "2")]
+    authority: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct SoftwareUpgradeProposal {
+    #[prost(string, tag = "1")]
+    title: String,
+    #[prost(string, tag = "2")]
+    description: String,
+    #[prost(message, optional, tag = "3")]
+    plan: Option<Plan>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gov::UpgradePlan;
+
+    /// Returns the given message packed into an `Any`.
+    fn pack<M: Message>(type_url: &str, msg: &M) -> prost_types::Any {
+        prost_types::Any {
+            type_url: type_url.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_proposal_from_proto() {
+        let plan = Plan {
+            name: "v16.0.0".to_string(),
+            height: 18_798_000,
+            info: "".to_string(),
+        };
+        let content = SoftwareUpgradeProposal {
+            title: "Evmos Mainnet v16.0.0 Upgrade".to_string(),
+            description: "Upgrade".to_string(),
+            plan: Some(plan),
+        };
+        let msg = MsgExecLegacyContent {
+            content: Some(pack(SOFTWARE_UPGRADE_PROPOSAL, &content)),
+            authority: "evmos10d07y265gmmuvt4z0w9aw880jnsr700jcrztvm".to_string(),
+        };
+
+        let proposal: Proposal = ProtoProposal {
+            id: 252,
+            messages: vec![
+                pack(MSG_EXEC_LEGACY_CONTENT, &msg),
+                prost_types::Any {
+                    type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+                    value: vec![],
+                },
+            ],
+            status: 2,
+            voting_start_time: None,
+            voting_end_time: Some(prost_types::Timestamp {
+                seconds: 1_705_158_252,
+                nanos: 0,
+            }),
+            title: "".to_string(),
+            summary: "".to_string(),
+        }
+        .into();
+
+        assert_eq!(proposal.id, "252");
+        assert_eq!(proposal.status, "PROPOSAL_STATUS_VOTING_PERIOD");
+        assert_eq!(
+            proposal.messages[1],
+            json!({"@type": "/cosmos.bank.v1beta1.MsgSend"})
+        );
+        assert_eq!(
+            proposal.voting_end_time.map(|time| time.to_rfc3339()),
+            Some("2024-01-13T15:04:12+00:00".to_string())
+        );
+        assert_eq!(
+            proposal.get_upgrade_plan(),
+            Some(UpgradePlan {
+                title: "Evmos Mainnet v16.0.0 Upgrade".to_string(),
+                description: "Upgrade".to_string(),
+                name: "v16.0.0".to_string(),
+                height: 18_798_000,
+                info: "".to_string(),
+            })
+        );
+    }
+}
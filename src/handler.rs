@@ -0,0 +1,289 @@
+use crate::errors::HandlerError;
+use crate::http::get_json;
+use octocrab::{models::repos::Content, Octocrab};
+use serde::Deserialize;
+use url::Url;
+
+/// The directory of the Evmos repository, which contains the upgrade handlers.
+const UPGRADES_PATH: &str = "app/upgrades";
+
+/// The REST endpoint for querying the height, at which an upgrade plan was applied.
+const APPLIED_PLAN_ENDPOINT: &str = "/cosmos/upgrade/v1beta1/applied_plan/";
+
+/// Represents the upgrade handler in the release, which is executed for the upgrade plan.
+#[derive(Debug, PartialEq)]
+pub struct UpgradeHandler {
+    /// The path of the directory, which contains the handler.
+    pub path: String,
+    /// The plan name as defined in the handler, which has to be used in the proposal.
+    pub plan_name: String,
+}
+
+/// Represents the response of the applied plan query, which contains a height of zero
+/// if the plan was not applied yet.
+#[derive(Deserialize)]
+struct AppliedPlanResponse {
+    height: String,
+}
+
+/// Returns the normalized plan name for the given name, which is trimmed and lowercase.
+pub fn normalize_plan_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+/// Returns the spellings of the given plan name, which are used for upgrade handlers,
+/// starting with the normalized name (e.g. `v16.0.0`, `v16_0_0` and `v1600`).
+fn get_plan_name_variants(name: &str) -> Vec<String> {
+    let normalized = normalize_plan_name(name);
+    let mut variants = vec![
+        normalized.clone(),
+        normalized.replace('.', "_"),
+        normalized.replace('.', ""),
+    ];
+    variants.dedup();
+
+    variants
+}
+
+/// Returns the upgrade handler in the given tag of the Evmos repository for the given plan name.
+///
+/// The handler is expected in a directory of `app/upgrades`, which matches the plan name (e.g. `v16` for `v16.0.0`),
+/// and one of its Go files has to define the plan name, e.g. as `UpgradeName = "v16.0.0"`.
+/// The plan name is normalized to the spelling used in the handler, e.g. without dots if the handler requires it.
+pub async fn find_upgrade_handler(
+    instance: &Octocrab,
+    tag: &str,
+    plan_name: &str,
+) -> Result<UpgradeHandler, HandlerError> {
+    let variants = get_plan_name_variants(plan_name);
+
+    let directories: Vec<Content> = get_contents(instance, UPGRADES_PATH, tag)
+        .await?
+        .into_iter()
+        .filter(|item| item.r#type == "dir")
+        .collect();
+
+    for directory in directories
+        .iter()
+        .filter(|directory| is_candidate(&directory.name, &variants[0]))
+    {
+        let files = get_contents(instance, &directory.path, tag).await?;
+        for file in files
+            .iter()
+            .filter(|file| file.name.ends_with(".go") && !file.name.ends_with("_test.go"))
+        {
+            // NOTE: the directory listing does not contain the contents, so that each file is queried separately
+            let sources: Vec<String> = get_contents(instance, &file.path, tag)
+                .await?
+                .iter()
+                .filter_map(Content::decoded_content)
+                .collect();
+            let defined_name = variants.iter().find(|variant| {
+                sources
+                    .iter()
+                    .any(|source| source.contains(&format!("\"{}\"", variant)))
+            });
+            if let Some(defined_name) = defined_name {
+                return Ok(UpgradeHandler {
+                    path: directory.path.clone(),
+                    plan_name: defined_name.clone(),
+                });
+            }
+        }
+    }
+
+    Err(HandlerError::NotFound(
+        plan_name.to_string(),
+        tag.to_string(),
+        directories
+            .into_iter()
+            .map(|directory| directory.name)
+            .collect(),
+    ))
+}
+
+/// Returns the height, at which the upgrade plan with the given name was applied on the network
+/// behind the given REST endpoint, or None if it was not applied yet.
+pub async fn get_applied_height(
+    base_url: &Url,
+    plan_name: &str,
+) -> Result<Option<u64>, HandlerError> {
+    let url = base_url.join(&format!("{}{}", APPLIED_PLAN_ENDPOINT, plan_name))?;
+    let response: AppliedPlanResponse = get_json(url).await?;
+
+    match response.height.parse::<u64>()? {
+        0 => Ok(None),
+        height => Ok(Some(height)),
+    }
+}
+
+/// Checks that the upgrade plan with the given name was not applied on the network yet,
+/// because a repeated plan name would not execute the upgrade handler again.
+pub async fn check_plan_not_applied(base_url: &Url, plan_name: &str) -> Result<(), HandlerError> {
+    match get_applied_height(base_url, plan_name).await? {
+        Some(height) => Err(HandlerError::AlreadyApplied(plan_name.to_string(), height)),
+        None => Ok(()),
+    }
+}
+
+/// Returns the items at the given path in the given tag of the Evmos repository.
+async fn get_contents(
+    instance: &Octocrab,
+    path: &str,
+    tag: &str,
+) -> Result<Vec<Content>, HandlerError> {
+    Ok(instance
+        .repos("evmos", "evmos")
+        .get_content()
+        .path(path)
+        .r#ref(tag)
+        .send()
+        .await?
+        .items)
+}
+
+/// Returns whether the given directory of upgrade handlers may contain the handler for the given plan name,
+/// e.g. `v16` or `v16_1` for `v16.1.0`.
+fn is_candidate(directory: &str, plan_name: &str) -> bool {
+    let version = directory.replace('_', ".");
+
+    plan_name == version
+        || plan_name.starts_with(&format!("{}.", version))
+        || plan_name.starts_with(&format!("{}-", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use serde_json::{json, Value};
+    use wiremock::{
+        matchers::{method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    /// Returns an item in the format of the GitHub contents API response.
+    fn content(path: &str, kind: &str, source: Option<&str>) -> Value {
+        let url = format!("https://api.github.com/repos/evmos/evmos/contents/{}", path);
+        json!({
+            "name": path.rsplit('/').next().unwrap(),
+            "path": path,
+            "sha": "3d21ec53a331a6f037a91c368710b99387d012c1",
+            "encoding": source.map(|_| "base64"),
+            "content": source.map(|source| STANDARD.encode(source)),
+            "size": source.map_or(0, str::len),
+            "url": url,
+            "html_url": null,
+            "git_url": null,
+            "download_url": null,
+            "type": kind,
+            "_links": {"self": url, "git": null, "html": null}
+        })
+    }
+
+    async fn setup_api() -> MockServer {
+        let mock_server = MockServer::start().await;
+        let responses = [
+            (
+                "app/upgrades",
+                json!([
+                    content("app/upgrades/v15", "dir", None),
+                    content("app/upgrades/v16", "dir", None),
+                ]),
+            ),
+            (
+                "app/upgrades/v16",
+                json!([
+                    content("app/upgrades/v16/handler_test.go", "file", None),
+                    content("app/upgrades/v16/constants.go", "file", None),
+                ]),
+            ),
+            (
+                "app/upgrades/v16/constants.go",
+                content(
+                    "app/upgrades/v16/constants.go",
+                    "file",
+                    Some("package v16\n\nconst (\n\tUpgradeName = \"v16.0.0\"\n)\n"),
+                ),
+            ),
+        ];
+        for (content_path, response) in responses {
+            Mock::given(method("GET"))
+                .and(path(format!(
+                    "/repos/evmos/evmos/contents/{}",
+                    content_path
+                )))
+                .and(query_param("ref", "v16.0.0"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(response))
+                .mount(&mock_server)
+                .await;
+        }
+
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_find_upgrade_handler() {
+        let mock_server = setup_api().await;
+        let client = Octocrab::builder()
+            .base_uri(mock_server.uri())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            find_upgrade_handler(&client, "v16.0.0", "V16.0.0")
+                .await
+                .unwrap(),
+            UpgradeHandler {
+                path: "app/upgrades/v16".to_string(),
+                plan_name: "v16.0.0".to_string(),
+            }
+        );
+        match find_upgrade_handler(&client, "v16.0.0", "v16.0.0-rc4").await {
+            Err(HandlerError::NotFound(_, _, directories)) => {
+                assert_eq!(directories, vec!["v15", "v16"])
+            }
+            res => panic!("expected missing handler, got {:?}", res),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_plan_not_applied() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/upgrade/v1beta1/applied_plan/v15.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"height": "16000000"})))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/upgrade/v1beta1/applied_plan/v16.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"height": "0"})))
+            .mount(&mock_server)
+            .await;
+        let base_url = Url::parse(&mock_server.uri()).unwrap();
+
+        assert!(check_plan_not_applied(&base_url, "v16.0.0").await.is_ok());
+        match check_plan_not_applied(&base_url, "v15.0.0").await {
+            Err(HandlerError::AlreadyApplied(_, height)) => assert_eq!(height, 16_000_000),
+            res => panic!("expected applied plan, got {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_get_plan_name_variants() {
+        assert_eq!(
+            get_plan_name_variants(" V16.0.0-rc1"),
+            vec!["v16.0.0-rc1", "v16_0_0-rc1", "v1600-rc1"]
+        );
+        assert_eq!(get_plan_name_variants("v16"), vec!["v16"]);
+    }
+
+    #[test]
+    fn test_is_candidate() {
+        assert!(is_candidate("v16", "v16.0.0"));
+        assert!(is_candidate("v16_1", "v16.1.0-rc1"));
+        assert!(!is_candidate("v1", "v16.0.0"));
+        assert!(!is_candidate("v15", "v16.0.0"));
+    }
+}
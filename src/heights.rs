@@ -0,0 +1,158 @@
+use crate::block::{get_block_time_estimate, BlockProvider};
+use crate::cache::DiskCache;
+use crate::chain::{ChainConfig, ReferenceChain};
+use crate::errors::BlockError;
+use crate::network::Network;
+use chrono::{DateTime, Utc};
+use num_format::ToFormattedString;
+use url::Url;
+
+/// Contains the estimated block height of a chain at the given time.
+#[derive(Debug)]
+pub struct ChainHeight {
+    /// The name of the chain.
+    pub chain: String,
+    /// The estimated height and the measured seconds per block, or the reason why the estimation failed.
+    pub estimate: Result<(u64, f32), String>,
+}
+
+/// Returns the block provider for the given reference chain.
+fn get_reference_provider(chain: &ReferenceChain) -> Result<BlockProvider, BlockError> {
+    Ok(BlockProvider {
+        rest: Url::parse(&chain.rest)?,
+        rpc: chain.tm_rpc.as_deref().map(Url::parse).transpose()?,
+        cache: None,
+    })
+}
+
+/// Returns the block providers of the given network and the reference chains of its chain configuration by name.
+/// The failures of their endpoints are stored in the given cache.
+pub fn get_providers(
+    network: Network,
+    chain_config: &ChainConfig,
+    cache: &DiskCache,
+) -> Result<Vec<(String, BlockProvider)>, BlockError> {
+    let mut providers = vec![(
+        format!("Evmos {}", network),
+        BlockProvider::for_network(network, chain_config)?.with_cache(cache),
+    )];
+    for chain in chain_config.reference_chains.iter() {
+        providers.push((
+            chain.name.clone(),
+            get_reference_provider(chain)?.with_cache(cache),
+        ));
+    }
+
+    Ok(providers)
+}
+
+/// Estimates the block heights of the given chains at the given time concurrently,
+/// using the same block time estimation as for the upgrade height.
+/// Failures are returned per chain, so that one unavailable chain does not hide the others.
+pub async fn estimate_heights(
+    providers: Vec<(String, BlockProvider)>,
+    time: DateTime<Utc>,
+) -> Vec<ChainHeight> {
+    let handles: Vec<_> = providers
+        .into_iter()
+        .map(|(chain, provider)| {
+            let handle = tokio::spawn(async move {
+                get_block_time_estimate(&provider)
+                    .await
+                    .map(|estimate| (estimate.height_at(time), estimate.seconds_per_block))
+            });
+            (chain, handle)
+        })
+        .collect();
+
+    let mut heights = Vec::new();
+    for (chain, handle) in handles {
+        let estimate = match handle.await {
+            Ok(estimate) => estimate.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        heights.push(ChainHeight { chain, estimate });
+    }
+
+    heights
+}
+
+/// Renders the Markdown table of the estimated heights per chain at the given time.
+pub fn render_table(heights: &[ChainHeight], time: DateTime<Utc>) -> String {
+    let mut table = format!(
+        "Estimated heights at {}\n\n| Chain | Estimated Height | Block Time |\n|---|---|---|\n",
+        time.format("%Y-%m-%d %H:%M UTC")
+    );
+    for height in heights {
+        let row = match &height.estimate {
+            Ok((estimated_height, seconds_per_block)) => format!(
+                "| {} | {} | {:.2}s |\n",
+                height.chain,
+                estimated_height.to_formatted_string(&num_format::Locale::en),
+                seconds_per_block
+            ),
+            Err(e) => format!("| {} | failed: {} | - |\n", height.chain, e),
+        };
+        table.push_str(&row);
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[test]
+    fn test_render_table() {
+        let time = Utc.with_ymd_and_hms(2024, 3, 1, 14, 0, 0).unwrap();
+        let heights = vec![
+            ChainHeight {
+                chain: "Evmos Mainnet".to_string(),
+                estimate: Ok((18_500_000, 1.9)),
+            },
+            ChainHeight {
+                chain: "Osmosis".to_string(),
+                estimate: Err("connection refused".to_string()),
+            },
+        ];
+
+        assert_eq!(
+            render_table(&heights, time),
+            "Estimated heights at 2024-03-01 14:00 UTC
+
+| Chain | Estimated Height | Block Time |
+|---|---|---|
+| Evmos Mainnet | 18,500,000 | 1.90s |
+| Osmosis | failed: connection refused | - |
+"
+        );
+    }
+
+    #[test]
+    fn test_get_providers() {
+        let chain_config = ChainConfig {
+            reference_chains: vec![ReferenceChain {
+                name: "Osmosis".to_string(),
+                rest: "https://lcd.osmosis.example.com".to_string(),
+                tm_rpc: None,
+            }],
+            ..Default::default()
+        };
+        let test_dir = TestDir::temp();
+        let cache = DiskCache::new(test_dir.path("cache"), chrono::Duration::minutes(10));
+        let providers = get_providers(Network::LocalNode, &chain_config, &cache)
+            .expect("failed to get providers");
+
+        assert_eq!(providers.len(), 2);
+        assert_eq!(providers[0].0, "Evmos Local Node");
+        assert_eq!(
+            providers[1].1.rest.as_str(),
+            "https://lcd.osmosis.example.com/"
+        );
+        assert!(providers[1].1.rpc.is_none());
+        assert_eq!(providers[1].1.cache, Some(cache));
+    }
+}
@@ -1,45 +1,165 @@
-use crate::block::{get_estimated_height, get_rest_provider, round_to_nearest_500};
-use crate::errors::{HelperError, InputError, ValidationError};
-use crate::llm::{create_summary, OpenAIModel};
-use crate::release::{get_instance, get_release};
-use crate::{evmosd, inputs, network::Network, version};
+use crate::block::{get_block_time_estimate, round_to_nearest_500, BlockTimeEstimate};
+use crate::breaking::{get_breaking_changes, get_pull_requests, BreakingChanges};
+use crate::context::Context;
+use crate::downtime::{estimate_downtime, DowntimeEstimate};
+use crate::errors::{BlockError, GovError, HelperError, InputError, SummaryError, ValidationError};
+use crate::gov::ProposalId;
+use crate::handler::{check_plan_not_applied, find_upgrade_handler};
+use crate::ibc::{get_expiring_clients, get_expiry_deadline, ExpiringClient};
+use crate::inputs::{ExistingArtifactsAction, SummaryFallback};
+use crate::llm::{
+    create_community_summary, create_summary, create_summary_with_citations, get_truncated_notes,
+    OpenAIModel,
+};
+use crate::locale::Locale;
+use crate::notes::{get_notes, NotesSource};
+use crate::release::{get_binaries, get_latest_rc, get_release, Binary};
+use crate::resolve::get_home_override;
+use crate::tracking::TrackingIssue;
+use crate::upgrade_info::PlanInfo;
+use crate::utils::{with_deadline, write_atomically};
+use crate::version::VersionPolicy;
+use crate::{evmosd, inputs, network, network::Network, profile, secret, version};
 use chrono::{DateTime, Duration, Utc};
+use clap::ValueEnum;
+use schemars::JsonSchema;
+use std::fs;
+use std::future::Future;
 use std::path::{Path, PathBuf};
-use std::{fs, io};
+use std::process;
+
+/// The maximum number of minutes that the estimated time for a manually set upgrade height
+/// may diverge from the chosen upgrade time before a warning is shown.
+const MAX_TIME_DIVERGENCE_MINUTES: i64 = 60;
+
+/// The supported file formats of the exported helper configuration,
+/// which are detected from the file extension.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ConfigFormat {
+    #[default]
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Returns the file extensions that are associated with the format.
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            ConfigFormat::Json => &["json"],
+            ConfigFormat::Toml => &["toml"],
+            ConfigFormat::Yaml => &["yaml", "yml"],
+        }
+    }
+
+    /// Returns the format of the given configuration file based on its extension.
+    pub fn from_path(path: &Path) -> Option<ConfigFormat> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+
+        ConfigFormat::value_variants()
+            .iter()
+            .find(|format| format.extensions().contains(&extension.as_str()))
+            .copied()
+    }
+}
 
 /// Contains all relevant information for the scheduled upgrade.
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, JsonSchema)]
 pub struct UpgradeHelper {
+    /// The released binaries with their checksums, which are listed in the proposal.
+    #[serde(default)]
+    pub binaries: Vec<Binary>,
+    /// The measured block time and assumptions used to estimate the upgrade height.
+    #[serde(default)]
+    pub block_time_estimate: Option<BlockTimeEstimate>,
+    /// The pull requests included in the release, which are labeled as breaking.
+    #[serde(default)]
+    pub breaking_changes: BreakingChanges,
     /// The chain ID of the node.
     pub chain_id: String,
     /// The link to the Commonwealth proposal (optional).
     pub commonwealth_link: Option<String>,
+    /// The plain-language summary of the changes in the release for the community (optional).
+    #[serde(default)]
+    pub community_summary: Option<String>,
     /// The name of the config file.
     pub config_file_name: String,
+    /// The time at which the configuration was first written (optional).
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// The user who created the configuration, taken from the Git configuration or the OS user (optional).
+    #[serde(default)]
+    pub created_by: Option<String>,
+    /// The initial deposit of the proposal in the smallest unit of the staking denomination,
+    /// e.g. `10000000000` (optional).
+    #[serde(default)]
+    pub deposit: Option<String>,
+    /// The expected downtime of the upgrade, based on the durations of previous upgrades.
+    #[serde(default)]
+    pub downtime_estimate: Option<DowntimeEstimate>,
+    /// Whether the configuration is a draft, which is generated before the release is cut
+    /// and contains placeholders for the upgrade height, time and release information.
+    #[serde(default)]
+    pub draft: bool,
+    /// The JSON pointers of the values, which were encrypted in the loaded configuration file
+    /// and are encrypted again when the configuration is exported.
+    #[serde(skip)]
+    pub encrypted_fields: Vec<String>,
     /// The home directory of the Evmos binary.
     pub evmosd_home: PathBuf,
+    /// The IBC clients of major counterparties that would expire before the end of the upgrade window.
+    #[serde(default)]
+    pub expiring_clients: Vec<ExpiringClient>,
+    /// The locale, in which numbers and dates are rendered in the proposal.
+    #[serde(default)]
+    pub locale: Locale,
     /// The network to create the commands and proposal description for.
     pub network: Network,
+    /// The contents of the `info` field of the upgrade plan.
+    #[serde(default)]
+    pub plan_info: PlanInfo,
+    /// The name of the upgrade plan as defined in the upgrade handler, if it differs from the target version (optional).
+    #[serde(default)]
+    pub plan_name: Option<String>,
     /// The previous version to upgrade from.
     pub previous_version: String,
     /// The name of the proposal.
     pub proposal_name: String,
     /// The name of the proposal file.
     pub proposal_file_name: String,
+    /// The expected or confirmed ID of the on-chain proposal (optional).
+    #[serde(default)]
+    pub proposal_id: Option<ProposalId>,
     /// The summary of the changes in the release.
     pub summary: String,
     /// The target version to upgrade to.
     pub target_version: String,
+    /// The issue, in which the upgrade is tracked (optional).
+    #[serde(default)]
+    pub tracking_issue: Option<TrackingIssue>,
     /// The scheduled height of the upgrade.
     pub upgrade_height: u64,
     /// The scheduled time of the upgrade.
     pub upgrade_time: DateTime<Utc>,
+    /// The time at which the configuration was last written (optional).
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
     /// The number of hours for the voting period.
     pub voting_period: i64,
+    /// The start of the voting period, which is expected at the time of generation
+    /// and replaced with the actual time once the proposal is on chain (optional).
+    #[serde(default)]
+    pub voting_start_time: Option<DateTime<Utc>>,
+    /// The end of the voting period, which is expected at the time of generation
+    /// and replaced with the actual time once the proposal is on chain (optional).
+    #[serde(default)]
+    pub voting_end_time: Option<DateTime<Utc>>,
 }
 
 impl UpgradeHelper {
     /// Creates a new instance of the upgrade helper.
+    /// The chain ID is the default of the network, which is replaced by the callers
+    /// with the chain ID from the chain configuration.
     pub fn new(
         evmosd_home: PathBuf,
         network: Network,
@@ -49,29 +169,68 @@ impl UpgradeHelper {
         upgrade_height: u64,
         summary: &str,
     ) -> UpgradeHelper {
-        let chain_id = get_chain_id(network);
+        let chain_id = network::get_chain_id(network);
         let proposal_name = format!("Evmos {} {} Upgrade", network, target_version);
         let voting_period = get_voting_period(network);
         let proposal_file_name = format!("proposal-{}-{}.md", network, target_version);
         let config_file_name = format!("proposal-{}-{}.json", network, target_version);
 
         UpgradeHelper {
+            binaries: Vec::new(),
+            block_time_estimate: None,
+            breaking_changes: BreakingChanges::default(),
             chain_id,
             commonwealth_link: None,
+            community_summary: None,
             config_file_name,
+            created_at: None,
+            created_by: None,
+            deposit: None,
+            downtime_estimate: None,
+            draft: false,
+            encrypted_fields: Vec::new(),
             evmosd_home,
+            expiring_clients: Vec::new(),
+            locale: Locale::default(),
             network,
+            plan_info: PlanInfo::default(),
+            plan_name: None,
             previous_version: previous_version.to_string(),
             proposal_name,
             proposal_file_name,
+            proposal_id: None,
             summary: summary.to_string(),
             target_version: target_version.to_string(),
+            tracking_issue: None,
             upgrade_height,
             upgrade_time,
+            updated_at: None,
             voting_period: voting_period.num_hours(),
+            voting_start_time: None,
+            voting_end_time: None,
         }
     }
 
+    /// Returns the name of the upgrade plan, which is the target version unless the upgrade handler
+    /// defines a different spelling.
+    pub fn get_plan_name(&self) -> &str {
+        self.plan_name.as_deref().unwrap_or(&self.target_version)
+    }
+
+    /// Sets the start and end of the voting period, if they are given.
+    /// Returns whether any of the times changed.
+    pub fn set_voting_times(
+        &mut self,
+        start: Option<DateTime<Utc>>,
+        end: Option<DateTime<Utc>>,
+    ) -> bool {
+        let previous = (self.voting_start_time, self.voting_end_time);
+        self.voting_start_time = start.or(self.voting_start_time);
+        self.voting_end_time = end.or(self.voting_end_time);
+
+        previous != (self.voting_start_time, self.voting_end_time)
+    }
+
     /// Validates the upgrade helper.
     pub fn validate(&self) -> Result<(), ValidationError> {
         // Check if the target version is valid
@@ -99,44 +258,324 @@ impl UpgradeHelper {
             return Err(ValidationError::HomeDir(self.evmosd_home.clone()));
         }
 
-        // Check if the home folder contains the client configuration
-        evmosd::get_client_config(&self.evmosd_home.join("config/client.toml"))?;
+        // Check if the home folder contains the client configuration for the selected network
+        let client_config =
+            evmosd::get_client_config(&self.evmosd_home.join("config/client.toml"))?;
+        check_chain_id(&client_config, self.network, &self.chain_id)?;
+
+        Ok(())
+    }
+
+    /// Checks the bump from the previous to the target version against the given policy.
+    pub fn check_version_policy(&self, policy: &VersionPolicy) -> Result<(), ValidationError> {
+        version::check_version_policy(
+            policy,
+            self.network,
+            &self.previous_version,
+            &self.target_version,
+        )
+    }
+
+    /// Checks that the configuration is not a draft, which must not be submitted on chain.
+    pub fn check_not_draft(&self) -> Result<(), ValidationError> {
+        if self.draft {
+            return Err(ValidationError::Draft(self.config_file_name.clone()));
+        }
 
         Ok(())
     }
 
-    /// Exports the upgrade helper to a JSON file.
-    pub fn write_to_json(&self) -> io::Result<()> {
-        let json = serde_json::to_string_pretty(&self).expect("Failed to convert to JSON");
-        let path = Path::new(&self.config_file_name);
+    /// Sets the format of the exported configuration by replacing the extension of the config file name.
+    pub fn set_config_format(&mut self, format: ConfigFormat) {
+        self.config_file_name = Path::new(&self.config_file_name)
+            .with_extension(format.extensions()[0])
+            .to_string_lossy()
+            .to_string();
+    }
+
+    /// Checks if the given helper has the same contents, ignoring the times of the exports.
+    fn has_same_contents(&self, other: &UpgradeHelper) -> bool {
+        let without_timestamps = |helper: &UpgradeHelper| {
+            serde_json::to_value(helper).ok().map(|mut value| {
+                if let Some(map) = value.as_object_mut() {
+                    map.remove("created_at");
+                    map.remove("updated_at");
+                }
+                value
+            })
+        };
+
+        without_timestamps(self).is_some_and(|value| Some(value) == without_timestamps(other))
+    }
+
+    /// Exports the upgrade helper to the config file, whose format is detected from the extension.
+    pub fn write_to_file(&mut self, now: DateTime<Utc>) -> Result<(), HelperError> {
+        let path = PathBuf::from(&self.config_file_name);
+        self.write_to_path(&path, now)
+    }
+
+    /// Exports the upgrade helper to the given path, whose format is detected from the extension.
+    /// The creation time is set on the first export and the update time on every export,
+    /// which changes the contents. An unchanged configuration is not written again and keeps
+    /// its timestamps, so that the generated artifacts are identical across runs.
+    ///
+    /// Values, which were encrypted in the loaded configuration, are encrypted again.
+    /// An existing configuration with encrypted values is not overwritten by a configuration,
+    /// which does not track them, so that secrets are never written back in plain text.
+    pub fn write_to_path(&mut self, path: &Path, now: DateTime<Utc>) -> Result<(), HelperError> {
+        let format = ConfigFormat::from_path(path)
+            .ok_or_else(|| HelperError::UnsupportedFormat(path.to_path_buf()))?;
+        if self.encrypted_fields.is_empty()
+            && fs::read_to_string(path).is_ok_and(|contents| secret::contains_encrypted(&contents))
+        {
+            return Err(HelperError::PlaintextOverwrite(path.to_path_buf()));
+        }
+
+        if let Some(previous) = from_file(path)
+            .ok()
+            .filter(|previous| self.has_same_contents(previous))
+        {
+            self.created_at = previous.created_at;
+            self.updated_at = previous.updated_at;
+            return Ok(());
+        }
+
+        self.created_at.get_or_insert(now);
+        self.updated_at = Some(now);
+
+        let contents = match self.encrypted_fields.is_empty() {
+            true => serialize(format, &*self)?,
+            false => {
+                let mut value = serde_json::to_value(&self)?;
+                secret::encrypt_values(
+                    &mut value,
+                    &self.encrypted_fields,
+                    &secret::get_passphrase()?,
+                )?;
+                // NOTE: unset optional values have to be removed, because TOML cannot represent them
+                remove_null_values(&mut value);
+                serialize(format, &value)?
+            }
+        };
+
+        Ok(write_atomically(path, contents.as_bytes())?)
+    }
+}
+
+/// Returns the upgrade helper from a configuration file, whose format is detected from the extension.
+/// Encrypted values in the configuration are decrypted transparently and their locations are kept
+/// in the helper, so that they are encrypted again when it is exported.
+pub fn from_file(path: &Path) -> Result<UpgradeHelper, HelperError> {
+    let format = ConfigFormat::from_path(path)
+        .ok_or_else(|| HelperError::UnsupportedFormat(path.to_path_buf()))?;
+    let contents = fs::read_to_string(path)?;
+    if secret::contains_encrypted(&contents) {
+        let mut value = parse_value(format, &contents)?;
+        let encrypted_fields = secret::decrypt_values(&mut value, &secret::get_passphrase()?)?;
+
+        let mut helper: UpgradeHelper = serde_json::from_value(value)?;
+        helper.encrypted_fields = encrypted_fields;
+
+        return Ok(helper);
+    }
+
+    Ok(match format {
+        ConfigFormat::Json => serde_json::from_str(&contents)?,
+        ConfigFormat::Toml => toml::from_str(&contents)?,
+        ConfigFormat::Yaml => serde_yaml::from_str(&contents)?,
+    })
+}
+
+/// Serializes the given value in the given configuration format.
+fn serialize<T: serde::Serialize>(format: ConfigFormat, value: &T) -> Result<String, HelperError> {
+    Ok(match format {
+        ConfigFormat::Json => serde_json::to_string_pretty(value)?,
+        ConfigFormat::Toml => toml::to_string_pretty(value)?,
+        ConfigFormat::Yaml => serde_yaml::to_string(value)?,
+    })
+}
 
-        fs::write(path, json)
+/// Removes the null values from all objects in the given value.
+fn remove_null_values(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Array(values) => values.iter_mut().for_each(remove_null_values),
+        serde_json::Value::Object(map) => {
+            map.retain(|_, value| !value.is_null());
+            map.values_mut().for_each(remove_null_values);
+        }
+        _ => {}
     }
 }
 
-/// Returns the upgrade helper from a JSON file.
-pub fn from_json(path: &Path) -> Result<UpgradeHelper, HelperError> {
-    let json = fs::read_to_string(path)?;
+/// Returns the name of the user creating the configuration, which is taken from the Git configuration
+/// and falls back to the OS user.
+fn get_creator() -> Option<String> {
+    process::Command::new("git")
+        .args(["config", "user.name"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or_else(|| {
+            std::env::var("USER")
+                .or_else(|_| std::env::var("USERNAME"))
+                .ok()
+        })
+}
+
+/// Parses the given contents of a configuration file in the given format into a generic value.
+fn parse_value(format: ConfigFormat, contents: &str) -> Result<serde_json::Value, HelperError> {
+    Ok(match format {
+        ConfigFormat::Json => serde_json::from_str(contents)?,
+        ConfigFormat::Toml => toml::from_str(contents)?,
+        ConfigFormat::Yaml => serde_yaml::from_str(contents)?,
+    })
+}
+
+/// Contains the metadata of an upgrade configuration, which is shown when choosing a configuration.
+#[derive(Debug, PartialEq)]
+pub struct ConfigMetadata {
+    /// The network of the upgrade.
+    pub network: Network,
+    /// The target version of the upgrade.
+    pub target_version: String,
+    /// The user who created the configuration (optional).
+    pub created_by: Option<String>,
+}
+
+/// Returns the metadata of the given file if it is an upgrade configuration.
+///
+/// Configurations are detected by the `chain_id` and `target_version` fields, so that other files
+/// in a supported format (e.g. exported proposal JSON files) are skipped. The values are read
+/// without decrypting them, so that listing the configurations never prompts for the passphrase.
+pub fn sniff_config(path: &Path) -> Option<ConfigMetadata> {
+    let contents = fs::read_to_string(path).ok()?;
+    let value = parse_value(ConfigFormat::from_path(path)?, &contents).ok()?;
+    value.get("chain_id")?.as_str()?;
 
-    Ok(serde_json::from_str(&json)?)
+    Some(ConfigMetadata {
+        network: serde_json::from_value(value.get("network")?.clone()).ok()?,
+        target_version: value.get("target_version")?.as_str()?.to_string(),
+        created_by: value
+            .get("created_by")
+            .and_then(|created_by| created_by.as_str())
+            .map(str::to_string),
+    })
 }
 
 /// Returns the upgrade helper from the command line arguments and
 /// runs some basic validation on the configuration.
-pub fn get_helper_from_json(path: &Path) -> Result<UpgradeHelper, HelperError> {
-    let upgrade_helper = from_json(path)?;
+pub fn get_helper_from_file(ctx: &Context, path: &Path) -> Result<UpgradeHelper, HelperError> {
+    let mut upgrade_helper = from_file(path)?;
+    // NOTE: the home directory of the configuration can be overridden by the `--home` flag or the environment
+    if let Some(home) = get_home_override(&ctx.flags, None) {
+        upgrade_helper.evmosd_home = home;
+    }
     upgrade_helper.validate()?;
 
     Ok(upgrade_helper)
 }
 
+/// Contains the paths of the artifacts, which were generated for a network and target version before.
+#[derive(Debug, PartialEq)]
+pub struct ExistingArtifacts {
+    /// The exported configuration.
+    pub config: PathBuf,
+    /// The rendered proposal (optional).
+    pub proposal: Option<PathBuf>,
+    /// The generated submission command (optional).
+    pub command: Option<PathBuf>,
+}
+
+impl ExistingArtifacts {
+    /// Returns the paths of all existing artifacts.
+    pub fn paths(&self) -> Vec<&PathBuf> {
+        [
+            Some(&self.config),
+            self.proposal.as_ref(),
+            self.command.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// Returns the artifacts in the given directory, which were generated for the given network
+/// and target version before. Artifacts are only detected if the configuration was exported,
+/// because the proposal and the command are rendered from it.
+pub fn find_existing_artifacts(
+    dir: &Path,
+    network: Network,
+    target_version: &str,
+) -> Option<ExistingArtifacts> {
+    let base_name = format!("proposal-{}-{}", network, target_version);
+    let config = ConfigFormat::value_variants()
+        .iter()
+        .flat_map(|format| format.extensions())
+        .map(|extension| dir.join(format!("{}.{}", base_name, extension)))
+        .find(|path| path.exists())?;
+    let existing = |extension: &str| {
+        let path = dir.join(format!("{}.{}", base_name, extension));
+        path.exists().then_some(path)
+    };
+
+    Some(ExistingArtifacts {
+        config,
+        proposal: existing("md"),
+        command: existing("sh"),
+    })
+}
+
+/// The outcome of querying the user for the upgrade helper.
+pub enum HelperOutcome {
+    /// A new helper was created from the inputs.
+    Created(UpgradeHelper),
+    /// The helper of a previous run was loaded to update its artifacts.
+    Existing(UpgradeHelper),
+    /// The artifacts of a previous run are kept untouched.
+    Kept,
+}
+
+/// Contains the options that control how the upgrade helper is created from the user inputs.
+pub struct InputOptions {
+    /// The LLM model to use for summarizing the release notes.
+    pub model: OpenAIModel,
+    /// The upgrade height to use instead of estimating it from the chosen upgrade time.
+    pub upgrade_height: Option<u64>,
+    /// The intended mainnet version, for which the latest release candidate is suggested
+    /// as the target version on non-mainnet networks.
+    pub mainnet_version: Option<String>,
+    /// Whether to additionally generate a plain-language summary for the community.
+    pub dual_summary: bool,
+    /// The source of the notes, which are summarized for the proposal.
+    pub notes_source: NotesSource,
+    /// Whether to generate a draft before the release is cut, which skips querying the release
+    /// and estimating the upgrade height.
+    pub draft: bool,
+}
+
 /// Creates a new instance of the upgrade helper based on querying the user for the necessary input.
-pub async fn get_helper_from_inputs(model: OpenAIModel) -> Result<UpgradeHelper, InputError> {
+///
+/// If an upgrade height is passed, it is used instead of estimating the height from the chosen upgrade time.
+/// If artifacts for the chosen network and target version exist already, the user decides whether
+/// to update them from the existing configuration, to regenerate them or to keep them untouched.
+pub async fn get_helper_from_inputs(
+    ctx: &Context,
+    options: InputOptions,
+) -> Result<HelperOutcome, InputError> {
+    let inputs = ctx.inputs.as_ref();
+
     // Query and check the network to use
-    let used_network = inputs::get_used_network()?;
+    let used_network = ctx.resolve_network(None)?;
 
     // Query and check the version to upgrade from
-    let previous_version = inputs::get_text("Previous version to upgrade from:")?;
+    let previous_version = inputs::get_text(
+        inputs,
+        "previous_version",
+        "Previous version to upgrade from:",
+    )?;
     let valid_version = version::is_valid_version(previous_version.as_str());
     if !valid_version {
         return Err(InputError::from(ValidationError::PreviousVersion(
@@ -144,8 +583,32 @@ pub async fn get_helper_from_inputs(model: OpenAIModel) -> Result<UpgradeHelper,
         )));
     }
 
+    // Detect the latest release candidate for the intended mainnet version to suggest it as the target version
+    let latest_rc = match (used_network, options.mainnet_version.as_deref()) {
+        (Network::Mainnet, _) | (_, None) => None,
+        (_, Some(mainnet_version)) => {
+            let latest_rc = with_deadline(
+                "query release candidates",
+                get_latest_rc(ctx.github.as_ref(), mainnet_version),
+            )
+            .await??;
+            if latest_rc.is_none() {
+                println!("No release candidate found for {}", mainnet_version);
+            }
+            latest_rc
+        }
+    };
+
     // Query and check the target version to upgrade to
-    let target_version = inputs::get_text("Target version to upgrade to:")?;
+    let target_version = match latest_rc {
+        Some(rc) => inputs::get_text_with_default(
+            inputs,
+            "target_version",
+            "Target version to upgrade to:",
+            &rc,
+        )?,
+        None => inputs::get_text(inputs, "target_version", "Target version to upgrade to:")?,
+    };
     if !version::is_valid_version_for_network(used_network, target_version.as_str()) {
         return Err(InputError::from(ValidationError::TargetVersion(
             used_network,
@@ -153,35 +616,530 @@ pub async fn get_helper_from_inputs(model: OpenAIModel) -> Result<UpgradeHelper,
         )));
     }
 
-    // Query and check the upgrade time and height
+    // Check for artifacts of a previous run, so that they are not overwritten unintentionally
+    if let Some(existing) =
+        find_existing_artifacts(&std::env::current_dir()?, used_network, &target_version)
+    {
+        match inputs::choose_existing_artifacts_action(inputs, &existing)? {
+            ExistingArtifactsAction::Update => {
+                return Ok(HelperOutcome::Existing(get_helper_from_file(
+                    ctx,
+                    &existing.config,
+                )?))
+            }
+            ExistingArtifactsAction::Regenerate => {}
+            ExistingArtifactsAction::Keep => return Ok(HelperOutcome::Kept),
+        }
+    }
+
+    // Query the contents of the upgrade plan info
+    let plan_info = inputs::get_plan_info(inputs)?;
+
     let voting_period = get_voting_period(used_network);
-    let upgrade_time = inputs::get_upgrade_time(voting_period, Utc::now())?;
-    let base_url = get_rest_provider(used_network);
-    let upgrade_height = round_to_nearest_500(get_estimated_height(&base_url, upgrade_time).await?);
+    if options.draft {
+        // NOTE: the schedule is not fixed for drafts, so the planned date is only used as a tentative upgrade time
+        let upgrade_time = inputs::calculate_planned_date(voting_period, Utc::now());
+        let evmosd_home = ctx.resolve_home(used_network, None)?;
 
-    // Query and check the summary of the changes in the release
-    let release = get_release(get_instance().as_ref(), target_version.as_str()).await?;
-    let summary = create_summary(&release, model).await?;
+        let mut upgrade_helper = create_draft_helper(
+            evmosd_home,
+            used_network,
+            &previous_version,
+            &target_version,
+            upgrade_time,
+        );
+        upgrade_helper.chain_id = ctx.chain_config(used_network)?.get_chain_id(used_network);
+        upgrade_helper.plan_info = plan_info;
+
+        return Ok(HelperOutcome::Created(upgrade_helper));
+    }
+
+    // Query the upgrade time
+    let upgrade_time = inputs::get_upgrade_time(
+        inputs,
+        voting_period,
+        Utc::now(),
+        &ctx.chain_config(used_network)?.validator_timezones,
+    )?;
 
     // Get the used home directory for the Evmos binary.
-    let evmosd_home = inputs::get_evmosd_home(&used_network)?;
+    let evmosd_home = ctx.resolve_home(used_network, None)?;
+
+    let mut upgrade_helper = create_helper(
+        ctx,
+        evmosd_home,
+        used_network,
+        &previous_version,
+        &target_version,
+        upgrade_time,
+        &options,
+    )
+    .await?;
+    upgrade_helper.plan_info = plan_info;
+
+    Ok(HelperOutcome::Created(upgrade_helper))
+}
+
+/// Creates the upgrade helper of a draft for the given network and versions.
+///
+/// The upgrade height and the release information are left empty, because the release
+/// is not cut yet. They are rendered as placeholders until the draft is finalized.
+fn create_draft_helper(
+    evmosd_home: PathBuf,
+    network: Network,
+    previous_version: &str,
+    target_version: &str,
+    upgrade_time: DateTime<Utc>,
+) -> UpgradeHelper {
+    let mut upgrade_helper = UpgradeHelper::new(
+        evmosd_home,
+        network,
+        previous_version,
+        target_version,
+        upgrade_time,
+        0,
+        "",
+    );
+    upgrade_helper.created_by = get_creator();
+    upgrade_helper.draft = true;
+
+    upgrade_helper
+}
+
+/// Finalizes the given draft once the release is cut and the schedule is fixed.
+///
+/// The upgrade time is queried from the user and the upgrade height, the summary and the release
+/// information are filled in the same way as when generating a proposal. The file names,
+/// the home directory, the locale and the audit information of the draft are kept.
+pub async fn finalize_helper(
+    ctx: &Context,
+    draft: &UpgradeHelper,
+    options: InputOptions,
+) -> Result<UpgradeHelper, InputError> {
+    if !draft.draft {
+        return Err(InputError::from(ValidationError::NotDraft(
+            draft.config_file_name.clone(),
+        )));
+    }
+
+    let upgrade_time = inputs::get_upgrade_time(
+        ctx.inputs.as_ref(),
+        get_voting_period(draft.network),
+        Utc::now(),
+        &ctx.chain_config(draft.network)?.validator_timezones,
+    )?;
+
+    let mut upgrade_helper = create_helper(
+        ctx,
+        draft.evmosd_home.clone(),
+        draft.network,
+        &draft.previous_version,
+        &draft.target_version,
+        upgrade_time,
+        &options,
+    )
+    .await?;
+    upgrade_helper.config_file_name = draft.config_file_name.clone();
+    upgrade_helper.created_at = draft.created_at;
+    upgrade_helper.created_by = draft.created_by.clone();
+    upgrade_helper.encrypted_fields = draft.encrypted_fields.clone();
+    upgrade_helper.locale = draft.locale;
+    upgrade_helper.plan_info = draft.plan_info.clone();
+    upgrade_helper.tracking_issue = draft.tracking_issue.clone();
+
+    Ok(upgrade_helper)
+}
+
+/// Creates the pair of upgrade helpers for the testnet release candidate and the mainnet release
+/// of the same version line based on querying the user for the necessary input.
+///
+/// The mainnet upgrade is scheduled the given lag after the testnet upgrade.
+pub async fn get_pair_from_inputs(
+    ctx: &Context,
+    options: InputOptions,
+    lag: Duration,
+) -> Result<(UpgradeHelper, UpgradeHelper), InputError> {
+    let inputs = ctx.inputs.as_ref();
+
+    // Query and check the versions of both networks
+    let testnet_previous_version = inputs::get_text(
+        inputs,
+        "testnet_previous_version",
+        "Testnet version to upgrade from:",
+    )?;
+    let mainnet_previous_version = inputs::get_text(
+        inputs,
+        "mainnet_previous_version",
+        "Mainnet version to upgrade from:",
+    )?;
+    for previous_version in [&testnet_previous_version, &mainnet_previous_version] {
+        if !version::is_valid_version(previous_version) {
+            return Err(InputError::from(ValidationError::PreviousVersion(
+                previous_version.clone(),
+            )));
+        }
+    }
+
+    let mainnet_version = match options.mainnet_version.as_deref() {
+        Some(version) => inputs::get_text_with_default(
+            inputs,
+            "mainnet_target_version",
+            "Mainnet version to upgrade to:",
+            version,
+        )?,
+        None => inputs::get_text(
+            inputs,
+            "mainnet_target_version",
+            "Mainnet version to upgrade to:",
+        )?,
+    };
+    if !version::is_valid_version_for_network(Network::Mainnet, &mainnet_version) {
+        return Err(InputError::from(ValidationError::TargetVersion(
+            Network::Mainnet,
+            mainnet_version,
+        )));
+    }
+
+    let latest_rc = with_deadline(
+        "query release candidates",
+        get_latest_rc(ctx.github.as_ref(), &mainnet_version),
+    )
+    .await??;
+    let testnet_version = match latest_rc {
+        Some(rc) => inputs::get_text_with_default(
+            inputs,
+            "testnet_target_version",
+            "Testnet version to upgrade to:",
+            &rc,
+        )?,
+        None => inputs::get_text(
+            inputs,
+            "testnet_target_version",
+            "Testnet version to upgrade to:",
+        )?,
+    };
+    check_pair_versions(&testnet_version, &mainnet_version)?;
+
+    // Query the contents of the upgrade plan info, which is used for both proposals
+    let plan_info = inputs::get_plan_info(inputs)?;
+
+    // Query the testnet upgrade time and schedule the mainnet upgrade after the lag
+    let testnet_time = inputs::get_upgrade_time(
+        inputs,
+        get_voting_period(Network::Testnet),
+        Utc::now(),
+        &ctx.chain_config(Network::Testnet)?.validator_timezones,
+    )?;
+    let mainnet_time = testnet_time + lag;
+    if !inputs::is_valid_upgrade_time(mainnet_time) {
+        return Err(InputError::from(ValidationError::UpgradeTime(mainnet_time)));
+    }
+    if mainnet_time < Utc::now() + get_voting_period(Network::Mainnet) {
+        println!(
+            "WARNING: mainnet upgrade at {} is before the end of the voting period if submitted now",
+            mainnet_time
+        );
+    }
+
+    // Get the used home directories for the Evmos binary.
+    let testnet_home = ctx.resolve_home(Network::Testnet, None)?;
+    let mainnet_home = ctx.resolve_home(Network::Mainnet, None)?;
+
+    let mut testnet_helper = create_helper(
+        ctx,
+        testnet_home,
+        Network::Testnet,
+        &testnet_previous_version,
+        &testnet_version,
+        testnet_time,
+        &options,
+    )
+    .await?;
+    let mut mainnet_helper = create_helper(
+        ctx,
+        mainnet_home,
+        Network::Mainnet,
+        &mainnet_previous_version,
+        &mainnet_version,
+        mainnet_time,
+        &options,
+    )
+    .await?;
+    testnet_helper.plan_info = plan_info.clone();
+    mainnet_helper.plan_info = plan_info;
+
+    Ok((testnet_helper, mainnet_helper))
+}
+
+/// Checks that the testnet version is a release candidate of the mainnet version,
+/// so that the paired proposals and their files are named consistently.
+fn check_pair_versions(
+    testnet_version: &str,
+    mainnet_version: &str,
+) -> Result<(), ValidationError> {
+    let is_rc = testnet_version
+        .strip_prefix(mainnet_version)
+        .and_then(|suffix| suffix.strip_prefix("-rc"))
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    if !is_rc {
+        return Err(ValidationError::PairVersion(
+            testnet_version.to_string(),
+            mainnet_version.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Creates the upgrade helper for the given home directory, network, versions and upgrade time.
+///
+/// The upgrade height is estimated from the upgrade time, the release notes are summarized
+/// and the IBC clients, breaking changes and the expected proposal ID are queried.
+async fn create_helper(
+    ctx: &Context,
+    evmosd_home: PathBuf,
+    used_network: Network,
+    previous_version: &str,
+    target_version: &str,
+    upgrade_time: DateTime<Utc>,
+    options: &InputOptions,
+) -> Result<UpgradeHelper, InputError> {
+    // Estimate and check the upgrade height
+    let block_provider = ctx.block_provider(used_network)?;
+    let base_url = block_provider.rest.clone();
+    let block_time_estimate = profile::timed(
+        "sample block times",
+        get_block_time_estimate(&block_provider),
+    )
+    .await?;
+    let upgrade_height = match options.upgrade_height {
+        Some(height) => {
+            check_upgrade_height(&block_time_estimate, height, upgrade_time)?;
+            height
+        }
+        None => round_to_nearest_500(block_time_estimate.height_at(upgrade_time)),
+    };
+
+    // Check for IBC clients of major counterparties that would expire during the upgrade window
+    let expiring_clients = match get_expiring_clients(
+        &base_url,
+        used_network,
+        get_expiry_deadline(upgrade_time),
+    )
+    .await
+    {
+        Ok(expiring_clients) => expiring_clients,
+        Err(e) => {
+            println!("WARNING: failed to check IBC clients for expiry: {}", e);
+            Vec::new()
+        }
+    };
+    for client in expiring_clients.iter() {
+        println!(
+            "WARNING: IBC client {} for {} expires at {} if not updated before",
+            client.client_id, client.chain_id, client.expiry
+        );
+    }
+
+    // Query and check the summary of the changes in the release
+    let release = with_deadline(
+        "query release",
+        get_release(ctx.github.as_ref(), target_version),
+    )
+    .await??;
+    // NOTE: the plan name of the upgrade is the target version, so that the release must contain a handler for it
+    let handler = with_deadline(
+        "check upgrade handler",
+        find_upgrade_handler(ctx.github.as_ref(), target_version, target_version),
+    )
+    .await??;
+    println!(
+        "Found upgrade handler for {} in {}",
+        handler.plan_name, handler.path
+    );
+    // Check that the plan name was not used before, because an applied plan is not executed again
+    check_plan_not_applied(&base_url, &handler.plan_name).await?;
+    // Query the included pull requests, which are cited in the summary if available
+    let pull_requests = match with_deadline(
+        "query pull requests",
+        get_pull_requests(ctx.github.as_ref(), previous_version, target_version),
+    )
+    .await?
+    {
+        Ok(pull_requests) => pull_requests,
+        Err(e) => {
+            println!("WARNING: failed to query included pull requests: {}", e);
+            Vec::new()
+        }
+    };
+    // Build the notes to summarize from the chosen source, e.g. for releases with sparse descriptions
+    let notes = with_deadline(
+        "query notes",
+        get_notes(
+            ctx.github.as_ref(),
+            options.notes_source,
+            &release,
+            &pull_requests,
+            previous_version,
+            target_version,
+        ),
+    )
+    .await??;
+    let llm = ctx.llm();
+    let summary = get_summary_with_fallback(ctx, "create summary", &notes, || async {
+        match pull_requests.is_empty() {
+            true => create_summary(llm.as_ref(), &notes, options.model).await,
+            false => {
+                create_summary_with_citations(llm.as_ref(), &notes, &pull_requests, options.model)
+                    .await
+            }
+        }
+    })
+    .await?;
+    let community_summary = match options.dual_summary {
+        true => Some(
+            get_summary_with_fallback(ctx, "create community summary", &notes, || {
+                create_community_summary(llm.as_ref(), &notes, options.model)
+            })
+            .await?,
+        ),
+        false => None,
+    };
+
+    // Query the released binaries and their checksums, which are listed in the proposal
+    let chain_config = ctx.chain_config(used_network)?;
+    let binaries = match with_deadline(
+        "query binaries",
+        get_binaries(&release, chain_config.checksum_signature.as_ref()),
+    )
+    .await?
+    {
+        Ok(binaries) => binaries,
+        Err(e) => {
+            println!("WARNING: failed to query binaries: {}", e);
+            Vec::new()
+        }
+    };
+
+    // Detect state- or API-breaking changes from the labels of the included pull requests
+    let breaking_changes = match with_deadline(
+        "query breaking changes",
+        get_breaking_changes(ctx.github.as_ref(), previous_version, target_version),
+    )
+    .await?
+    {
+        Ok(breaking_changes) => breaking_changes,
+        Err(e) => {
+            println!("WARNING: failed to detect breaking changes: {}", e);
+            BreakingChanges::default()
+        }
+    };
+    breaking_changes.warn();
+
+    // Query the ID that the proposal is expected to get, which is confirmed once it is submitted
+    let proposal_id = match with_deadline(
+        "query next proposal ID",
+        get_expected_proposal_id(ctx, used_network),
+    )
+    .await?
+    {
+        Ok(proposal_id) => Some(proposal_id),
+        Err(e) => {
+            println!("WARNING: failed to query next proposal ID: {}", e);
+            None
+        }
+    };
 
     // Create an instance of the helper
-    Ok(UpgradeHelper::new(
+    let mut upgrade_helper = UpgradeHelper::new(
         evmosd_home,
         used_network,
-        previous_version.as_str(),
-        target_version.as_str(),
+        previous_version,
+        target_version,
         upgrade_time,
         upgrade_height,
         summary.as_str(),
-    ))
+    );
+    upgrade_helper.binaries = binaries;
+    upgrade_helper.chain_id = ctx.chain_config(used_network)?.get_chain_id(used_network);
+    upgrade_helper.created_by = get_creator();
+    upgrade_helper.block_time_estimate = Some(block_time_estimate);
+    upgrade_helper.breaking_changes = breaking_changes;
+    upgrade_helper.community_summary = community_summary;
+    upgrade_helper.expiring_clients = expiring_clients;
+    upgrade_helper.proposal_id = proposal_id;
+    if handler.plan_name != target_version {
+        upgrade_helper.plan_name = Some(handler.plan_name);
+    }
+    // NOTE: the proposal is expected to be submitted right after generating it
+    let now = Utc::now();
+    upgrade_helper.set_voting_times(Some(now), Some(now + get_voting_period(used_network)));
+    upgrade_helper.downtime_estimate = estimate_downtime(&chain_config.upgrade_durations);
+
+    Ok(upgrade_helper)
+}
+
+/// Returns the ID that the next proposal submitted on the given network is expected to get.
+async fn get_expected_proposal_id(ctx: &Context, network: Network) -> Result<ProposalId, GovError> {
+    let id = ctx.gov_client(network)?.next_proposal_id().await?;
+
+    Ok(ProposalId {
+        id,
+        confirmed: false,
+    })
+}
+
+/// Checks that the chain ID in the client configuration matches the expected chain ID of the given network.
+/// This prevents generating commands with e.g. a testnet home for a mainnet upgrade.
+fn check_chain_id(
+    client_config: &evmosd::ClientConfig,
+    network: Network,
+    expected_chain_id: &str,
+) -> Result<(), ValidationError> {
+    if client_config.chain_id != expected_chain_id {
+        return Err(ValidationError::ChainId(
+            client_config.chain_id.clone(),
+            network,
+            expected_chain_id.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks the manually set upgrade height against the chosen upgrade time.
+/// The expected time to reach the height is printed and a warning is shown,
+/// if it diverges too much from the chosen upgrade time.
+fn check_upgrade_height(
+    block_time_estimate: &BlockTimeEstimate,
+    height: u64,
+    upgrade_time: DateTime<Utc>,
+) -> Result<(), BlockError> {
+    let estimated_time = block_time_estimate.time_at(height)?;
+    println!(
+        "Upgrade height {} is estimated to be reached at {}",
+        height,
+        inputs::get_time_string(estimated_time)
+    );
+
+    let divergence = (estimated_time - upgrade_time).num_minutes().abs();
+    if divergence > MAX_TIME_DIVERGENCE_MINUTES {
+        println!(
+            "WARNING: estimated time diverges by {} minutes from the chosen upgrade time {}",
+            divergence,
+            inputs::get_time_string(upgrade_time)
+        );
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
 mod helper_tests {
     use super::*;
     use chrono::TimeZone;
+    use rstest::rstest;
+    use test_dir::{DirBuilder, TestDir};
 
     #[test]
     fn test_new_upgrade_helper() {
@@ -213,9 +1171,67 @@ mod helper_tests {
     }
 
     #[test]
-    fn test_write_to_json_and_read_from_json() {
+    fn test_create_draft_helper() {
+        let helper = create_draft_helper(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v14.0.0",
+            "v14.0.0-rc1",
+            Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+        );
+        assert!(helper.draft);
+        assert_eq!(helper.upgrade_height, 0);
+        assert!(helper.binaries.is_empty());
+        assert!(matches!(
+            helper.check_not_draft(),
+            Err(ValidationError::Draft(_))
+        ));
+    }
+
+    #[test]
+    fn test_write_to_path_keeps_unchanged_config() {
+        let test_dir = TestDir::temp();
+        let path = test_dir.path("proposal.json");
+        let created_at = Utc.with_ymd_and_hms(2023, 10, 1, 12, 0, 0).unwrap();
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v14.0.0",
+            "v14.0.0-rc1",
+            Utc.with_ymd_and_hms(2023, 10, 23, 16, 0, 0).unwrap(),
+            60,
+            "summary",
+        );
+        helper
+            .write_to_path(&path, created_at)
+            .expect("failed to write helper configuration");
+        let exported = fs::read(&path).unwrap();
+
+        let mut read_helper = from_file(&path).expect("failed to read helper");
+        read_helper
+            .write_to_path(&path, created_at + chrono::Duration::days(1))
+            .expect("failed to write helper configuration");
+        assert_eq!(fs::read(&path).unwrap(), exported);
+        assert_eq!(read_helper.updated_at, Some(created_at));
+
+        let updated_at = created_at + chrono::Duration::days(2);
+        read_helper.upgrade_height = 120;
+        read_helper
+            .write_to_path(&path, updated_at)
+            .expect("failed to write helper configuration");
+        let read_helper = from_file(&path).expect("failed to read helper");
+        assert_eq!(read_helper.created_at, Some(created_at));
+        assert_eq!(read_helper.updated_at, Some(updated_at));
+    }
+
+    #[allow(
+        clippy::assertions_on_constants,
+        clippy::needless_borrows_for_generic_args
+    )]
+    #[test]
+    fn test_write_to_file_and_read_from_file() {
         let upgrade_height = 60;
-        let helper = UpgradeHelper::new(
+        let mut helper = UpgradeHelper::new(
             PathBuf::from("./.evmosd"),
             Network::Testnet,
             "v14.0.0",
@@ -226,7 +1242,7 @@ mod helper_tests {
         );
 
         assert!(
-            helper.write_to_json().is_ok(),
+            helper.write_to_file(Utc::now()).is_ok(),
             "expected success writing helper information to JSON file"
         );
 
@@ -234,10 +1250,12 @@ mod helper_tests {
         let path = Path::new(&helper.config_file_name);
         assert!(path_exists(path), "expected config file to exist");
 
-        let read_input_helper = from_json(path).expect("failed to read helper from JSON file");
+        let read_input_helper = from_file(path).expect("failed to read helper from JSON file");
         assert_eq!(helper.chain_id, read_input_helper.chain_id);
         assert_eq!(helper.config_file_name, read_input_helper.config_file_name);
         assert_eq!(helper.upgrade_height, read_input_helper.upgrade_height);
+        assert!(read_input_helper.created_at.is_some());
+        assert_eq!(read_input_helper.created_at, read_input_helper.updated_at);
 
         // remove the config file
         match fs::remove_file(&path) {
@@ -248,6 +1266,210 @@ mod helper_tests {
             }
         }
     }
+
+    #[rstest]
+    #[case(ConfigFormat::Toml, "proposal-Testnet-v14.0.0-rc2.toml")]
+    #[case(ConfigFormat::Yaml, "proposal-Testnet-v14.0.0-rc2.yaml")]
+    fn test_write_and_read_config_formats(#[case] format: ConfigFormat, #[case] file_name: &str) {
+        let test_dir = TestDir::temp();
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v14.0.0",
+            "v14.0.0-rc2",
+            Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+            60,
+            "summary",
+        );
+        helper.set_config_format(format);
+        assert_eq!(helper.config_file_name, file_name);
+        helper.block_time_estimate = Some(BlockTimeEstimate {
+            seconds_per_block: 2.0,
+            n_blocks: 50_000,
+            latest_height: 40,
+            latest_time: Utc.with_ymd_and_hms(2020, 12, 31, 0, 0, 0).unwrap(),
+            estimated_at: Utc.with_ymd_and_hms(2020, 12, 31, 0, 1, 0).unwrap(),
+        });
+
+        // NOTE: the config file name is relative, so it is written to the test directory
+        helper.config_file_name = test_dir.path(file_name).to_string_lossy().to_string();
+        helper
+            .write_to_file(Utc::now())
+            .expect("failed to write helper configuration");
+
+        let read_helper = from_file(&test_dir.path(file_name)).expect("failed to read helper");
+        assert_eq!(read_helper.summary, helper.summary);
+        assert_eq!(read_helper.upgrade_time, helper.upgrade_time);
+        assert_eq!(read_helper.block_time_estimate, helper.block_time_estimate);
+    }
+
+    #[rstest]
+    #[case("proposal-Testnet-v14.0.0-rc2.json")]
+    #[case("proposal-Testnet-v14.0.0-rc2.toml")]
+    fn test_write_keeps_encrypted_values(#[case] file_name: &str) {
+        let passphrase = "test-passphrase";
+        std::env::set_var("PROPOSER_PASSPHRASE", passphrase);
+
+        let test_dir = TestDir::temp();
+        let path = test_dir.path(file_name);
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v14.0.0",
+            "v14.0.0-rc2",
+            Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap(),
+            60,
+            "summary",
+        );
+        helper.commonwealth_link = Some(
+            secret::encrypt("https://commonwealth.im/evmos/discussion/1", passphrase).unwrap(),
+        );
+        helper
+            .write_to_path(&path, Utc::now())
+            .expect("failed to write helper configuration");
+
+        let mut read_helper = from_file(&path).expect("failed to read helper");
+        assert_eq!(
+            read_helper.commonwealth_link.as_deref(),
+            Some("https://commonwealth.im/evmos/discussion/1")
+        );
+        assert_eq!(read_helper.encrypted_fields, vec!["/commonwealth_link"]);
+
+        read_helper.upgrade_height = 120;
+        read_helper
+            .write_to_path(&path, Utc::now())
+            .expect("failed to write helper configuration");
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(secret::contains_encrypted(&contents));
+        assert!(!contents.contains("https://commonwealth.im"));
+
+        let read_helper = from_file(&path).expect("failed to read helper");
+        assert_eq!(
+            read_helper.commonwealth_link.as_deref(),
+            Some("https://commonwealth.im/evmos/discussion/1")
+        );
+        assert_eq!(read_helper.upgrade_height, 120);
+
+        // NOTE: a configuration without the tracked values must not overwrite the encrypted values
+        let mut plain_helper = from_file(&path).expect("failed to read helper");
+        plain_helper.encrypted_fields.clear();
+        assert!(matches!(
+            plain_helper.write_to_path(&path, Utc::now()),
+            Err(HelperError::PlaintextOverwrite(_))
+        ));
+    }
+
+    #[test]
+    fn test_sniff_config() {
+        let test_dir = TestDir::temp();
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v14.0.0",
+            "v14.0.0-rc2",
+            Utc::now(),
+            60,
+            "summary",
+        );
+        helper.config_file_name = test_dir
+            .path("proposal-Testnet-v14.0.0-rc2.json")
+            .to_string_lossy()
+            .to_string();
+        helper
+            .write_to_file(Utc::now())
+            .expect("failed to write helper configuration");
+        fs::write(test_dir.path("other.json"), r#"{"messages": []}"#).unwrap();
+
+        assert_eq!(
+            sniff_config(&test_dir.path("proposal-Testnet-v14.0.0-rc2.json")),
+            Some(ConfigMetadata {
+                network: Network::Testnet,
+                target_version: "v14.0.0-rc2".to_string(),
+                created_by: None,
+            })
+        );
+        assert_eq!(sniff_config(&test_dir.path("other.json")), None);
+    }
+
+    #[test]
+    fn test_config_format_from_path() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("proposal.json")),
+            Some(ConfigFormat::Json)
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("proposal.YML")),
+            Some(ConfigFormat::Yaml)
+        );
+        assert_eq!(ConfigFormat::from_path(Path::new("proposal.md")), None);
+        assert!(from_file(Path::new("proposal.md")).is_err());
+    }
+
+    #[test]
+    fn test_find_existing_artifacts() {
+        let test_dir = TestDir::temp();
+        assert_eq!(
+            find_existing_artifacts(test_dir.root(), Network::Testnet, "v17.0.0-rc1"),
+            None
+        );
+
+        fs::write(test_dir.path("proposal-Testnet-v17.0.0-rc1.md"), "").unwrap();
+        assert_eq!(
+            find_existing_artifacts(test_dir.root(), Network::Testnet, "v17.0.0-rc1"),
+            None
+        );
+
+        fs::write(test_dir.path("proposal-Testnet-v17.0.0-rc1.yaml"), "").unwrap();
+        assert_eq!(
+            find_existing_artifacts(test_dir.root(), Network::Testnet, "v17.0.0-rc1"),
+            Some(ExistingArtifacts {
+                config: test_dir.path("proposal-Testnet-v17.0.0-rc1.yaml"),
+                proposal: Some(test_dir.path("proposal-Testnet-v17.0.0-rc1.md")),
+                command: None,
+            })
+        );
+        assert_eq!(
+            find_existing_artifacts(test_dir.root(), Network::Mainnet, "v17.0.0-rc1"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_check_chain_id() {
+        let mut client_config = evmosd::ClientConfig {
+            chain_id: "evmos_9001-2".to_string(),
+            keyring_backend: "os".to_string(),
+            output: "text".to_string(),
+            node: "tcp://localhost:26657".to_string(),
+            broadcast_mode: "sync".to_string(),
+        };
+        assert!(check_chain_id(&client_config, Network::Mainnet, "evmos_9001-2").is_ok());
+
+        client_config.chain_id = "evmos_9000-4".to_string();
+        let err = check_chain_id(&client_config, Network::Mainnet, "evmos_9001-2")
+            .expect_err("expected error for testnet chain ID on mainnet");
+        assert_eq!(
+            err.to_string(),
+            "Client configuration uses chain ID evmos_9000-4, but the Mainnet network requires evmos_9001-2"
+        );
+    }
+
+    #[rstest]
+    #[case("v17.0.0-rc1", "v17.0.0", true)]
+    #[case("v17.0.0-rc12", "v17.0.0", true)]
+    #[case("v17.0.0", "v17.0.0", false)]
+    #[case("v17.0.1-rc1", "v17.0.0", false)]
+    #[case("v17.0.0-rc", "v17.0.0", false)]
+    fn test_check_pair_versions(
+        #[case] testnet_version: &str,
+        #[case] mainnet_version: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            check_pair_versions(testnet_version, mainnet_version).is_ok(),
+            expected
+        );
+    }
 }
 
 /// Checks whether a given path exists.
@@ -263,12 +1485,14 @@ fn path_exists(path: &Path) -> bool {
 mod path_tests {
     use super::*;
 
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_path_exists() {
         let path = Path::new("/tmp");
         assert_eq!(path_exists(path), true);
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_path_does_not_exist() {
         let path = Path::new("/tmp/does-not-exist");
@@ -276,6 +1500,46 @@ mod path_tests {
     }
 }
 
+/// Creates a summary of the given release notes with the given LLM call. If the call fails, the user is prompted
+/// to enter the summary manually, to use the truncated release notes or to retry,
+/// so that the proposal generation does not fail because of an unavailable LLM.
+async fn get_summary_with_fallback<F, Fut>(
+    ctx: &Context,
+    step: &'static str,
+    notes: &str,
+    create: F,
+) -> Result<String, InputError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<String, SummaryError>>,
+{
+    loop {
+        let error = match with_deadline(step, create()).await {
+            Ok(Ok(summary)) => return Ok(summary),
+            Ok(Err(e)) => e.to_string(),
+            Err(e) => e.to_string(),
+        };
+
+        println!("WARNING: failed to {}: {}", step, error);
+        match inputs::choose_summary_fallback(ctx.inputs.as_ref())? {
+            SummaryFallback::Manual => return inputs::get_manual_summary(ctx.inputs.as_ref()),
+            SummaryFallback::ReleaseNotes => return Ok(get_truncated_notes(notes)),
+            SummaryFallback::Retry => continue,
+        }
+    }
+}
+
+/// Returns the current estimate of the time, at which the upgrade height of the given helper is reached.
+pub async fn get_estimated_upgrade_time(
+    ctx: &Context,
+    helper: &UpgradeHelper,
+) -> Result<DateTime<Utc>, BlockError> {
+    let provider = ctx.block_provider(helper.network)?;
+    let estimate = get_block_time_estimate(&provider).await?;
+
+    estimate.time_at(helper.upgrade_height)
+}
+
 /// Returns the voting period duration based on the network.
 pub fn get_voting_period(network: Network) -> Duration {
     match network {
@@ -284,12 +1548,3 @@ pub fn get_voting_period(network: Network) -> Duration {
         Network::Mainnet => Duration::hours(120),
     }
 }
-
-/// Returns the chain ID based on the network.
-fn get_chain_id(network: Network) -> String {
-    match network {
-        Network::LocalNode => "evmos_9000-4".to_string(),
-        Network::Testnet => "evmos_9000-4".to_string(),
-        Network::Mainnet => "evmos_9001-2".to_string(),
-    }
-}
@@ -0,0 +1,328 @@
+use crate::chain::get_config_dir;
+use crate::errors::HistoryError;
+use crate::helper::UpgradeHelper;
+use crate::network::Network;
+use crate::utils::write_atomically;
+use chrono::{DateTime, Datelike, Utc};
+use num_format::ToFormattedString;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The name of the file inside of the proposer configuration directory,
+/// which contains the record of all generated upgrades.
+const HISTORY_FILE: &str = "history.json";
+
+/// Represents a generated upgrade and, once known, its on-chain proposal and execution.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UpgradeRecord {
+    /// The network of the upgrade.
+    pub network: Network,
+    /// The version that was upgraded from.
+    pub previous_version: String,
+    /// The version that was upgraded to.
+    pub target_version: String,
+    /// The scheduled upgrade height.
+    pub upgrade_height: u64,
+    /// The estimated time at which the upgrade height is reached.
+    pub estimated_time: DateTime<Utc>,
+    /// The time at which the proposal was generated.
+    pub generated_at: DateTime<Utc>,
+    /// The ID of the on-chain proposal.
+    #[serde(default)]
+    pub proposal_id: Option<u64>,
+    /// The time of the block at the upgrade height, once it was reached.
+    #[serde(default)]
+    pub executed_at: Option<DateTime<Utc>>,
+}
+
+impl UpgradeRecord {
+    /// Returns the difference between the actual and the estimated upgrade time in minutes,
+    /// if the upgrade was executed. Positive values mean that the upgrade happened later than estimated.
+    pub fn prediction_error_minutes(&self) -> Option<i64> {
+        self.executed_at
+            .map(|executed_at| (executed_at - self.estimated_time).num_minutes())
+    }
+}
+
+/// Contains the aggregated statistics over all recorded upgrades.
+#[derive(Debug, PartialEq)]
+pub struct HistoryStats {
+    /// The number of recorded upgrades.
+    pub n_upgrades: usize,
+    /// The average number of proposals per calendar quarter, between the first and last recorded upgrade.
+    pub proposals_per_quarter: f64,
+    /// The average absolute prediction error of the executed upgrades in minutes.
+    pub average_prediction_error_minutes: Option<f64>,
+}
+
+/// Represents the record of all generated upgrades.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    /// The recorded upgrades in the order they were generated.
+    pub records: Vec<UpgradeRecord>,
+}
+
+impl History {
+    /// Loads the history from the given file.
+    /// If the file does not exist yet, an empty history is returned.
+    pub fn load(path: &Path) -> Result<History, HistoryError> {
+        if !path.exists() {
+            return Ok(History::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the history to the given file.
+    pub fn save(&self, path: &Path) -> Result<(), HistoryError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        Ok(write_atomically(
+            path,
+            serde_json::to_string_pretty(self)?.as_bytes(),
+        )?)
+    }
+
+    /// Records the generated upgrade of the given helper.
+    /// If an upgrade to the same version on the same network was already recorded,
+    /// it is replaced because the proposal was regenerated.
+    pub fn record_generated(&mut self, helper: &UpgradeHelper, generated_at: DateTime<Utc>) {
+        let record = UpgradeRecord {
+            network: helper.network,
+            previous_version: helper.previous_version.clone(),
+            target_version: helper.target_version.clone(),
+            upgrade_height: helper.upgrade_height,
+            estimated_time: helper.upgrade_time,
+            generated_at,
+            proposal_id: None,
+            executed_at: None,
+        };
+
+        match self.find_mut(helper.network, &helper.target_version) {
+            Some(existing) => *existing = record,
+            None => self.records.push(record),
+        }
+    }
+
+    /// Sets the proposal ID for the recorded upgrade to the given version on the given network.
+    /// Returns false if no such upgrade was recorded.
+    pub fn set_proposal_id(
+        &mut self,
+        network: Network,
+        target_version: &str,
+        proposal_id: u64,
+    ) -> bool {
+        match self.find_mut(network, target_version) {
+            Some(record) => {
+                record.proposal_id = Some(proposal_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the recorded upgrade to the given version on the given network.
+    pub fn get(&self, network: Network, target_version: &str) -> Option<&UpgradeRecord> {
+        self.records
+            .iter()
+            .find(|record| record.network == network && record.target_version == target_version)
+    }
+
+    /// Returns the mutable recorded upgrade to the given version on the given network.
+    fn find_mut(&mut self, network: Network, target_version: &str) -> Option<&mut UpgradeRecord> {
+        self.records
+            .iter_mut()
+            .find(|record| record.network == network && record.target_version == target_version)
+    }
+
+    /// Returns the aggregated statistics over all recorded upgrades.
+    pub fn stats(&self) -> HistoryStats {
+        let quarters: Vec<i32> = self
+            .records
+            .iter()
+            .map(|record| get_quarter_index(record.estimated_time))
+            .collect();
+        let proposals_per_quarter = match (quarters.iter().min(), quarters.iter().max()) {
+            (Some(first), Some(last)) => self.records.len() as f64 / (last - first + 1) as f64,
+            _ => 0.0,
+        };
+
+        let errors: Vec<i64> = self
+            .records
+            .iter()
+            .filter_map(|record| record.prediction_error_minutes())
+            .map(|error| error.abs())
+            .collect();
+        let average_prediction_error_minutes = match errors.is_empty() {
+            true => None,
+            false => Some(errors.iter().sum::<i64>() as f64 / errors.len() as f64),
+        };
+
+        HistoryStats {
+            n_upgrades: self.records.len(),
+            proposals_per_quarter,
+            average_prediction_error_minutes,
+        }
+    }
+
+    /// Renders the recorded upgrades as a table followed by the aggregated statistics.
+    pub fn render(&self) -> String {
+        let mut lines = vec![format!(
+            "{:<12} {:<10} {:<14} {:>12} {:<20} {:>8} {:>10}",
+            "Network", "From", "To", "Height", "Estimated Time", "Proposal", "Error"
+        )];
+
+        for record in self.records.iter() {
+            lines.push(format!(
+                "{:<12} {:<10} {:<14} {:>12} {:<20} {:>8} {:>10}",
+                record.network.to_string(),
+                record.previous_version,
+                record.target_version,
+                record
+                    .upgrade_height
+                    .to_formatted_string(&num_format::Locale::en),
+                record.estimated_time.format("%Y-%m-%d %H:%M").to_string(),
+                record
+                    .proposal_id
+                    .map_or("-".to_string(), |id| format!("#{}", id)),
+                record
+                    .prediction_error_minutes()
+                    .map_or("-".to_string(), |error| format!("{:+} min", error)),
+            ));
+        }
+
+        let stats = self.stats();
+        lines.push(String::new());
+        lines.push(format!("Recorded upgrades:         {}", stats.n_upgrades));
+        lines.push(format!(
+            "Proposals per quarter:     {:.1}",
+            stats.proposals_per_quarter
+        ));
+        lines.push(format!(
+            "Average prediction error:  {}",
+            stats
+                .average_prediction_error_minutes
+                .map_or("-".to_string(), |error| format!("{:.1} min", error))
+        ));
+
+        lines.join("\n")
+    }
+}
+
+/// Returns the path to the history file inside of the proposer configuration directory.
+pub fn get_history_path() -> PathBuf {
+    get_config_dir().join(HISTORY_FILE)
+}
+
+/// Returns a continuous index of the calendar quarter of the given time.
+fn get_quarter_index(time: DateTime<Utc>) -> i32 {
+    time.year() * 4 + (time.month0() / 3) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+    use test_dir::{DirBuilder, TestDir};
+
+    /// Returns an upgrade helper for testing with the given target version and upgrade time.
+    fn get_helper(target_version: &str, upgrade_time: DateTime<Utc>) -> UpgradeHelper {
+        UpgradeHelper::new(
+            PathBuf::from("/root/.evmosd"),
+            Network::Mainnet,
+            "v15.0.0",
+            target_version,
+            upgrade_time,
+            18_798_000,
+            "",
+        )
+    }
+
+    #[test]
+    fn test_record_generated() {
+        let upgrade_time = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        let mut history = History::default();
+
+        history.record_generated(&get_helper("v16.0.0", upgrade_time), upgrade_time);
+        assert!(history.set_proposal_id(Network::Mainnet, "v16.0.0", 252));
+        assert!(!history.set_proposal_id(Network::Testnet, "v16.0.0", 253));
+
+        // NOTE: regenerating the proposal replaces the existing record
+        let mut helper = get_helper("v16.0.0", upgrade_time);
+        helper.upgrade_height = 18_800_000;
+        history.record_generated(&helper, upgrade_time);
+
+        assert_eq!(history.records.len(), 1);
+        assert_eq!(history.records[0].upgrade_height, 18_800_000);
+        assert_eq!(history.records[0].proposal_id, None);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut history = History::default();
+        for (version, time) in [
+            (
+                "v14.0.0",
+                Utc.with_ymd_and_hms(2023, 8, 1, 16, 0, 0).unwrap(),
+            ),
+            (
+                "v15.0.0",
+                Utc.with_ymd_and_hms(2023, 11, 1, 16, 0, 0).unwrap(),
+            ),
+            (
+                "v16.0.0",
+                Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap(),
+            ),
+        ] {
+            history.record_generated(&get_helper(version, time), time);
+        }
+        history.records[0].executed_at =
+            Some(history.records[0].estimated_time + Duration::minutes(10));
+        history.records[1].executed_at =
+            Some(history.records[1].estimated_time - Duration::minutes(20));
+
+        assert_eq!(history.records[1].prediction_error_minutes(), Some(-20));
+        assert_eq!(
+            history.stats(),
+            HistoryStats {
+                n_upgrades: 3,
+                proposals_per_quarter: 1.0,
+                average_prediction_error_minutes: Some(15.0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        assert_eq!(
+            History::default().stats(),
+            HistoryStats {
+                n_upgrades: 0,
+                proposals_per_quarter: 0.0,
+                average_prediction_error_minutes: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_load_and_save() {
+        let test_dir = TestDir::temp();
+        let path = test_dir.path("proposer/history.json");
+
+        let history = History::load(&path).expect("failed to load missing history");
+        assert_eq!(history, History::default());
+
+        let upgrade_time = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        let mut history = History::default();
+        history.record_generated(&get_helper("v16.0.0", upgrade_time), upgrade_time);
+        history.save(&path).expect("failed to save history");
+
+        assert_eq!(
+            History::load(&path).expect("failed to load history"),
+            history
+        );
+    }
+}
@@ -1,15 +1,208 @@
-use reqwest::get as getReqwest;
+use crate::cache::DiskCache;
+use crate::errors::HttpError;
+use chrono::Utc;
+use reqwest::{header::CONTENT_TYPE, Certificate, Client, Proxy, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
 use url::Url;
 
+/// The maximum number of characters of a response body, which are included in error messages.
+const SNIPPET_LENGTH: usize = 200;
+
+/// The environment variable containing the proxy to use for all requests.
+/// If not set, the standard `HTTP_PROXY` and `HTTPS_PROXY` variables are respected.
+const PROXY_VAR: &str = "PROPOSER_PROXY";
+
+/// The environment variable containing the path to a PEM encoded CA certificate,
+/// which is trusted in addition to the system certificates.
+const CA_CERT_VAR: &str = "PROPOSER_CA_CERT";
+
+/// The environment variable containing the request timeout in seconds.
+const TIMEOUT_VAR: &str = "PROPOSER_HTTP_TIMEOUT";
+
+/// The default request timeout in seconds.
+const DEFAULT_TIMEOUT_SECONDS: u64 = 30;
+
+/// The shared HTTP client, which is built once from the environment.
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// Contains the configuration of the shared HTTP client.
+#[derive(Debug, PartialEq)]
+pub struct HttpConfig {
+    /// The proxy to use for all requests.
+    pub proxy: Option<String>,
+    /// The path to an additional CA certificate.
+    pub ca_cert: Option<PathBuf>,
+    /// The timeout of a single request.
+    pub timeout: Duration,
+}
+
+impl HttpConfig {
+    /// Reads the HTTP configuration from the environment.
+    pub fn from_env() -> Result<HttpConfig, HttpError> {
+        HttpConfig::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    /// Reads the HTTP configuration using the given lookup of variables.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<HttpConfig, HttpError> {
+        let timeout = match lookup(TIMEOUT_VAR) {
+            Some(timeout) => timeout
+                .parse::<u64>()
+                .map_err(|_| HttpError::Config(format!("invalid {}: {}", TIMEOUT_VAR, timeout)))?,
+            None => DEFAULT_TIMEOUT_SECONDS,
+        };
+
+        Ok(HttpConfig {
+            proxy: lookup(PROXY_VAR),
+            ca_cert: lookup(CA_CERT_VAR).map(PathBuf::from),
+            timeout: Duration::from_secs(timeout),
+        })
+    }
+}
+
+/// Builds a HTTP client with the given configuration.
+pub fn build_client(config: &HttpConfig) -> Result<Client, HttpError> {
+    let mut builder = Client::builder()
+        .timeout(config.timeout)
+        .user_agent(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ));
+
+    if let Some(proxy) = &config.proxy {
+        builder = builder.proxy(Proxy::all(proxy)?);
+    }
+
+    if let Some(ca_cert) = &config.ca_cert {
+        let pem = std::fs::read(ca_cert).map_err(HttpError::ReadCertificate)?;
+        builder = builder.add_root_certificate(Certificate::from_pem(&pem)?);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Returns the shared HTTP client, which is configured from the environment on first use.
+pub fn get_client() -> Result<&'static Client, HttpError> {
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+
+    let client = build_client(&HttpConfig::from_env()?)?;
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+/// Sends a GET request to the given URL using the shared HTTP client.
+async fn get(url: Url) -> Result<Response, HttpError> {
+    Ok(get_client()?.get(url).send().await?)
+}
+
 // Queries the given URL and returns the response body.
-pub async fn get_body(url: Url) -> reqwest::Result<String> {
-    getReqwest(url).await?.text().await
+// Unsuccessful status codes and HTML error pages are returned as errors, which contain a snippet of the body.
+pub async fn get_body(url: Url) -> Result<String, HttpError> {
+    let response = check_status(url.clone(), get(url.clone()).await?).await?;
+    let is_html = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("text/html"));
+
+    let body = response.text().await?;
+    if is_html {
+        return Err(HttpError::UnexpectedHtml(
+            url.to_string(),
+            get_snippet(&body),
+        ));
+    }
+
+    Ok(body)
+}
+
+// Queries the given URL and returns the response body of a web page.
+// In contrast to `get_body`, HTML responses are expected here.
+pub async fn get_page(url: Url) -> Result<String, HttpError> {
+    Ok(check_status(url.clone(), get(url).await?)
+        .await?
+        .text()
+        .await?)
+}
+
+// Queries the given URL like `get_page`, but returns successful responses from the disk cache
+// if they were received recently. This is used for link checks, which are repeated across runs.
+pub async fn get_page_cached(url: Url) -> Result<String, HttpError> {
+    get_page_with_cache(url, &DiskCache::from_env()?).await
+}
+
+/// Returns the page at the given URL from the given cache or queries it and stores the response.
+pub async fn get_page_with_cache(url: Url, cache: &DiskCache) -> Result<String, HttpError> {
+    if let Some(body) = cache.get(url.as_str(), Utc::now()) {
+        return Ok(body);
+    }
+
+    let body = get_page(url.clone()).await?;
+    // NOTE: failing to store the response only means that it is queried again next time
+    if let Err(e) = cache.put(url.as_str(), &body, Utc::now()) {
+        println!("WARNING: failed to cache response from {}: {}", url, e);
+    }
+
+    Ok(body)
+}
+
+// Queries the given URL and deserializes the JSON response body.
+// If the body does not match the expected schema, the error contains a snippet of the body.
+pub async fn get_json<T: DeserializeOwned>(url: Url) -> Result<T, HttpError> {
+    let body = get_body(url.clone()).await?;
+
+    serde_json::from_str(&body)
+        .map_err(|e| HttpError::Deserialize(url.to_string(), e, get_snippet(&body)))
+}
+
+// Queries the given URL and returns the raw response body, e.g. for binary files.
+pub async fn get_bytes(url: Url) -> Result<Vec<u8>, HttpError> {
+    Ok(check_status(url.clone(), get(url).await?)
+        .await?
+        .bytes()
+        .await?
+        .to_vec())
+}
+
+/// Checks the status code of the given response and maps common upstream failures
+/// to dedicated errors.
+async fn check_status(url: Url, response: Response) -> Result<Response, HttpError> {
+    match response.status() {
+        status if status.is_success() => Ok(response),
+        StatusCode::TOO_MANY_REQUESTS => Err(HttpError::RateLimited(url.to_string())),
+        status @ (StatusCode::BAD_GATEWAY
+        | StatusCode::SERVICE_UNAVAILABLE
+        | StatusCode::GATEWAY_TIMEOUT) => Err(HttpError::Upstream(url.to_string(), status)),
+        status => Err(HttpError::Status(
+            url.to_string(),
+            status,
+            get_snippet(&response.text().await.unwrap_or_default()),
+        )),
+    }
+}
+
+/// Returns the beginning of the given body with collapsed whitespace, which is used in error messages.
+fn get_snippet(body: &str) -> String {
+    let collapsed = body.split_whitespace().collect::<Vec<&str>>().join(" ");
+    match collapsed.char_indices().nth(SNIPPET_LENGTH) {
+        Some((idx, _)) => format!("{}...", &collapsed[..idx]),
+        None => collapsed,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use serde_json::Value;
+    use test_dir::{DirBuilder, TestDir};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
+    #[allow(clippy::bool_assert_comparison)]
     #[tokio::test]
     async fn test_get_body_pass() {
         let url = Url::parse("https://httpbin.org/get").unwrap();
@@ -17,10 +210,157 @@ mod tests {
         assert_eq!(res.is_ok(), true, "the request should be successful");
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[tokio::test]
     async fn test_get_body_fail() {
         let url = Url::parse("https://invalidurl.org/get").unwrap();
         let res = get_body(url).await;
         assert_eq!(res.is_err(), true);
     }
+
+    #[tokio::test]
+    async fn test_get_body_upstream_errors() {
+        let mock_server = MockServer::start().await;
+        for (endpoint, template) in [
+            ("/rate-limited", ResponseTemplate::new(429)),
+            ("/bad-gateway", ResponseTemplate::new(502)),
+            (
+                "/not-found",
+                ResponseTemplate::new(404).set_body_string("not found"),
+            ),
+            (
+                "/html",
+                ResponseTemplate::new(200)
+                    .set_body_raw("<html>\n  <body>Maintenance</body>\n</html>", "text/html"),
+            ),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(endpoint))
+                .respond_with(template)
+                .mount(&mock_server)
+                .await;
+        }
+
+        let url = |endpoint: &str| {
+            Url::parse(&mock_server.uri())
+                .unwrap()
+                .join(endpoint)
+                .unwrap()
+        };
+
+        assert!(matches!(
+            get_body(url("/rate-limited")).await,
+            Err(HttpError::RateLimited(_))
+        ));
+        assert!(matches!(
+            get_body(url("/bad-gateway")).await,
+            Err(HttpError::Upstream(_, StatusCode::BAD_GATEWAY))
+        ));
+        assert!(matches!(
+            get_body(url("/not-found")).await,
+            Err(HttpError::Status(_, StatusCode::NOT_FOUND, snippet)) if snippet == "not found"
+        ));
+        assert!(matches!(
+            get_body(url("/html")).await,
+            Err(HttpError::UnexpectedHtml(_, snippet)) if snippet == "<html> <body>Maintenance</body> </html>"
+        ));
+        assert!(get_page(url("/html")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_get_json_invalid_schema() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("upstream connect error"))
+            .mount(&mock_server)
+            .await;
+
+        let url = Url::parse(&mock_server.uri())
+            .unwrap()
+            .join("/json")
+            .unwrap();
+        assert!(matches!(
+            get_json::<Value>(url).await,
+            Err(HttpError::Deserialize(_, _, snippet)) if snippet == "upstream connect error"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_page_with_cache() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/discussion"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<html>Upgrade</html>"))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let test_dir = TestDir::temp();
+        let cache = DiskCache::new(test_dir.path("cache"), chrono::Duration::minutes(10));
+        let url = Url::parse(&mock_server.uri())
+            .unwrap()
+            .join("/discussion")
+            .unwrap();
+
+        for _ in 0..2 {
+            assert_eq!(
+                get_page_with_cache(url.clone(), &cache)
+                    .await
+                    .expect("failed to get page"),
+                "<html>Upgrade</html>"
+            );
+        }
+    }
+
+    #[test]
+    fn test_http_config_from_lookup() {
+        let config = HttpConfig::from_lookup(|name| match name {
+            PROXY_VAR => Some("http://proxy.internal:3128".to_string()),
+            TIMEOUT_VAR => Some("5".to_string()),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!(
+            config,
+            HttpConfig {
+                proxy: Some("http://proxy.internal:3128".to_string()),
+                ca_cert: None,
+                timeout: Duration::from_secs(5),
+            }
+        );
+        assert!(build_client(&config).is_ok());
+
+        let config = HttpConfig::from_lookup(|_| None).unwrap();
+        assert_eq!(config.timeout, Duration::from_secs(DEFAULT_TIMEOUT_SECONDS));
+
+        assert!(
+            HttpConfig::from_lookup(|name| (name == TIMEOUT_VAR).then(|| "soon".to_string()))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_build_client_missing_ca_cert() {
+        let config = HttpConfig {
+            proxy: None,
+            ca_cert: Some(PathBuf::from("/nonexistent/ca.pem")),
+            timeout: Duration::from_secs(1),
+        };
+
+        assert!(matches!(
+            build_client(&config),
+            Err(HttpError::ReadCertificate(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_snippet() {
+        assert_eq!(get_snippet("a\n  b"), "a b");
+        assert_eq!(
+            get_snippet(&"x".repeat(300)),
+            format!("{}...", "x".repeat(200))
+        );
+    }
 }
@@ -0,0 +1,250 @@
+use crate::errors::IbcError;
+use crate::{http::get_json, network::Network, utils::parse_proto_duration};
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The REST endpoint for querying all IBC client states.
+const CLIENT_STATES_ENDPOINT: &str = "/ibc/core/client/v1/client_states";
+
+/// The REST endpoint for querying the consensus states of an IBC client.
+const CONSENSUS_STATES_ENDPOINT: &str = "/ibc/core/client/v1/consensus_states/";
+
+/// The buffer after the upgrade time, during which clients should not expire.
+/// This accounts for the chain downtime during the upgrade, where relayers cannot update any clients.
+pub const UPGRADE_BUFFER_HOURS: i64 = 48;
+
+/// Represents an IBC client of a major counterparty chain, that would expire
+/// before the end of the upgrade window.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ExpiringClient {
+    /// The client ID on the configured chain.
+    pub client_id: String,
+    /// The chain ID of the counterparty chain.
+    pub chain_id: String,
+    /// The estimated time of expiry of the client.
+    pub expiry: DateTime<Utc>,
+}
+
+/// Represents the relevant information from the client states query response.
+#[derive(Serialize, Deserialize)]
+struct ClientStatesResponse {
+    client_states: Vec<IdentifiedClientState>,
+}
+
+/// Represents a client state together with its client ID.
+#[derive(Serialize, Deserialize)]
+struct IdentifiedClientState {
+    client_id: String,
+    client_state: ClientState,
+}
+
+/// Represents the relevant fields of a Tendermint light client state.
+/// Other light client types (e.g. solo machines) don't contain these fields.
+#[derive(Serialize, Deserialize)]
+struct ClientState {
+    chain_id: Option<String>,
+    trusting_period: Option<String>,
+    latest_height: Option<Height>,
+}
+
+/// Represents an IBC height.
+#[derive(Serialize, Deserialize)]
+struct Height {
+    revision_number: String,
+    revision_height: String,
+}
+
+/// Represents the relevant information from the consensus state query response.
+#[derive(Serialize, Deserialize)]
+struct ConsensusStateResponse {
+    consensus_state: ConsensusState,
+}
+
+/// Represents the relevant fields of a Tendermint consensus state.
+#[derive(Serialize, Deserialize)]
+struct ConsensusState {
+    timestamp: String,
+}
+
+/// Returns the chain IDs of the major counterparty chains for the given network.
+fn get_major_counterparties(network: Network) -> Vec<&'static str> {
+    match network {
+        Network::LocalNode => vec![],
+        Network::Testnet => vec![
+            "theta-testnet-001",
+            "osmo-test-5",
+            "axelar-testnet-lisbon-3",
+        ],
+        Network::Mainnet => vec![
+            "cosmoshub-4",
+            "osmosis-1",
+            "axelar-dojo-1",
+            "noble-1",
+            "stride-1",
+            "juno-1",
+        ],
+    }
+}
+
+/// Returns the IBC clients of major counterparty chains, that would expire before the given deadline
+/// if they are not updated in time.
+pub async fn get_expiring_clients(
+    base_url: &Url,
+    network: Network,
+    deadline: DateTime<Utc>,
+) -> Result<Vec<ExpiringClient>, IbcError> {
+    let counterparties = get_major_counterparties(network);
+    let client_states = get_client_states(base_url).await?;
+
+    let mut expiring_clients = Vec::new();
+    for identified_state in client_states {
+        let state = identified_state.client_state;
+        let (Some(chain_id), Some(trusting_period), Some(height)) =
+            (state.chain_id, state.trusting_period, state.latest_height)
+        else {
+            continue;
+        };
+
+        if !counterparties.contains(&chain_id.as_str()) {
+            continue;
+        }
+
+        let last_update =
+            get_consensus_state_time(base_url, &identified_state.client_id, &height).await?;
+        let expiry = last_update + parse_trusting_period(&trusting_period)?;
+
+        if expiry < deadline {
+            expiring_clients.push(ExpiringClient {
+                client_id: identified_state.client_id,
+                chain_id,
+                expiry,
+            });
+        }
+    }
+
+    Ok(expiring_clients)
+}
+
+/// Returns the deadline until which no major IBC client should expire for the given upgrade time.
+pub fn get_expiry_deadline(upgrade_time: DateTime<Utc>) -> DateTime<Utc> {
+    upgrade_time + Duration::hours(UPGRADE_BUFFER_HOURS)
+}
+
+/// Gets all IBC client states from the configured chain.
+async fn get_client_states(base_url: &Url) -> Result<Vec<IdentifiedClientState>, IbcError> {
+    let mut url = base_url.join(CLIENT_STATES_ENDPOINT)?;
+    url.query_pairs_mut()
+        .append_pair("pagination.limit", "1000");
+
+    let response: ClientStatesResponse = get_json(url).await?;
+
+    Ok(response.client_states)
+}
+
+/// Gets the time of the consensus state for the given client at the given height.
+/// This corresponds to the last time the client was updated.
+async fn get_consensus_state_time(
+    base_url: &Url,
+    client_id: &str,
+    height: &Height,
+) -> Result<DateTime<Utc>, IbcError> {
+    let url = base_url.join(
+        format!(
+            "{}{}/revision/{}/height/{}",
+            CONSENSUS_STATES_ENDPOINT, client_id, height.revision_number, height.revision_height
+        )
+        .as_str(),
+    )?;
+
+    let response: ConsensusStateResponse = get_json(url).await?;
+
+    Ok(DateTime::parse_from_rfc3339(&response.consensus_state.timestamp)?.with_timezone(&Utc))
+}
+
+/// Parses the trusting period from the protobuf JSON duration format (e.g. `1209600s`).
+fn parse_trusting_period(trusting_period: &str) -> Result<Duration, IbcError> {
+    parse_proto_duration(trusting_period)
+        .ok_or(IbcError::TrustingPeriod(trusting_period.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::Value;
+    use std::str::FromStr;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Sets up a mock server to return the IBC client states and the consensus states
+    /// for the contained Tendermint clients.
+    async fn setup_mock_api() -> MockServer {
+        let client_states: Value =
+            serde_json::from_str(include_str!("testdata/ibc_client_states.json"))
+                .expect("failed to parse client states JSON");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(CLIENT_STATES_ENDPOINT))
+            .respond_with(ResponseTemplate::new(200).set_body_json(client_states))
+            .mount(&mock_server)
+            .await;
+
+        for (client_id, height, timestamp) in [
+            ("07-tendermint-0", "18798834", "2024-01-01T12:00:00Z"),
+            (
+                "07-tendermint-3",
+                "13400000",
+                "2024-01-06T12:00:00.123456789Z",
+            ),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!(
+                    "{}{}/revision/4/height/{}",
+                    CONSENSUS_STATES_ENDPOINT, client_id, height
+                )))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "consensus_state": {"timestamp": timestamp}
+                })))
+                .mount(&mock_server)
+                .await;
+        }
+
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_get_expiring_clients() {
+        let mock_server = setup_mock_api().await;
+        let mock_path =
+            Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
+
+        // NOTE: the trusting period of both clients is 14 days, so only the client
+        // updated on January 1st expires before the deadline.
+        let deadline = Utc.with_ymd_and_hms(2024, 1, 17, 0, 0, 0).unwrap();
+        let res = get_expiring_clients(&mock_path, Network::Mainnet, deadline).await;
+        assert!(res.is_ok(), "expected no error; got: {}", res.unwrap_err());
+
+        let clients = res.unwrap();
+        assert_eq!(clients.len(), 1, "expected one expiring client");
+        assert_eq!(clients[0].client_id, "07-tendermint-0");
+        assert_eq!(clients[0].chain_id, "cosmoshub-4");
+        assert_eq!(
+            clients[0].expiry,
+            Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap(),
+            "expected different expiry"
+        );
+    }
+
+    #[test]
+    fn test_parse_trusting_period() {
+        assert_eq!(
+            parse_trusting_period("1209600s").unwrap(),
+            Duration::days(14)
+        );
+        assert!(parse_trusting_period("1209600").is_err());
+        assert!(parse_trusting_period("abcs").is_err());
+    }
+}
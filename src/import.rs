@@ -0,0 +1,166 @@
+use crate::errors::ImportError;
+use crate::gov::{ProposalId, UpgradePlan};
+use crate::helper::UpgradeHelper;
+use crate::network::Network;
+use crate::version::is_valid_version;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::path::PathBuf;
+
+/// Contains the information, which is not part of the on-chain proposal
+/// and is needed to reconstruct the upgrade helper.
+pub struct ImportOptions {
+    /// The network, on which the proposal was submitted.
+    pub network: Network,
+    /// The ID of the on-chain proposal.
+    pub proposal_id: u64,
+    /// The home directory of the Evmos binary.
+    pub evmosd_home: PathBuf,
+    /// The version to upgrade from, which is detected from the description if not given.
+    pub previous_version: Option<String>,
+    /// The (estimated) time of reaching the upgrade height.
+    pub upgrade_time: DateTime<Utc>,
+}
+
+/// Reconstructs the upgrade helper from the upgrade plan of an on-chain proposal,
+/// so that follow-up artifacts can be generated for proposals created by someone else.
+///
+/// The target version is taken from the plan name, while the previous version, the summary
+/// and the Commonwealth link are detected from the proposal description if it was generated
+/// with the proposal template.
+pub fn get_helper_from_plan(
+    plan: &UpgradePlan,
+    options: ImportOptions,
+) -> Result<UpgradeHelper, ImportError> {
+    if !is_valid_version(&plan.name) {
+        return Err(ImportError::InvalidVersion(plan.name.clone()));
+    }
+
+    let previous_version = options
+        .previous_version
+        .or_else(|| get_previous_version(&plan.description))
+        .ok_or(ImportError::PreviousVersion(options.proposal_id))?;
+    if !is_valid_version(&previous_version) {
+        return Err(ImportError::InvalidVersion(previous_version));
+    }
+
+    let summary = get_features(&plan.description).unwrap_or_default();
+    let mut helper = UpgradeHelper::new(
+        options.evmosd_home,
+        options.network,
+        &previous_version,
+        &plan.name,
+        options.upgrade_time,
+        plan.height,
+        &summary,
+    );
+    if !plan.title.is_empty() {
+        helper.proposal_name = plan.title.clone();
+    }
+    helper.commonwealth_link = get_commonwealth_link(&plan.description);
+    helper.proposal_id = Some(ProposalId {
+        id: options.proposal_id,
+        confirmed: true,
+    });
+
+    Ok(helper)
+}
+
+/// Returns the previous version from the description, e.g. from
+/// `from its current version [v15.0.0](https://...) to [v16.0.0](https://...)`.
+fn get_previous_version(description: &str) -> Option<String> {
+    Regex::new(r"from its current version \[?(v\d+\.\d+\.\d+(?:-rc\d+)?)")
+        .expect("invalid regex")
+        .captures(description)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Returns the summary of the changes, which is contained in the impact section of the description.
+fn get_features(description: &str) -> Option<String> {
+    Regex::new(r"(?s)contains the following enhancements:\s*(.*?)\s*A full changelog")
+        .expect("invalid regex")
+        .captures(description)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Returns the link to the Commonwealth discussion contained in the description.
+fn get_commonwealth_link(description: &str) -> Option<String> {
+    Regex::new(r"\((https://commonwealth\.im/[^)\s]+)\)")
+        .expect("invalid regex")
+        .captures(description)
+        .map(|captures| captures[1].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// Returns the upgrade plan with the given description.
+    fn get_plan(description: &str) -> UpgradePlan {
+        UpgradePlan {
+            title: "Evmos Mainnet v16.0.0 Upgrade".to_string(),
+            description: description.to_string(),
+            name: "v16.0.0".to_string(),
+            height: 18_798_000,
+            info: String::new(),
+        }
+    }
+
+    /// Returns the import options with the given previous version.
+    fn get_options(previous_version: Option<&str>) -> ImportOptions {
+        ImportOptions {
+            network: Network::Mainnet,
+            proposal_id: 252,
+            evmosd_home: PathBuf::from("./.evmosd"),
+            previous_version: previous_version.map(str::to_string),
+            upgrade_time: Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_get_helper_from_plan() {
+        let description = "If successful, this proposal will schedule an Evmos Mainnet software upgrade \
+            at block height 18,798,000 from its current version [v15.0.5](https://github.com/evmos/evmos/releases/tag/v15.0.5) \
+            to [v16.0.0](https://github.com/evmos/evmos/releases/tag/v16.0.0).\n\n\
+            ## Impact\n\nEvmos v16.0.0 contains the following enhancements:\n\n\
+            - Add precompile\n- Fix bug\n\nA full changelog can be found [here](https://github.com).\n\n\
+            Please follow and discuss this proposal using the official \
+            [discussion on Commonwealth](https://commonwealth.im/evmos/discussion/1234).";
+
+        let helper = get_helper_from_plan(&get_plan(description), get_options(None))
+            .expect("failed to import helper");
+        assert_eq!(helper.previous_version, "v15.0.5");
+        assert_eq!(helper.target_version, "v16.0.0");
+        assert_eq!(helper.upgrade_height, 18_798_000);
+        assert_eq!(helper.summary, "- Add precompile\n- Fix bug");
+        assert_eq!(
+            helper.commonwealth_link.as_deref(),
+            Some("https://commonwealth.im/evmos/discussion/1234")
+        );
+        assert_eq!(
+            helper.proposal_id,
+            Some(ProposalId {
+                id: 252,
+                confirmed: true
+            })
+        );
+        assert_eq!(helper.config_file_name, "proposal-Mainnet-v16.0.0.json");
+    }
+
+    #[test]
+    fn test_get_helper_from_plan_without_template() {
+        let plan = get_plan("# Evmos v16.0.0 Upgrade\n\nUpgrade Evmos to v16.0.0.");
+
+        assert!(matches!(
+            get_helper_from_plan(&plan, get_options(None)),
+            Err(ImportError::PreviousVersion(252))
+        ));
+
+        let helper = get_helper_from_plan(&plan, get_options(Some("v15.0.0")))
+            .expect("failed to import helper");
+        assert_eq!(helper.previous_version, "v15.0.0");
+        assert_eq!(helper.summary, "");
+        assert_eq!(helper.commonwealth_link, None);
+    }
+}
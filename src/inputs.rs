@@ -1,68 +1,197 @@
 use crate::{
-    errors::{CommonwealthError::InvalidCommonwealthLink, InputError},
-    network::Network,
+    address::to_hex_address,
+    answers,
+    errors::{AnswersError, CommonwealthError::InvalidCommonwealthLink, ConfigError, InputError},
+    evmosd::detect_running_homes,
+    helper::{sniff_config, ConfigFormat, ConfigMetadata, ExistingArtifacts},
+    keys::KeyWithBalance,
+    locale::Locale,
+    messages::read_message,
+    network::{format_coin, Network},
+    schedule::{
+        rank_upgrade_times, Suggestion, TimezoneShare, DEFAULT_UPGRADE_HOUR, N_SUGGESTIONS,
+    },
+    upgrade_info::PlanInfo,
 };
 use chrono::{
-    DateTime, Datelike, Duration, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc, Weekday,
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+    Weekday,
 };
+use chrono_tz::{America, Asia, Europe, Tz};
+use clap::ValueEnum;
 use inquire::{validator::Validation::Valid, DateSelect, Select};
-use std::{fs, ops::Add, path::PathBuf};
-
-const MONTHS: [&str; 13] = [
-    "",
-    "January",
-    "February",
-    "March",
-    "April",
-    "May",
-    "June",
-    "July",
-    "August",
-    "September",
-    "October",
-    "November",
-    "December",
+use std::{fmt::Display, fs, ops::Add, path::PathBuf};
+
+/// The timezones in which the upgrade time is displayed, in addition to UTC.
+/// Most validators think in their local time, so the time is shown for the
+/// major regions that the validators are located in.
+pub const DISPLAY_TIMEZONES: [(&str, Tz); 3] = [
+    ("ET", America::New_York),
+    ("CET", Europe::Berlin),
+    ("KST", Asia::Seoul),
 ];
 
-/// Scans the current folder for existing proposal configurations (stored as JSON)
+/// Represents an option of a selection prompt, which is displayed together with
+/// additional metadata columns that are aligned across all options.
+struct SelectOption<T> {
+    /// The value that is returned when the option is selected.
+    value: T,
+    /// The displayed columns of the option.
+    columns: Vec<String>,
+}
+
+impl<T> Display for SelectOption<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.columns.join("  ").trim_end())
+    }
+}
+
+/// Creates the options for a selection prompt from the given values and their metadata columns.
+/// The columns are padded to the same width, so that they are displayed as a table.
+fn get_select_options<T>(rows: Vec<(T, Vec<String>)>) -> Vec<SelectOption<T>> {
+    let mut widths: Vec<usize> = Vec::new();
+    for (_, columns) in rows.iter() {
+        for (i, column) in columns.iter().enumerate() {
+            match widths.get_mut(i) {
+                Some(width) => *width = (*width).max(column.chars().count()),
+                None => widths.push(column.chars().count()),
+            }
+        }
+    }
+
+    rows.into_iter()
+        .map(|(value, columns)| SelectOption {
+            value,
+            columns: columns
+                .into_iter()
+                .zip(widths.iter())
+                .map(|(column, width)| format!("{:<width$}", column, width = width))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Checks if all characters of the input appear in the given option in the same order,
+/// ignoring the case, so that e.g. `mnv16` matches `proposal-Mainnet-v16.0.0.json`.
+fn fuzzy_filter<T>(input: &str, _: &T, option: &str, _: usize) -> bool {
+    let mut option_chars = option.chars().flat_map(char::to_lowercase);
+
+    input
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|c| option_chars.any(|o| o == c))
+}
+
+/// Prompts the user to select one of the given options, which can be searched
+/// by typing a fuzzy pattern.
+fn fuzzy_select<T>(message: &str, options: Vec<SelectOption<T>>) -> Result<T, InputError> {
+    Ok(Select::new(message, options)
+        .with_filter(&fuzzy_filter)
+        .prompt()?
+        .value)
+}
+
+/// Scans the current folder for existing proposal configurations (stored as JSON, TOML or YAML)
 /// and lets the user choose the desired configuration file to use.
-pub fn choose_config() -> Result<PathBuf, InputError> {
+///
+/// Files in a supported format, which are not upgrade configurations (e.g. exported proposal JSON files),
+/// are skipped. The configurations are listed with the most recently modified first and show
+/// the time of the last modification as well as the configured network and target version.
+pub fn choose_config(inputs: &dyn InputProvider) -> Result<PathBuf, InputError> {
+    if let Some(config) = answers::get_parsed::<PathBuf>(inputs, "config")? {
+        return Ok(config);
+    }
+
     let current_dir = std::env::current_dir()?;
 
     // Get all files in the current directory
     let paths = fs::read_dir(&current_dir)?;
 
-    // Filter for JSON files
-    let json_files = paths.filter(|path| {
-        path.as_ref()
-            .unwrap()
-            .path()
-            .to_str()
-            .unwrap()
-            .ends_with(".json")
-    });
-
-    // Collect the file names
-    let config_files: Vec<String> = json_files
-        .map(|file| file.unwrap().path().to_string_lossy().to_string())
+    // Filter for upgrade configurations in one of the supported formats
+    let mut config_files: Vec<(PathBuf, ConfigMetadata, Option<DateTime<Utc>>)> = paths
+        .filter_map(|path| path.ok().map(|path| path.path()))
+        .filter(|path| ConfigFormat::from_path(path).is_some())
+        .filter_map(|path| {
+            let metadata = sniff_config(&path)?;
+            let modified = fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(DateTime::<Utc>::from);
+            Some((path, metadata, modified))
+        })
         .collect();
 
     if config_files.is_empty() {
         return Err(InputError::NoConfigFiles(current_dir));
     }
 
+    config_files.sort_by(|(a_path, _, a_time), (b_path, _, b_time)| {
+        b_time.cmp(a_time).then_with(|| a_path.cmp(b_path))
+    });
+
+    let rows = config_files
+        .into_iter()
+        .map(|(path, metadata, modified)| {
+            let columns = vec![
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                modified.map_or("-".to_string(), |time| {
+                    time.format("%Y-%m-%d %H:%M").to_string()
+                }),
+                metadata.network.to_string(),
+                metadata.target_version,
+                metadata.created_by.unwrap_or_else(|| "-".to_string()),
+            ];
+            (path, columns)
+        })
+        .collect();
+
     // Prompt the user to select the configuration file
-    //
-    // FIXME: Why does the question mark operator not work here? It doesn't register the #[from] attribute in the error enum somehow?
-    match Select::new("Select configuration file", config_files).prompt() {
-        Ok(file) => Ok(current_dir.join(file)),
-        Err(e) => Err(InputError::UserInput(e)),
+    fuzzy_select("Select configuration file", get_select_options(rows))
+}
+
+/// Provides the values, which the sub-commands would otherwise prompt the user for,
+/// so that they can be run with predefined inputs.
+///
+/// Every prompt of this module asks the provider for the answer of its key (see `answers::PROMPT_KEYS`)
+/// first and only prompts the user in the terminal, if the provider does not answer it.
+pub trait InputProvider: Send + Sync {
+    /// Returns the answer of the prompt with the given key as text, if it is answered.
+    fn get_text(&self, key: &str) -> Result<Option<String>, AnswersError>;
+
+    /// Returns the answer of the prompt with the given key as a list of texts, if it is answered.
+    fn get_list(&self, key: &str) -> Result<Option<Vec<String>>, AnswersError>;
+}
+
+/// Prompts the user for all inputs in the terminal, which is used if no answers file is given.
+pub struct Prompt;
+
+impl InputProvider for Prompt {
+    fn get_text(&self, _key: &str) -> Result<Option<String>, AnswersError> {
+        Ok(None)
+    }
+
+    fn get_list(&self, _key: &str) -> Result<Option<Vec<String>>, AnswersError> {
+        Ok(None)
     }
 }
 
 /// Prompts the user to input the link to the Commonwealth proposal and checks if the input is a valid URL
 /// that points to the Commonwealth page.
-pub async fn choose_commonwealth_link() -> Result<String, InputError> {
+pub async fn choose_commonwealth_link(inputs: &dyn InputProvider) -> Result<String, InputError> {
+    if let Some(link) = answers::get_text(inputs, "commonwealth_link")? {
+        if !link.starts_with("https://commonwealth.im/evmos") {
+            return Err(AnswersError::InvalidValue(
+                "commonwealth_link".to_string(),
+                InvalidCommonwealthLink.to_string(),
+            )
+            .into());
+        }
+        return Ok(link);
+    }
+
     let link = inquire::Text::new("Enter the link to the Commonwealth proposal")
         .with_validator(|input: &str| {
             if input.starts_with("https://commonwealth.im/evmos") {
@@ -76,28 +205,133 @@ pub async fn choose_commonwealth_link() -> Result<String, InputError> {
     Ok(link)
 }
 
+/// Prompts the user for the JSON files of messages, which are added to the gov v1 proposal
+/// after the software upgrade message, e.g. a `MsgUpdateParams`.
+/// Each file is checked to contain a single message with its type URL.
+pub fn get_message_files(inputs: &dyn InputProvider) -> Result<Vec<PathBuf>, InputError> {
+    if let Some(paths) = answers::get_list(inputs, "message_files")? {
+        let message_files: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+        for path in message_files.iter() {
+            read_message(path).map_err(|e| {
+                AnswersError::InvalidValue("message_files".to_string(), e.to_string())
+            })?;
+        }
+        return Ok(message_files);
+    }
+
+    let mut message_files = Vec::new();
+
+    while inquire::Confirm::new("Add another message to the proposal?")
+        .with_default(false)
+        .prompt()?
+    {
+        let path = inquire::Text::new(&format!(
+            "Enter the path to the JSON file of message #{}",
+            message_files.len() + 2
+        ))
+        .with_validator(|input: &str| match read_message(&PathBuf::from(input)) {
+            Ok(_) => Ok(Valid),
+            Err(e) => Err(e.into()),
+        })
+        .prompt()?;
+
+        message_files.push(PathBuf::from(path));
+    }
+
+    Ok(message_files)
+}
+
 /// Prompts the user to select the network type used.
-pub fn get_used_network() -> Result<Network, InputError> {
-    let network_options = vec!["Local Node", "Testnet", "Mainnet"];
-    let chosen_network = Select::new("Select network", network_options).prompt()?;
-
-    // TODO: improve handling here! Should be more elegant to reverse the print stuff from the Network
-    // type.
-    let used_network = match chosen_network {
-        "Local Node" => Network::LocalNode,
-        "Testnet" => Network::Testnet,
-        "Mainnet" => Network::Mainnet,
-        &_ => {
-            return Err(InputError::InvalidNetwork(chosen_network.to_string()));
+/// The networks are listed with the chain IDs returned by the given function.
+pub fn get_used_network(
+    inputs: &dyn InputProvider,
+    get_chain_id: impl Fn(Network) -> Result<String, ConfigError>,
+) -> Result<Network, InputError> {
+    if let Some(network) = answers::get_choice::<Network>(inputs, "network")? {
+        return Ok(network);
+    }
+
+    let rows = Network::value_variants()
+        .iter()
+        .map(|network| Ok((*network, vec![network.to_string(), get_chain_id(*network)?])))
+        .collect::<Result<_, ConfigError>>()?;
+
+    fuzzy_select("Select network", get_select_options(rows))
+}
+
+/// Prompts the user to input the home path of the Evmos keyring.
+///
+/// The home directories of running `evmosd` processes and the existing default home directories
+/// are offered as selectable defaults. If there is at most one candidate, the path is entered
+/// as text with the candidate (or the default home of the network) as the default value.
+pub fn get_evmosd_home(
+    inputs: &dyn InputProvider,
+    network: &Network,
+) -> Result<PathBuf, InputError> {
+    if let Some(home) = answers::get_parsed::<PathBuf>(inputs, "home")? {
+        return Ok(home);
+    }
+
+    let default_path = get_default_evmosd_home(network);
+    let default_paths: Vec<PathBuf> = [network, &Network::LocalNode, &Network::Mainnet]
+        .into_iter()
+        .map(get_default_evmosd_home)
+        .filter(|path| path.exists())
+        .collect();
+    let candidates = get_home_candidates(detect_running_homes(), default_paths);
+
+    if candidates.len() > 1 {
+        let mut rows: Vec<(Option<PathBuf>, Vec<String>)> = candidates
+            .iter()
+            .map(|(path, source)| {
+                (
+                    Some(path.clone()),
+                    vec![path.display().to_string(), source.clone()],
+                )
+            })
+            .collect();
+        rows.push((None, vec!["Enter a different path".to_string()]));
+
+        if let Some(path) = fuzzy_select(
+            "Select the home path to your Evmos keyring",
+            get_select_options(rows),
+        )? {
+            return Ok(path);
         }
-    };
+    }
+
+    let default_path = candidates
+        .into_iter()
+        .next()
+        .map_or(default_path, |(path, _)| path);
+    let selected_option = inquire::Text::new("Enter the home path to your Evmos keyring")
+        .with_default(default_path.as_os_str().to_str().unwrap())
+        .prompt()?;
 
-    Ok(used_network)
+    Ok(PathBuf::from(selected_option))
 }
 
-/// Prompts the user to input the duration of the voting period.
-/// The duration is given in hours.
-pub fn get_evmosd_home(network: &Network) -> Result<PathBuf, InputError> {
+/// Returns the candidates for the home directory of the Evmos binary with the source they were
+/// detected from. The home directories of running nodes are listed first, duplicates are removed.
+fn get_home_candidates(
+    running_homes: Vec<PathBuf>,
+    default_paths: Vec<PathBuf>,
+) -> Vec<(PathBuf, String)> {
+    let mut candidates: Vec<(PathBuf, String)> = Vec::new();
+    let running = running_homes.into_iter().map(|path| (path, "running node"));
+    let defaults = default_paths.into_iter().map(|path| (path, "default"));
+
+    for (path, source) in running.chain(defaults) {
+        if !candidates.iter().any(|(candidate, _)| *candidate == path) {
+            candidates.push((path, source.to_string()));
+        }
+    }
+
+    candidates
+}
+
+/// Returns the default home directory of the Evmos binary for the given network.
+pub fn get_default_evmosd_home(network: &Network) -> PathBuf {
     let mut default_path = dirs::home_dir().expect("failed to get home directory");
 
     match network {
@@ -105,43 +339,90 @@ pub fn get_evmosd_home(network: &Network) -> Result<PathBuf, InputError> {
         _ => &default_path.push(".evmosd"),
     };
 
-    let selected_option = inquire::Text::new("Enter the home path to your Evmos keyring")
-        .with_default(default_path.as_os_str().to_str().unwrap())
-        .prompt()?;
+    default_path
+}
 
-    Ok(PathBuf::from(selected_option))
+/// Prompts the user to input some plain text, unless the prompt with the given key is answered.
+pub fn get_text(inputs: &dyn InputProvider, key: &str, prompt: &str) -> Result<String, InputError> {
+    match answers::get_text(inputs, key)? {
+        Some(answer) => Ok(answer),
+        None => Ok(inquire::Text::new(prompt).prompt()?),
+    }
 }
 
-/// Prompts the user to input some plain text.
-pub fn get_text(prompt: &str) -> Result<String, InputError> {
-    Ok(inquire::Text::new(prompt).prompt()?)
+/// Prompts the user to input some plain text, suggesting the given default value,
+/// unless the prompt with the given key is answered.
+pub fn get_text_with_default(
+    inputs: &dyn InputProvider,
+    key: &str,
+    prompt: &str,
+    default: &str,
+) -> Result<String, InputError> {
+    match answers::get_text(inputs, key)? {
+        Some(answer) => Ok(answer),
+        None => Ok(inquire::Text::new(prompt).with_default(default).prompt()?),
+    }
 }
 
 /// Prompts the user to input the date for the planned upgrade.
 /// The date is calculated based on the current time and the voting period duration.
+///
+/// If a distribution of the validator timezones is given, the user chooses the time from
+/// the hours when most validator operators are awake. Otherwise, 16:00 UTC is used.
 pub fn get_upgrade_time(
+    inputs: &dyn InputProvider,
     voting_period: Duration,
     utc_time: DateTime<Utc>,
+    validator_timezones: &[TimezoneShare],
 ) -> Result<DateTime<Utc>, InputError> {
     let default_date = calculate_planned_date(voting_period, utc_time);
 
     // Prompt the user to input the desired upgrade date
-    let date = DateSelect::new("Select date for the planned upgrade")
-        .with_min_date(utc_time.date_naive())
-        .with_default(default_date.date_naive())
-        .with_week_start(Weekday::Mon)
-        .prompt()?;
+    let date = match answers::get_parsed::<NaiveDate>(inputs, "upgrade_date")? {
+        Some(date) => date,
+        None => DateSelect::new("Select date for the planned upgrade")
+            .with_min_date(utc_time.date_naive())
+            .with_default(default_date.date_naive())
+            .with_week_start(Weekday::Mon)
+            .prompt()?,
+    };
 
-    let time = NaiveTime::from_hms_opt(16, 0, 0).unwrap();
+    let hour = answers::get_upgrade_hour(inputs)?;
+    if hour.is_none() && !validator_timezones.is_empty() {
+        return choose_suggested_time(date, validator_timezones);
+    }
+
+    let time = NaiveTime::from_hms_opt(hour.unwrap_or(DEFAULT_UPGRADE_HOUR), 0, 0).unwrap();
     let upgrade_time = NaiveDateTime::new(date, time);
 
     Ok(Utc.from_local_datetime(&upgrade_time).unwrap())
 }
 
+/// Prompts the user to choose the upgrade time on the given date from the hours,
+/// which are ranked highest by the share of validator operators that are awake.
+fn choose_suggested_time(
+    date: NaiveDate,
+    validator_timezones: &[TimezoneShare],
+) -> Result<DateTime<Utc>, InputError> {
+    let suggestions: Vec<Suggestion> = rank_upgrade_times(date, validator_timezones)?
+        .into_iter()
+        .take(N_SUGGESTIONS)
+        .collect();
+    let options: Vec<String> = suggestions.iter().map(Suggestion::describe).collect();
+
+    let choice = Select::new("Select time for the planned upgrade", options.clone()).prompt()?;
+    let index = options
+        .iter()
+        .position(|option| option == &choice)
+        .expect("selected option not found");
+
+    Ok(suggestions[index].time)
+}
+
 /// Calculates the date for the planned upgrade given the current time and the voting period duration.
 /// Per default, 4 pm UTC is used as a reference time.
 /// If the passed UTC time is after 2 pm UTC, the planned date will be shifted to the next day.
-fn calculate_planned_date(voting_period: Duration, utc_time: DateTime<Utc>) -> DateTime<Utc> {
+pub fn calculate_planned_date(voting_period: Duration, utc_time: DateTime<Utc>) -> DateTime<Utc> {
     let mut end_of_voting = utc_time.add(voting_period);
 
     // NOTE: if using the tool after 2pm UTC or the end of voting would be at or after 2 PM, the upgrade should happen on the next day
@@ -173,24 +454,299 @@ pub fn is_valid_upgrade_time(upgrade_time: DateTime<Utc>) -> bool {
     !matches!(upgrade_time.weekday(), Weekday::Sat | Weekday::Sun)
 }
 
-/// Returns a string representation of the upgrade time.
-pub fn get_time_string(time: DateTime<Utc>) -> String {
-    let (is_pm, hour) = time.hour12();
-    format!(
-        "{}{} {} on {}., {} {}., {}",
-        hour,
-        if is_pm { "PM" } else { "AM" },
-        time.timezone(),
-        time.weekday(),
-        MONTHS[time.month() as usize],
-        time.day(),
-        time.year(),
-    )
+/// Returns a string representation of the upgrade time in English.
+pub fn get_time_string<T: TimeZone>(time: DateTime<T>) -> String
+where
+    T::Offset: Display,
+{
+    Locale::En.format_time(time)
+}
+
+/// Returns the string representations of the given time in UTC and the configured display timezones
+/// in the given locale, together with the name of the corresponding timezone.
+pub fn get_time_strings_by_timezone(time: DateTime<Utc>, locale: Locale) -> Vec<(String, String)> {
+    let mut time_strings = vec![("UTC".to_string(), locale.format_time(time))];
+    for (name, timezone) in DISPLAY_TIMEZONES {
+        time_strings.push((
+            name.to_string(),
+            locale.format_time(time.with_timezone(&timezone)),
+        ));
+    }
+
+    time_strings
 }
 
 /// Lets the user choose the desired key to use.
-pub fn get_key(keys: Vec<String>) -> Result<String, InputError> {
-    Ok(Select::new("Select key to submit proposal", keys).prompt()?)
+/// The keys are listed with their address, the corresponding hex address on the EVM
+/// and the balance in the given denomination of the fees.
+pub fn get_key(
+    inputs: &dyn InputProvider,
+    keys: Vec<KeyWithBalance>,
+    fee_denom: &str,
+    network: Network,
+) -> Result<String, InputError> {
+    if let Some(name) = answers::get_text(inputs, "key")? {
+        if !keys.iter().any(|key| key.name == name) {
+            return Err(AnswersError::InvalidValue(
+                "key".to_string(),
+                format!("{} is not one of the available keys", name),
+            )
+            .into());
+        }
+        return Ok(name);
+    }
+
+    let rows = keys
+        .into_iter()
+        .map(|key| {
+            let columns = vec![
+                key.name.clone(),
+                key.address.clone(),
+                to_hex_address(&key.address).unwrap_or_default(),
+                format_coin(&key.balance.to_string(), fee_denom, network),
+            ];
+            (key.name, columns)
+        })
+        .collect();
+
+    fuzzy_select("Select key to submit proposal", get_select_options(rows))
+}
+
+/// The ways to continue if the summary of the release notes could not be created with the LLM.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum SummaryFallback {
+    /// Enter the summary manually.
+    Manual,
+    /// Use the truncated release notes as the summary.
+    ReleaseNotes,
+    /// Retry creating the summary with the LLM.
+    // NOTE: retrying cannot be answered, because the same answer would be used for every attempt
+    #[value(skip)]
+    Retry,
+}
+
+impl Display for SummaryFallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let option = match self {
+            SummaryFallback::Manual => "Enter the summary manually",
+            SummaryFallback::ReleaseNotes => "Use the truncated release notes",
+            SummaryFallback::Retry => "Retry",
+        };
+        write!(f, "{}", option)
+    }
+}
+
+/// Prompts the user to choose how to continue after the summary could not be created with the LLM.
+pub fn choose_summary_fallback(inputs: &dyn InputProvider) -> Result<SummaryFallback, InputError> {
+    if let Some(fallback) = answers::get_choice::<SummaryFallback>(inputs, "summary_fallback")? {
+        return Ok(fallback);
+    }
+
+    let options = vec![
+        SummaryFallback::Manual,
+        SummaryFallback::ReleaseNotes,
+        SummaryFallback::Retry,
+    ];
+
+    Ok(Select::new("How do you want to continue?", options).prompt()?)
+}
+
+/// The ways to continue if artifacts for the chosen network and target version exist already.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ExistingArtifactsAction {
+    /// Re-render the artifacts from the existing configuration.
+    Update,
+    /// Generate a new configuration and overwrite the existing artifacts.
+    Regenerate,
+    /// Keep the existing artifacts untouched.
+    Keep,
+}
+
+impl Display for ExistingArtifactsAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let option = match self {
+            ExistingArtifactsAction::Update => {
+                "Update the proposal from the existing configuration"
+            }
+            ExistingArtifactsAction::Regenerate => "Regenerate and overwrite the existing files",
+            ExistingArtifactsAction::Keep => "Keep the existing files and exit",
+        };
+        write!(f, "{}", option)
+    }
+}
+
+/// Lists the given existing artifacts and prompts the user to choose how to continue.
+pub fn choose_existing_artifacts_action(
+    inputs: &dyn InputProvider,
+    existing: &ExistingArtifacts,
+) -> Result<ExistingArtifactsAction, InputError> {
+    println!("Found existing files for this upgrade:");
+    for path in existing.paths() {
+        println!("  {}", path.display());
+    }
+
+    if let Some(action) =
+        answers::get_choice::<ExistingArtifactsAction>(inputs, "existing_artifacts")?
+    {
+        return Ok(action);
+    }
+
+    let options = vec![
+        ExistingArtifactsAction::Update,
+        ExistingArtifactsAction::Regenerate,
+        ExistingArtifactsAction::Keep,
+    ];
+
+    Ok(Select::new("How do you want to continue?", options).prompt()?)
+}
+
+/// The kinds of contents of the `info` field of the upgrade plan.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+enum PlanInfoKind {
+    Binaries,
+    Url,
+    ReleaseNotes,
+    Custom,
+}
+
+impl Display for PlanInfoKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let option = match self {
+            PlanInfoKind::Binaries => {
+                "Binaries JSON with checksums (enables Cosmovisor auto-download)"
+            }
+            PlanInfoKind::Url => "URL of a hosted upgrade info file",
+            PlanInfoKind::ReleaseNotes => "Link to the release notes",
+            PlanInfoKind::Custom => "Custom text",
+        };
+        write!(f, "{}", option)
+    }
+}
+
+/// Prompts the user to choose the contents of the `info` field of the upgrade plan.
+pub fn get_plan_info(inputs: &dyn InputProvider) -> Result<PlanInfo, InputError> {
+    let options = vec![
+        PlanInfoKind::Binaries,
+        PlanInfoKind::Url,
+        PlanInfoKind::ReleaseNotes,
+        PlanInfoKind::Custom,
+    ];
+
+    let kind = match answers::get_choice::<PlanInfoKind>(inputs, "plan_info")? {
+        Some(kind) => kind,
+        None => Select::new("Contents of the upgrade plan info:", options).prompt()?,
+    };
+
+    Ok(match kind {
+        PlanInfoKind::Binaries => PlanInfo::Binaries,
+        PlanInfoKind::Url => PlanInfo::Url {
+            url: get_text(inputs, "plan_info_url", "URL of the upgrade info:")?,
+        },
+        PlanInfoKind::ReleaseNotes => PlanInfo::ReleaseNotes,
+        PlanInfoKind::Custom => PlanInfo::Custom {
+            text: get_text(inputs, "plan_info_text", "Upgrade plan info:")?,
+        },
+    })
+}
+
+/// Prompts the user to enter the summary as bullet points, one at a time,
+/// until an empty bullet point is entered.
+pub fn get_manual_summary(inputs: &dyn InputProvider) -> Result<String, InputError> {
+    if let Some(summary) = answers::get_text(inputs, "manual_summary")? {
+        return Ok(summary.trim().to_string());
+    }
+
+    let mut bullet_points: Vec<String> = Vec::new();
+    loop {
+        let bullet_point = inquire::Text::new(&format!(
+            "Enter bullet point #{} (leave empty to finish)",
+            bullet_points.len() + 1
+        ))
+        .prompt()?;
+        if bullet_point.trim().is_empty() {
+            break;
+        }
+
+        bullet_points.push(format!(
+            "- {}",
+            bullet_point.trim().trim_start_matches("- ")
+        ));
+    }
+
+    Ok(bullet_points.join("\n"))
+}
+
+/// The ways to provide the description of a text proposal.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum DescriptionSource {
+    /// Draft the description from bullet points with the LLM.
+    Draft,
+    /// Read the description from a Markdown file.
+    File,
+    /// Enter the description in a single line.
+    Manual,
+}
+
+impl Display for DescriptionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let option = match self {
+            DescriptionSource::Draft => "Draft from bullet points with the LLM",
+            DescriptionSource::File => "Read from a Markdown file",
+            DescriptionSource::Manual => "Enter the description manually",
+        };
+        write!(f, "{}", option)
+    }
+}
+
+/// Prompts the user to choose how to provide the description of the text proposal.
+pub fn choose_description_source(
+    inputs: &dyn InputProvider,
+) -> Result<DescriptionSource, InputError> {
+    if let Some(source) = answers::get_choice::<DescriptionSource>(inputs, "description_source")? {
+        return Ok(source);
+    }
+
+    let options = vec![
+        DescriptionSource::Draft,
+        DescriptionSource::File,
+        DescriptionSource::Manual,
+    ];
+
+    Ok(Select::new("How do you want to provide the description?", options).prompt()?)
+}
+
+/// Prompts the user to select the fee granter, whose allowance is used to pay the fees.
+/// If no fee granters are available, no prompt is shown.
+pub fn get_fee_granter(
+    inputs: &dyn InputProvider,
+    fee_granters: Vec<String>,
+) -> Result<Option<String>, InputError> {
+    if fee_granters.is_empty() {
+        return Ok(None);
+    }
+
+    if let Some(granter) = answers::get_text(inputs, "fee_granter")? {
+        return match granter.as_str() {
+            "none" => Ok(None),
+            granter if fee_granters.iter().any(|g| g == granter) => Ok(Some(granter.to_string())),
+            granter => Err(AnswersError::InvalidValue(
+                "fee_granter".to_string(),
+                format!(
+                    "{} has no allowance for the key; use none to pay the fees",
+                    granter
+                ),
+            )
+            .into()),
+        };
+    }
+
+    let no_granter = "None (pay fees from key)".to_string();
+    let mut options = vec![no_granter.clone()];
+    options.extend(fee_granters);
+
+    let chosen = Select::new("Select fee granter", options).prompt()?;
+
+    Ok((chosen != no_granter).then_some(chosen))
 }
 
 #[cfg(test)]
@@ -319,4 +875,76 @@ mod tests {
             "expected different time string",
         );
     }
+
+    #[test]
+    fn test_get_time_strings_by_timezone() {
+        let time = Utc.with_ymd_and_hms(2023, 10, 23, 16, 0, 0).unwrap();
+        assert_eq!(
+            get_time_strings_by_timezone(time, Locale::En),
+            vec![
+                (
+                    "UTC".to_string(),
+                    "4PM UTC on Mon., October 23., 2023".to_string()
+                ),
+                (
+                    "ET".to_string(),
+                    "12PM EDT on Mon., October 23., 2023".to_string()
+                ),
+                (
+                    "CET".to_string(),
+                    "6PM CEST on Mon., October 23., 2023".to_string()
+                ),
+                (
+                    "KST".to_string(),
+                    "1AM KST on Tue., October 24., 2023".to_string()
+                ),
+            ],
+            "expected different time strings",
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_filter() {
+        let option = "proposal-Mainnet-v16.0.0.json  2024-01-17 16:00  Mainnet";
+        assert!(fuzzy_filter("mnv16", &(), option, 0));
+        assert!(fuzzy_filter("Main JSON", &(), option, 0));
+        assert!(fuzzy_filter("", &(), option, 0));
+        assert!(!fuzzy_filter("testnet", &(), option, 0));
+        assert!(!fuzzy_filter("16v", &(), "v16", 0));
+    }
+
+    #[test]
+    fn test_get_select_options() {
+        let options = get_select_options(vec![
+            ("dev0", vec!["dev0".to_string(), "evmos1abc".to_string()]),
+            (
+                "validator",
+                vec!["validator".to_string(), "evmos1d".to_string()],
+            ),
+        ]);
+
+        assert_eq!(options[0].value, "dev0");
+        assert_eq!(options[0].to_string(), "dev0       evmos1abc");
+        assert_eq!(options[1].to_string(), "validator  evmos1d");
+    }
+
+    #[test]
+    fn test_get_home_candidates() {
+        let candidates = get_home_candidates(
+            vec![PathBuf::from("/data/evmos"), PathBuf::from("/root/.evmosd")],
+            vec![
+                PathBuf::from("/root/.evmosd"),
+                PathBuf::from("/root/.tmp-evmosd"),
+            ],
+        );
+
+        assert_eq!(
+            candidates,
+            vec![
+                (PathBuf::from("/data/evmos"), "running node".to_string()),
+                (PathBuf::from("/root/.evmosd"), "running node".to_string()),
+                (PathBuf::from("/root/.tmp-evmosd"), "default".to_string()),
+            ]
+        );
+    }
 }
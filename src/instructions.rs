@@ -0,0 +1,129 @@
+use crate::{
+    chain::ChainConfig, errors::InstructionsError, helper::UpgradeHelper, template,
+    upgrade_info::PlanInfo,
+};
+use serde_json::{json, Value};
+
+/// The path to the template of the upgrade instructions for validators.
+pub const INSTRUCTIONS_TEMPLATE: &str = "src/templates/instructions.hbs";
+
+/// Returns the file name of the upgrade instructions for the given helper.
+pub fn get_instructions_file_name(helper: &UpgradeHelper) -> String {
+    format!("instructions-{}.md", helper.target_version)
+}
+
+/// Renders the upgrade instructions for validators, which cover the automatic download with Cosmovisor
+/// and building and swapping the binary manually.
+///
+/// The given assets are the binaries JSON of the release. The automatic download is only described
+/// if the upgrade plan info contains the binaries or links to a hosted upgrade info file.
+pub fn render_instructions(
+    helper: &UpgradeHelper,
+    chain_config: &ChainConfig,
+    assets: &str,
+    strict: bool,
+) -> Result<String, InstructionsError> {
+    let handlebars =
+        template::get_handlebars("instructions", INSTRUCTIONS_TEMPLATE, strict, false)?;
+    let assets: Value = serde_json::from_str(assets)?;
+
+    let binaries: Vec<Value> = assets["binaries"]
+        .as_object()
+        .map(|binaries| {
+            binaries
+                .iter()
+                .map(|(platform, url)| json!({"platform": platform, "url": url}))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let info_url = match &helper.plan_info {
+        PlanInfo::Url { url } => Some(url.as_str()),
+        _ => None,
+    };
+
+    let data = json!({
+        "assets": serde_json::to_string_pretty(&assets)?,
+        "auto_download": helper.plan_info.supports_auto_download(),
+        "info_url": info_url,
+        "binaries": binaries,
+        "daemon": chain_config.daemon,
+        "height": helper.upgrade_height,
+        "network": helper.network,
+        "previous_version": helper.previous_version,
+        "tracking_issue": helper.tracking_issue.as_ref().map(|issue| issue.url.clone()),
+        "version": helper.target_version,
+    });
+
+    Ok(template::render(
+        &handlebars,
+        "instructions",
+        INSTRUCTIONS_TEMPLATE,
+        &data,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_render_instructions() {
+        let helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v15.0.0",
+            "v16.0.0",
+            Utc::now(),
+            19_000_000,
+            "",
+        );
+        assert_eq!(
+            get_instructions_file_name(&helper),
+            "instructions-v16.0.0.md"
+        );
+
+        let assets = r#"{"binaries":{"linux/amd64":"https://github.com/evmos/evmos/releases/download/v16.0.0/evmos_16.0.0_Linux_amd64.tar.gz?checksum=abc"}}"#;
+        let instructions = render_instructions(&helper, &ChainConfig::default(), assets, true)
+            .expect("failed to render instructions");
+        assert!(instructions.contains("from v15.0.0 to v16.0.0 at block height 19000000"));
+        assert!(instructions.contains(
+            "| linux/amd64 | https://github.com/evmos/evmos/releases/download/v16.0.0/evmos_16.0.0_Linux_amd64.tar.gz?checksum=abc |"
+        ));
+        assert!(instructions.contains("\"linux/amd64\": \"https://github.com/evmos"));
+        assert!(instructions.contains("git checkout v16.0.0\nmake install"));
+        assert!(
+            instructions.contains("$DAEMON_HOME/cosmovisor/upgrades/v16.0.0/bin/evmosd version")
+        );
+    }
+
+    #[test]
+    fn test_render_instructions_without_binaries() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v15.0.0",
+            "v16.0.0",
+            Utc::now(),
+            19_000_000,
+            "",
+        );
+        helper.plan_info = PlanInfo::ReleaseNotes;
+
+        let instructions = render_instructions(&helper, &ChainConfig::default(), "{}", true)
+            .expect("failed to render instructions");
+        assert!(instructions.contains("Cosmovisor cannot download the new binary automatically"));
+        assert!(!instructions.contains("DAEMON_ALLOW_DOWNLOAD_BINARIES"));
+
+        helper.plan_info = PlanInfo::Url {
+            url: "https://example.com/upgrade-info.json".to_string(),
+        };
+        let instructions = render_instructions(&helper, &ChainConfig::default(), "{}", true)
+            .expect("failed to render instructions");
+        assert!(instructions.contains("hosted at https://example.com/upgrade-info.json"));
+        assert!(!instructions.contains("in the `info` field of the upgrade plan"));
+    }
+}
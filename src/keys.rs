@@ -1,29 +1,113 @@
-use crate::evmosd::ClientConfig;
-use crate::{balance, block::get_rest_provider, errors::KeysError, network::Network};
+use crate::evmosd::{get_client_config, ClientConfig};
+use crate::{
+    account::Account, address::validate_address, balance, block::get_rpc_url, chain::ChainConfig,
+    errors::KeysError, feegrant::get_fee_granters, network::Network,
+};
+use chrono::Utc;
+use clap::ValueEnum;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process;
+use url::Url;
+
+/// The keyring backend, into which keys are imported. The `os` backend is never used,
+/// so that keys of ephemeral CI pipelines don't end up in the keychain of the machine.
+pub const IMPORT_KEYRING_BACKEND: &str = "test";
+
+/// The key types that can be used to sign the proposal submission.
+/// Multisig and offline keys cannot sign the transaction directly.
+const SUPPORTED_KEY_TYPES: [&str; 2] = ["local", "ledger"];
 
 /// Represents a key with its name and address.
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Key {
+    pub name: String,
+    #[serde(rename = "type", default = "default_key_type")]
+    pub key_type: String,
+    pub address: String,
+}
+
+/// Returns the default key type for keyring outputs, which do not contain the type.
+fn default_key_type() -> String {
+    "local".to_string()
+}
+
+/// Represents a key that can submit the proposal, together with its balance in the native denomination.
+#[derive(Debug, PartialEq)]
+pub struct KeyWithBalance {
     pub name: String,
     pub address: String,
+    pub balance: u128,
 }
 
 /// Contains all necessary configuration to get the keys from the keyring and filter for ones with a balance.
+#[derive(Clone)]
 pub struct FilterKeysConfig {
     pub config: ClientConfig,
     pub home: PathBuf,
-    pub network: Network,
+    /// The Bech32 prefix of the account addresses on the configured chain.
+    pub bech32_prefix: String,
+    /// The denomination of the fees, in which the balances of the keys are queried.
+    pub fee_denom: String,
+    /// The pattern, which the key names have to match. If not set, all keys are considered.
+    pub name_filter: Option<Regex>,
+    /// The REST endpoint of the configured chain, through which the balances and fee allowances are queried.
+    pub rest_url: Url,
 }
 
-/// Returns a list of keys that have a non-zero balance on the configured network.
-pub async fn get_keys_with_balances(config: FilterKeysConfig) -> Result<Vec<String>, KeysError> {
-    let keys = get_keys_from_keyring(&config)?;
+/// Returns a list of keys that have a non-zero balance on the configured network
+/// or that were granted a fee allowance, so that they can submit the proposal.
+/// The keys are sorted by their balance in descending order.
+pub async fn get_keys_with_balances(
+    config: FilterKeysConfig,
+) -> Result<Vec<KeyWithBalance>, KeysError> {
+    let keys = filter_keys_by_prefix(
+        filter_keys_by_name(get_keys_from_keyring(&config)?, config.name_filter.as_ref()),
+        &config.bech32_prefix,
+    );
     filter_keys_with_balance(config, keys).await
 }
 
+/// Filters the keys for ones whose name matches the given pattern.
+/// The filter is applied before querying the balances, so that large keyrings
+/// do not require a query for every contained key.
+fn filter_keys_by_name(keys: Vec<Key>, name_filter: Option<&Regex>) -> Vec<Key> {
+    match name_filter {
+        Some(name_filter) => keys
+            .into_iter()
+            .filter(|key| name_filter.is_match(&key.name))
+            .collect(),
+        None => keys,
+    }
+}
+
+/// Filters the keys for ones with a valid address for the given Bech32 prefix.
+/// Keys of other chains in the same keyring are skipped with a warning.
+fn filter_keys_by_prefix(keys: Vec<Key>, prefix: &str) -> Vec<Key> {
+    keys.into_iter()
+        .filter(|key| match validate_address(&key.address, prefix) {
+            Ok(()) => true,
+            Err(e) => {
+                println!("WARNING: skipping key {}: {}", key.name, e);
+                false
+            }
+        })
+        .collect()
+}
+
+/// Returns the address of the key with the given name in the configured keyring.
+pub fn get_key_address(config: &FilterKeysConfig, name: &str) -> Result<String, KeysError> {
+    get_keys_from_keyring(config)?
+        .into_iter()
+        .find(|key| key.name == name)
+        .map(|key| key.address)
+        .ok_or(KeysError::KeyNotFound(name.to_string()))
+}
+
 /// Returns a list of keys from the configured keyring.
 /// The keyring is configured in the user's home directory.
 fn get_keys_from_keyring(config: &FilterKeysConfig) -> Result<Vec<Key>, KeysError> {
@@ -46,22 +130,52 @@ fn get_keys_from_keyring(config: &FilterKeysConfig) -> Result<Vec<Key>, KeysErro
 
 /// Parses the output from the `keys list` command.
 /// Returns a list of keys.
+///
+/// Entries, which cannot be parsed or have a key type that cannot sign the submission,
+/// are skipped with a warning instead of failing for the whole keyring.
 fn parse_keys_output(output: &str) -> Result<Vec<Key>, serde_json::Error> {
-    let keys: Vec<Key> = serde_json::from_str(output)?;
-    Ok(keys)
+    let entries: Vec<Value> = serde_json::from_str(output)?;
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| match serde_json::from_value::<Key>(entry.clone()) {
+            Ok(key) if SUPPORTED_KEY_TYPES.contains(&key.key_type.as_str()) => Some(key),
+            Ok(key) => {
+                println!(
+                    "WARNING: skipping key {} with unsupported type {}",
+                    key.name, key.key_type
+                );
+                None
+            }
+            Err(e) => {
+                println!("WARNING: skipping invalid keyring entry {}: {}", entry, e);
+                None
+            }
+        })
+        .collect())
 }
 
-/// Filters the keys for ones that have a non-zero balance on the configured network.
+/// Filters the keys for ones that have a non-zero balance on the configured network
+/// or for which an existing fee allowance can be used.
 async fn filter_keys_with_balance(
     config: FilterKeysConfig,
     keys: Vec<Key>,
-) -> Result<Vec<String>, KeysError> {
+) -> Result<Vec<KeyWithBalance>, KeysError> {
     let mut keys_with_balance = Vec::new();
-    let base_url = get_rest_provider(config.network);
 
     for key in keys {
-        if balance::has_balance(&key.address, &config.network, &base_url).await? {
-            keys_with_balance.push(key.name);
+        let balance =
+            balance::get_balance(&key.address, &config.fee_denom, &config.rest_url).await?;
+        if balance > 0
+            || !get_fee_granters(&key.address, &config.rest_url, Utc::now())
+                .await?
+                .is_empty()
+        {
+            keys_with_balance.push(KeyWithBalance {
+                name: key.name,
+                address: key.address,
+                balance,
+            });
         }
     }
 
@@ -69,13 +183,162 @@ async fn filter_keys_with_balance(
         return Err(KeysError::NoKeysWithBalance);
     }
 
+    keys_with_balance.sort_by_key(|key| std::cmp::Reverse(key.balance));
+
     Ok(keys_with_balance)
 }
 
+/// The formats, in which a key can be imported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum ImportSource {
+    /// A BIP-39 mnemonic, from which the key is recovered.
+    #[default]
+    Mnemonic,
+    /// A hex encoded Ethereum private key.
+    Hex,
+}
+
+/// Derives the account of the given secret natively, which checks that the secret is valid
+/// for the given import source, e.g. that the mnemonic has a valid checksum.
+fn derive_account(source: ImportSource, secret: &str, prefix: &str) -> Result<Account, KeysError> {
+    Account::from_secret(source, secret, prefix)
+        .map_err(|e| KeysError::InvalidSecret(e.to_string()))
+}
+
+/// Checks that the given home directory can be used for imported keys.
+/// The default home directories are rejected, so that the keyring of the user is never modified,
+/// as well as home directories whose client configuration uses another keyring backend.
+fn check_import_home(home: &Path, default_homes: &[PathBuf]) -> Result<(), KeysError> {
+    if default_homes
+        .iter()
+        .any(|default_home| default_home == home)
+    {
+        return Err(KeysError::ImportHome(
+            home.to_path_buf(),
+            "it is a default home directory".to_string(),
+        ));
+    }
+
+    let client_config_path = home.join("config/client.toml");
+    if client_config_path.exists() {
+        let client_config = get_client_config(&client_config_path)
+            .map_err(|e| KeysError::ImportHome(home.to_path_buf(), e.to_string()))?;
+        if client_config.keyring_backend != IMPORT_KEYRING_BACKEND {
+            return Err(KeysError::ImportHome(
+                home.to_path_buf(),
+                format!(
+                    "it uses the {} keyring backend",
+                    client_config.keyring_backend
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the arguments of the Evmos CLI to import the key with the given name into the given home.
+/// Mnemonics are passed via the standard input, so that they don't show up in the process list.
+fn get_import_args(home: &str, name: &str, source: ImportSource, secret: &str) -> Vec<String> {
+    let mut args: Vec<String> = match source {
+        ImportSource::Mnemonic => ["keys", "add", name, "--recover", "--algo", "eth_secp256k1"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect(),
+        ImportSource::Hex => vec![
+            "keys".to_string(),
+            "unsafe-import-eth-key".to_string(),
+            name.to_string(),
+            secret.trim_start_matches("0x").to_string(),
+        ],
+    };
+    args.extend(
+        ["--keyring-backend", IMPORT_KEYRING_BACKEND, "--home", home]
+            .iter()
+            .map(|arg| arg.to_string()),
+    );
+
+    args
+}
+
+/// Returns the client configuration for the keyring of imported keys on the given network
+/// with the given chain ID.
+fn get_import_client_config(network: Network, chain_id: String) -> ClientConfig {
+    ClientConfig {
+        chain_id,
+        keyring_backend: IMPORT_KEYRING_BACKEND.to_string(),
+        output: "text".to_string(),
+        node: get_rpc_url(network),
+        broadcast_mode: "sync".to_string(),
+    }
+}
+
+/// Imports the key with the given name from the given secret into the `test` keyring
+/// in the given home directory, which must not be one of the given default home directories.
+///
+/// If the home directory has no client configuration yet, it is written for the given network
+/// with the chain ID from the given chain configuration, so that the command to submit
+/// the proposal can be generated with the imported key.
+///
+/// Returns the address of the key with the Bech32 prefix of the chain, which is derived natively,
+/// so that it does not have to be queried from the keyring.
+pub fn import_key(
+    home: &Path,
+    default_homes: &[PathBuf],
+    name: &str,
+    source: ImportSource,
+    secret: &str,
+    network: Network,
+    chain_config: &ChainConfig,
+) -> Result<String, KeysError> {
+    let secret = secret.trim();
+    let account = derive_account(source, secret, &chain_config.bech32_prefix)?;
+    check_import_home(home, default_homes)?;
+
+    let home_str = home
+        .to_str()
+        .expect("failed to convert home path to string");
+    let mut child = process::Command::new("evmosd")
+        .args(get_import_args(home_str, name, source, secret))
+        .stdin(process::Stdio::piped())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::piped())
+        .spawn()?;
+
+    if let (ImportSource::Mnemonic, Some(stdin)) = (source, child.stdin.as_mut()) {
+        writeln!(stdin, "{}", secret)?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(KeysError::Import(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+
+    let client_config_path = home.join("config/client.toml");
+    if !client_config_path.exists() {
+        fs::create_dir_all(home.join("config"))?;
+        fs::write(
+            client_config_path,
+            toml::to_string(&get_import_client_config(
+                network,
+                chain_config.get_chain_id(network),
+            ))?,
+        )?;
+    }
+
+    Ok(account.address)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::address::from_hex_address;
+    use crate::block::get_rest_provider;
+    use test_dir::{DirBuilder, FileType, TestDir};
 
+    #[allow(clippy::len_zero)]
     #[cfg(not(feature = "remote"))]
     #[tokio::test]
     async fn test_get_keys_with_balances() {
@@ -88,7 +351,10 @@ mod tests {
                 broadcast_mode: "sync".to_string(),
             },
             home: dirs::home_dir().unwrap().join(".evmosd"),
-            network: Network::Testnet,
+            bech32_prefix: "evmos".to_string(),
+            fee_denom: "atevmos".to_string(),
+            name_filter: None,
+            rest_url: get_rest_provider(Network::Testnet),
         };
 
         let res = get_keys_with_balances(filter_config).await;
@@ -116,4 +382,117 @@ mod tests {
             "evmos1k0sx0f62383ufue5gn6xth029wut0twut294fw"
         );
     }
+
+    #[test]
+    fn test_parse_keys_output_unsupported_types() {
+        let output = r#"[
+            {"name":"local-key","type":"local","address":"evmos12ly0g0dj6amk5uch77mz7d022h3sd10enf4ln9"},
+            {"name":"multisig","type":"multi","address":"evmos1k0sx0f62383ufue5gn6xth029wut0twut294fw"},
+            {"name":"ledger-key","type":"ledger","address":"evmos1hafptm4zxy7y4fj6j7m6fj5n89v2zjy5l7ltae"},
+            {"name":"broken"}
+        ]"#;
+
+        let keys = parse_keys_output(output).expect("failed to parse keys output");
+        let names: Vec<&str> = keys.iter().map(|key| key.name.as_str()).collect();
+        assert_eq!(names, vec!["local-key", "ledger-key"]);
+    }
+
+    #[test]
+    fn test_filter_keys_by_prefix() {
+        let hex_address = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+        let keys = vec![
+            ("evmos-key", from_hex_address(hex_address, "evmos").unwrap()),
+            (
+                "cosmos-key",
+                from_hex_address(hex_address, "cosmos").unwrap(),
+            ),
+            ("invalid", "evmos1invalid".to_string()),
+        ]
+        .into_iter()
+        .map(|(name, address)| Key {
+            name: name.to_string(),
+            key_type: default_key_type(),
+            address,
+        })
+        .collect();
+
+        let keys = filter_keys_by_prefix(keys, "evmos");
+        let names: Vec<&str> = keys.iter().map(|key| key.name.as_str()).collect();
+        assert_eq!(names, vec!["evmos-key"]);
+    }
+
+    #[test]
+    fn test_filter_keys_by_name() {
+        let keys = vec!["dev0", "dev1", "validator"]
+            .into_iter()
+            .map(|name| Key {
+                name: name.to_string(),
+                key_type: default_key_type(),
+                address: String::new(),
+            })
+            .collect();
+
+        let filter = Regex::new("^dev").unwrap();
+        let keys = filter_keys_by_name(keys, Some(&filter));
+        let names: Vec<&str> = keys.iter().map(|key| key.name.as_str()).collect();
+        assert_eq!(names, vec!["dev0", "dev1"]);
+    }
+
+    #[test]
+    fn test_derive_account() {
+        let mnemonic = format!("{} art", ["abandon"; 23].join(" "));
+        assert!(derive_account(ImportSource::Mnemonic, &mnemonic, "evmos").is_ok());
+        assert!(derive_account(ImportSource::Mnemonic, "abandon abandon", "evmos").is_err());
+        assert!(matches!(
+            derive_account(ImportSource::Mnemonic, &["abandon"; 24].join(" "), "evmos"),
+            Err(KeysError::InvalidSecret(_))
+        ));
+
+        let key = format!("0x{}", "ab".repeat(32));
+        assert!(derive_account(ImportSource::Hex, &key, "evmos").is_ok());
+        assert!(derive_account(ImportSource::Hex, &key[..40], "evmos").is_err());
+        assert!(derive_account(ImportSource::Hex, &"zz".repeat(32), "evmos").is_err());
+    }
+
+    #[test]
+    fn test_check_import_home() {
+        let test_dir = TestDir::temp().create("config", FileType::Dir);
+        let home = test_dir.root();
+        assert!(check_import_home(home, &[]).is_ok());
+        assert!(matches!(
+            check_import_home(home, &[home.to_path_buf()]),
+            Err(KeysError::ImportHome(_, _))
+        ));
+
+        std::fs::write(
+            home.join("config/client.toml"),
+            r#"
+            chain-id = "evmos_9000-4"
+            keyring-backend = "os"
+            output = "text"
+            node = "tcp://localhost:26657"
+            broadcast-mode = "sync"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            check_import_home(home, &[]),
+            Err(KeysError::ImportHome(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_get_import_args() {
+        let args = get_import_args("/tmp/ci", "ci", ImportSource::Mnemonic, "secret words");
+        assert_eq!(
+            args.join(" "),
+            "keys add ci --recover --algo eth_secp256k1 --keyring-backend test --home /tmp/ci"
+        );
+
+        let args = get_import_args("/tmp/ci", "ci", ImportSource::Hex, "0xabcd");
+        assert_eq!(
+            args.join(" "),
+            "keys unsafe-import-eth-key ci abcd --keyring-backend test --home /tmp/ci"
+        );
+    }
 }
@@ -0,0 +1,64 @@
+use crate::cache::DiskCache;
+use crate::errors::HttpError;
+use crate::http::get_page_with_cache;
+use regex::Regex;
+use url::Url;
+
+/// Returns the unique HTTP(S) links contained in the given Markdown in order of their appearance,
+/// e.g. the links to the Commonwealth discussion, the block explorer or the release assets.
+pub fn extract_links(markdown: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    for link in Regex::new(r#"https?://[^\s)\]>"'`]+"#)
+        .expect("invalid regex")
+        .find_iter(markdown)
+        .map(|link| link.as_str().trim_end_matches(['.', ',']).to_string())
+    {
+        if !links.contains(&link) {
+            links.push(link);
+        }
+    }
+
+    links
+}
+
+/// Checks that the links in the given Markdown can be retrieved and returns the broken ones
+/// together with the corresponding error.
+///
+/// Successful responses are stored in the given cache for a short time, so that repeated checks
+/// do not send the same requests to the external services.
+pub async fn check_links(markdown: &str, cache: &DiskCache) -> Vec<(String, HttpError)> {
+    let mut broken = Vec::new();
+    for link in extract_links(markdown) {
+        let result = match Url::parse(&link) {
+            Ok(url) => get_page_with_cache(url, cache).await.map(|_| ()),
+            Err(e) => Err(HttpError::Config(format!("invalid link: {}", e))),
+        };
+
+        if let Err(e) = result {
+            broken.push((link, e));
+        }
+    }
+
+    broken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links() {
+        let markdown =
+            "Upgrade at height [18,798,000](https://mintscan.io/evmos/blocks/18798000).\n\
+                        See https://github.com/evmos/evmos/releases/tag/v16.0.0, \
+                        or `https://github.com/evmos/evmos/releases/tag/v16.0.0`.";
+
+        assert_eq!(
+            extract_links(markdown),
+            vec![
+                "https://mintscan.io/evmos/blocks/18798000",
+                "https://github.com/evmos/evmos/releases/tag/v16.0.0",
+            ]
+        );
+    }
+}
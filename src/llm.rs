@@ -1,9 +1,12 @@
+use crate::breaking::PullRequest;
 use crate::errors::SummaryError;
-use crate::release::get_release_notes;
+use crate::glossary::get_glossary;
 use async_openai::types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs};
 use async_openai::Client;
+use async_trait::async_trait;
 use clap::ValueEnum;
-use octocrab::models::repos::Release;
+use regex::Regex;
+use std::collections::HashSet;
 use std::fmt::Display;
 
 /// The used OpenAI model.
@@ -29,11 +32,14 @@ impl OpenAIModel {
     }
 }
 
-/// Creates the summary for the release notes by querying the LLM.
-pub async fn create_summary(release: &Release, model: OpenAIModel) -> Result<String, SummaryError> {
-    let notes = get_release_notes(release)?;
-    let summary_prompt = build_summary_prompt(notes.as_str());
-    prompt_for_summary(summary_prompt, model).await
+/// Creates the summary for the given release notes by querying the LLM.
+pub async fn create_summary(
+    llm: &dyn LlmProvider,
+    notes: &str,
+    model: OpenAIModel,
+) -> Result<String, SummaryError> {
+    let summary_prompt = build_summary_prompt(notes);
+    generate_text(llm, summary_prompt, model).await
 }
 
 /// Builds the prompt for the LLM to generate the release notes summary.
@@ -49,30 +55,315 @@ fn build_summary_prompt(release_notes: &str) -> String {
     )
 }
 
-/// Prompts the LLM to get the summary for the release notes.
-async fn prompt_for_summary(prompt: String, model: OpenAIModel) -> Result<String, SummaryError> {
-    let client = Client::new();
+/// Creates the summary for the given release notes by querying the LLM, which attaches the numbers
+/// of the relevant pull requests from the given list to the bullet points.
+///
+/// Citations of pull requests, which are not included in the release, are removed with a warning,
+/// so that the summary never references changes outside of the compared range.
+pub async fn create_summary_with_citations(
+    llm: &dyn LlmProvider,
+    notes: &str,
+    pull_requests: &[PullRequest],
+    model: OpenAIModel,
+) -> Result<String, SummaryError> {
+    let summary_prompt = build_citation_prompt(notes, pull_requests);
+    let summary = generate_text(llm, summary_prompt, model).await?;
 
-    let request = CreateChatCompletionRequestArgs::default()
-        .max_tokens(2000u16)
-        .model(model.model_name())
-        .messages([ChatCompletionRequestUserMessageArgs::default()
-            .content(prompt)
-            .build()?
-            .into()])
-        .build()?;
-
-    let response = client.chat().create(request).await?;
-    let choice = response.choices.first().ok_or(SummaryError::NoSummary)?;
-    let summary = choice
-        .message
-        .content
-        .clone()
-        .ok_or(SummaryError::NoSummary)?;
+    let included: HashSet<u64> = pull_requests.iter().map(|pr| pr.number).collect();
+    let (summary, invalid) = remove_invalid_citations(&summary, &included);
+    if !invalid.is_empty() {
+        println!(
+            "WARNING: removed citations of pull requests that are not included in the release: {}",
+            invalid
+                .iter()
+                .map(|number| format!("#{}", number))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
 
     Ok(summary)
 }
 
+/// Builds the prompt for the LLM to generate the release notes summary,
+/// which cites the numbers of the included pull requests.
+fn build_citation_prompt(release_notes: &str, pull_requests: &[PullRequest]) -> String {
+    let pull_requests = pull_requests
+        .iter()
+        .map(|pr| format!("#{}: {}", pr.number, pr.title))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "{}\n\nAt the end of each bullet point, please add the numbers of the pull requests from the following list,\
+         which contain the summarized changes, in parentheses, e.g. (#1234, #1240).\
+         Please only use numbers from the list and do not add any links.\
+         \nPull requests:\n\"{}\"",
+        build_summary_prompt(release_notes),
+        pull_requests
+    )
+}
+
+/// Removes the citations of pull requests, which are not contained in the given set, from the
+/// trailing parentheses of the bullet points. Returns the cleaned summary and the removed numbers.
+fn remove_invalid_citations(summary: &str, included: &HashSet<u64>) -> (String, Vec<u64>) {
+    let citations = Regex::new(r"\s*\((#\d+(?:,\s*#\d+)*)\)\s*$").expect("invalid regex");
+    let mut invalid = Vec::new();
+
+    let lines: Vec<String> = summary
+        .lines()
+        .map(|line| {
+            let Some(captures) = citations.captures(line) else {
+                return line.to_string();
+            };
+
+            let (valid, removed): (Vec<u64>, Vec<u64>) = captures[1]
+                .split(',')
+                .filter_map(|citation| citation.trim().trim_start_matches('#').parse().ok())
+                .partition(|number| included.contains(number));
+            invalid.extend(removed);
+
+            let text = &line[..captures.get(0).map_or(line.len(), |m| m.start())];
+            match valid.is_empty() {
+                true => text.to_string(),
+                false => format!(
+                    "{} ({})",
+                    text,
+                    valid
+                        .iter()
+                        .map(|number| format!("#{}", number))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        })
+        .collect();
+
+    (lines.join("\n"), invalid)
+}
+
+/// Creates a plain-language summary of the given release notes for the community by querying the LLM.
+pub async fn create_community_summary(
+    llm: &dyn LlmProvider,
+    notes: &str,
+    model: OpenAIModel,
+) -> Result<String, SummaryError> {
+    let summary_prompt = build_community_summary_prompt(notes);
+    generate_text(llm, summary_prompt, model).await
+}
+
+/// The maximum number of characters of the release notes, which are used as the summary
+/// if it could not be created with the LLM.
+const MAX_FALLBACK_NOTES_LENGTH: usize = 1500;
+
+/// Returns the given release notes truncated at the last complete line within the maximum length,
+/// which are used as the summary if it could not be created with the LLM.
+pub fn get_truncated_notes(notes: &str) -> String {
+    truncate_notes(notes, MAX_FALLBACK_NOTES_LENGTH)
+}
+
+/// Truncates the given notes at the last complete line within the given number of characters.
+fn truncate_notes(notes: &str, max_length: usize) -> String {
+    let notes = notes.trim();
+    if notes.chars().count() <= max_length {
+        return notes.to_string();
+    }
+
+    let mut truncated = String::new();
+    for line in notes.lines() {
+        if truncated.chars().count() + line.chars().count() + 1 > max_length {
+            break;
+        }
+        truncated.push_str(line);
+        truncated.push('\n');
+    }
+
+    format!("{}...", truncated)
+}
+
+/// Builds the prompt for the LLM to generate the plain-language summary of the release notes.
+fn build_community_summary_prompt(release_notes: &str) -> String {
+    format!(
+        "Please explain the following release notes in plain language for community members without a technical background.\
+         Describe what changes for users of the chain and why it matters in a short paragraph of at most five sentences.\
+         Avoid technical terms, module names and abbreviations or explain them briefly.\
+         Please do not include any pull request links.\
+         \n\"{}\"",
+        release_notes
+    )
+}
+
+/// Drafts the description of a text proposal with the given title from the given bullet points
+/// by querying the LLM.
+pub async fn draft_description(
+    llm: &dyn LlmProvider,
+    title: &str,
+    bullet_points: &str,
+    model: OpenAIModel,
+) -> Result<String, SummaryError> {
+    let description_prompt = build_description_prompt(title, bullet_points);
+    generate_text(llm, description_prompt, model).await
+}
+
+/// Builds the prompt for the LLM to draft the description of a text proposal.
+fn build_description_prompt(title: &str, bullet_points: &str) -> String {
+    format!(
+        "Please write the description of an on-chain governance proposal in Markdown based on the given title and bullet points.\
+         Structure it with the sections Summary, Motivation and Impact.\
+         Do not add facts, numbers or links that are not contained in the bullet points.\
+         \nTitle:\n\"{}\"\
+         \nBullet points:\n\"{}\"",
+        title, bullet_points
+    )
+}
+
+/// Reviews the given proposal against the given governance guidelines by querying the LLM.
+/// Returns the guideline items, which are missing in the proposal.
+pub async fn lint_proposal(
+    llm: &dyn LlmProvider,
+    proposal: &str,
+    guidelines: &str,
+    model: OpenAIModel,
+) -> Result<Vec<String>, SummaryError> {
+    let lint_prompt = build_lint_prompt(proposal, guidelines);
+    let response = llm.complete(lint_prompt, model).await?;
+
+    Ok(parse_lint_response(&response))
+}
+
+/// Builds the prompt for the LLM to review the proposal against the governance guidelines.
+fn build_lint_prompt(proposal: &str, guidelines: &str) -> String {
+    format!(
+        "Please review the following governance proposal against the given guidelines.\
+         List every item of the guidelines that is missing or insufficiently covered in the proposal \
+         as a bullet point starting with \"- \", followed by a short explanation.\
+         Do not list items that are covered.\
+         If all items are covered, answer only with \"{}\".\
+         \nGuidelines:\n\"{}\"\
+         \nProposal:\n\"{}\"",
+        LINT_PASSED, guidelines, proposal
+    )
+}
+
+/// The answer of the LLM if the proposal covers all items of the guidelines.
+const LINT_PASSED: &str = "NONE";
+
+/// Parses the bullet points of missing items from the LLM response.
+fn parse_lint_response(response: &str) -> Vec<String> {
+    if response.trim() == LINT_PASSED {
+        return Vec::new();
+    }
+
+    response
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("- "))
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Prompts the LLM for a text, which is published in the proposal, and returns it.
+/// The preferred terminology of the glossary is added to the prompt and the phrases to avoid
+/// are replaced in the response, so that the wording stays consistent across releases.
+async fn generate_text(
+    llm: &dyn LlmProvider,
+    prompt: String,
+    model: OpenAIModel,
+) -> Result<String, SummaryError> {
+    let glossary = get_glossary()?;
+    let text = llm
+        .complete(format!("{}{}", prompt, glossary.to_prompt()), model)
+        .await?;
+
+    Ok(glossary.apply(&text))
+}
+
+/// The backends, which can answer the prompts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum LlmBackend {
+    /// Queries the OpenAI API, which requires the `OPENAI_API_KEY` environment variable.
+    #[default]
+    #[value(name = "openai")]
+    OpenAI,
+    /// Returns deterministic answers without network access, e.g. for development and CI.
+    Mock,
+}
+
+/// Returns the provider of the given backend.
+pub fn get_provider(backend: LlmBackend) -> Box<dyn LlmProvider> {
+    match backend {
+        LlmBackend::OpenAI => Box::new(OpenAIProvider),
+        LlmBackend::Mock => Box::new(MockProvider),
+    }
+}
+
+/// The common interface of the LLM backends.
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    /// Returns the answer to the given prompt.
+    async fn complete(&self, prompt: String, model: OpenAIModel) -> Result<String, SummaryError>;
+}
+
+/// Answers the prompts using the OpenAI API.
+pub struct OpenAIProvider;
+
+#[async_trait]
+impl LlmProvider for OpenAIProvider {
+    async fn complete(&self, prompt: String, model: OpenAIModel) -> Result<String, SummaryError> {
+        let client = Client::new();
+
+        let request = CreateChatCompletionRequestArgs::default()
+            .max_tokens(2000u16)
+            .model(model.model_name())
+            .messages([ChatCompletionRequestUserMessageArgs::default()
+                .content(prompt)
+                .build()?
+                .into()])
+            .build()?;
+
+        let response = client.chat().create(request).await?;
+        let choice = response.choices.first().ok_or(SummaryError::NoSummary)?;
+        let summary = choice
+            .message
+            .content
+            .clone()
+            .ok_or(SummaryError::NoSummary)?;
+
+        Ok(summary)
+    }
+}
+
+/// The maximum number of bullet points, which are echoed by the mock backend.
+const MOCK_BULLET_POINTS: usize = 5;
+
+/// The answer of the mock backend if the prompt does not contain any bullet points.
+const MOCK_ANSWER: &str = "- This text was generated by the mock LLM backend.";
+
+/// Answers the prompts deterministically without network access by echoing the first bullet points
+/// contained in the prompt (e.g. of the release notes) or a canned answer if there are none.
+pub struct MockProvider;
+
+#[async_trait]
+impl LlmProvider for MockProvider {
+    async fn complete(&self, prompt: String, _model: OpenAIModel) -> Result<String, SummaryError> {
+        let bullet_points: Vec<String> = prompt
+            .lines()
+            .filter_map(|line| {
+                // NOTE: the inputs are quoted in the prompts, so that the quotes are removed first
+                let line = line.trim().trim_matches('"');
+                line.strip_prefix("- ").or_else(|| line.strip_prefix("* "))
+            })
+            .take(MOCK_BULLET_POINTS)
+            .map(|item| format!("- {}", item.trim()))
+            .collect();
+
+        match bullet_points.is_empty() {
+            true => Ok(MOCK_ANSWER.to_string()),
+            false => Ok(bullet_points.join("\n")),
+        }
+    }
+}
+
 #[cfg(not(feature = "remote"))]
 #[cfg(test)]
 mod summary_tests {
@@ -84,7 +375,8 @@ mod summary_tests {
         let release: Release = serde_json::from_str(include_str!("testdata/release.json"))
             .expect("failed to parse release JSON");
 
-        let res = create_summary(&release, OpenAIModel::Gpt4o).await;
+        let notes = crate::release::get_release_notes(&release).unwrap();
+        let res = create_summary(&OpenAIProvider, &notes, OpenAIModel::Gpt4o).await;
         assert!(
             res.is_ok(),
             "expected no error; got:\n{}\n",
@@ -99,3 +391,140 @@ mod summary_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod community_summary_tests {
+    use super::*;
+
+    #[test]
+    fn test_build_community_summary_prompt() {
+        let prompt = build_community_summary_prompt("- add precompile");
+        assert!(prompt.contains("plain language"));
+        assert!(prompt.ends_with("\n\"- add precompile\""));
+    }
+}
+
+#[cfg(test)]
+mod description_tests {
+    use super::*;
+
+    #[test]
+    fn test_build_description_prompt() {
+        let prompt = build_description_prompt("Community Pool Spend", "- fund audits");
+        assert!(prompt.contains("Title:\n\"Community Pool Spend\""));
+        assert!(prompt.ends_with("Bullet points:\n\"- fund audits\""));
+    }
+}
+
+#[cfg(test)]
+mod citation_tests {
+    use super::*;
+
+    #[test]
+    fn test_build_citation_prompt() {
+        let prompt = build_citation_prompt(
+            "- add precompile",
+            &[PullRequest {
+                number: 1234,
+                title: "feat(evm): add precompile".to_string(),
+            }],
+        );
+        assert!(prompt.contains("\"- add precompile\""));
+        assert!(prompt.ends_with("Pull requests:\n\"#1234: feat(evm): add precompile\""));
+    }
+
+    #[test]
+    fn test_remove_invalid_citations() {
+        let included = HashSet::from([1234, 1240]);
+        let summary = "- Added the staking precompile (#1234, #999)\n\
+                       - Fixed the API response (#1240)\n\
+                       - Improved performance (#42)\n\
+                       - Bumped dependencies";
+
+        let (summary, invalid) = remove_invalid_citations(summary, &included);
+        assert_eq!(
+            summary,
+            "- Added the staking precompile (#1234)\n\
+             - Fixed the API response (#1240)\n\
+             - Improved performance\n\
+             - Bumped dependencies"
+        );
+        assert_eq!(invalid, vec![999, 42]);
+    }
+}
+
+#[cfg(test)]
+mod truncate_tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_notes() {
+        assert_eq!(truncate_notes("- a\n- b\n", 10), "- a\n- b");
+        assert_eq!(
+            truncate_notes("- first\n- second\n- third", 18),
+            "- first\n- second\n..."
+        );
+    }
+}
+
+#[cfg(test)]
+mod lint_tests {
+    use super::*;
+
+    #[test]
+    fn test_build_lint_prompt() {
+        let prompt = build_lint_prompt("# Upgrade", "- Rollback plan");
+        assert!(prompt.contains("Guidelines:\n\"- Rollback plan\""));
+        assert!(prompt.contains("Proposal:\n\"# Upgrade\""));
+    }
+
+    #[test]
+    fn test_parse_lint_response() {
+        let response =
+            "Missing items:\n- Rollback plan: not mentioned\n  - Risks: no risks are listed\n";
+        assert_eq!(
+            parse_lint_response(response),
+            vec!["Rollback plan: not mentioned", "Risks: no risks are listed"]
+        );
+        assert!(parse_lint_response(" NONE\n").is_empty());
+    }
+}
+
+#[cfg(test)]
+mod mock_tests {
+    use super::*;
+    use octocrab::models::repos::Release;
+
+    #[tokio::test]
+    async fn test_mock_provider() {
+        let release: Release = serde_json::from_str(include_str!("testdata/release.json"))
+            .expect("failed to parse release JSON");
+        let prompt = build_summary_prompt(&crate::release::get_release_notes(&release).unwrap());
+
+        let summary = MockProvider
+            .complete(prompt.clone(), OpenAIModel::Gpt4o)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            MockProvider
+                .complete(prompt, OpenAIModel::Gpt4o)
+                .await
+                .unwrap(),
+            "expected deterministic answer"
+        );
+        assert!(summary.starts_with("- "));
+        assert!(summary.lines().count() <= MOCK_BULLET_POINTS);
+
+        assert_eq!(
+            MockProvider
+                .complete(
+                    build_description_prompt("Title", "no bullet points"),
+                    OpenAIModel::Gpt4o
+                )
+                .await
+                .unwrap(),
+            MOCK_ANSWER
+        );
+    }
+}
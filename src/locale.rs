@@ -0,0 +1,178 @@
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Weekday};
+use clap::ValueEnum;
+use num_format::ToFormattedString;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// The locales, in which numbers and dates are rendered in the proposal and announcements.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// English, e.g. `18,500,000` and `4PM UTC on Mon., October 23., 2023`.
+    #[default]
+    En,
+    /// German, e.g. `18.500.000` and `Mo., 23. Oktober 2023, 16:00 Uhr UTC`.
+    De,
+    /// Spanish, e.g. `18.500.000` and `lun., 23 de octubre de 2023, 16:00 UTC`.
+    Es,
+}
+
+impl Locale {
+    /// Returns the names of the months in the locale, starting with January.
+    fn months(&self) -> [&'static str; 12] {
+        match self {
+            Locale::En => [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+            Locale::De => [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            Locale::Es => [
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+        }
+    }
+
+    /// Returns the abbreviated name of the given weekday in the locale, without the trailing period.
+    fn weekday(&self, weekday: Weekday) -> String {
+        let names = match self {
+            Locale::En => return weekday.to_string(),
+            Locale::De => ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+            Locale::Es => ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+        };
+
+        names[weekday.num_days_from_monday() as usize].to_string()
+    }
+
+    /// Returns the given number with the thousands separator of the locale.
+    pub fn format_number(&self, number: u64) -> String {
+        let locale = match self {
+            Locale::En => num_format::Locale::en,
+            Locale::De => num_format::Locale::de,
+            Locale::Es => num_format::Locale::es,
+        };
+
+        number.to_formatted_string(&locale)
+    }
+
+    /// Returns a string representation of the given time in the locale,
+    /// which is used for the upgrade time and the voting period.
+    pub fn format_time<T: TimeZone>(&self, time: DateTime<T>) -> String
+    where
+        T::Offset: Display,
+    {
+        let month = self.months()[time.month0() as usize];
+        let weekday = self.weekday(time.weekday());
+
+        match self {
+            Locale::En => {
+                let (is_pm, hour) = time.hour12();
+                format!(
+                    "{}{} {} on {}., {} {}., {}",
+                    hour,
+                    if is_pm { "PM" } else { "AM" },
+                    time.offset(),
+                    weekday,
+                    month,
+                    time.day(),
+                    time.year(),
+                )
+            }
+            Locale::De => format!(
+                "{}., {}. {} {}, {:02}:{:02} Uhr {}",
+                weekday,
+                time.day(),
+                month,
+                time.year(),
+                time.hour(),
+                time.minute(),
+                time.offset(),
+            ),
+            Locale::Es => format!(
+                "{}., {} de {} de {}, {:02}:{:02} {}",
+                weekday,
+                time.day(),
+                month,
+                time.year(),
+                time.hour(),
+                time.minute(),
+                time.offset(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use chrono_tz::Europe;
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(Locale::En.format_number(18_500_000), "18,500,000");
+        assert_eq!(Locale::De.format_number(18_500_000), "18.500.000");
+        assert_eq!(Locale::Es.format_number(18_500_000), "18.500.000");
+    }
+
+    #[test]
+    fn test_format_time() {
+        let time = Utc.with_ymd_and_hms(2023, 10, 23, 16, 0, 0).unwrap();
+        assert_eq!(
+            Locale::En.format_time(time),
+            "4PM UTC on Mon., October 23., 2023"
+        );
+        assert_eq!(
+            Locale::De.format_time(time),
+            "Mo., 23. Oktober 2023, 16:00 Uhr UTC"
+        );
+        assert_eq!(
+            Locale::Es.format_time(time),
+            "lun., 23 de octubre de 2023, 16:00 UTC"
+        );
+        assert_eq!(
+            Locale::De.format_time(time.with_timezone(&Europe::Berlin)),
+            "Mo., 23. Oktober 2023, 18:00 Uhr CEST"
+        );
+    }
+
+    #[test]
+    fn test_parse_locale() {
+        let locale: Locale = serde_json::from_str("\"de\"").unwrap();
+        assert_eq!(locale, Locale::De);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::chain::ChainConfig;
+use crate::errors::LocalnetError;
+use crate::evmosd::ClientConfig;
+use crate::helper::get_voting_period;
+use crate::network::Network;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::{fs, process};
+
+/// The names of the funded test keys, that are created in the local node keyring.
+pub const LOCALNET_KEYS: [&str; 3] = ["dev0", "dev1", "dev2"];
+
+/// The moniker of the local node.
+const MONIKER: &str = "localtestnet";
+
+/// The amount of tokens that each test key is funded with in the genesis file.
+const GENESIS_BALANCE: &str = "100000000000000000000000000";
+
+/// The amount of tokens that the first test key is staking in the genesis transaction.
+const GENTX_AMOUNT: &str = "1000000000000000000000";
+
+/// Initializes the home directory for a local node, which can be used to test the generated commands.
+///
+/// This creates the genesis file with a short voting period, funded test keys in the `test` keyring
+/// and the client configuration for the local node.
+pub fn init(home: &Path, chain_config: &ChainConfig, overwrite: bool) -> Result<(), LocalnetError> {
+    if home.exists() {
+        if !overwrite {
+            return Err(LocalnetError::HomeExists(home.to_path_buf()));
+        }
+        fs::remove_dir_all(home)?;
+    }
+
+    let network = Network::LocalNode;
+    let chain_id = chain_config.get_chain_id(network);
+    let fee_denom = chain_config.get_fee_denom(network);
+    let stake_denom = chain_config.get_stake_denom(network);
+    // NOTE: the test keys need tokens to stake and to pay the fees, which can be different denominations
+    let genesis_balance = match fee_denom == stake_denom {
+        true => format!("{}{}", GENESIS_BALANCE, stake_denom),
+        false => format!(
+            "{}{},{}{}",
+            GENESIS_BALANCE, fee_denom, GENESIS_BALANCE, stake_denom
+        ),
+    };
+    let home_str = home
+        .to_str()
+        .expect("failed to convert home path to string");
+
+    run_evmosd(&["init", MONIKER, "--chain-id", &chain_id, "--home", home_str])?;
+
+    for key in LOCALNET_KEYS {
+        run_evmosd(&[
+            "keys",
+            "add",
+            key,
+            "--keyring-backend",
+            "test",
+            "--algo",
+            "eth_secp256k1",
+            "--home",
+            home_str,
+        ])?;
+
+        run_evmosd(&[
+            "add-genesis-account",
+            key,
+            &genesis_balance,
+            "--keyring-backend",
+            "test",
+            "--home",
+            home_str,
+        ])?;
+    }
+
+    let genesis_path = get_genesis_path(home);
+    let mut genesis: Value = serde_json::from_str(&fs::read_to_string(&genesis_path)?)?;
+    update_genesis(&mut genesis, network, &fee_denom, &stake_denom);
+    fs::write(&genesis_path, serde_json::to_string_pretty(&genesis)?)?;
+
+    run_evmosd(&[
+        "gentx",
+        LOCALNET_KEYS[0],
+        &format!("{}{}", GENTX_AMOUNT, stake_denom),
+        "--keyring-backend",
+        "test",
+        "--chain-id",
+        &chain_id,
+        "--home",
+        home_str,
+    ])?;
+    run_evmosd(&["collect-gentxs", "--home", home_str])?;
+    run_evmosd(&["validate-genesis", "--home", home_str])?;
+
+    let app_config_path = home.join("config/app.toml");
+    let app_config = enable_api(&fs::read_to_string(&app_config_path)?)?;
+    fs::write(app_config_path, app_config)?;
+
+    let client_config = get_localnet_client_config(chain_id);
+    fs::write(
+        home.join("config/client.toml"),
+        toml::to_string(&client_config)?,
+    )?;
+
+    Ok(())
+}
+
+/// Returns the path to the genesis file in the given home directory.
+fn get_genesis_path(home: &Path) -> PathBuf {
+    home.join("config/genesis.json")
+}
+
+/// Runs the Evmos CLI with the given arguments and returns the standard output.
+fn run_evmosd(args: &[&str]) -> Result<String, LocalnetError> {
+    let output = process::Command::new("evmosd").args(args).output()?;
+    if !output.status.success() {
+        return Err(LocalnetError::Command(
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Updates the genesis file to use the given fee and staking denominations
+/// and the configured voting period, so that proposals can be tested in a timely manner.
+/// The fee denomination is also used as the gas token of the EVM.
+fn update_genesis(genesis: &mut Value, network: Network, fee_denom: &str, stake_denom: &str) {
+    let voting_period = format!("{}s", get_voting_period(network).num_seconds());
+    let app_state = &mut genesis["app_state"];
+
+    app_state["staking"]["params"]["bond_denom"] = json!(stake_denom);
+    app_state["crisis"]["constant_fee"]["denom"] = json!(fee_denom);
+    app_state["evm"]["params"]["evm_denom"] = json!(fee_denom);
+    app_state["inflation"]["params"]["mint_denom"] = json!(stake_denom);
+
+    let gov_params = &mut app_state["gov"]["params"];
+    gov_params["min_deposit"] = json!([{"denom": stake_denom, "amount": "1000000"}]);
+    gov_params["max_deposit_period"] = json!(voting_period);
+    gov_params["voting_period"] = json!(voting_period);
+}
+
+/// Enables the REST API in the given app configuration, which is needed
+/// to query the local node.
+fn enable_api(app_config: &str) -> Result<String, LocalnetError> {
+    let mut config: toml::Table = toml::from_str(app_config)?;
+    let api = config
+        .entry("api")
+        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+
+    if let toml::Value::Table(api) = api {
+        api.insert("enable".to_string(), toml::Value::Boolean(true));
+    }
+
+    Ok(toml::to_string(&config)?)
+}
+
+/// Returns the client configuration to interact with the local node.
+fn get_localnet_client_config(chain_id: String) -> ClientConfig {
+    ClientConfig {
+        chain_id,
+        keyring_backend: "test".to_string(),
+        output: "text".to_string(),
+        node: "tcp://localhost:26657".to_string(),
+        broadcast_mode: "sync".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::get_chain_id;
+
+    #[test]
+    fn test_update_genesis() {
+        let mut genesis = json!({
+            "app_state": {
+                "gov": {
+                    "params": {
+                        "min_deposit": [{"denom": "stake", "amount": "10000000"}],
+                        "max_deposit_period": "172800s",
+                        "voting_period": "172800s",
+                    }
+                },
+                "staking": {"params": {"bond_denom": "stake"}},
+            }
+        });
+
+        update_genesis(&mut genesis, Network::LocalNode, "agas", "aevmos");
+
+        let gov_params = &genesis["app_state"]["gov"]["params"];
+        assert_eq!(gov_params["voting_period"], "3600s");
+        assert_eq!(gov_params["max_deposit_period"], "3600s");
+        assert_eq!(gov_params["min_deposit"][0]["denom"], "aevmos");
+        assert_eq!(
+            genesis["app_state"]["staking"]["params"]["bond_denom"],
+            "aevmos"
+        );
+        assert_eq!(genesis["app_state"]["evm"]["params"]["evm_denom"], "agas");
+    }
+
+    #[test]
+    fn test_enable_api() {
+        let app_config = r#"
+            minimum-gas-prices = "0aevmos"
+
+            [api]
+            enable = false
+            address = "tcp://0.0.0.0:1317"
+        "#;
+
+        let updated = enable_api(app_config).expect("failed to enable API");
+        let config: toml::Table = toml::from_str(&updated).expect("failed to parse config");
+        assert_eq!(config["api"]["enable"].as_bool(), Some(true));
+        assert_eq!(
+            config["api"]["address"].as_str(),
+            Some("tcp://0.0.0.0:1317")
+        );
+        assert_eq!(config["minimum-gas-prices"].as_str(), Some("0aevmos"));
+    }
+
+    #[test]
+    fn test_get_localnet_client_config() {
+        let client_config = get_localnet_client_config(get_chain_id(Network::LocalNode));
+        let contents = toml::to_string(&client_config).expect("failed to serialize config");
+        let parsed: ClientConfig = toml::from_str(&contents).expect("failed to parse config");
+
+        assert_eq!(parsed.chain_id, "evmos_9000-4");
+        assert_eq!(parsed.keyring_backend, "test");
+        assert!(contents.contains("keyring-backend = \"test\""));
+    }
+}
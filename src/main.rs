@@ -1,42 +1,334 @@
+mod account;
+mod address;
+mod answers;
+mod approval;
+mod authority;
 mod balance;
 mod block;
+mod breaking;
+mod cache;
+mod calendar;
+mod chain;
 mod cli;
+mod color;
 mod command;
 mod commonwealth;
+mod context;
+mod diff;
+mod digest;
+mod downtime;
+mod endpoints;
 mod errors;
 mod evmosd;
+mod export;
+mod feegrant;
+mod glossary;
+mod gov;
+mod grpc;
+mod handler;
+mod heights;
 mod helper;
+mod history;
 mod http;
+mod ibc;
+mod import;
 mod inputs;
+mod instructions;
 mod keys;
+mod links;
 mod llm;
+mod locale;
+mod localnet;
+mod manifest;
+mod markdown;
+mod messages;
 mod mock_error;
 mod network;
+mod notes;
+mod notifier;
+mod preflight;
+mod profile;
 mod proposal;
+mod readability;
+mod registry;
 mod release;
+mod reminder;
+mod resolve;
+mod revision;
+mod runbook;
+mod schedule;
+mod schema;
+mod secret;
+mod sequence;
+mod serve;
+mod signature;
+mod status;
+mod telegram;
+mod telemetry;
+mod template;
+mod tracking;
+mod tx;
+mod upgrade_info;
+mod upload;
 mod utils;
+mod validators;
 mod version;
+mod watch;
 
 use clap::Parser;
-use std::process; // NOTE: needs to be imported for Cli::parse() to work
+use std::process;
+use std::sync::Arc; // NOTE: needs to be imported for Cli::parse() to work
 
 // Crate imports
-use crate::cli::{Cli, SubCommand};
+use crate::cli::{
+    ChainSubCommand, Cli, KeysSubCommand, LocalnetSubCommand, SubCommand, TemplateSubCommand,
+};
+use crate::context::Context;
+use crate::inputs::{InputProvider, Prompt};
+use crate::resolve::GlobalFlags;
 
 #[tokio::main]
 async fn main() {
-    match Cli::parse().subcmd {
+    let cli = Cli::parse();
+    if cli.profile {
+        profile::enable();
+    }
+    let colored = color::apply_color_choice(cli.color);
+    let inputs: Box<dyn InputProvider> = match cli.answers {
+        Some(path) => match answers::Answers::load(&path) {
+            Ok(answers) => Box::new(answers),
+            Err(e) => {
+                println!("Error loading answers file: {}", e);
+                process::exit(1);
+            }
+        },
+        None => Box::new(Prompt),
+    };
+    let flags = GlobalFlags {
+        network: cli.network,
+        home: cli.home,
+    };
+
+    let ctx = match Context::from_env(inputs, flags, cli.llm_backend, colored) {
+        Ok(ctx) => Arc::new(ctx),
+        Err(e) => {
+            println!("Error setting up context: {}", e);
+            process::exit(1);
+        }
+    };
+
+    // NOTE: files are written atomically, so interrupting does not leave partial files behind
+    tokio::select! {
+        _ = run(&ctx, cli.subcmd) => profile::print_report(),
+        _ = tokio::signal::ctrl_c() => {
+            println!("Interrupted; exiting");
+            process::exit(130);
+        }
+    }
+}
+
+/// Runs the given sub-command with the given context and exits with an error code if it fails.
+async fn run(ctx: &Arc<Context>, subcmd: SubCommand) {
+    match subcmd {
         SubCommand::GenerateProposal(args) => {
-            if let Err(e) = cli::generate_proposal(args).await {
+            if let Err(e) = cli::generate_proposal(ctx, args).await {
                 println!("Error generating proposal: {}", e);
                 process::exit(1);
             };
         }
         SubCommand::GenerateCommand(args) => {
-            if let Err(e) = cli::generate_command(args).await {
+            if let Err(e) = cli::generate_command(ctx, args).await {
                 println!("Error generating command: {}", e);
                 process::exit(1);
             }
         }
+        SubCommand::TextProposal(args) => {
+            if let Err(e) = cli::generate_text_proposal(ctx, args).await {
+                println!("Error generating text proposal: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Block(args) => {
+            if let Err(e) = cli::query_block(ctx, args).await {
+                println!("Error querying block: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Localnet(args) => match args.subcmd {
+            LocalnetSubCommand::Init(args) => {
+                if let Err(e) = cli::init_localnet(ctx, args) {
+                    println!("Error initializing local node: {}", e);
+                    process::exit(1);
+                }
+            }
+        },
+        SubCommand::Diff(args) => {
+            if let Err(e) = cli::diff_configs(ctx, args) {
+                println!("Error comparing configurations: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Schema(args) => {
+            if let Err(e) = cli::export_schema(args) {
+                println!("Error exporting schema: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Verify(args) => {
+            if let Err(e) = cli::verify_proposal(ctx, args).await {
+                println!("Error verifying proposal: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::ComparePrevious(args) => {
+            if let Err(e) = cli::compare_previous(ctx, args).await {
+                println!("Error comparing with previous proposal: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::History(args) => {
+            if let Err(e) = cli::show_history(ctx, args).await {
+                println!("Error showing history: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Status(args) => {
+            if let Err(e) = cli::show_status(ctx, args).await {
+                println!("Error querying status: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::ExportStatus(args) => {
+            if let Err(e) = cli::export_status(ctx, args).await {
+                println!("Error exporting proposal status: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Digest(args) => {
+            if let Err(e) = cli::show_digest(ctx, args).await {
+                println!("Error creating digest: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Lint(args) => {
+            if let Err(e) = cli::lint_proposal(ctx, args).await {
+                println!("Error linting proposal: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Validators(args) => {
+            if let Err(e) = cli::export_validators(ctx, args).await {
+                println!("Error exporting validators: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Runbook(args) => {
+            if let Err(e) = cli::generate_runbook(ctx, args).await {
+                println!("Error generating runbook: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Calendar(args) => {
+            if let Err(e) = cli::generate_calendar(ctx, args).await {
+                println!("Error generating calendar: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Reminder(args) => {
+            if let Err(e) = cli::generate_vote_reminder(ctx, args).await {
+                println!("Error generating vote reminder: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Nag(args) => {
+            if let Err(e) = cli::show_nag_list(ctx, args).await {
+                println!("Error creating nag list: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Import(args) => {
+            if let Err(e) = cli::import_proposal(ctx, args).await {
+                println!("Error importing proposal: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Instructions(args) => {
+            if let Err(e) = cli::generate_instructions(ctx, args).await {
+                println!("Error generating instructions: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Serve(args) => {
+            if let Err(e) = cli::run_server(ctx.clone(), args).await {
+                println!("Error running server: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Telegram(args) => {
+            if let Err(e) = cli::run_telegram_bot(ctx, args).await {
+                println!("Error running Telegram bot: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Encrypt(args) => {
+            if let Err(e) = cli::encrypt_value(args) {
+                println!("Error encrypting value: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Promote(args) => {
+            if let Err(e) = cli::promote_revision(ctx, args) {
+                println!("Error promoting revision: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Finalize(args) => {
+            if let Err(e) = cli::finalize(ctx, args).await {
+                println!("Error finalizing draft: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Approve(args) => {
+            if let Err(e) = cli::approve(ctx, args) {
+                println!("Error approving proposal: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::VerifyApprovals(args) => {
+            if let Err(e) = cli::verify_approvals(ctx, args) {
+                println!("Error verifying approvals: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Keys(args) => match args.subcmd {
+            KeysSubCommand::Import(args) => {
+                if let Err(e) = cli::import_key(ctx, args) {
+                    println!("Error importing key: {}", e);
+                    process::exit(1);
+                }
+            }
+        },
+        SubCommand::Chain(args) => match args.subcmd {
+            ChainSubCommand::Add(args) => {
+                if let Err(e) = cli::add_chain(ctx, args).await {
+                    println!("Error adding chain: {}", e);
+                    process::exit(1);
+                }
+            }
+        },
+        SubCommand::Heights(args) => {
+            if let Err(e) = cli::estimate_heights(ctx, args).await {
+                println!("Error estimating heights: {}", e);
+                process::exit(1);
+            }
+        }
+        SubCommand::Template(args) => match args.subcmd {
+            TemplateSubCommand::Test(args) => {
+                if let Err(e) = cli::test_template(ctx, args).await {
+                    println!("Error testing template: {}", e);
+                    process::exit(1);
+                }
+            }
+        },
     }
 }
@@ -0,0 +1,125 @@
+use crate::endpoints::get_used_endpoints;
+use crate::errors::ManifestError;
+use crate::utils::write_atomically;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The name of the manifest file in the working directory, which lists the generated artifacts
+/// with their hashes, so that published files can be verified against the reviewed ones.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Represents a generated artifact in the manifest.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Artifact {
+    /// The SHA256 hash of the artifact.
+    pub sha256: String,
+    /// The configuration file, from which the artifact was generated (optional).
+    #[serde(default)]
+    pub config: Option<String>,
+    /// The SHA256 hash of the configuration file at the time of generation (optional).
+    #[serde(default)]
+    pub config_sha256: Option<String>,
+    /// The time at which the artifact was generated.
+    pub generated_at: DateTime<Utc>,
+    /// The endpoints, which were queried during the generation, so that the used data can be reproduced.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<String>,
+}
+
+/// Represents the manifest of the generated artifacts, which are keyed by their file name.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub artifacts: BTreeMap<String, Artifact>,
+}
+
+impl Manifest {
+    /// Loads the manifest from the given file.
+    /// If the file does not exist yet, an empty manifest is returned.
+    pub fn load(path: &Path) -> Result<Manifest, ManifestError> {
+        if !path.exists() {
+            return Ok(Manifest::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the manifest to the given file.
+    pub fn save(&self, path: &Path) -> Result<(), ManifestError> {
+        Ok(write_atomically(
+            path,
+            serde_json::to_string_pretty(self)?.as_bytes(),
+        )?)
+    }
+}
+
+/// Returns the SHA256 hash of the file at the given path.
+pub fn hash_file(path: &Path) -> Result<String, ManifestError> {
+    Ok(hex::encode(Sha256::digest(fs::read(path)?)))
+}
+
+/// Records the given files in the given directory as generated artifacts in the manifest,
+/// together with the hash of the configuration they were generated from.
+///
+/// Entries of previously generated artifacts are kept, so that the manifest covers
+/// the proposal, the command and the other artifacts generated in separate runs.
+pub fn record(
+    dir: &Path,
+    config: Option<&Path>,
+    files: &[String],
+    now: DateTime<Utc>,
+) -> Result<(), ManifestError> {
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let mut manifest = Manifest::load(&manifest_path)?;
+    let config_sha256 = config.map(hash_file).transpose()?;
+    let endpoints = get_used_endpoints();
+
+    for file in files {
+        manifest.artifacts.insert(
+            file.clone(),
+            Artifact {
+                sha256: hash_file(&dir.join(file))?,
+                config: config.map(|config| config.to_string_lossy().to_string()),
+                config_sha256: config_sha256.clone(),
+                generated_at: now,
+                endpoints: endpoints.clone(),
+            },
+        );
+    }
+
+    manifest.save(&manifest_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[test]
+    fn test_record() {
+        let test_dir = TestDir::temp();
+        let dir = test_dir.root();
+        let config = dir.join("proposal.json");
+        fs::write(&config, "{}").unwrap();
+        fs::write(dir.join("proposal.md"), "# Description").unwrap();
+        fs::write(dir.join("proposal.sh"), "evmosd tx gov").unwrap();
+        let now = Utc::now();
+
+        record(dir, Some(&config), &["proposal.md".to_string()], now).unwrap();
+        record(dir, None, &["proposal.sh".to_string()], now).unwrap();
+
+        let manifest = Manifest::load(&dir.join(MANIFEST_FILE)).unwrap();
+        assert_eq!(manifest.artifacts.len(), 2);
+
+        let proposal = &manifest.artifacts["proposal.md"];
+        assert_eq!(
+            proposal.sha256,
+            "8343b066b64de2be4e1c31cd1b145dd4149124aefd942ec833a893c4607a1480"
+        );
+        assert_eq!(proposal.config_sha256, Some(hash_file(&config).unwrap()));
+        assert!(manifest.artifacts["proposal.sh"].config.is_none());
+    }
+}
@@ -0,0 +1,527 @@
+use crate::chain::get_config_dir;
+use crate::errors::MarkdownError;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// The name of the file inside of the proposer configuration directory,
+/// which contains the style that rendered Markdown is formatted with.
+const MARKDOWN_FILE: &str = "markdown.toml";
+
+/// The list markers, which are allowed in Markdown.
+const LIST_MARKERS: [char; 3] = ['-', '*', '+'];
+
+/// The deepest level of ATX headings.
+const MAX_HEADING_LEVEL: usize = 6;
+
+/// Contains the style, which the rendered Markdown is formatted with.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct MarkdownStyle {
+    /// The maximum width of prose lines, at which they are wrapped. Lines are not wrapped if no width is set.
+    #[serde(default)]
+    pub line_width: Option<usize>,
+    /// The marker of unordered list items, which is one of `-`, `*` or `+`.
+    #[serde(default = "default_list_marker")]
+    pub list_marker: char,
+}
+
+impl Default for MarkdownStyle {
+    fn default() -> Self {
+        MarkdownStyle {
+            line_width: None,
+            list_marker: default_list_marker(),
+        }
+    }
+}
+
+/// Returns the default marker of unordered list items.
+fn default_list_marker() -> char {
+    '-'
+}
+
+impl MarkdownStyle {
+    /// Loads the style from the given file. If the file does not exist, the default style is used.
+    pub fn load(path: &Path) -> Result<MarkdownStyle, MarkdownError> {
+        if !path.exists() {
+            return Ok(MarkdownStyle::default());
+        }
+
+        let style: MarkdownStyle = toml::from_str(&fs::read_to_string(path)?)?;
+        if !LIST_MARKERS.contains(&style.list_marker) {
+            return Err(MarkdownError::InvalidListMarker(style.list_marker));
+        }
+
+        Ok(style)
+    }
+}
+
+/// Loads the Markdown style from the proposer configuration directory.
+pub fn get_markdown_style() -> Result<MarkdownStyle, MarkdownError> {
+    MarkdownStyle::load(&get_config_dir().join(MARKDOWN_FILE))
+}
+
+/// Formats the given Markdown text with the given style, similar to the autofix of `markdownlint`.
+///
+/// Heading levels only increase by one at a time, headings use a single space after the hashes,
+/// unordered lists use the configured marker, trailing whitespace and repeated blank lines are removed
+/// and prose lines exceeding the configured width are wrapped. Code blocks are left unchanged.
+pub fn format_markdown(markdown: &str, style: &MarkdownStyle) -> String {
+    format_markdown_below(markdown, style, 0)
+}
+
+/// Formats the given Markdown fragment like [`format_markdown`], which is inserted below a heading
+/// of the given level, e.g. generated text in a section of a template.
+///
+/// The headings of the fragment are nested below the enclosing heading, so that they are neither
+/// demoted to the top level nor end the enclosing section.
+pub fn format_markdown_below(markdown: &str, style: &MarkdownStyle, base_level: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut fence: Option<String> = None;
+    let mut previous_level = base_level;
+
+    for line in markdown.lines() {
+        if let Some(marker) = &fence {
+            if line.trim_start().starts_with(marker.as_str()) {
+                fence = None;
+            }
+            lines.push(line.to_string());
+            continue;
+        }
+        if let Some(marker) = get_fence_marker(line) {
+            fence = Some(marker);
+            lines.push(line.trim_end().to_string());
+            continue;
+        }
+
+        let hard_break = line.ends_with("  ") && !line.trim().is_empty();
+        let line = line.trim_end();
+        if line.is_empty() {
+            if lines.last().is_some_and(|last| !last.is_empty()) {
+                lines.push(String::new());
+            }
+            continue;
+        }
+
+        if let Some((level, text)) = parse_heading(line) {
+            // NOTE: a heading may only be one level deeper than the previous one
+            let level = level
+                .clamp(base_level + 1, previous_level + 1)
+                .min(MAX_HEADING_LEVEL);
+            previous_level = level;
+            lines.push(format!("{} {}", "#".repeat(level), text));
+            continue;
+        }
+
+        let line = replace_list_marker(line, style.list_marker);
+        let mut wrapped = match style.line_width {
+            Some(width) if is_prose(&line) => wrap_line(&line, width),
+            _ => vec![line],
+        };
+        if hard_break {
+            if let Some(last) = wrapped.last_mut() {
+                last.push_str("  ");
+            }
+        }
+        lines.extend(wrapped);
+    }
+
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+
+    format!("{}\n", lines.join("\n"))
+}
+
+/// Returns the marker of the code fence, which is opened by the given line, if any.
+fn get_fence_marker(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    ["```", "~~~"]
+        .into_iter()
+        .find(|marker| trimmed.starts_with(marker))
+        .map(str::to_string)
+}
+
+/// Returns the level of the last heading before the first line of the given template,
+/// which contains the given placeholder, or zero if there is none.
+pub fn get_enclosing_heading_level(template: &str, placeholder: &str) -> usize {
+    let mut fence: Option<String> = None;
+    let mut level = 0;
+
+    for line in template.lines() {
+        if line.contains(placeholder) {
+            break;
+        }
+        if let Some(marker) = &fence {
+            if line.trim_start().starts_with(marker.as_str()) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(marker) = get_fence_marker(line) {
+            fence = Some(marker);
+            continue;
+        }
+        if let Some((heading_level, _)) = parse_heading(line.trim_end()) {
+            level = heading_level;
+        }
+    }
+
+    level
+}
+
+/// Returns the level and text of the given line, if it is an ATX heading.
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > MAX_HEADING_LEVEL {
+        return None;
+    }
+
+    let rest = &line[level..];
+    // NOTE: hashtags like `#123` are not headings, which requires a space or nothing after the hashes
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) && !rest.chars().all(|c| c == '#') {
+        return None;
+    }
+
+    let text = rest.trim().trim_end_matches('#').trim_end();
+    Some((level, text))
+}
+
+/// Returns the given line with the marker of an unordered list item replaced by the given marker.
+fn replace_list_marker(line: &str, marker: char) -> String {
+    let content = line.trim_start();
+    let indent = &line[..line.len() - content.len()];
+
+    match content.chars().next() {
+        Some(c) if LIST_MARKERS.contains(&c) && content[1..].starts_with(' ') => {
+            // NOTE: thematic breaks like `* * *` must not be turned into list items
+            if content
+                .chars()
+                .all(|c| c == ' ' || LIST_MARKERS.contains(&c))
+            {
+                return line.to_string();
+            }
+            format!("{}{}{}", indent, marker, &content[1..])
+        }
+        _ => line.to_string(),
+    }
+}
+
+/// Returns whether the given line contains prose, which can be wrapped.
+///
+/// Tables, block quotes, HTML, link reference definitions and indented code are left unchanged.
+fn is_prose(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    if line.starts_with("    ") && !is_list_item(trimmed) {
+        return false;
+    }
+
+    !(trimmed.starts_with('|')
+        || trimmed.starts_with('>')
+        || trimmed.starts_with('<')
+        || (trimmed.starts_with('[') && trimmed.contains("]:")))
+}
+
+/// Returns whether the given line without indentation is an item of an unordered or ordered list.
+fn is_list_item(line: &str) -> bool {
+    get_list_prefix_width(line).is_some()
+}
+
+/// Returns the width of the list marker including the following space, if the given line is a list item.
+fn get_list_prefix_width(line: &str) -> Option<usize> {
+    if line.len() > 1 && LIST_MARKERS.contains(&line.chars().next()?) && line[1..].starts_with(' ')
+    {
+        return Some(2);
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    match digits > 0 && line[digits..].starts_with(". ") {
+        true => Some(digits + 2),
+        false => None,
+    }
+}
+
+/// Wraps the given line at the spaces between words, so that the lines do not exceed the given width.
+///
+/// Words, which exceed the width on their own (e.g. long links), are kept on a single line.
+/// The continuation lines of list items are indented to align with the text of the item.
+/// Lines are not broken before block markers like `-` or `1.`, which would turn the continuation
+/// into a list item, and inline code spans are kept intact.
+fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    if line.chars().count() <= width {
+        return vec![line.to_string()];
+    }
+
+    let content = line.trim_start();
+    let indent = line.len() - content.len();
+    let continuation = " ".repeat(indent + get_list_prefix_width(content).unwrap_or_default());
+
+    let mut wrapped = Vec::new();
+    let mut current = line[..indent].to_string();
+    let mut current_width = indent;
+    let mut is_empty = true;
+    for word in split_words(content) {
+        let word_width = word.chars().count();
+        if !is_empty && current_width + 1 + word_width > width && !is_block_marker(word) {
+            wrapped.push(current);
+            current = continuation.clone();
+            current_width = continuation.len();
+            is_empty = true;
+        }
+        if !is_empty {
+            current.push(' ');
+            current_width += 1;
+        }
+        current.push_str(word);
+        current_width += word_width;
+        is_empty = false;
+    }
+    wrapped.push(current);
+
+    wrapped
+}
+
+/// Splits the given text into the words between its spaces.
+/// Inline code spans are kept within a single word, so that the spaces inside of them are preserved.
+fn split_words(text: &str) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut words = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b' ' => {
+                if let Some(word_start) = start.take() {
+                    words.push(&text[word_start..idx]);
+                }
+                idx += 1;
+            }
+            b'`' => {
+                start.get_or_insert(idx);
+                let run = get_backtick_run(&bytes[idx..]);
+                idx += run;
+                // NOTE: backticks without a closing run of the same length are literal backticks
+                if let Some(end) = find_closing_run(&bytes[idx..], run) {
+                    idx += end + run;
+                }
+            }
+            _ => {
+                start.get_or_insert(idx);
+                idx += 1;
+            }
+        }
+    }
+    if let Some(word_start) = start {
+        words.push(&text[word_start..]);
+    }
+
+    words
+}
+
+/// Returns the number of backticks at the start of the given bytes.
+fn get_backtick_run(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|b| **b == b'`').count()
+}
+
+/// Returns the position of the first run of exactly the given number of backticks in the given bytes,
+/// which closes an inline code span.
+fn find_closing_run(bytes: &[u8], len: usize) -> Option<usize> {
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match get_backtick_run(&bytes[idx..]) {
+            0 => idx += 1,
+            run if run == len => return Some(idx),
+            run => idx += run,
+        }
+    }
+
+    None
+}
+
+/// Returns whether the given word starts a block when it is at the start of a line,
+/// i.e. it is the marker of a list item, a heading or a block quote.
+fn is_block_marker(word: &str) -> bool {
+    let digits = word.chars().take_while(char::is_ascii_digit).count();
+    let is_ordered_marker = digits > 0 && matches!(&word[digits..], "." | ")");
+
+    is_ordered_marker
+        || word == ">"
+        || (word.len() == 1 && LIST_MARKERS.contains(&word.chars().next().unwrap_or_default()))
+        || (word.len() <= MAX_HEADING_LEVEL && word.chars().all(|c| c == '#'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[test]
+    fn test_format_headings_and_lists() {
+        let markdown = "# Description\n\n\n#### Author  \n\n* first\n  + nested\n\n---\n* * *\n\n###Not a heading #123\n\n```\n* code\n\n\n```\n\n";
+        let expected = "# Description\n\n## Author\n\n- first\n  - nested\n\n---\n* * *\n\n###Not a heading #123\n\n```\n* code\n\n\n```\n";
+
+        assert_eq!(
+            format_markdown(markdown, &MarkdownStyle::default()),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        let style = MarkdownStyle {
+            line_width: Some(40),
+            list_marker: '-',
+        };
+        let markdown = "# Upgrade\n\nThe chain will be upgraded at the estimated time. Please prepare your nodes.\n";
+        let formatted = format_markdown(markdown, &style);
+
+        assert_eq!(format_markdown(&formatted, &style), formatted);
+    }
+
+    #[test]
+    fn test_wrap_line() {
+        assert_eq!(
+            wrap_line("The chain will be upgraded. Please prepare your nodes.", 30),
+            vec!["The chain will be upgraded.", "Please prepare your nodes."]
+        );
+        assert_eq!(
+            wrap_line("- Validators should upgrade their nodes in time.", 30),
+            vec!["- Validators should upgrade", "  their nodes in time."]
+        );
+        assert_eq!(
+            wrap_line(
+                "See https://github.com/evmos/evmos/releases/tag/v16.0.0 for details.",
+                30
+            ),
+            vec![
+                "See",
+                "https://github.com/evmos/evmos/releases/tag/v16.0.0",
+                "for details."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrap_line_block_markers() {
+        assert_eq!(
+            wrap_line(
+                "The upgrade is scheduled for block 1234 - please prepare.",
+                40
+            ),
+            vec![
+                "The upgrade is scheduled for block 1234 -",
+                "please prepare."
+            ]
+        );
+        assert_eq!(
+            wrap_line("Validators have to upgrade to version 1. Then restart.", 38),
+            vec!["Validators have to upgrade to version 1.", "Then restart."]
+        );
+        assert_eq!(
+            wrap_line("The fee is paid in the native token # of the chain.", 35),
+            vec!["The fee is paid in the native token #", "of the chain."]
+        );
+    }
+
+    #[test]
+    fn test_wrap_line_code_spans() {
+        assert_eq!(
+            wrap_line("Run `evmosd  tx  gov` to submit the proposal.", 20),
+            vec!["Run", "`evmosd  tx  gov` to", "submit the proposal."]
+        );
+        assert_eq!(
+            split_words("a ``code ` span`` and `unclosed span"),
+            vec!["a", "``code ` span``", "and", "`unclosed", "span"]
+        );
+    }
+
+    #[test]
+    fn test_format_markdown_below() {
+        let style = MarkdownStyle::default();
+        let features = "# Highlights
+
+#### Fixes
+
+## Features
+";
+
+        assert_eq!(
+            format_markdown_below(features, &style, 2),
+            "### Highlights
+
+#### Fixes
+
+### Features
+"
+        );
+        assert_eq!(
+            format_markdown(features, &style),
+            "# Highlights
+
+## Fixes
+
+## Features
+"
+        );
+    }
+
+    #[test]
+    fn test_get_enclosing_heading_level() {
+        let template = "# Description
+
+## Impact
+
+```
+# not a heading
+```
+
+{{features}}
+
+## Binaries
+";
+
+        assert_eq!(get_enclosing_heading_level(template, "{{features}}"), 2);
+        assert_eq!(get_enclosing_heading_level(template, "{{missing}}"), 2);
+        assert_eq!(
+            get_enclosing_heading_level("{{features}}", "{{features}}"),
+            0
+        );
+    }
+
+    #[test]
+    fn test_tables_are_not_wrapped() {
+        let style = MarkdownStyle {
+            line_width: Some(10),
+            list_marker: '*',
+        };
+        let markdown =
+            "| Timezone | Estimated Upgrade Time |\n|----------|------------------------|\n";
+
+        assert_eq!(format_markdown(markdown, &style), markdown);
+    }
+
+    #[test]
+    fn test_load_style() {
+        let test_dir = TestDir::temp();
+        let path = test_dir.path(MARKDOWN_FILE);
+
+        fs::write(&path, "line_width = 100\nlist_marker = \"*\"\n").unwrap();
+        assert_eq!(
+            MarkdownStyle::load(&path).unwrap(),
+            MarkdownStyle {
+                line_width: Some(100),
+                list_marker: '*',
+            }
+        );
+
+        fs::write(&path, "list_marker = \"1\"\n").unwrap();
+        assert!(matches!(
+            MarkdownStyle::load(&path),
+            Err(MarkdownError::InvalidListMarker('1'))
+        ));
+        assert_eq!(
+            MarkdownStyle::load(&test_dir.path("missing.toml")).unwrap(),
+            MarkdownStyle::default()
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use crate::errors::MessageError;
+use crate::gov::{Coin, MSG_SOFTWARE_UPGRADE};
+use crate::helper::UpgradeHelper;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+/// The default maximum length of the proposal metadata, as enforced by the governance module.
+const MAX_METADATA_LENGTH: usize = 255;
+
+/// Represents the proposal JSON that is submitted with `tx gov submit-proposal`
+/// for the gov v1 module. It can contain multiple messages, which are executed in order
+/// if the proposal passes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct V1Proposal {
+    /// The messages to execute.
+    pub messages: Vec<Value>,
+    /// The metadata of the proposal, e.g. a link to the forum discussion.
+    pub metadata: String,
+    /// The initial deposit, e.g. `10000000000aevmos`. Empty if no initial deposit is made.
+    pub deposit: String,
+    /// The title of the proposal.
+    pub title: String,
+    /// The summary of the proposal, which contains the proposal description.
+    pub summary: String,
+}
+
+impl V1Proposal {
+    /// Creates a new proposal, which contains the software upgrade message of the given helper
+    /// followed by the given additional messages.
+    pub fn new(
+        helper: &UpgradeHelper,
+        description: &str,
+        assets: &str,
+        authority: &str,
+        additional_messages: Vec<Value>,
+    ) -> V1Proposal {
+        let mut messages = vec![get_upgrade_message(helper, assets, authority)];
+        messages.extend(additional_messages);
+
+        V1Proposal {
+            messages,
+            metadata: helper.commonwealth_link.clone().unwrap_or_default(),
+            deposit: String::new(),
+            title: helper.proposal_name.clone(),
+            summary: description.to_string(),
+        }
+    }
+
+    /// Creates a new text proposal, which contains no messages and only records
+    /// the signaling vote on the given title and summary.
+    pub fn text(title: &str, summary: &str, metadata: &str) -> V1Proposal {
+        V1Proposal {
+            messages: Vec::new(),
+            metadata: metadata.to_string(),
+            deposit: String::new(),
+            title: title.to_string(),
+            summary: summary.to_string(),
+        }
+    }
+
+    /// Validates the combined metadata of the proposal and its messages.
+    pub fn validate(&self) -> Result<(), MessageError> {
+        if self.title.trim().is_empty() {
+            return Err(MessageError::EmptyField("title"));
+        }
+
+        if self.summary.trim().is_empty() {
+            return Err(MessageError::EmptyField("summary"));
+        }
+
+        if self.metadata.len() > MAX_METADATA_LENGTH {
+            return Err(MessageError::MetadataTooLong(self.metadata.len()));
+        }
+
+        let mut authority: Option<&str> = None;
+        let mut n_upgrades = 0;
+        for (idx, message) in self.messages.iter().enumerate() {
+            let msg_type = get_message_type(message).ok_or(MessageError::MissingType(idx))?;
+            if msg_type == MSG_SOFTWARE_UPGRADE {
+                n_upgrades += 1;
+            }
+
+            // NOTE: all messages are executed by the governance module, so the authorities must match
+            if let Some(msg_authority) = message["authority"].as_str() {
+                match authority {
+                    Some(expected) if expected != msg_authority => {
+                        return Err(MessageError::AuthorityMismatch(
+                            expected.to_string(),
+                            msg_authority.to_string(),
+                        ))
+                    }
+                    _ => authority = Some(msg_authority),
+                }
+            }
+        }
+
+        if n_upgrades > 1 {
+            return Err(MessageError::MultipleUpgrades(n_upgrades));
+        }
+
+        self.deposit_coins()?;
+
+        Ok(())
+    }
+
+    /// Returns the coins of the initial deposit, which is given as a comma-separated list
+    /// of amounts with their denomination, e.g. `10000000000aevmos`.
+    pub fn deposit_coins(&self) -> Result<Vec<Coin>, MessageError> {
+        self.deposit
+            .split(',')
+            .map(str::trim)
+            .filter(|coin| !coin.is_empty())
+            .map(|coin| {
+                let denom_start = coin
+                    .find(|c: char| !c.is_ascii_digit())
+                    .filter(|idx| *idx > 0)
+                    .ok_or_else(|| MessageError::Deposit(coin.to_string()))?;
+
+                Ok(Coin {
+                    amount: coin[..denom_start].to_string(),
+                    denom: coin[denom_start..].to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the software upgrade message for the given helper and binaries JSON,
+/// which is executed by the given governance module authority.
+pub fn get_upgrade_message(helper: &UpgradeHelper, assets: &str, authority: &str) -> Value {
+    json!({
+        "@type": MSG_SOFTWARE_UPGRADE,
+        "authority": authority,
+        "plan": {
+            "name": helper.get_plan_name(),
+            "height": helper.upgrade_height.to_string(),
+            "info": assets,
+        }
+    })
+}
+
+/// Returns the type URL of the given message.
+pub fn get_message_type(message: &Value) -> Option<&str> {
+    message["@type"].as_str().filter(|t| !t.is_empty())
+}
+
+/// Reads a single message from the given JSON file.
+/// The message has to contain its type URL in the `@type` field.
+pub fn read_message(path: &Path) -> Result<Value, MessageError> {
+    let message: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    if get_message_type(&message).is_none() {
+        return Err(MessageError::MissingType(0));
+    }
+
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    const TEST_AUTHORITY: &str = "evmos10d07y265gmmuvt4z0w9aw880jnsr700jcrztvm";
+
+    /// Returns a proposal for testing, which contains the upgrade message
+    /// and a parameter update.
+    fn get_proposal() -> V1Proposal {
+        let helper = UpgradeHelper::new(
+            PathBuf::from("/root/.evmosd"),
+            Network::Testnet,
+            "v15.0.0",
+            "v16.0.0-rc1",
+            Utc::now(),
+            1_000_000,
+            "",
+        );
+
+        V1Proposal::new(
+            &helper,
+            "This is a test proposal.",
+            "{}",
+            TEST_AUTHORITY,
+            vec![json!({
+                "@type": "/evmos.erc20.v1.MsgUpdateParams",
+                "authority": TEST_AUTHORITY,
+                "params": {"enable_erc20": true}
+            })],
+        )
+    }
+
+    #[test]
+    fn test_new_proposal() {
+        let proposal = get_proposal();
+        assert_eq!(proposal.messages.len(), 2);
+        assert_eq!(
+            get_message_type(&proposal.messages[0]),
+            Some(MSG_SOFTWARE_UPGRADE)
+        );
+        assert_eq!(proposal.messages[0]["plan"]["height"], "1000000");
+        assert!(proposal.validate().is_ok());
+    }
+
+    #[test]
+    fn test_text_proposal() {
+        let proposal = V1Proposal::text("Community Pool Spend", "Fund audits.", "");
+        assert!(proposal.messages.is_empty());
+        assert!(proposal.validate().is_ok());
+
+        let proposal = V1Proposal::text("Community Pool Spend", " ", "");
+        assert!(matches!(
+            proposal.validate(),
+            Err(MessageError::EmptyField("summary"))
+        ));
+    }
+
+    #[test]
+    fn test_validate_fail() {
+        let mut proposal = get_proposal();
+        proposal.messages[1]["authority"] = json!("evmos1other");
+        assert!(matches!(
+            proposal.validate(),
+            Err(MessageError::AuthorityMismatch(_, _))
+        ));
+
+        let mut proposal = get_proposal();
+        proposal.messages.push(proposal.messages[0].clone());
+        assert!(matches!(
+            proposal.validate(),
+            Err(MessageError::MultipleUpgrades(2))
+        ));
+
+        let mut proposal = get_proposal();
+        proposal.messages.push(json!({"authority": TEST_AUTHORITY}));
+        assert!(matches!(
+            proposal.validate(),
+            Err(MessageError::MissingType(2))
+        ));
+
+        let mut proposal = get_proposal();
+        proposal.metadata = "a".repeat(256);
+        assert!(matches!(
+            proposal.validate(),
+            Err(MessageError::MetadataTooLong(256))
+        ));
+
+        let mut proposal = get_proposal();
+        proposal.deposit = "aevmos".to_string();
+        assert!(matches!(proposal.validate(), Err(MessageError::Deposit(_))));
+    }
+
+    #[test]
+    fn test_deposit_coins() {
+        let mut proposal = get_proposal();
+        assert_eq!(proposal.deposit_coins().unwrap(), vec![]);
+
+        proposal.deposit = "10000000000aevmos, 5ibc/ABC".to_string();
+        assert_eq!(
+            proposal.deposit_coins().unwrap(),
+            vec![
+                Coin {
+                    denom: "aevmos".to_string(),
+                    amount: "10000000000".to_string(),
+                },
+                Coin {
+                    denom: "ibc/ABC".to_string(),
+                    amount: "5".to_string(),
+                },
+            ]
+        );
+
+        proposal.deposit = "10000".to_string();
+        assert!(proposal.deposit_coins().is_err());
+    }
+}
@@ -1,7 +1,12 @@
+use clap::ValueEnum;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 // Enum to represent different network options
-#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, ValueEnum, Serialize, Deserialize, JsonSchema,
+)]
 pub enum Network {
     LocalNode,
     Testnet,
@@ -26,3 +31,93 @@ pub fn get_denom(network: Network) -> String {
         Network::Mainnet => "aevmos".to_string(),
     }
 }
+
+/// Returns the default chain ID for the given network.
+pub fn get_chain_id(network: Network) -> String {
+    match network {
+        Network::LocalNode => "evmos_9000-4".to_string(),
+        Network::Testnet => "evmos_9000-4".to_string(),
+        Network::Mainnet => "evmos_9001-2".to_string(),
+    }
+}
+
+/// Returns the display denomination for the given network, which is used
+/// to show amounts in a human-readable way.
+pub fn get_display_denom(network: Network) -> String {
+    match network {
+        Network::LocalNode => "EVMOS".to_string(),
+        Network::Testnet => "tEVMOS".to_string(),
+        Network::Mainnet => "EVMOS".to_string(),
+    }
+}
+
+/// Returns the exponent of the display denomination relative to the native denomination
+/// of the given network, i.e. the number of decimals of the display denomination.
+pub fn get_denom_exponent(network: Network) -> u32 {
+    match network {
+        Network::LocalNode | Network::Testnet | Network::Mainnet => 18,
+    }
+}
+
+/// Formats the given amount in the native denomination of the network
+/// as a decimal amount in the display denomination, e.g. `0.00000001 EVMOS`.
+pub fn format_amount(amount: u128, network: Network) -> String {
+    let exponent = get_denom_exponent(network);
+    let unit = 10u128.pow(exponent);
+    let fraction = format!("{:0width$}", amount % unit, width = exponent as usize);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        format!("{} {}", amount / unit, get_display_denom(network))
+    } else {
+        format!(
+            "{}.{} {}",
+            amount / unit,
+            fraction,
+            get_display_denom(network)
+        )
+    }
+}
+
+/// Formats the given amount of the given denomination in the display denomination of the network
+/// if it is the native denomination. Other denominations are returned unchanged, e.g. `100ibc/...`.
+pub fn format_coin(amount: &str, denom: &str, network: Network) -> String {
+    match amount.parse::<u128>() {
+        Ok(amount) if denom == get_denom(network) => format_amount(amount, network),
+        _ => format!("{}{}", amount, denom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount(0, Network::Mainnet), "0 EVMOS");
+        assert_eq!(
+            format_amount(10_000_000_000, Network::Mainnet),
+            "0.00000001 EVMOS"
+        );
+        assert_eq!(
+            format_amount(1_500_000_000_000_000_000_000, Network::Testnet),
+            "1500 tEVMOS"
+        );
+        assert_eq!(
+            format_amount(2_250_000_000_000_000_000, Network::Mainnet),
+            "2.25 EVMOS"
+        );
+    }
+
+    #[test]
+    fn test_format_coin() {
+        assert_eq!(
+            format_coin("1000000000000000000000", "aevmos", Network::Mainnet),
+            "1000 EVMOS"
+        );
+        assert_eq!(
+            format_coin("1000000000000000000000", "aevmos", Network::Testnet),
+            "1000000000000000000000aevmos"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::breaking::{get_commit_messages, PullRequest};
+use crate::errors::NotesError;
+use crate::release::get_release_notes;
+use clap::ValueEnum;
+use octocrab::{models::repos::Release, Octocrab};
+use std::fmt;
+
+/// The sources, from which the input of the release notes summary is built.
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum NotesSource {
+    /// The description of the GitHub release.
+    #[default]
+    Release,
+    /// The titles of the pull requests, which are included between the previous and the target version.
+    PullRequests,
+    /// The commit messages between the previous and the target version.
+    Commits,
+}
+
+impl fmt::Display for NotesSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let source = match self {
+            NotesSource::Release => "release description",
+            NotesSource::PullRequests => "pull request titles",
+            NotesSource::Commits => "commit messages",
+        };
+        write!(f, "{}", source)
+    }
+}
+
+/// Returns the notes, which are summarized for the proposal, from the given source.
+///
+/// The pull requests are passed in, because they are queried anyway to cite them in the summary,
+/// while the commits are only queried from the compared range of tags if they are used.
+pub async fn get_notes(
+    instance: &Octocrab,
+    source: NotesSource,
+    release: &Release,
+    pull_requests: &[PullRequest],
+    previous_version: &str,
+    target_version: &str,
+) -> Result<String, NotesError> {
+    let notes = match source {
+        NotesSource::Release => get_release_notes(release)?,
+        NotesSource::PullRequests => get_pull_request_notes(pull_requests),
+        NotesSource::Commits => get_commit_notes(
+            &get_commit_messages(instance, previous_version, target_version).await?,
+        ),
+    };
+
+    match notes.trim().is_empty() {
+        true => Err(NotesError::Empty(source)),
+        false => Ok(notes),
+    }
+}
+
+/// Returns the notes built from the titles of the given pull requests, one bullet point per pull request.
+fn get_pull_request_notes(pull_requests: &[PullRequest]) -> String {
+    pull_requests
+        .iter()
+        .map(|pr| format!("- {} (#{})", pr.title, pr.number))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the notes built from the subject lines of the given commit messages, one bullet point per commit.
+///
+/// Merge commits are skipped, because their subject does not describe the change.
+fn get_commit_notes(messages: &[String]) -> String {
+    messages
+        .iter()
+        .filter_map(|message| message.lines().next())
+        .map(str::trim)
+        .filter(|subject| !subject.is_empty() && !subject.starts_with("Merge "))
+        .map(|subject| format!("- {}", subject))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_get_notes() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/evmos/evmos/compare/v15.0.0...v16.0.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "commits": [
+                    {"commit": {"message": "feat(evm): add precompile (#1234)\n\nlong description"}},
+                    {"commit": {"message": "Merge pull request #12 from evmos/branch"}},
+                    {"commit": {"message": "chore: bump version"}}
+                ]
+            })))
+            .mount(&mock_server)
+            .await;
+        let instance = Octocrab::builder()
+            .base_uri(mock_server.uri())
+            .unwrap()
+            .build()
+            .unwrap();
+        let release: Release = serde_json::from_str(include_str!("testdata/release.json"))
+            .expect("failed to parse release JSON");
+        let pull_requests = vec![PullRequest {
+            number: 1234,
+            title: "feat(evm): add precompile".to_string(),
+        }];
+
+        let get = |source| {
+            get_notes(
+                &instance,
+                source,
+                &release,
+                &pull_requests,
+                "v15.0.0",
+                "v16.0.0",
+            )
+        };
+        assert!(get(NotesSource::Release).await.unwrap().contains("v14.0.0"));
+        assert_eq!(
+            get(NotesSource::PullRequests).await.unwrap(),
+            "- feat(evm): add precompile (#1234)"
+        );
+        assert_eq!(
+            get(NotesSource::Commits).await.unwrap(),
+            "- feat(evm): add precompile (#1234)\n- chore: bump version"
+        );
+
+        match get_notes(
+            &instance,
+            NotesSource::PullRequests,
+            &release,
+            &[],
+            "v15.0.0",
+            "v16.0.0",
+        )
+        .await
+        {
+            Err(NotesError::Empty(source)) => assert_eq!(source, NotesSource::PullRequests),
+            res => panic!("expected empty notes, got {:?}", res),
+        }
+    }
+}
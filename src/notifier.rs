@@ -0,0 +1,416 @@
+use crate::chain::get_config_dir;
+use crate::errors::NotifyError;
+use crate::http::get_client;
+use crate::secret;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process;
+
+/// The name of the file inside of the proposer configuration directory,
+/// which contains the notification channels. Without this file, no notifications are sent.
+const NOTIFICATIONS_FILE: &str = "notifications.toml";
+
+/// The URL of the PagerDuty Events API v2.
+const PAGERDUTY_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// The severities of notifications, ordered from lowest to highest.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Informational updates, e.g. reached milestones of an upgrade.
+    #[default]
+    Info,
+    /// Problems that might need attention, e.g. a failing estimation.
+    Warning,
+    /// Failures that need immediate attention, e.g. a stopped monitor.
+    Critical,
+}
+
+/// Represents a notification, which is sent to the configured channels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    /// The short title of the notification.
+    pub title: String,
+    /// The message of the notification.
+    pub message: String,
+    /// The severity of the notification.
+    pub severity: Severity,
+}
+
+impl Notification {
+    /// Creates a new notification with the given severity.
+    pub fn new(severity: Severity, title: &str, message: &str) -> Notification {
+        Notification {
+            title: title.to_string(),
+            message: message.to_string(),
+            severity,
+        }
+    }
+}
+
+/// The common interface of the notification backends.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Sends the given notification.
+    async fn notify(&self, notification: &Notification) -> Result<(), NotifyError>;
+}
+
+/// Contains the configuration of a single notification channel.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChannelConfig {
+    /// The backend, to which the notifications of the channel are sent.
+    #[serde(flatten)]
+    pub backend: Backend,
+    /// The lowest severity of the notifications, which are sent to the channel.
+    #[serde(default)]
+    pub min_severity: Severity,
+}
+
+/// The supported notification backends with their configuration.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum Backend {
+    /// Sends the notifications to a Discord webhook.
+    Discord { webhook_url: String },
+    /// Sends the notifications as emails using the local `sendmail` binary.
+    Email { to: String },
+    /// Triggers PagerDuty incidents with the given integration key of the Events API v2.
+    PagerDuty {
+        routing_key: String,
+        #[serde(default = "default_pagerduty_url")]
+        url: String,
+    },
+    /// Sends the notifications to a Slack webhook.
+    Slack { webhook_url: String },
+}
+
+impl Backend {
+    /// Returns the name of the backend kind, which is used instead of the URL
+    /// to refer to the target of failed requests, because webhook URLs are secrets.
+    fn kind(&self) -> &'static str {
+        match self {
+            Backend::Discord { .. } => "Discord",
+            Backend::Email { .. } => "email",
+            Backend::PagerDuty { .. } => "PagerDuty",
+            Backend::Slack { .. } => "Slack",
+        }
+    }
+}
+
+/// Returns the default URL of the PagerDuty Events API.
+fn default_pagerduty_url() -> String {
+    PAGERDUTY_URL.to_string()
+}
+
+/// Contains the notification channels, which are keyed by their name.
+#[derive(Debug, Default, PartialEq, Deserialize)]
+pub struct NotificationsConfig {
+    #[serde(default)]
+    pub channels: BTreeMap<String, ChannelConfig>,
+}
+
+/// Returns the notification channels from the given file.
+/// If the file does not exist, no channels are configured.
+/// Encrypted values, e.g. webhook URLs, are decrypted with the configured passphrase.
+fn load_config(path: &Path) -> Result<NotificationsConfig, NotifyError> {
+    if !path.exists() {
+        return Ok(NotificationsConfig::default());
+    }
+
+    let contents = fs::read_to_string(path)?;
+    if secret::contains_encrypted(&contents) {
+        let mut value: Value = toml::from_str(&contents)?;
+        secret::decrypt_values(&mut value, &secret::get_passphrase()?)?;
+
+        return Ok(serde_json::from_value(value)?);
+    }
+
+    Ok(toml::from_str(&contents)?)
+}
+
+#[async_trait]
+impl Notifier for Backend {
+    async fn notify(&self, notification: &Notification) -> Result<(), NotifyError> {
+        match self {
+            Backend::Discord { webhook_url } => {
+                post_json(self.kind(), webhook_url, &get_discord_body(notification)).await
+            }
+            Backend::Email { to } => send_email(to, notification),
+            Backend::PagerDuty { routing_key, url } => {
+                post_json(
+                    self.kind(),
+                    url,
+                    &get_pagerduty_body(routing_key, notification),
+                )
+                .await
+            }
+            Backend::Slack { webhook_url } => {
+                post_json(self.kind(), webhook_url, &get_slack_body(notification)).await
+            }
+        }
+    }
+}
+
+/// Returns the text of the given notification, which is prefixed with its severity.
+fn get_text(notification: &Notification) -> String {
+    format!(
+        "[{:?}] {}\n{}",
+        notification.severity, notification.title, notification.message
+    )
+}
+
+/// Returns the body of the Discord webhook request.
+fn get_discord_body(notification: &Notification) -> Value {
+    json!({ "content": get_text(notification) })
+}
+
+/// Returns the body of the Slack webhook request.
+fn get_slack_body(notification: &Notification) -> Value {
+    json!({ "text": get_text(notification) })
+}
+
+/// Returns the body of the PagerDuty event, which triggers an incident.
+fn get_pagerduty_body(routing_key: &str, notification: &Notification) -> Value {
+    let severity = match notification.severity {
+        Severity::Info => "info",
+        Severity::Warning => "warning",
+        Severity::Critical => "critical",
+    };
+
+    json!({
+        "routing_key": routing_key,
+        "event_action": "trigger",
+        "payload": {
+            "summary": format!("{}: {}", notification.title, notification.message),
+            "source": "proposer",
+            "severity": severity,
+        }
+    })
+}
+
+/// Posts the given JSON body to the given URL of the given backend kind.
+/// The URL is removed from failed requests, so that webhook URLs are not printed.
+async fn post_json(kind: &'static str, url: &str, body: &Value) -> Result<(), NotifyError> {
+    let response = get_client()?
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| NotifyError::Request(kind, e.without_url()))?;
+    if !response.status().is_success() {
+        return Err(NotifyError::Status(response.status().as_u16()));
+    }
+
+    Ok(())
+}
+
+/// Sends the given notification as an email to the given address using `sendmail`.
+fn send_email(to: &str, notification: &Notification) -> Result<(), NotifyError> {
+    let mut child = process::Command::new("sendmail")
+        .arg(to)
+        .stdin(process::Stdio::piped())
+        .spawn()?;
+
+    if let Some(stdin) = child.stdin.as_mut() {
+        write!(
+            stdin,
+            "To: {}\nSubject: [proposer] {}\n\n{}\n",
+            to, notification.title, notification.message
+        )?;
+    }
+
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(NotifyError::Sendmail(status.code().unwrap_or(-1)));
+    }
+
+    Ok(())
+}
+
+/// Sends the given notification to all channels in the given configuration,
+/// whose minimum severity is reached, and returns the failures by channel name.
+async fn notify_channels(
+    config: &NotificationsConfig,
+    notification: &Notification,
+) -> Vec<(String, NotifyError)> {
+    let mut failures = Vec::new();
+    for (name, channel) in config.channels.iter() {
+        if notification.severity < channel.min_severity {
+            continue;
+        }
+
+        if let Err(e) = channel.backend.notify(notification).await {
+            failures.push((name.clone(), e));
+        }
+    }
+
+    failures
+}
+
+/// Sends the given notification to the channels configured in the proposer configuration directory.
+///
+/// Failures are only printed as warnings, so that notifications never interrupt the monitors using them.
+pub async fn notify(notification: Notification) {
+    let config = match load_config(&get_config_dir().join(NOTIFICATIONS_FILE)) {
+        Ok(config) => config,
+        Err(e) => {
+            println!("WARNING: failed to load notification channels: {}", e);
+            return;
+        }
+    };
+
+    for (name, e) in notify_channels(&config, &notification).await {
+        println!("WARNING: failed to send notification to {}: {}", name, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+    use wiremock::{
+        matchers::{body_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[test]
+    fn test_load_encrypted_config() {
+        std::env::set_var("PROPOSER_PASSPHRASE", "test-passphrase");
+        let webhook_url =
+            secret::encrypt("https://hooks.slack.com/services/abc", "test-passphrase").unwrap();
+        let test_dir = TestDir::temp();
+        let path = test_dir.path(NOTIFICATIONS_FILE);
+        fs::write(
+            &path,
+            format!(
+                "[channels.team]\nbackend = \"slack\"\nwebhook_url = \"{}\"\n",
+                webhook_url
+            ),
+        )
+        .unwrap();
+
+        let config = load_config(&path).expect("failed to load notification channels");
+        assert_eq!(
+            config.channels["team"].backend,
+            Backend::Slack {
+                webhook_url: "https://hooks.slack.com/services/abc".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let config: NotificationsConfig = toml::from_str(
+            r#"
+            [channels.team]
+            backend = "slack"
+            webhook_url = "https://hooks.slack.com/services/abc"
+
+            [channels.oncall]
+            backend = "pagerduty"
+            routing_key = "key"
+            min_severity = "critical"
+            "#,
+        )
+        .expect("failed to parse notification channels");
+
+        assert_eq!(
+            config.channels["team"],
+            ChannelConfig {
+                backend: Backend::Slack {
+                    webhook_url: "https://hooks.slack.com/services/abc".to_string()
+                },
+                min_severity: Severity::Info,
+            }
+        );
+        assert_eq!(
+            config.channels["oncall"].backend,
+            Backend::PagerDuty {
+                routing_key: "key".to_string(),
+                url: PAGERDUTY_URL.to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_channels() {
+        let mock_server = MockServer::start().await;
+        let notification = Notification::new(
+            Severity::Warning,
+            "Estimation failed",
+            "The REST provider is unavailable",
+        );
+        Mock::given(method("POST"))
+            .and(path("/discord"))
+            .and(body_json(get_discord_body(&notification)))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let mut config = NotificationsConfig::default();
+        config.channels.insert(
+            "discord".to_string(),
+            ChannelConfig {
+                backend: Backend::Discord {
+                    webhook_url: format!("{}/discord", mock_server.uri()),
+                },
+                min_severity: Severity::Warning,
+            },
+        );
+        config.channels.insert(
+            "oncall".to_string(),
+            ChannelConfig {
+                backend: Backend::PagerDuty {
+                    routing_key: "key".to_string(),
+                    url: format!("{}/pagerduty", mock_server.uri()),
+                },
+                min_severity: Severity::Critical,
+            },
+        );
+        config.channels.insert(
+            "slack".to_string(),
+            ChannelConfig {
+                backend: Backend::Slack {
+                    webhook_url: format!("{}/slack", mock_server.uri()),
+                },
+                min_severity: Severity::Info,
+            },
+        );
+
+        // NOTE: the PagerDuty channel is skipped because of its severity, the Slack webhook is not mocked
+        let failures = notify_channels(&config, &notification).await;
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "slack");
+        assert!(matches!(failures[0].1, NotifyError::Status(404)));
+    }
+
+    #[tokio::test]
+    async fn test_notify_hides_webhook_url() {
+        // NOTE: binding to port 0 reserves a free port, which refuses connections after dropping the listener
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let backend = Backend::Slack {
+            webhook_url: format!("http://127.0.0.1:{}/services/SECRET", port),
+        };
+        let err = backend
+            .notify(&Notification::new(Severity::Info, "Title", "Message"))
+            .await
+            .expect_err("expected the request to fail");
+
+        assert!(matches!(err, NotifyError::Request("Slack", _)));
+        assert!(
+            !err.to_string().contains("SECRET"),
+            "webhook URL leaked: {}",
+            err
+        );
+        assert!(!format!("{:?}", err).contains("SECRET"));
+    }
+}
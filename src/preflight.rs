@@ -0,0 +1,190 @@
+use crate::block::get_rest_endpoint;
+use crate::context::Context;
+use crate::errors::PreflightError;
+use crate::http::get_client;
+use crate::llm::LlmBackend;
+use crate::network::Network;
+use std::time::Duration;
+
+/// The timeout of a single reachability check in seconds,
+/// which is kept short so that the checks don't delay the wizard noticeably.
+const CHECK_TIMEOUT_SECONDS: u64 = 5;
+
+/// The base URL of the GitHub API, which is used to query the releases.
+const GITHUB_URL: &str = "https://api.github.com";
+
+/// The base URL of the OpenAI API, which is used to summarize the release notes.
+const OPENAI_URL: &str = "https://api.openai.com";
+
+/// The flows, for which the needed endpoints are checked before any questions are asked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Flow {
+    /// Generating a proposal, which needs the REST providers, GitHub and OpenAI.
+    Proposal,
+    /// Generating the command for a proposal on the given network.
+    Command(Network),
+}
+
+/// Represents an endpoint, which is checked for reachability.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    /// The name of the service behind the endpoint.
+    pub name: String,
+    /// The URL of the endpoint.
+    pub url: String,
+    /// Whether the flow cannot be completed without the endpoint.
+    /// Optional endpoints, e.g. OpenAI with the manual summary fallback, are only reported.
+    pub required: bool,
+}
+
+impl Check {
+    /// Creates a new check for the given endpoint.
+    fn new(name: &str, url: &str, required: bool) -> Check {
+        Check {
+            name: name.to_string(),
+            url: url.to_string(),
+            required,
+        }
+    }
+}
+
+/// Returns the endpoints that are needed in the given flow.
+/// The REST providers are taken from the chain configurations of the networks.
+pub fn get_checks(ctx: &Context, flow: Flow) -> Result<Vec<Check>, PreflightError> {
+    let mut checks = vec![Check::new("GitHub", GITHUB_URL, true)];
+    match flow {
+        Flow::Proposal => {
+            // NOTE: the network is only chosen in the wizard, so the providers of all public networks are checked
+            for network in [Network::Testnet, Network::Mainnet] {
+                checks.push(Check::new(
+                    &format!("{} REST provider", network),
+                    get_rest_endpoint(network, &ctx.chain_config(network)?)?.as_str(),
+                    false,
+                ));
+            }
+            if ctx.llm_backend == LlmBackend::OpenAI {
+                checks.push(Check::new("OpenAI", OPENAI_URL, false));
+            }
+        }
+        Flow::Command(network) => checks.push(Check::new(
+            &format!("{} REST provider", network),
+            get_rest_endpoint(network, &ctx.chain_config(network)?)?.as_str(),
+            true,
+        )),
+    }
+
+    Ok(checks)
+}
+
+/// Checks that the given URL is reachable.
+/// Any HTTP response counts as reachable, because only the connectivity is checked.
+async fn is_reachable(url: String) -> Result<(), PreflightError> {
+    get_client()?
+        .head(url)
+        .timeout(Duration::from_secs(CHECK_TIMEOUT_SECONDS))
+        .send()
+        .await?;
+
+    Ok(())
+}
+
+/// Runs the given checks concurrently and returns the unreachable endpoints with the reason.
+pub async fn get_unreachable(checks: Vec<Check>) -> Vec<(Check, String)> {
+    let handles: Vec<_> = checks
+        .into_iter()
+        .map(|check| {
+            let handle = tokio::spawn(is_reachable(check.url.clone()));
+            (check, handle)
+        })
+        .collect();
+
+    let mut unreachable = Vec::new();
+    for (check, handle) in handles {
+        match handle.await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => unreachable.push((check, e.to_string())),
+            Err(e) => unreachable.push((check, e.to_string())),
+        }
+    }
+
+    unreachable
+}
+
+/// Checks the reachability of the endpoints needed in the given flow before any questions are asked.
+///
+/// Unreachable optional endpoints are printed as warnings.
+/// If any required endpoint is unreachable, an error listing all of them is returned.
+pub async fn run(ctx: &Context, flow: Flow) -> Result<(), PreflightError> {
+    let unreachable = get_unreachable(get_checks(ctx, flow)?).await;
+
+    let mut required = Vec::new();
+    for (check, reason) in unreachable {
+        match check.required {
+            true => required.push(format!("{} ({})", check.name, check.url)),
+            false => println!(
+                "WARNING: {} ({}) is unreachable: {}",
+                check.name, check.url, reason
+            ),
+        }
+    }
+
+    if !required.is_empty() {
+        return Err(PreflightError::Unreachable(required.join(", ")));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::ChainConfig;
+    use test_dir::{DirBuilder, TestDir};
+    use wiremock::MockServer;
+
+    #[tokio::test]
+    async fn test_get_checks() {
+        let test_dir = TestDir::temp();
+        let custom_config = ChainConfig {
+            rest_url: Some("https://rest.example.com".to_string()),
+            ..ChainConfig::default()
+        };
+        let mut ctx = Context::for_tests(test_dir.path("cache"), &[])
+            .with_chain_config(Network::Testnet, custom_config)
+            .with_chain_config(Network::Mainnet, ChainConfig::default())
+            .with_chain_config(Network::LocalNode, ChainConfig::default());
+        ctx.llm_backend = LlmBackend::OpenAI;
+
+        let checks = get_checks(&ctx, Flow::Proposal).unwrap();
+        assert_eq!(checks.len(), 4);
+        assert!(checks[0].required);
+        assert!(checks[1..].iter().all(|check| !check.required));
+        assert_eq!(
+            checks[1],
+            Check::new("Testnet REST provider", "https://rest.example.com/", false)
+        );
+
+        let checks = get_checks(&ctx, Flow::Command(Network::LocalNode)).unwrap();
+        assert_eq!(
+            checks[1],
+            Check::new("Local Node REST provider", "http://localhost:1317/", true)
+        );
+
+        ctx.llm_backend = LlmBackend::Mock;
+        assert_eq!(get_checks(&ctx, Flow::Proposal).unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_get_unreachable() {
+        // NOTE: the mock server responds with 404 to all requests, which still counts as reachable
+        let mock_server = MockServer::start().await;
+
+        let unreachable = get_unreachable(vec![
+            Check::new("mock", &mock_server.uri(), true),
+            Check::new("closed", "http://127.0.0.1:1", false),
+        ])
+        .await;
+        assert_eq!(unreachable.len(), 1);
+        assert_eq!(unreachable[0].0.name, "closed");
+    }
+}
@@ -0,0 +1,113 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Whether the durations of the steps are recorded and reported.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The recorded durations of the steps in the order of their completion.
+static TIMINGS: Mutex<Vec<(&'static str, Duration)>> = Mutex::new(Vec::new());
+
+/// Enables recording the durations of the steps, e.g. when running with `--profile`.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Returns whether profiling is enabled.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs the given future and records its duration under the given step name if profiling is enabled.
+pub async fn timed<T>(step: &'static str, future: impl Future<Output = T>) -> T {
+    let start = Instant::now();
+    let output = future.await;
+    record(step, start.elapsed());
+
+    output
+}
+
+/// Runs the given closure and records its duration under the given step name if profiling is enabled.
+pub fn measure<T>(step: &'static str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let output = f();
+    record(step, start.elapsed());
+
+    output
+}
+
+/// Records the duration of the given step, which is printed right away,
+/// so that also the steps before a failure are reported.
+pub fn record(step: &'static str, duration: Duration) {
+    if !is_enabled() {
+        return;
+    }
+
+    println!("PROFILE: {} took {:.2}s", step, duration.as_secs_f64());
+    TIMINGS
+        .lock()
+        .expect("failed to lock timings")
+        .push((step, duration));
+}
+
+/// Prints the summary of the recorded steps, sorted by their duration, if profiling is enabled.
+pub fn print_report() {
+    if !is_enabled() {
+        return;
+    }
+
+    let timings = TIMINGS.lock().expect("failed to lock timings");
+    print!("{}", render_report(&timings));
+}
+
+/// Renders the summary of the given step durations with the slowest step first.
+fn render_report(timings: &[(&'static str, Duration)]) -> String {
+    let mut sorted = timings.to_vec();
+    sorted.sort_by_key(|(_, duration)| std::cmp::Reverse(*duration));
+    let total: Duration = timings.iter().map(|(_, duration)| *duration).sum();
+    let width = sorted
+        .iter()
+        .map(|(step, _)| step.len())
+        .max()
+        .unwrap_or_default();
+
+    let mut report = String::from("\nStep timings:\n");
+    for (step, duration) in sorted {
+        report.push_str(&format!(
+            "  {:<width$}  {:>7.2}s\n",
+            step,
+            duration.as_secs_f64(),
+            width = width
+        ));
+    }
+    report.push_str(&format!(
+        "  {:<width$}  {:>7.2}s\n",
+        "total",
+        total.as_secs_f64(),
+        width = width
+    ));
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_report() {
+        let timings = vec![
+            ("query release", Duration::from_millis(1200)),
+            ("create summary", Duration::from_millis(8500)),
+        ];
+
+        assert_eq!(
+            render_report(&timings),
+            "\nStep timings:\n  \
+             create summary     8.50s\n  \
+             query release      1.20s\n  \
+             total              9.70s\n"
+        );
+    }
+}
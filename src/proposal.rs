@@ -1,47 +1,241 @@
 use crate::{
-    block::N_BLOCKS, errors::ProposalError, helper::UpgradeHelper, inputs::get_time_string,
+    block::N_BLOCKS,
+    chain::ChainConfig,
+    errors::{ProposalError, TemplateError},
+    helper::UpgradeHelper,
+    inputs::get_time_strings_by_timezone,
+    locale::Locale,
+    markdown,
     network::Network,
+    template,
 };
-use handlebars::Handlebars;
-use num_format::ToFormattedString;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
 use serde_json::json;
+use std::fs;
+
+/// The path to the proposal template.
+pub const PROPOSAL_TEMPLATE: &str = "src/templates/proposal.hbs";
+
+/// The placeholder of the summarized features in the proposal template.
+const FEATURES_PLACEHOLDER: &str = "{{features}}";
+
+/// The path to the default governance guidelines, which proposals are linted against.
+pub const GUIDELINES_FILE: &str = "src/templates/guidelines.md";
+
+/// The placeholder, which is rendered for the values that are not known yet in a draft proposal.
+pub const DRAFT_PLACEHOLDER: &str = "TBD";
+
+/// The targets that the proposal description can be rendered for.
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum RenderTarget {
+    /// The canonical Markdown file.
+    Markdown,
+    /// Commonwealth-friendly Markdown, which is written in addition to the canonical file.
+    Commonwealth,
+}
+
+/// Returns the file name of the proposal for the given render target.
+pub fn get_proposal_file_name(helper: &UpgradeHelper, target: RenderTarget) -> String {
+    match target {
+        RenderTarget::Markdown => helper.proposal_file_name.clone(),
+        RenderTarget::Commonwealth => helper.proposal_file_name.replace(".md", "-commonwealth.md"),
+    }
+}
 
 /// Renders the proposal template, filling in the necessary information.
-pub fn render_proposal(helper: &UpgradeHelper) -> Result<String, ProposalError> {
-    let mut handlebars = Handlebars::new();
-    handlebars.set_strict_mode(true);
+///
+/// In strict mode, rendering fails with a report of the unknown placeholders used in the template.
+/// Otherwise, the report is printed as a warning and unknown placeholders are rendered empty.
+/// The rendered Markdown is formatted with the configured style, so that diffs between upgrades are minimal.
+pub fn render_proposal(
+    helper: &UpgradeHelper,
+    chain_config: &ChainConfig,
+    strict: bool,
+) -> Result<String, ProposalError> {
+    let handlebars = template::get_handlebars("proposal", PROPOSAL_TEMPLATE, strict, true)?;
+    let style = markdown::get_markdown_style()?;
+    let mut data = get_proposal_data(helper, chain_config)?;
 
-    handlebars
-        .register_template_file("proposal", "src/templates/proposal.hbs")
-        .unwrap();
+    // NOTE: the generated features may contain headings, which are nested below the section of the template
+    let level = markdown::get_enclosing_heading_level(
+        &fs::read_to_string(PROPOSAL_TEMPLATE).map_err(TemplateError::from)?,
+        FEATURES_PLACEHOLDER,
+    );
+    if let Some(features) = data["features"].as_str() {
+        data["features"] = json!(markdown::format_markdown_below(features, &style, level));
+    }
+    let rendered = template::render(&handlebars, "proposal", PROPOSAL_TEMPLATE, &data)?;
 
-    let height_link = get_height_with_link(helper.network, helper.upgrade_height);
-    let n_blocks = N_BLOCKS.to_formatted_string(&num_format::Locale::en);
-    let upgrade_time = get_time_string(helper.upgrade_time);
+    Ok(markdown::format_markdown(&rendered, &style))
+}
 
-    let data = json!({
+/// Returns the data of the given configuration and chain configuration, which is available in the proposal template.
+pub fn get_proposal_data(
+    helper: &UpgradeHelper,
+    chain_config: &ChainConfig,
+) -> Result<serde_json::Value, ProposalError> {
+    let locale = helper.locale;
+    let height_link = get_height_with_link(
+        helper.network,
+        helper.upgrade_height,
+        locale,
+        chain_config.explorer_url.as_deref(),
+    );
+    let n_blocks = locale.format_number(
+        helper
+            .block_time_estimate
+            .as_ref()
+            .map_or(N_BLOCKS, |estimate| estimate.n_blocks),
+    );
+    let block_time = helper.block_time_estimate.as_ref().map(|estimate| {
+        json!({
+            "estimated_at": locale.format_time(estimate.estimated_at),
+            "latest_height": locale.format_number(estimate.latest_height),
+            "seconds_per_block": format!("{:.2}", estimate.seconds_per_block),
+        })
+    });
+    let upgrade_time = locale.format_time(helper.upgrade_time);
+    let upgrade_times: Vec<_> = get_time_strings_by_timezone(helper.upgrade_time, locale)
+        .into_iter()
+        .map(|(timezone, time)| json!({"timezone": timezone, "time": time}))
+        .collect();
+    let ibc_clients: Vec<String> = helper
+        .expiring_clients
+        .iter()
+        .map(|client| {
+            format!(
+                "`{}` ({}), expiring around {}",
+                client.client_id,
+                client.chain_id,
+                locale.format_time(client.expiry)
+            )
+        })
+        .collect();
+
+    let downtime = helper.downtime_estimate.as_ref().map(|estimate| {
+        json!({
+            "expected_minutes": estimate.expected_minutes,
+            "expected_resumption": get_clock_time(estimate.expected_resumption(helper.upgrade_time)),
+            "latest_resumption": get_clock_time(estimate.latest_resumption(helper.upgrade_time)),
+            "max_minutes": estimate.max_minutes,
+            "n_upgrades": estimate.n_upgrades,
+            "stop_transactions_at": get_clock_time(estimate.stop_transactions_at(helper.upgrade_time)),
+        })
+    });
+
+    let breaking = json!({
+        "api_breaking": get_pr_md_links(&helper.breaking_changes.api_breaking),
+        "state_breaking": get_pr_md_links(&helper.breaking_changes.state_breaking),
+    });
+
+    let binaries: Vec<_> = helper
+        .binaries
+        .iter()
+        .map(|binary| {
+            json!({
+                "checksum": binary.checksum,
+                "link": format!("[{}]({})", binary.url.rsplit('/').next().unwrap_or_default(), binary.url),
+                "platform": binary.platform,
+            })
+        })
+        .collect();
+
+    let audit = chain_config.audit_header.then(|| get_audit(helper));
+
+    // NOTE: the upgrade schedule and the release are not fixed for drafts, so placeholders are rendered instead
+    let (height_link, upgrade_time, upgrade_times, features, block_time) = match helper.draft {
+        true => (
+            DRAFT_PLACEHOLDER.to_string(),
+            DRAFT_PLACEHOLDER.to_string(),
+            upgrade_times
+                .into_iter()
+                .map(|mut time| {
+                    time["time"] = json!(DRAFT_PLACEHOLDER);
+                    time
+                })
+                .collect(),
+            DRAFT_PLACEHOLDER.to_string(),
+            None,
+        ),
+        false => (
+            height_link,
+            upgrade_time,
+            upgrade_times,
+            helper.summary.clone(),
+            block_time,
+        ),
+    };
+
+    Ok(json!({
+        "audit": audit,
         "author": "Malte Herrmann, Evmos Core Team",
+        "binaries": binaries,
+        "block_time": block_time,
+        "breaking": breaking,
+        "community_summary": helper.community_summary,
         "diff_link": format!("https://github.com/evmos/evmos/compare/{}..{}",
             helper.previous_version,
             helper.target_version,
         ),
+        "downtime": downtime,
+        "draft": helper.draft,
         "estimated_time": upgrade_time,
-        "features": helper.summary,
+        "features": features,
         "height": height_link,
+        "ibc_clients": ibc_clients,
         "name": helper.proposal_name,
         "n_blocks": n_blocks,
         "network": helper.network,
+        "plan_info": helper.plan_info.describe(&helper.target_version),
         "previous_version": get_release_md_link(helper.previous_version.as_str()),
+        "tracking_issue": helper.tracking_issue.as_ref().map(|issue| issue.url.clone()),
+        "upgrade_times": upgrade_times,
         "version": get_release_md_link(helper.target_version.as_str()),
+        "voting_end_time": helper.voting_end_time.map(|time| locale.format_time(time)),
+        "voting_start_time": helper.voting_start_time.map(|time| locale.format_time(time)),
         "voting_time": helper.voting_period,
-    });
+    }))
+}
+
+/// Returns the audit information of the configuration, which is rendered into the proposal header.
+fn get_audit(helper: &UpgradeHelper) -> serde_json::Value {
+    let format_time = |time: Option<DateTime<Utc>>| {
+        time.map_or("-".to_string(), |time| {
+            time.format("%Y-%m-%d %H:%M UTC").to_string()
+        })
+    };
+
+    json!({
+        "created_at": format_time(helper.created_at),
+        "created_by": helper.created_by.as_deref().unwrap_or("unknown"),
+        "updated_at": format_time(helper.updated_at),
+    })
+}
 
-    Ok(handlebars.render("proposal", &data)?)
+/// Returns the time of day in UTC with minute precision, which is used for the downtime guidance.
+fn get_clock_time(time: DateTime<Utc>) -> String {
+    time.format("%H:%M UTC").to_string()
 }
 
-/// Returns the appropriate Markdown link to the block on Mintscan for the given network and height.
-fn get_height_with_link(network: Network, height: u64) -> String {
-    let height_with_commas = height.to_formatted_string(&num_format::Locale::en);
+/// Returns the appropriate Markdown link to the block on the configured explorer or Mintscan
+/// for the given network and height, which is displayed with the thousands separator of the given locale.
+fn get_height_with_link(
+    network: Network,
+    height: u64,
+    locale: Locale,
+    explorer_url: Option<&str>,
+) -> String {
+    let height_with_commas = locale.format_number(height);
+    if let Some(explorer_url) = explorer_url {
+        return format!(
+            "[{}]({}/blocks/{})",
+            height_with_commas,
+            explorer_url.trim_end_matches('/'),
+            height
+        );
+    }
+
     match network {
         Network::LocalNode => format!(
             "[{}](https://mintscan.io/evmos/blocks/{})",
@@ -58,6 +252,15 @@ fn get_height_with_link(network: Network, height: u64) -> String {
     }
 }
 
+/// Returns the comma-separated Markdown links to the given pull requests on GitHub.
+fn get_pr_md_links(pull_requests: &[u64]) -> String {
+    pull_requests
+        .iter()
+        .map(|number| format!("[#{0}](https://github.com/evmos/evmos/pull/{0})", number))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 /// Returns the appropriate Markdown link to the release on GitHub for the given version.
 fn get_release_md_link(version: &str) -> String {
     format!(
@@ -69,8 +272,15 @@ fn get_release_md_link(version: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use crate::block::BlockTimeEstimate;
+    use crate::breaking::BreakingChanges;
+    use crate::downtime::DowntimeEstimate;
+    use crate::helper::from_file;
+    use crate::ibc::ExpiringClient;
+    use crate::release::Binary;
+    use chrono::TimeZone;
     use std::path::PathBuf;
+    use test_dir::{DirBuilder, TestDir};
 
     #[test]
     fn test_render_proposal_pass() {
@@ -84,11 +294,316 @@ mod tests {
             "",
         );
 
-        let result = render_proposal(&helper);
+        let result = render_proposal(&helper, &ChainConfig::default(), true);
         assert!(
             result.is_ok(),
             "Error rendering proposal: {}",
             result.unwrap_err(),
         );
+        assert!(
+            !result.as_ref().unwrap().contains("IBC Client Expiry"),
+            "expected no IBC section without expiring clients"
+        );
+        assert!(
+            !result.unwrap().contains("Expected Downtime"),
+            "expected no downtime section without estimate"
+        );
+    }
+
+    #[test]
+    fn test_render_proposal_deterministic() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v13.0.0",
+            "v14.0.0",
+            Utc.with_ymd_and_hms(2023, 10, 23, 16, 0, 0).unwrap(),
+            18_500_000,
+            "- Add feature",
+        );
+        helper.binaries = vec![Binary {
+            platform: "linux/amd64".to_string(),
+            url: "https://github.com/evmos/evmos/releases/download/v14.0.0/evmos_14.0.0_Linux_amd64.tar.gz".to_string(),
+            checksum: "427c2c4a".to_string(),
+        }];
+        let test_dir = TestDir::temp();
+        let path = test_dir.path("proposal.json");
+        helper
+            .write_to_path(&path, Utc.with_ymd_and_hms(2023, 10, 1, 12, 0, 0).unwrap())
+            .expect("failed to export helper");
+        let exported = fs::read(&path).expect("failed to read exported helper");
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+
+        // NOTE: re-running with the exported configuration at a later time has to produce the identical artifacts
+        let mut imported = from_file(&path).expect("failed to import helper");
+        imported
+            .write_to_path(&path, Utc.with_ymd_and_hms(2023, 10, 2, 12, 0, 0).unwrap())
+            .expect("failed to export helper");
+        assert_eq!(
+            fs::read(&path).expect("failed to read exported helper"),
+            exported
+        );
+        assert_eq!(
+            render_proposal(&imported, &ChainConfig::default(), true)
+                .expect("failed to render proposal"),
+            proposal
+        );
+    }
+
+    #[test]
+    fn test_render_proposal_draft() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v0.0.1",
+            "v0.1.0-rc1",
+            Utc.with_ymd_and_hms(2023, 10, 23, 16, 0, 0).unwrap(),
+            0,
+            "",
+        );
+        helper.draft = true;
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.starts_with("> **DRAFT**"));
+        assert!(proposal.contains("at block height TBD (estimated to be around TBD"));
+        assert!(proposal.contains("| UTC | TBD |"));
+        assert!(!proposal.contains("October 23."));
+
+        helper.draft = false;
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(!proposal.contains("DRAFT"));
+    }
+
+    #[test]
+    fn test_render_proposal_upgrade_times() {
+        let helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v0.0.1",
+            "v0.1.0",
+            Utc.with_ymd_and_hms(2023, 10, 23, 16, 0, 0).unwrap(),
+            60,
+            "",
+        );
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.contains("| UTC | 4PM UTC on Mon., October 23., 2023 |"));
+        assert!(proposal.contains("| KST | 1AM KST on Tue., October 24., 2023 |"));
+        assert!(!proposal.contains("The voting period is expected to start"));
+
+        let mut helper = helper;
+        assert!(helper.set_voting_times(
+            Some(Utc.with_ymd_and_hms(2023, 10, 16, 16, 0, 0).unwrap()),
+            Some(Utc.with_ymd_and_hms(2023, 10, 21, 16, 0, 0).unwrap()),
+        ));
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.contains("The voting period is expected to start around"));
+    }
+
+    #[test]
+    fn test_get_audit() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v13.0.0",
+            "v14.0.0",
+            Utc::now(),
+            60,
+            "",
+        );
+        helper.created_at = Some(Utc.with_ymd_and_hms(2024, 1, 8, 9, 30, 0).unwrap());
+        helper.created_by = Some("Jane Doe".to_string());
+
+        assert_eq!(
+            get_audit(&helper),
+            json!({
+                "created_at": "2024-01-08 09:30 UTC",
+                "created_by": "Jane Doe",
+                "updated_at": "-",
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_proposal_with_binaries() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v13.0.0",
+            "v14.0.0",
+            Utc::now(),
+            60,
+            "",
+        );
+        helper.binaries = vec![Binary {
+            platform: "linux/amd64".to_string(),
+            url: "https://github.com/evmos/evmos/releases/download/v14.0.0/evmos_14.0.0_Linux_amd64.tar.gz".to_string(),
+            checksum: "427c2c4a".to_string(),
+        }];
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.contains(
+            "| linux/amd64 | [evmos_14.0.0_Linux_amd64.tar.gz](https://github.com/evmos/evmos/releases/download/v14.0.0/evmos_14.0.0_Linux_amd64.tar.gz) | `427c2c4a` |"
+        ));
+    }
+
+    #[test]
+    fn test_render_proposal_with_block_time_estimate() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v0.0.1",
+            "v0.1.0",
+            Utc::now(),
+            60,
+            "",
+        );
+        helper.block_time_estimate = Some(BlockTimeEstimate {
+            seconds_per_block: 1.9876,
+            n_blocks: 50_000,
+            latest_height: 18_798_834,
+            latest_time: Utc.with_ymd_and_hms(2023, 10, 23, 15, 59, 0).unwrap(),
+            estimated_at: Utc.with_ymd_and_hms(2023, 10, 23, 16, 0, 0).unwrap(),
+        });
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.contains(
+            "The height was estimated at 4PM UTC on Mon., October 23., 2023 \
+            using an average block time of 1.99 seconds, \
+            which was measured over the last 50,000 blocks up to block 18,798,834."
+        ));
+    }
+
+    #[test]
+    fn test_render_proposal_with_expiring_clients() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v0.0.1",
+            "v0.1.0",
+            Utc::now(),
+            60,
+            "",
+        );
+        helper.expiring_clients = vec![ExpiringClient {
+            client_id: "07-tendermint-0".to_string(),
+            chain_id: "cosmoshub-4".to_string(),
+            expiry: Utc.with_ymd_and_hms(2023, 10, 23, 16, 0, 0).unwrap(),
+        }];
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.contains("## IBC Client Expiry"));
+        assert!(proposal.contains(
+            "`07-tendermint-0` (cosmoshub-4), expiring around 4PM UTC on Mon., October 23., 2023"
+        ));
+    }
+
+    #[test]
+    fn test_render_proposal_with_breaking_changes() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v0.0.1",
+            "v0.1.0",
+            Utc::now(),
+            60,
+            "",
+        );
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(
+            !proposal.contains("Breaking Changes"),
+            "expected no caution section without breaking changes"
+        );
+
+        helper.breaking_changes = BreakingChanges {
+            state_breaking: vec![1234, 1240],
+            api_breaking: vec![],
+        };
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.contains("## Caution: State-Breaking Changes"));
+        assert!(proposal.contains(
+            "[#1234](https://github.com/evmos/evmos/pull/1234), [#1240](https://github.com/evmos/evmos/pull/1240)"
+        ));
+        assert!(!proposal.contains("## Caution: API-Breaking Changes"));
+    }
+
+    #[test]
+    fn test_render_proposal_with_community_summary() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v0.0.1",
+            "v0.1.0",
+            Utc::now(),
+            60,
+            "- technical summary",
+        );
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(!proposal.contains("## In Plain Words"));
+
+        helper.community_summary = Some("Transactions become cheaper.".to_string());
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.contains("- technical summary"));
+        assert!(proposal.contains("## In Plain Words\n\nTransactions become cheaper."));
+    }
+
+    #[test]
+    fn test_render_proposal_with_feature_headings() {
+        let helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v0.0.1",
+            "v0.1.0",
+            Utc::now(),
+            60,
+            "# Highlights\n\n- cheaper transactions\n\n## Fixes\n\n- fee estimation",
+        );
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.contains("## Impact\n\nEvmos"));
+        assert!(proposal.contains("### Highlights\n\n- cheaper transactions\n\n### Fixes\n"));
+    }
+
+    #[test]
+    fn test_render_proposal_with_downtime_estimate() {
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v0.0.1",
+            "v0.1.0",
+            Utc.with_ymd_and_hms(2023, 10, 23, 16, 0, 0).unwrap(),
+            60,
+            "",
+        );
+        helper.downtime_estimate = Some(DowntimeEstimate {
+            expected_minutes: 16,
+            max_minutes: 25,
+            n_upgrades: 3,
+        });
+
+        let proposal = render_proposal(&helper, &ChainConfig::default(), true)
+            .expect("failed to render proposal");
+        assert!(proposal.contains("## Expected Downtime"));
+        assert!(proposal.contains(
+            "Based on the durations of the last 3 upgrades, the upgrade is expected to take around 16 minutes"
+        ));
+        assert!(proposal.contains("stop sending transactions from 15:30 UTC"));
+        assert!(proposal.contains("resume around 16:16 UTC and at the latest around 16:25 UTC"));
     }
 }
@@ -0,0 +1,214 @@
+use crate::chain::get_config_dir;
+use crate::errors::ReadabilityError;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The name of the file inside of the proposer configuration directory,
+/// which contains the limits of the community guidelines for the length and readability of proposals.
+const READABILITY_FILE: &str = "readability.toml";
+
+/// The average number of words read per minute, which is used to estimate the reading time.
+const WORDS_PER_MINUTE: usize = 200;
+
+/// Contains the length and readability metrics of a proposal.
+#[derive(Debug, PartialEq)]
+pub struct ProposalMetrics {
+    /// The number of words in the proposal.
+    pub words: usize,
+    /// The estimated reading time in minutes, rounded up.
+    pub reading_minutes: usize,
+    /// The Flesch reading ease score, where higher values are easier to read.
+    pub reading_ease: f64,
+}
+
+impl fmt::Display for ProposalMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} words, about {} min reading time, reading ease {:.0}",
+            self.words, self.reading_minutes, self.reading_ease
+        )
+    }
+}
+
+/// Contains the limits of the community guidelines, which the proposal should stay within.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct ReadabilityLimits {
+    /// The maximum estimated reading time in minutes.
+    #[serde(default = "default_max_reading_minutes")]
+    pub max_reading_minutes: usize,
+    /// The minimum Flesch reading ease score.
+    #[serde(default = "default_min_reading_ease")]
+    pub min_reading_ease: f64,
+}
+
+impl Default for ReadabilityLimits {
+    fn default() -> Self {
+        ReadabilityLimits {
+            max_reading_minutes: default_max_reading_minutes(),
+            min_reading_ease: default_min_reading_ease(),
+        }
+    }
+}
+
+/// Returns the default maximum reading time in minutes.
+fn default_max_reading_minutes() -> usize {
+    5
+}
+
+/// Returns the default minimum reading ease, below which texts are considered difficult to read.
+fn default_min_reading_ease() -> f64 {
+    30.0
+}
+
+impl ReadabilityLimits {
+    /// Loads the limits from the given file. If the file does not exist, the default limits are used.
+    pub fn load(path: &Path) -> Result<ReadabilityLimits, ReadabilityError> {
+        if !path.exists() {
+            return Ok(ReadabilityLimits::default());
+        }
+
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Returns the warnings for the given metrics, which exceed the limits.
+    pub fn check(&self, metrics: &ProposalMetrics) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if metrics.reading_minutes > self.max_reading_minutes {
+            warnings.push(format!(
+                "the estimated reading time of {} min exceeds the guideline of {} min",
+                metrics.reading_minutes, self.max_reading_minutes
+            ));
+        }
+        if metrics.reading_ease < self.min_reading_ease {
+            warnings.push(format!(
+                "the reading ease of {:.0} is below the guideline of {:.0}",
+                metrics.reading_ease, self.min_reading_ease
+            ));
+        }
+
+        warnings
+    }
+}
+
+/// Loads the readability limits from the proposer configuration directory.
+pub fn get_readability_limits() -> Result<ReadabilityLimits, ReadabilityError> {
+    ReadabilityLimits::load(&get_config_dir().join(READABILITY_FILE))
+}
+
+/// Computes the length and readability metrics of the given Markdown text.
+/// Code blocks and the link targets are not counted, because they are not read as prose.
+pub fn get_metrics(markdown: &str) -> ProposalMetrics {
+    let prose = get_prose(markdown);
+    let words: Vec<&str> = prose
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .collect();
+    let n_sentences = prose
+        .split(['.', '!', '?', '\n'])
+        .filter(|sentence| sentence.chars().any(char::is_alphabetic))
+        .count()
+        .max(1);
+    let n_syllables: usize = words.iter().map(|word| count_syllables(word)).sum();
+
+    let reading_ease = match words.is_empty() {
+        true => 100.0,
+        false => {
+            206.835
+                - 1.015 * (words.len() as f64 / n_sentences as f64)
+                - 84.6 * (n_syllables as f64 / words.len() as f64)
+        }
+    };
+
+    ProposalMetrics {
+        words: words.len(),
+        reading_minutes: words.len().div_ceil(WORDS_PER_MINUTE),
+        reading_ease,
+    }
+}
+
+/// Returns the prose of the given Markdown text without code blocks and link targets.
+fn get_prose(markdown: &str) -> String {
+    let mut in_code_block = false;
+    let lines: Vec<&str> = markdown
+        .lines()
+        .filter(|line| {
+            if line.trim_start().starts_with("```") {
+                in_code_block = !in_code_block;
+                return false;
+            }
+            !in_code_block
+        })
+        .collect();
+
+    let links = regex::Regex::new(r"\]\([^)]*\)").expect("invalid regex");
+    links.replace_all(&lines.join("\n"), "]").to_string()
+}
+
+/// Estimates the number of syllables of the given word by counting the groups of vowels.
+fn count_syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for c in word.chars().filter(|c| c.is_alphabetic()) {
+        let is_vowel = "aeiouy".contains(c);
+        if is_vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = is_vowel;
+    }
+
+    // NOTE: a silent `e` at the end of a word does not form a syllable
+    if word.ends_with('e') && count > 1 {
+        count -= 1;
+    }
+
+    count.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_metrics() {
+        let markdown = "# Upgrade\n\nThe chain will be upgraded. Please prepare your nodes.\n\n\
+            ```\nevmosd tx gov vote 1 yes --from validator\n```\n\
+            See the [release notes](https://github.com/evmos/evmos/releases/tag/v16.0.0).";
+        let metrics = get_metrics(markdown);
+
+        assert_eq!(metrics.words, 14);
+        assert_eq!(metrics.reading_minutes, 1);
+        assert!(
+            metrics.reading_ease > 60.0,
+            "expected simple text to be easy to read, got {}",
+            metrics.reading_ease
+        );
+    }
+
+    #[test]
+    fn test_count_syllables() {
+        assert_eq!(count_syllables("upgrade"), 2);
+        assert_eq!(count_syllables("validator"), 4);
+        assert_eq!(count_syllables("the"), 1);
+    }
+
+    #[test]
+    fn test_check_limits() {
+        let limits = ReadabilityLimits::default();
+        let metrics = ProposalMetrics {
+            words: 1500,
+            reading_minutes: 8,
+            reading_ease: 20.0,
+        };
+
+        let warnings = limits.check(&metrics);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("8 min exceeds the guideline of 5 min"));
+        assert!(limits
+            .check(&get_metrics("The chain will be upgraded."))
+            .is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::errors::RegistryError;
+use crate::http::get_json;
+use crate::network::Network;
+use serde::Deserialize;
+use url::Url;
+
+/// The base URL of the raw files in the Cosmos chain registry on GitHub.
+pub const CHAIN_REGISTRY_URL: &str =
+    "https://raw.githubusercontent.com/cosmos/chain-registry/master/";
+
+/// Represents the relevant information of a chain in the `chain.json` file of the chain registry.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct RegistryChain {
+    /// The chain ID, e.g. `evmos_9001-2`.
+    pub chain_id: String,
+    /// The Bech32 prefix of the account addresses.
+    pub bech32_prefix: String,
+    /// The name of the daemon binary (optional).
+    #[serde(default)]
+    pub daemon_name: Option<String>,
+    /// The public API endpoints of the chain.
+    #[serde(default)]
+    pub apis: RegistryApis,
+    /// The block explorers of the chain.
+    #[serde(default)]
+    pub explorers: Vec<RegistryExplorer>,
+}
+
+/// Contains the public API endpoints of a chain in the chain registry.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct RegistryApis {
+    #[serde(default)]
+    pub rest: Vec<RegistryEndpoint>,
+    #[serde(default)]
+    pub rpc: Vec<RegistryEndpoint>,
+    #[serde(default)]
+    pub grpc: Vec<RegistryEndpoint>,
+}
+
+/// Represents an API endpoint in the chain registry.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct RegistryEndpoint {
+    /// The address of the endpoint.
+    pub address: String,
+    /// The provider of the endpoint (optional).
+    #[serde(default)]
+    pub provider: Option<String>,
+}
+
+/// Represents a block explorer in the chain registry.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct RegistryExplorer {
+    /// The name of the explorer (optional).
+    #[serde(default)]
+    pub kind: Option<String>,
+    /// The base URL of the explorer for the chain.
+    pub url: String,
+}
+
+impl RegistryChain {
+    /// Returns the values of the chain configuration, which are discovered from the registry.
+    /// The first listed endpoint of each kind is used.
+    pub fn to_config_values(&self) -> toml::Table {
+        let mut values = toml::Table::new();
+        values.insert("chain_id".to_string(), self.chain_id.clone().into());
+        values.insert(
+            "bech32_prefix".to_string(),
+            self.bech32_prefix.clone().into(),
+        );
+
+        let optional = [
+            ("daemon", self.daemon_name.clone()),
+            (
+                "rest_url",
+                self.apis
+                    .rest
+                    .first()
+                    .map(|endpoint| endpoint.address.clone()),
+            ),
+            (
+                "tm_rpc",
+                self.apis
+                    .rpc
+                    .first()
+                    .map(|endpoint| endpoint.address.clone()),
+            ),
+            (
+                "grpc_url",
+                self.apis
+                    .grpc
+                    .first()
+                    .map(|endpoint| endpoint.address.clone()),
+            ),
+            (
+                "explorer_url",
+                self.explorers.first().map(|explorer| explorer.url.clone()),
+            ),
+        ];
+        for (key, value) in optional {
+            if let Some(value) = value {
+                values.insert(key.to_string(), value.into());
+            }
+        }
+
+        values
+    }
+}
+
+/// Returns the path of the chain in the chain registry, where testnets are stored in a separate directory,
+/// e.g. `evmos/chain.json` or `testnets/evmostestnet/chain.json`.
+fn get_registry_path(name: &str, network: Network) -> String {
+    match network {
+        Network::Testnet => format!("testnets/{}/chain.json", name),
+        Network::LocalNode | Network::Mainnet => format!("{}/chain.json", name),
+    }
+}
+
+/// Queries the information of the chain with the given name from the chain registry at the given base URL.
+pub async fn get_registry_chain(
+    base_url: &Url,
+    name: &str,
+    network: Network,
+) -> Result<RegistryChain, RegistryError> {
+    Ok(get_json(base_url.join(&get_registry_path(name, network))?).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_get_registry_path() {
+        assert_eq!(
+            get_registry_path("evmos", Network::Mainnet),
+            "evmos/chain.json"
+        );
+        assert_eq!(
+            get_registry_path("evmostestnet", Network::Testnet),
+            "testnets/evmostestnet/chain.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_registry_chain() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/evmos/chain.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "chain_name": "evmos",
+                "chain_id": "evmos_9001-2",
+                "bech32_prefix": "evmos",
+                "daemon_name": "evmosd",
+                "apis": {
+                    "rpc": [{"address": "https://rpc.evmos.example.com", "provider": "Example"}],
+                    "rest": [
+                        {"address": "https://rest.evmos.example.com"},
+                        {"address": "https://rest2.evmos.example.com"}
+                    ]
+                },
+                "explorers": [{"kind": "mintscan", "url": "https://www.mintscan.io/evmos"}]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let base_url = Url::from_str(&mock_server.uri()).unwrap();
+        let chain = get_registry_chain(&base_url, "evmos", Network::Mainnet)
+            .await
+            .expect("failed to get chain from registry");
+        assert_eq!(chain.chain_id, "evmos_9001-2");
+
+        let values = chain.to_config_values();
+        assert_eq!(
+            values["rest_url"].as_str(),
+            Some("https://rest.evmos.example.com")
+        );
+        assert_eq!(
+            values["tm_rpc"].as_str(),
+            Some("https://rpc.evmos.example.com")
+        );
+        assert_eq!(
+            values["explorer_url"].as_str(),
+            Some("https://www.mintscan.io/evmos")
+        );
+        assert_eq!(values["daemon"].as_str(), Some("evmosd"));
+        assert!(!values.contains_key("grpc_url"));
+    }
+}
@@ -1,20 +1,111 @@
 use crate::errors::{PrepareError, ReleaseError};
 use crate::http::get_body;
+use crate::signature::{verify_checksums, ChecksumSignature, CHECKSUMS_FILE};
+use crate::version::parse_version;
 use octocrab::{
-    models::repos::{Asset, Release},
+    models::repos::{Asset, Release, Tag},
     Octocrab, Result,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// The number of attempts to query a release if GitHub cannot be reached.
+const RELEASE_ATTEMPTS: u32 = 3;
+
+/// The delay between the attempts to query a release if GitHub cannot be reached.
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// The number of existing tags, which are suggested if the tag of a release is not found.
+const N_NEAREST_TAGS: usize = 3;
 
 /// Sends a HTTP request to the GitHub release page and returns the response.
-pub async fn get_release(instance: &Octocrab, version: &str) -> Result<Release> {
-    instance
+///
+/// Network failures are retried a few times before failing. If no release exists for the given tag,
+/// the nearest existing tags are listed in the returned error.
+pub async fn get_release(
+    instance: &Octocrab,
+    version: &str,
+) -> std::result::Result<Release, ReleaseError> {
+    let mut attempt = 1;
+    loop {
+        match instance
+            .repos("evmos", "evmos")
+            .releases()
+            .get_by_tag(version)
+            .await
+        {
+            Ok(release) => return Ok(release),
+            Err(e) if is_not_found(&e) => {
+                return Err(ReleaseError::TagNotFound(
+                    version.to_string(),
+                    get_nearest_tags(instance, version).await,
+                ))
+            }
+            Err(e) if is_network_failure(&e) && attempt < RELEASE_ATTEMPTS => {
+                println!("WARNING: failed to reach GitHub ({}); retrying", e);
+                tokio::time::sleep(RETRY_DELAY).await;
+                attempt += 1;
+            }
+            Err(e) if is_network_failure(&e) => return Err(ReleaseError::Network(e)),
+            Err(e) => return Err(ReleaseError::GitHub(e)),
+        }
+    }
+}
+
+/// Returns whether the given error of the GitHub client is caused by a missing resource.
+fn is_not_found(error: &octocrab::Error) -> bool {
+    matches!(error, octocrab::Error::GitHub { source, .. } if source.message == "Not Found")
+}
+
+/// Returns whether the given error of the GitHub client is caused by a failed connection,
+/// so that the request can be retried.
+fn is_network_failure(error: &octocrab::Error) -> bool {
+    matches!(
+        error,
+        octocrab::Error::Hyper { .. }
+            | octocrab::Error::Service { .. }
+            | octocrab::Error::Http { .. }
+    )
+}
+
+/// Returns the existing tags among the latest tags of the repository, which are closest to the given version.
+/// Failures to list the tags are ignored, because the tags are only suggestions.
+async fn get_nearest_tags(instance: &Octocrab, version: &str) -> Vec<String> {
+    let Some(target) = parse_version(version) else {
+        return Vec::new();
+    };
+    let Ok(page) = instance
         .repos("evmos", "evmos")
-        .releases()
-        .get_by_tag(version)
+        .list_tags()
+        .per_page(100)
+        .send()
         .await
+    else {
+        return Vec::new();
+    };
+
+    let mut tags: Vec<(u64, u64, u64, String)> = page
+        .items
+        .into_iter()
+        .filter_map(|tag| {
+            let parsed = parse_version(&tag.name)?;
+            Some((
+                parsed.major.abs_diff(target.major),
+                parsed.minor.abs_diff(target.minor),
+                parsed.patch.abs_diff(target.patch),
+                tag.name,
+            ))
+        })
+        .collect();
+    tags.sort();
+
+    tags.into_iter()
+        .take(N_NEAREST_TAGS)
+        .map(|(.., name)| name)
+        .collect()
 }
 
 #[cfg(test)]
@@ -80,6 +171,7 @@ mod release_tests {
         assert_eq!(release.tag_name, "v14.0.0");
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[tokio::test]
     async fn test_get_release_fail() {
         let template = ResponseTemplate::new(404);
@@ -89,6 +181,145 @@ mod release_tests {
         let res = get_release(&client, "invalidj.xjaf/ie").await;
         assert_eq!(res.is_err(), true);
     }
+
+    #[tokio::test]
+    async fn test_get_release_tag_not_found() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/evmos/evmos/releases/tags/v14.0.2"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "message": "Not Found",
+                "documentation_url": "https://docs.github.com/rest/releases/releases#get-a-release-by-tag-name"
+            })))
+            .mount(&mock_server)
+            .await;
+        let tags: Vec<_> = ["v15.0.0", "v14.1.0", "v14.0.1", "v14.0.0", "v13.0.2"]
+            .iter()
+            .map(|name| serde_json::json!({
+                "name": name,
+                "commit": {
+                    "sha": "c5b85c5e2c3aa5e4a2e4d4d6a4b1b6b3a4e3e3e3",
+                    "url": "https://api.github.com/repos/evmos/evmos/commits/c5b85c5e2c3aa5e4a2e4d4d6a4b1b6b3a4e3e3e3"
+                },
+                "zipball_url": format!("https://api.github.com/repos/evmos/evmos/zipball/refs/tags/{}", name),
+                "tarball_url": format!("https://api.github.com/repos/evmos/evmos/tarball/refs/tags/{}", name),
+                "node_id": "MDM6UmVmMTIzNDU2Nzg5OnJlZnMvdGFncy92MTQuMC4w"
+            }))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/repos/evmos/evmos/tags"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(tags))
+            .mount(&mock_server)
+            .await;
+        let client = setup_octocrab(&mock_server.uri());
+
+        match get_release(&client, "v14.0.2").await {
+            Err(ReleaseError::TagNotFound(version, nearest)) => {
+                assert_eq!(version, "v14.0.2");
+                assert_eq!(nearest, vec!["v14.0.1", "v14.0.0", "v14.1.0"]);
+            }
+            res => panic!(
+                "expected missing tag, got {:?}",
+                res.map(|release| release.tag_name)
+            ),
+        }
+    }
+}
+
+/// Returns the latest release candidate tag (e.g. `v14.0.0-rc4`) for the given version
+/// or None if no release candidate exists for it.
+pub async fn get_latest_rc(instance: &Octocrab, version: &str) -> Result<Option<String>> {
+    let mut page = instance
+        .repos("evmos", "evmos")
+        .list_tags()
+        .per_page(100)
+        .send()
+        .await?;
+
+    let mut release_candidates: Vec<(u32, String)> = Vec::new();
+    loop {
+        release_candidates.extend(
+            page.items.iter().filter_map(|tag| {
+                get_rc_number(&tag.name, version).map(|rc| (rc, tag.name.clone()))
+            }),
+        );
+
+        match instance.get_page::<Tag>(&page.next).await? {
+            Some(next_page) => page = next_page,
+            None => break,
+        }
+    }
+
+    Ok(release_candidates
+        .into_iter()
+        .max_by_key(|(rc, _)| *rc)
+        .map(|(_, tag)| tag))
+}
+
+/// Returns the release candidate number if the given tag is a release candidate
+/// for the given version.
+fn get_rc_number(tag: &str, version: &str) -> Option<u32> {
+    tag.strip_prefix(version)?.strip_prefix("-rc")?.parse().ok()
+}
+
+#[cfg(test)]
+mod rc_tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::{
+        matchers::{method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    /// Returns a tag in the format of the GitHub tags API response.
+    fn tag(name: &str) -> serde_json::Value {
+        json!({
+            "name": name,
+            "commit": {
+                "sha": "c5b85c5e2c3aa5e4a2e4d4d6a4b1b6b3a4e3e3e3",
+                "url": "https://api.github.com/repos/evmos/evmos/commits/c5b85c5e2c3aa5e4a2e4d4d6a4b1b6b3a4e3e3e3"
+            },
+            "zipball_url": format!("https://api.github.com/repos/evmos/evmos/zipball/refs/tags/{}", name),
+            "tarball_url": format!("https://api.github.com/repos/evmos/evmos/tarball/refs/tags/{}", name),
+            "node_id": "MDM6UmVmMTIzNDU2Nzg5OnJlZnMvdGFncy92MTQuMC4w"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_latest_rc() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/evmos/evmos/tags"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                tag("v14.0.1-rc1"),
+                tag("v14.0.0-rc10"),
+                tag("v14.0.0-rc9"),
+                tag("v14.0.0-rc2"),
+                tag("v13.0.0-rc12"),
+            ])))
+            .mount(&mock_server)
+            .await;
+
+        let client = Octocrab::builder()
+            .base_uri(mock_server.uri())
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let rc = get_latest_rc(&client, "v14.0.0").await.unwrap();
+        assert_eq!(rc, Some("v14.0.0-rc10".to_string()));
+
+        let rc = get_latest_rc(&client, "v15.0.0").await.unwrap();
+        assert_eq!(rc, None);
+    }
+
+    #[test]
+    fn test_get_rc_number() {
+        assert_eq!(get_rc_number("v14.0.0-rc3", "v14.0.0"), Some(3));
+        assert_eq!(get_rc_number("v14.0.0", "v14.0.0"), None);
+        assert_eq!(get_rc_number("v14.0.0-rc3", "v14.0.1"), None);
+        assert_eq!(get_rc_number("v14.0.0-rcX", "v14.0.0"), None);
+    }
 }
 
 /// Returns the release notes from the Release.
@@ -119,51 +350,88 @@ mod release_notes_tests {
     }
 }
 
+/// Represents a released binary for a single platform.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Binary {
+    /// The platform of the binary, e.g. `linux/amd64`.
+    pub platform: String,
+    /// The download URL of the binary.
+    pub url: String,
+    /// The SHA256 checksum of the binary.
+    pub checksum: String,
+}
+
+/// Returns the binaries of the release with their checksums, sorted by platform.
+/// The binaries are built from the same checksum map as the asset string.
+pub async fn get_binaries(
+    release: &Release,
+    signature: Option<&ChecksumSignature>,
+) -> Result<Vec<Binary>, PrepareError> {
+    let checksums = get_checksum_map(&release.assets, signature).await?;
+
+    Ok(collect_binaries(release, &checksums))
+}
+
 /// Returns the asset string for the release assets.
 /// The asset string is used in the Evmos CLI command.
-pub async fn get_asset_string(release: &Release) -> Result<String, PrepareError> {
-    let checksums = get_checksum_map(&release.assets).await?;
+///
+/// If a checksum signature is configured, the signature of the checksum file
+/// is verified before its contents are used.
+pub async fn get_asset_string(
+    release: &Release,
+    signature: Option<&ChecksumSignature>,
+) -> Result<String, PrepareError> {
+    let checksums = get_checksum_map(&release.assets, signature).await?;
 
     Ok(build_assets_json(release, checksums).to_string())
 }
 
-/// Builds the assets JSON object.
-fn build_assets_json(release: &Release, checksums: HashMap<String, String>) -> Value {
-    let mut assets = serde_json::json!({
-        "binaries": {}
-    });
-
-    release.assets.iter()
+/// Collects the binaries of the release, for which a checksum is contained in the checksum map.
+fn collect_binaries(release: &Release, checksums: &BTreeMap<String, String>) -> Vec<Binary> {
+    let mut binaries: Vec<Binary> = release
+        .assets
+        .iter()
         .filter_map(|asset| {
-            let os_key = get_os_key_from_asset_name(&asset.name)?;
+            let platform = get_os_key_from_asset_name(&asset.name)?;
             let checksum = checksums.get(&asset.name)?;
-            let url = format!("{}?checksum={}", asset.browser_download_url, checksum);
-            
-            Some((os_key, url))
+
+            Some(Binary {
+                platform,
+                url: asset.browser_download_url.to_string(),
+                checksum: checksum.clone(),
+            })
         })
-        .for_each(|(os_key, url)| {
-            insert_into_assets(&mut assets, os_key, url);
-        });
+        .collect();
+    binaries.sort_by(|a, b| a.platform.cmp(&b.platform));
 
-    assets
+    binaries
 }
 
-/// Inserts a new key value pair into the assets binaries.
-/// The key is the OS key and the value is the download URL.
-fn insert_into_assets(assets: &mut Value, key: String, url: String) {
-    let binaries = assets["binaries"].as_object_mut().unwrap();
-    binaries.insert(key, serde_json::json!(url));
+/// Builds the assets JSON object, which maps the OS keys to the download URLs.
+/// The binaries are collected into an ordered map, so that the JSON is identical across runs.
+fn build_assets_json(release: &Release, checksums: BTreeMap<String, String>) -> Value {
+    let binaries: BTreeMap<String, String> = collect_binaries(release, &checksums)
+        .into_iter()
+        .map(|binary| {
+            let url = format!("{}?checksum={}", binary.url, binary.checksum);
+            (binary.platform, url)
+        })
+        .collect();
+
+    serde_json::json!({
+        "binaries": binaries
+    })
 }
 
 /// Returns the checksum from the release assets.
 fn get_checksum_from_assets(assets: &[Asset]) -> Option<&Asset> {
-    assets.iter().find(|asset| asset.name == "checksums.txt")
+    assets.iter().find(|asset| asset.name == CHECKSUMS_FILE)
 }
 
 /// Returns the OS key from the asset name.
 fn get_os_key_from_asset_name(name: &str) -> Option<String> {
     // Check for regex (Linux|Darwin)_(amd64|arm64).tar.gz and store os and arch in variables
-    return match regex::Regex::new(r"(Linux|Darwin)_(amd64|arm64)") {
+    match regex::Regex::new(r"(Linux|Darwin)_(amd64|arm64)") {
         Ok(re) => {
             let captures = re.captures(name)?;
             let os = captures.get(1)?.as_str().to_ascii_lowercase();
@@ -175,15 +443,21 @@ fn get_os_key_from_asset_name(name: &str) -> Option<String> {
             println!("no key found for asset: {}", name);
             None
         }
-    };
+    }
 }
 
 /// Downloads the checksum file from the release assets and returns the built checksum string.
-async fn get_checksum_map(assets: &[Asset]) -> Result<HashMap<String, String>, PrepareError> {
-    let checksum = get_checksum_from_assets(assets)
-        .ok_or(PrepareError::GetChecksumAsset)?;
-    
+/// If a checksum signature is configured, the downloaded file is verified first.
+async fn get_checksum_map(
+    assets: &[Asset],
+    signature: Option<&ChecksumSignature>,
+) -> Result<BTreeMap<String, String>, PrepareError> {
+    let checksum = get_checksum_from_assets(assets).ok_or(PrepareError::GetChecksumAsset)?;
+
     let body = get_body(checksum.browser_download_url.clone()).await?;
+    if let Some(signature) = signature {
+        verify_checksums(signature, &body, assets).await?;
+    }
 
     let checksums = body
         .lines()
@@ -199,14 +473,8 @@ fn parse_checksum_line(line: &str) -> Option<(String, String)> {
     let mut parts = line.split_whitespace();
     let checksum = parts.next()?.to_string();
     let asset_name = parts.next()?.to_string();
-    
-    (!parts.next().is_some() && !asset_name.contains("Windows"))
-        .then_some((asset_name, checksum))
-}
 
-/// Returns an Octocrab instance.
-pub fn get_instance() -> Arc<Octocrab> {
-    octocrab::instance()
+    (parts.next().is_none() && !asset_name.contains("Windows")).then_some((asset_name, checksum))
 }
 
 #[cfg(test)]
@@ -218,7 +486,7 @@ mod assets_tests {
     async fn test_get_checksum_map_pass() {
         let release: Release = serde_json::from_str(include_str!("testdata/release.json")).unwrap();
 
-        let checksums = get_checksum_map(&release.assets).await.unwrap();
+        let checksums = get_checksum_map(&release.assets, None).await.unwrap();
 
         assert!(checksums.contains_key("evmos_14.0.0_Linux_amd64.tar.gz"));
         assert!(checksums.contains_key("evmos_14.0.0_Linux_arm64.tar.gz"));
@@ -230,7 +498,7 @@ mod assets_tests {
     async fn test_get_asset_string_pass() {
         let release: Release = serde_json::from_str(include_str!("testdata/release.json")).unwrap();
 
-        let res = get_asset_string(&release).await;
+        let res = get_asset_string(&release, None).await;
         assert!(res.is_ok());
 
         let assets = res.unwrap();
@@ -250,12 +518,30 @@ mod assets_tests {
         );
     }
 
+    #[test]
+    fn test_collect_binaries() {
+        let release: Release = serde_json::from_str(include_str!("testdata/release.json")).unwrap();
+        let checksums = BTreeMap::from([(
+            "evmos_14.0.0_Linux_amd64.tar.gz".to_string(),
+            "427c2c4a37f3e8cf6833388240fcda152a5372d4c5132ca2e3861a7085d35cd0".to_string(),
+        )]);
+
+        assert_eq!(
+            collect_binaries(&release, &checksums),
+            vec![Binary {
+                platform: "linux/amd64".to_string(),
+                url: "https://github.com/evmos/evmos/releases/download/v14.0.0/evmos_14.0.0_Linux_amd64.tar.gz".to_string(),
+                checksum: "427c2c4a37f3e8cf6833388240fcda152a5372d4c5132ca2e3861a7085d35cd0".to_string(),
+            }]
+        );
+    }
+
     #[tokio::test]
     async fn test_get_asset_string_fail() {
         let release: Release =
             serde_json::from_str(include_str!("testdata/release_no_assets.json")).unwrap();
 
-        assert!(get_asset_string(&release).await.is_err());
+        assert!(get_asset_string(&release, None).await.is_err());
     }
 
     #[test]
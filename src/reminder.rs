@@ -0,0 +1,120 @@
+use crate::{
+    block::get_rpc_url, chain::ChainConfig, command::FEE_AMOUNT, errors::ReminderError,
+    helper::UpgradeHelper, inputs::get_time_strings_by_timezone, network::Network, template,
+};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+/// The path to the template of the vote reminder for validators.
+pub const REMINDER_TEMPLATE: &str = "src/templates/vote_reminder.hbs";
+
+/// Returns the file name of the vote reminder for the given helper.
+pub fn get_reminder_file_name(helper: &UpgradeHelper) -> String {
+    helper
+        .proposal_file_name
+        .replace(".md", "-vote-reminder.md")
+}
+
+/// Returns the link to vote on the given proposal on the configured explorer or Mintscan.
+pub fn get_vote_link(network: Network, proposal_id: u64, explorer_url: Option<&str>) -> String {
+    if let Some(explorer_url) = explorer_url {
+        return format!(
+            "{}/proposals/{}",
+            explorer_url.trim_end_matches('/'),
+            proposal_id
+        );
+    }
+
+    match network {
+        Network::LocalNode | Network::Mainnet => {
+            format!("https://www.mintscan.io/evmos/proposals/{}", proposal_id)
+        }
+        Network::Testnet => format!(
+            "https://www.mintscan.io/evmos-testnet/proposals/{}",
+            proposal_id
+        ),
+    }
+}
+
+/// Renders the reminder for validators to vote on the given proposal, which contains
+/// the link to vote on the explorer, the CLI command to vote and the given end of the voting period
+/// in UTC and the display timezones.
+pub fn render_reminder(
+    helper: &UpgradeHelper,
+    chain_config: &ChainConfig,
+    proposal_id: u64,
+    voting_end_time: DateTime<Utc>,
+) -> Result<String, ReminderError> {
+    let handlebars = template::get_handlebars("vote_reminder", REMINDER_TEMPLATE, true, false)?;
+    let deadlines: Vec<_> = get_time_strings_by_timezone(voting_end_time, helper.locale)
+        .into_iter()
+        .map(|(timezone, time)| json!({"timezone": timezone, "time": time}))
+        .collect();
+
+    let data = json!({
+        "chain_id": helper.chain_id,
+        "daemon": chain_config.daemon,
+        "deadlines": deadlines,
+        "fees": format!("{}{}", FEE_AMOUNT, chain_config.get_fee_denom(helper.network)),
+        "height": helper.upgrade_height,
+        "name": helper.proposal_name,
+        "network": helper.network,
+        "proposal_id": proposal_id,
+        "tm_rpc": chain_config
+            .tm_rpc
+            .clone()
+            .unwrap_or_else(|| get_rpc_url(helper.network)),
+        "tracking_issue": helper.tracking_issue.as_ref().map(|issue| issue.url.clone()),
+        "version": helper.target_version,
+        "vote_link": get_vote_link(helper.network, proposal_id, chain_config.explorer_url.as_deref()),
+    });
+
+    Ok(template::render(
+        &handlebars,
+        "vote_reminder",
+        REMINDER_TEMPLATE,
+        &data,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_get_vote_link() {
+        assert_eq!(
+            get_vote_link(Network::Testnet, 210, None),
+            "https://www.mintscan.io/evmos-testnet/proposals/210"
+        );
+        assert_eq!(
+            get_vote_link(Network::Mainnet, 210, Some("https://explorer.example.com/")),
+            "https://explorer.example.com/proposals/210"
+        );
+    }
+
+    #[test]
+    fn test_render_reminder() {
+        let voting_end_time = Utc.with_ymd_and_hms(2024, 1, 15, 16, 0, 0).unwrap();
+        let helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v15.0.0",
+            "v16.0.0-rc4",
+            Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap(),
+            19_000_000,
+            "",
+        );
+
+        let reminder = render_reminder(&helper, &ChainConfig::default(), 210, voting_end_time)
+            .expect("failed to render reminder");
+        assert!(reminder.contains("Proposal #210 to upgrade the Evmos Testnet to v16.0.0-rc4"));
+        assert!(reminder.contains("- UTC: 4PM UTC on Mon., January 15., 2024"));
+        assert!(reminder.contains("- KST: "));
+        assert!(reminder.contains("https://www.mintscan.io/evmos-testnet/proposals/210"));
+        assert!(reminder.contains("evmosd tx gov vote 210 yes"));
+        assert!(reminder.contains("--chain-id evmos_9000-4"));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::errors::InputError;
+use crate::network::Network;
+use clap::ValueEnum;
+use std::path::PathBuf;
+
+/// The environment variable, which sets the network if no `--network` flag is given.
+pub const NETWORK_VAR: &str = "PROPOSER_NETWORK";
+
+/// The environment variable, which sets the home directory of the Evmos binary if no `--home` flag is given.
+pub const HOME_VAR: &str = "PROPOSER_HOME";
+
+/// Contains the values of the global `--network` and `--home` flags, which override
+/// the network and home directory for any sub-command.
+#[derive(Clone, Debug, Default)]
+pub struct GlobalFlags {
+    /// The network to use instead of prompting for it.
+    pub network: Option<Network>,
+    /// The home directory of the Evmos binary to use instead of the configured one.
+    pub home: Option<PathBuf>,
+}
+
+/// Returns the network, which is set by a flag or the environment, if any.
+///
+/// The given flag of the sub-command takes precedence over the global flag,
+/// which takes precedence over the `PROPOSER_NETWORK` environment variable.
+pub fn get_network_override(
+    flags: &GlobalFlags,
+    flag: Option<Network>,
+) -> Result<Option<Network>, InputError> {
+    if let Some(network) = flag.or(flags.network) {
+        return Ok(Some(network));
+    }
+
+    std::env::var(NETWORK_VAR)
+        .ok()
+        .map(|value| parse_network(&value))
+        .transpose()
+}
+
+/// Returns the home directory, which is set by a flag or the environment, if any.
+///
+/// The given flag of the sub-command takes precedence over the global flag,
+/// which takes precedence over the `PROPOSER_HOME` environment variable.
+pub fn get_home_override(flags: &GlobalFlags, flag: Option<PathBuf>) -> Option<PathBuf> {
+    flag.or_else(|| flags.home.clone())
+        .or_else(|| std::env::var(HOME_VAR).ok().map(PathBuf::from))
+}
+
+/// Parses the given network name as accepted by the `--network` flag, e.g. `mainnet` or `local-node`.
+fn parse_network(value: &str) -> Result<Network, InputError> {
+    Network::from_str(value.trim(), true)
+        .map_err(|_| InputError::InvalidEnvVar(NETWORK_VAR, value.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_network() {
+        assert_eq!(parse_network("mainnet").unwrap(), Network::Mainnet);
+        assert_eq!(parse_network(" Testnet").unwrap(), Network::Testnet);
+        assert_eq!(parse_network("local-node").unwrap(), Network::LocalNode);
+        assert!(parse_network("devnet").is_err());
+    }
+
+    #[test]
+    fn test_flag_takes_precedence() {
+        let flags = GlobalFlags {
+            network: Some(Network::Mainnet),
+            home: Some(PathBuf::from("/root/.evmosd")),
+        };
+
+        assert_eq!(
+            get_network_override(&flags, Some(Network::Testnet)).unwrap(),
+            Some(Network::Testnet)
+        );
+        assert_eq!(
+            get_home_override(&flags, Some(PathBuf::from("/root/.evmosd-test"))),
+            Some(PathBuf::from("/root/.evmosd-test"))
+        );
+    }
+
+    #[test]
+    fn test_global_flags() {
+        let flags = GlobalFlags {
+            network: Some(Network::Mainnet),
+            home: Some(PathBuf::from("/root/.evmosd")),
+        };
+
+        assert_eq!(
+            get_network_override(&flags, None).unwrap(),
+            Some(Network::Mainnet)
+        );
+        assert_eq!(
+            get_home_override(&flags, None),
+            Some(PathBuf::from("/root/.evmosd"))
+        );
+    }
+}
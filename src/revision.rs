@@ -0,0 +1,190 @@
+use crate::errors::RevisionError;
+use crate::utils::write_atomically;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The name of the index file in the working directory, which lists the draft revisions
+/// of the generated proposals and the revision that was promoted as final.
+pub const INDEX_FILE: &str = "revisions.json";
+
+/// Represents a draft revision of a proposal.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Revision {
+    /// The number of the revision, starting at 1.
+    pub revision: u32,
+    /// The name of the file containing the revision.
+    pub file: String,
+    /// The time at which the revision was created.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Contains the revisions of a single proposal.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProposalRevisions {
+    /// The revisions in the order they were created.
+    pub revisions: Vec<Revision>,
+    /// The revision that was promoted as final, if any.
+    #[serde(default)]
+    pub final_revision: Option<u32>,
+}
+
+/// Represents the index of the draft revisions of all proposals in the working directory,
+/// which are keyed by the name of the proposal file.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RevisionIndex {
+    pub proposals: BTreeMap<String, ProposalRevisions>,
+}
+
+impl RevisionIndex {
+    /// Loads the index from the given file.
+    /// If the file does not exist yet, an empty index is returned.
+    pub fn load(path: &Path) -> Result<RevisionIndex, RevisionError> {
+        if !path.exists() {
+            return Ok(RevisionIndex::default());
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the index to the given file.
+    pub fn save(&self, path: &Path) -> Result<(), RevisionError> {
+        Ok(write_atomically(
+            path,
+            serde_json::to_string_pretty(self)?.as_bytes(),
+        )?)
+    }
+}
+
+/// Returns the name of the file containing the given revision of the proposal file,
+/// e.g. `proposal-Mainnet-v17.0.0.r2.md`.
+pub fn get_revision_file_name(file_name: &str, revision: u32) -> String {
+    match file_name.rsplit_once('.') {
+        Some((stem, extension)) => format!("{}.r{}.{}", stem, revision, extension),
+        None => format!("{}.r{}", file_name, revision),
+    }
+}
+
+/// Writes the given contents as a new draft revision of the proposal file in the given directory
+/// and returns the number of the revision.
+///
+/// If the contents match the latest revision, no new revision is created, so that regenerating
+/// without changes is idempotent. The proposal file itself is updated with the contents
+/// unless a revision was already promoted as final.
+pub fn write_revision(
+    dir: &Path,
+    file_name: &str,
+    contents: &str,
+    now: DateTime<Utc>,
+) -> Result<u32, RevisionError> {
+    let index_path = dir.join(INDEX_FILE);
+    let mut index = RevisionIndex::load(&index_path)?;
+    let revisions = index.proposals.entry(file_name.to_string()).or_default();
+
+    let latest = revisions.revisions.last();
+    let unchanged = latest.is_some_and(|latest| {
+        fs::read_to_string(dir.join(&latest.file)).is_ok_and(|latest| latest == contents)
+    });
+    let revision = match (latest, unchanged) {
+        (Some(latest), true) => latest.revision,
+        _ => {
+            let revision = latest.map_or(1, |latest| latest.revision + 1);
+            let revision_file = get_revision_file_name(file_name, revision);
+            write_atomically(&dir.join(&revision_file), contents.as_bytes())?;
+            revisions.revisions.push(Revision {
+                revision,
+                file: revision_file,
+                created_at: now,
+            });
+
+            revision
+        }
+    };
+
+    match revisions.final_revision {
+        Some(final_revision) => println!(
+            "WARNING: revision {} of {} is promoted as final; the draft was written to {}",
+            final_revision,
+            file_name,
+            get_revision_file_name(file_name, revision)
+        ),
+        None => write_atomically(&dir.join(file_name), contents.as_bytes())?,
+    }
+
+    index.save(&index_path)?;
+
+    Ok(revision)
+}
+
+/// Promotes the given revision of the proposal file in the given directory as final,
+/// which copies its contents to the proposal file and marks it as final in the index.
+pub fn promote(dir: &Path, file_name: &str, revision: u32) -> Result<(), RevisionError> {
+    let index_path = dir.join(INDEX_FILE);
+    let mut index = RevisionIndex::load(&index_path)?;
+    let revisions = index
+        .proposals
+        .get_mut(file_name)
+        .ok_or_else(|| RevisionError::NotFound(file_name.to_string(), revision))?;
+    let entry = revisions
+        .revisions
+        .iter()
+        .find(|entry| entry.revision == revision)
+        .ok_or_else(|| RevisionError::NotFound(file_name.to_string(), revision))?;
+
+    let contents = fs::read(dir.join(&entry.file))?;
+    write_atomically(&dir.join(file_name), &contents)?;
+    revisions.final_revision = Some(revision);
+
+    index.save(&index_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, TestDir};
+
+    const FILE_NAME: &str = "proposal-Mainnet-v17.0.0.md";
+
+    #[test]
+    fn test_get_revision_file_name() {
+        assert_eq!(
+            get_revision_file_name(FILE_NAME, 2),
+            "proposal-Mainnet-v17.0.0.r2.md"
+        );
+    }
+
+    #[test]
+    fn test_write_and_promote_revisions() {
+        let test_dir = TestDir::temp();
+        let dir = test_dir.root();
+        let now = Utc::now();
+
+        assert_eq!(write_revision(dir, FILE_NAME, "first", now).unwrap(), 1);
+        assert_eq!(write_revision(dir, FILE_NAME, "first", now).unwrap(), 1);
+        assert_eq!(write_revision(dir, FILE_NAME, "second", now).unwrap(), 2);
+        assert_eq!(
+            fs::read_to_string(dir.join("proposal-Mainnet-v17.0.0.r1.md")).unwrap(),
+            "first"
+        );
+        assert_eq!(fs::read_to_string(dir.join(FILE_NAME)).unwrap(), "second");
+
+        promote(dir, FILE_NAME, 1).unwrap();
+        assert_eq!(fs::read_to_string(dir.join(FILE_NAME)).unwrap(), "first");
+
+        // NOTE: new drafts don't replace the promoted revision
+        assert_eq!(write_revision(dir, FILE_NAME, "third", now).unwrap(), 3);
+        assert_eq!(fs::read_to_string(dir.join(FILE_NAME)).unwrap(), "first");
+
+        let index = RevisionIndex::load(&dir.join(INDEX_FILE)).unwrap();
+        let revisions = &index.proposals[FILE_NAME];
+        assert_eq!(revisions.revisions.len(), 3);
+        assert_eq!(revisions.final_revision, Some(1));
+
+        assert!(matches!(
+            promote(dir, FILE_NAME, 4),
+            Err(RevisionError::NotFound(_, 4))
+        ));
+    }
+}
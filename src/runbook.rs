@@ -0,0 +1,259 @@
+use crate::{
+    block::get_rpc_url,
+    chain::{get_network_key, ChainConfig},
+    errors::RunbookError,
+    helper::UpgradeHelper,
+    inputs::get_time_strings_by_timezone,
+    template,
+};
+use chrono::{DateTime, Duration, Utc};
+use handlebars::{no_escape, Handlebars};
+use serde_json::json;
+use std::path::Path;
+
+/// The path to the runbook template.
+pub const RUNBOOK_TEMPLATE: &str = "src/templates/runbook.hbs";
+
+/// The path to the template of the emergency instructions to skip the upgrade.
+pub const SKIP_UPGRADE_TEMPLATE: &str = "src/templates/skip_upgrade.hbs";
+
+/// Returns the file name of the runbook for the given helper.
+pub fn get_runbook_file_name(helper: &UpgradeHelper) -> String {
+    helper.proposal_file_name.replace(".md", "-runbook.md")
+}
+
+/// Returns the file name of the emergency instructions to skip the upgrade for the given helper.
+pub fn get_skip_upgrade_file_name(helper: &UpgradeHelper) -> String {
+    helper.proposal_file_name.replace(".md", "-skip-upgrade.md")
+}
+
+/// Renders the template at the given path with the given data.
+fn render_template(
+    name: &str,
+    path: &str,
+    data: &serde_json::Value,
+) -> Result<String, RunbookError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(true);
+    handlebars.register_escape_fn(no_escape);
+    template::register_partials(&mut handlebars, Path::new(template::PARTIALS_DIR))?;
+
+    handlebars
+        .register_template_file(name, path)
+        .unwrap_or_else(|_| panic!("Failed to register {} template file", name));
+
+    Ok(handlebars.render(name, data)?)
+}
+
+/// Renders the runbook for the day of the upgrade, which contains a timeline relative
+/// to the given estimated time of reaching the upgrade height.
+pub fn render_runbook(
+    helper: &UpgradeHelper,
+    chain_config: &ChainConfig,
+    estimated_time: DateTime<Utc>,
+) -> Result<String, RunbookError> {
+    let downtime = helper.downtime_estimate.as_ref().map(|estimate| {
+        json!({
+            "expected_resumption": get_timeline_time(estimate.expected_resumption(estimated_time)),
+            "latest_resumption": get_timeline_time(estimate.latest_resumption(estimated_time)),
+        })
+    });
+    let upgrade_times: Vec<_> = get_time_strings_by_timezone(estimated_time, helper.locale)
+        .into_iter()
+        .map(|(timezone, time)| json!({"timezone": timezone, "time": time}))
+        .collect();
+    let data = json!({
+        "daemon": chain_config.daemon,
+        "downtime": downtime,
+        "estimated_time": helper.locale.format_time(estimated_time),
+        "height": helper.upgrade_height,
+        "name": helper.proposal_name,
+        "network": helper.network,
+        "network_arg": get_network_key(helper.network),
+        "plan_name": helper.get_plan_name(),
+        "previous_version": helper.previous_version,
+        "timeline": get_timeline(helper, estimated_time),
+        "tm_rpc": chain_config
+            .tm_rpc
+            .clone()
+            .unwrap_or_else(|| get_rpc_url(helper.network)),
+        "tracking_issue": helper.tracking_issue.as_ref().map(|issue| issue.url.clone()),
+        "upgrade_times": upgrade_times,
+        "version": helper.target_version,
+    });
+
+    render_template("runbook", RUNBOOK_TEMPLATE, &data)
+}
+
+/// Renders the emergency instructions to skip the upgrade, which are prepared alongside the runbook
+/// in case the upgrade has to be aborted at the last minute. The coordination message is pre-filled
+/// with the upgrade height, the versions and the given estimated time of reaching the upgrade height.
+pub fn render_skip_upgrade(
+    helper: &UpgradeHelper,
+    chain_config: &ChainConfig,
+    estimated_time: DateTime<Utc>,
+) -> Result<String, RunbookError> {
+    let data = json!({
+        "daemon": chain_config.daemon,
+        "estimated_time": helper.locale.format_time(estimated_time),
+        "height": helper.upgrade_height,
+        "name": helper.proposal_name,
+        "network": helper.network,
+        "network_arg": get_network_key(helper.network),
+        "previous_version": helper.previous_version,
+        "tm_rpc": chain_config
+            .tm_rpc
+            .clone()
+            .unwrap_or_else(|| get_rpc_url(helper.network)),
+        "tracking_issue": helper.tracking_issue.as_ref().map(|issue| issue.url.clone()),
+        "version": helper.target_version,
+    });
+
+    render_template("skip_upgrade", SKIP_UPGRADE_TEMPLATE, &data)
+}
+
+/// Returns the steps of the upgrade day with their times relative to the estimated upgrade time.
+fn get_timeline(helper: &UpgradeHelper, estimated_time: DateTime<Utc>) -> Vec<serde_json::Value> {
+    let mut timeline = vec![
+        (
+            "T-24h",
+            estimated_time - Duration::hours(24),
+            "Communicate the upgrade to validators and the community".to_string(),
+        ),
+        (
+            "T-1h",
+            estimated_time - Duration::hours(1),
+            "Check the block height estimation and the release binaries".to_string(),
+        ),
+    ];
+
+    if let Some(estimate) = &helper.downtime_estimate {
+        timeline.push((
+            "T-30m",
+            estimate.stop_transactions_at(estimated_time),
+            "Users and integrators stop sending transactions".to_string(),
+        ));
+    }
+
+    timeline.push((
+        "T-0",
+        estimated_time,
+        format!(
+            "Chain halts at height {} and nodes switch to {}",
+            helper.upgrade_height, helper.target_version
+        ),
+    ));
+
+    let resumption = helper
+        .downtime_estimate
+        .as_ref()
+        .map_or(estimated_time + Duration::minutes(30), |estimate| {
+            estimate.expected_resumption(estimated_time)
+        });
+    timeline.push((
+        "After resumption",
+        resumption,
+        "Verify the applied upgrade and the block production".to_string(),
+    ));
+
+    timeline
+        .into_iter()
+        .map(|(offset, time, step)| {
+            json!({"offset": offset, "time": get_timeline_time(time), "step": step})
+        })
+        .collect()
+}
+
+/// Returns the time with minute precision, which is used for the steps of the timeline.
+fn get_timeline_time(time: DateTime<Utc>) -> String {
+    time.format("%a, %b %d %Y, %H:%M UTC").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::downtime::DowntimeEstimate;
+    use crate::network::Network;
+    use chrono::TimeZone;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_render_runbook() {
+        let upgrade_time = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        let mut helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v15.0.0",
+            "v16.0.0-rc4",
+            upgrade_time,
+            19_000_000,
+            "",
+        );
+
+        let runbook = render_runbook(&helper, &ChainConfig::default(), upgrade_time)
+            .expect("failed to render runbook");
+        assert!(runbook.contains("around 4PM UTC on Wed., January 17., 2024."));
+        assert!(runbook.contains("| T-24h | Tue, Jan 16 2024, 16:00 UTC |"));
+        assert!(runbook.contains("| T-1h | Wed, Jan 17 2024, 15:00 UTC |"));
+        assert!(runbook.contains("proposer block --network testnet"));
+        assert!(runbook.contains("`UPGRADE \"v16.0.0-rc4\" NEEDED at height: 19000000`"));
+        assert!(
+            !runbook.contains("T-30m"),
+            "expected no stop without downtime"
+        );
+
+        helper.downtime_estimate = Some(DowntimeEstimate {
+            expected_minutes: 16,
+            max_minutes: 25,
+            n_upgrades: 3,
+        });
+        let runbook = render_runbook(&helper, &ChainConfig::default(), upgrade_time)
+            .expect("failed to render runbook");
+        assert!(runbook.contains("| T-30m | Wed, Jan 17 2024, 15:30 UTC |"));
+        assert!(runbook.contains("| After resumption | Wed, Jan 17 2024, 16:16 UTC |"));
+        assert!(runbook.contains("resume around Wed, Jan 17 2024, 16:16 UTC"));
+    }
+
+    #[test]
+    fn test_render_skip_upgrade() {
+        let upgrade_time = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        let helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v15.0.0",
+            "v16.0.0",
+            upgrade_time,
+            19_000_000,
+            "",
+        );
+
+        let instructions = render_skip_upgrade(&helper, &ChainConfig::default(), upgrade_time)
+            .expect("failed to render instructions");
+        assert!(instructions.contains("the v16.0.0 upgrade at height 19000000 is aborted"));
+        assert!(instructions.contains("Keep running v15.0.0"));
+        assert!(instructions.contains("evmosd start --unsafe-skip-upgrades 19000000"));
+        assert!(instructions.contains("(estimated around 4PM UTC on Wed., January 17., 2024)"));
+        assert!(instructions.contains("proposer block --network mainnet"));
+        assert_eq!(
+            get_skip_upgrade_file_name(&helper),
+            "proposal-Mainnet-v16.0.0-skip-upgrade.md"
+        );
+    }
+
+    #[test]
+    fn test_get_runbook_file_name() {
+        let helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v15.0.0",
+            "v16.0.0",
+            Utc::now(),
+            19_000_000,
+            "",
+        );
+        assert_eq!(
+            get_runbook_file_name(&helper),
+            "proposal-Mainnet-v16.0.0-runbook.md"
+        );
+    }
+}
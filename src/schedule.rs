@@ -0,0 +1,174 @@
+use crate::errors::ScheduleError;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The first local hour, at which validator operators are considered to be awake.
+const AWAKE_FROM_HOUR: u32 = 8;
+
+/// The local hour, from which validator operators are considered to be asleep.
+const AWAKE_UNTIL_HOUR: u32 = 22;
+
+/// The hour of the default upgrade time in UTC, which is preferred among equally ranked times.
+pub const DEFAULT_UPGRADE_HOUR: u32 = 16;
+
+/// The number of suggested upgrade times, which are presented to the user.
+pub const N_SUGGESTIONS: usize = 3;
+
+/// Represents the share of validator operators located in a timezone.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimezoneShare {
+    /// The name of the timezone in the IANA database, e.g. `Europe/Berlin`.
+    pub timezone: String,
+    /// The relative share of the validator operators in the timezone, e.g. the number of validators.
+    pub share: f64,
+}
+
+/// Represents a candidate upgrade time together with the share of validator operators,
+/// for which it is within their waking hours.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Suggestion {
+    /// The suggested upgrade time.
+    pub time: DateTime<Utc>,
+    /// The share of validator operators, who are awake at the suggested time, between 0 and 1.
+    pub awake: f64,
+}
+
+impl Suggestion {
+    /// Returns the description of the suggestion for the date and time prompt.
+    pub fn describe(&self) -> String {
+        format!(
+            "{} UTC ({:.0}% of validators awake)",
+            self.time.format("%H:%M"),
+            self.awake * 100.0
+        )
+    }
+}
+
+/// Returns whether the given local hour is within the waking hours of validator operators.
+fn is_awake(hour: u32) -> bool {
+    (AWAKE_FROM_HOUR..AWAKE_UNTIL_HOUR).contains(&hour)
+}
+
+/// Ranks the full hours of the given date as upgrade times by the share of validator operators,
+/// who are awake according to the given timezone distribution.
+///
+/// Times with the same share are ranked by their distance to the default upgrade time.
+pub fn rank_upgrade_times(
+    date: NaiveDate,
+    distribution: &[TimezoneShare],
+) -> Result<Vec<Suggestion>, ScheduleError> {
+    let timezones = distribution
+        .iter()
+        .map(|entry| {
+            Tz::from_str(&entry.timezone)
+                .map(|timezone| (timezone, entry.share))
+                .map_err(|_| ScheduleError::InvalidTimezone(entry.timezone.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let total: f64 = timezones.iter().map(|(_, share)| share).sum();
+    if total <= 0.0 {
+        return Err(ScheduleError::EmptyDistribution);
+    }
+
+    let mut suggestions: Vec<Suggestion> = (0..24)
+        .map(|hour| {
+            let time = Utc.from_utc_datetime(
+                &date.and_time(NaiveTime::from_hms_opt(hour, 0, 0).expect("invalid hour")),
+            );
+            let awake: f64 = timezones
+                .iter()
+                .filter(|(timezone, _)| is_awake(time.with_timezone(timezone).hour()))
+                .map(|(_, share)| share)
+                .sum();
+
+            Suggestion {
+                time,
+                awake: awake / total,
+            }
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| {
+        b.awake.total_cmp(&a.awake).then_with(|| {
+            a.time
+                .hour()
+                .abs_diff(DEFAULT_UPGRADE_HOUR)
+                .cmp(&b.time.hour().abs_diff(DEFAULT_UPGRADE_HOUR))
+        })
+    });
+
+    Ok(suggestions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_distribution() -> Vec<TimezoneShare> {
+        vec![
+            TimezoneShare {
+                timezone: "Europe/Berlin".to_string(),
+                share: 50.0,
+            },
+            TimezoneShare {
+                timezone: "Asia/Seoul".to_string(),
+                share: 30.0,
+            },
+            TimezoneShare {
+                timezone: "America/New_York".to_string(),
+                share: 20.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_rank_upgrade_times() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+        let suggestions = rank_upgrade_times(date, &get_distribution()).unwrap();
+        assert_eq!(suggestions.len(), 24);
+
+        // NOTE: in winter, 7-12 UTC are waking hours in Berlin (8-13), Seoul (16-21)
+        // and only from 13 UTC in New York, so no hour covers all operators
+        let best = &suggestions[0];
+        assert_eq!(best.awake, 0.8);
+        assert_eq!(best.time.hour(), 12);
+        assert_eq!(best.describe(), "12:00 UTC (80% of validators awake)");
+        assert!(suggestions
+            .windows(2)
+            .all(|pair| pair[0].awake >= pair[1].awake));
+    }
+
+    #[test]
+    fn test_rank_upgrade_times_prefers_default_hour() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+        let distribution = vec![TimezoneShare {
+            timezone: "UTC".to_string(),
+            share: 1.0,
+        }];
+
+        let suggestions = rank_upgrade_times(date, &distribution).unwrap();
+        assert_eq!(suggestions[0].time.hour(), DEFAULT_UPGRADE_HOUR);
+        assert_eq!(suggestions[0].awake, 1.0);
+    }
+
+    #[test]
+    fn test_rank_upgrade_times_fail() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+        let distribution = vec![TimezoneShare {
+            timezone: "Mars/Olympus".to_string(),
+            share: 1.0,
+        }];
+
+        assert!(matches!(
+            rank_upgrade_times(date, &distribution),
+            Err(ScheduleError::InvalidTimezone(_))
+        ));
+        assert!(matches!(
+            rank_upgrade_times(date, &[]),
+            Err(ScheduleError::EmptyDistribution)
+        ));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::errors::MessageError;
+use crate::helper::UpgradeHelper;
+use crate::messages::V1Proposal;
+use clap::ValueEnum;
+use jsonschema::JSONSchema;
+use schemars::schema_for;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The versions of the Cosmos SDK, for which a JSON schema of the gov v1 proposal is bundled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize, ValueEnum)]
+pub enum SdkVersion {
+    /// Cosmos SDK v0.47, which is used by Evmos.
+    #[default]
+    #[serde(rename = "v0.47")]
+    #[clap(name = "v0.47")]
+    V047,
+    /// Cosmos SDK v0.50, which adds expedited proposals.
+    #[serde(rename = "v0.50")]
+    #[clap(name = "v0.50")]
+    V050,
+}
+
+/// Returns the bundled JSON schema of the gov v1 proposal for the given SDK version.
+/// The schemas are derived from the protobuf definitions of the SDK release.
+fn get_schema(sdk_version: SdkVersion) -> &'static str {
+    match sdk_version {
+        SdkVersion::V047 => include_str!("schemas/gov-v1-proposal-v0.47.json"),
+        SdkVersion::V050 => include_str!("schemas/gov-v1-proposal-v0.50.json"),
+    }
+}
+
+/// Validates the given proposal against the JSON schema of the given SDK version,
+/// so that typos in field names or wrong value types are caught before broadcasting.
+pub fn validate_proposal(
+    proposal: &V1Proposal,
+    sdk_version: SdkVersion,
+) -> Result<(), MessageError> {
+    validate_json(&serde_json::to_value(proposal)?, sdk_version)
+}
+
+/// Validates the given proposal JSON against the JSON schema of the given SDK version.
+/// All violations are returned together with the path of the invalid value.
+fn validate_json(proposal: &Value, sdk_version: SdkVersion) -> Result<(), MessageError> {
+    let schema: Value = serde_json::from_str(get_schema(sdk_version))?;
+    let compiled = JSONSchema::compile(&schema).map_err(|e| MessageError::Schema(e.to_string()))?;
+
+    if let Err(errors) = compiled.validate(proposal) {
+        let violations: Vec<String> = errors
+            .map(|e| match e.instance_path.to_string() {
+                path if path.is_empty() => e.to_string(),
+                path => format!("{}: {}", path, e),
+            })
+            .collect();
+
+        return Err(MessageError::Schema(violations.join("; ")));
+    }
+
+    Ok(())
+}
+
+/// Returns the JSON schema of the upgrade configuration, so that external tools and editors
+/// can validate and autocomplete the configuration files.
+pub fn get_helper_schema() -> Value {
+    serde_json::to_value(schema_for!(UpgradeHelper)).expect("failed to serialize helper schema")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn get_proposal() -> Value {
+        json!({
+            "messages": [{
+                "@type": "/cosmos.upgrade.v1beta1.MsgSoftwareUpgrade",
+                "authority": "evmos10d07y265gmmuvt4z0w9aw880jnsr700jcrztvm",
+                "plan": {
+                    "name": "v17.0.0",
+                    "height": "18500000",
+                    "info": "{}"
+                }
+            }],
+            "metadata": "",
+            "deposit": "",
+            "title": "Evmos v17.0.0 Upgrade",
+            "summary": "Upgrade to v17.0.0"
+        })
+    }
+
+    #[test]
+    fn test_validate_json() {
+        let proposal = get_proposal();
+        assert!(validate_json(&proposal, SdkVersion::V047).is_ok());
+        assert!(validate_json(&proposal, SdkVersion::V050).is_ok());
+
+        let mut expedited = proposal.clone();
+        expedited["expedited"] = json!(true);
+        assert!(validate_json(&expedited, SdkVersion::V047).is_err());
+        assert!(validate_json(&expedited, SdkVersion::V050).is_ok());
+    }
+
+    #[test]
+    fn test_get_helper_schema() {
+        let schema = get_helper_schema();
+        assert_eq!(schema["title"], "UpgradeHelper");
+        assert!(schema["required"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("target_version")));
+
+        let helper = UpgradeHelper::new(
+            std::path::PathBuf::from("/root/.evmosd"),
+            crate::network::Network::Mainnet,
+            "v15.0.0",
+            "v16.0.0",
+            chrono::Utc::now(),
+            18_798_000,
+            "This is a summary.",
+        );
+        let compiled = JSONSchema::compile(&schema).expect("failed to compile helper schema");
+        assert!(
+            compiled.is_valid(&serde_json::to_value(&helper).expect("failed to serialize helper"))
+        );
+
+        let mut invalid = serde_json::to_value(&helper).unwrap();
+        invalid["network"] = json!("Devnet");
+        assert!(!compiled.is_valid(&invalid));
+    }
+
+    #[test]
+    fn test_validate_json_fail() {
+        let mut proposal = get_proposal();
+        proposal["messages"][0]["plan"]["height"] = json!(18500000);
+        proposal["messages"][0]["plan"]["hieght"] = json!("18500000");
+
+        match validate_json(&proposal, SdkVersion::V047) {
+            Err(MessageError::Schema(violations)) => {
+                assert!(violations.contains("/messages/0/plan/height"));
+                assert!(violations.contains("hieght"));
+            }
+            res => panic!("expected schema violations, got {:?}", res),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use crate::errors::SecretError;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use serde_json::Value;
+use sha2::Sha256;
+use std::process;
+use std::sync::OnceLock;
+
+/// The prefix of encrypted values in the configuration files, which contains the version of the encryption scheme.
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// The environment variable containing the passphrase to encrypt and decrypt configuration values.
+const PASSPHRASE_VAR: &str = "PROPOSER_PASSPHRASE";
+
+/// The service name, under which the passphrase is stored in the OS keychain.
+const KEYCHAIN_SERVICE: &str = "proposer";
+
+/// The number of PBKDF2 iterations to derive the encryption key from the passphrase.
+const KDF_ITERATIONS: u32 = 100_000;
+
+/// The length of the random salt, which is stored in front of the nonce and ciphertext.
+const SALT_LENGTH: usize = 16;
+
+/// The length of the nonce of the ChaCha20-Poly1305 cipher.
+const NONCE_LENGTH: usize = 12;
+
+/// The passphrase, which is looked up once per run.
+static PASSPHRASE: OnceLock<String> = OnceLock::new();
+
+/// Returns whether the given contents of a configuration file contain encrypted values.
+pub fn contains_encrypted(contents: &str) -> bool {
+    contents.contains(ENCRYPTED_PREFIX)
+}
+
+/// Encrypts the given value with a key derived from the passphrase.
+/// The result is prefixed with [`ENCRYPTED_PREFIX`] and can be used in place of
+/// any string value of the upgrade configurations or the chain configuration.
+pub fn encrypt(value: &str, passphrase: &str) -> Result<String, SecretError> {
+    let mut salt = [0u8; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = get_cipher(passphrase, &salt)
+        .encrypt(&nonce, value.as_bytes())
+        .map_err(|_| SecretError::Encrypt)?;
+
+    let mut payload = salt.to_vec();
+    payload.extend_from_slice(&nonce);
+    payload.extend(ciphertext);
+
+    Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(payload)))
+}
+
+/// Decrypts the given value, which was encrypted with [`encrypt`].
+pub fn decrypt(value: &str, passphrase: &str) -> Result<String, SecretError> {
+    let encoded = value
+        .strip_prefix(ENCRYPTED_PREFIX)
+        .ok_or_else(|| SecretError::Format("missing prefix".to_string()))?;
+    let payload = STANDARD.decode(encoded)?;
+    if payload.len() < SALT_LENGTH + NONCE_LENGTH {
+        return Err(SecretError::Format("payload too short".to_string()));
+    }
+
+    let (salt, rest) = payload.split_at(SALT_LENGTH);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LENGTH);
+    let plaintext = get_cipher(passphrase, salt)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| SecretError::Decrypt)?;
+
+    String::from_utf8(plaintext).map_err(|_| SecretError::Format("invalid UTF-8".to_string()))
+}
+
+/// Decrypts all encrypted string values in the given value in place.
+/// Returns the JSON pointers of the decrypted values, so that they can be encrypted again
+/// with [`encrypt_values`] when the value is written back.
+pub fn decrypt_values(value: &mut Value, passphrase: &str) -> Result<Vec<String>, SecretError> {
+    let mut pointers = Vec::new();
+    decrypt_values_at(value, "", passphrase, &mut pointers)?;
+
+    Ok(pointers)
+}
+
+/// Decrypts the encrypted string values in the given value, which is located at the given pointer,
+/// and collects the pointers of the decrypted values.
+fn decrypt_values_at(
+    value: &mut Value,
+    pointer: &str,
+    passphrase: &str,
+    pointers: &mut Vec<String>,
+) -> Result<(), SecretError> {
+    match value {
+        Value::String(s) if s.starts_with(ENCRYPTED_PREFIX) => {
+            *s = decrypt(s, passphrase)?;
+            pointers.push(pointer.to_string());
+        }
+        Value::Array(values) => {
+            for (idx, value) in values.iter_mut().enumerate() {
+                decrypt_values_at(value, &format!("{}/{}", pointer, idx), passphrase, pointers)?;
+            }
+        }
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                // NOTE: the special characters of the keys are escaped as defined in RFC 6901
+                let key = key.replace('~', "~0").replace('/', "~1");
+                decrypt_values_at(value, &format!("{}/{}", pointer, key), passphrase, pointers)?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Encrypts the string values at the given JSON pointers in the given value in place.
+/// Pointers, which do not point to a string value (anymore), are skipped.
+pub fn encrypt_values(
+    value: &mut Value,
+    pointers: &[String],
+    passphrase: &str,
+) -> Result<(), SecretError> {
+    for pointer in pointers {
+        if let Some(Value::String(s)) = value.pointer_mut(pointer) {
+            if !s.starts_with(ENCRYPTED_PREFIX) {
+                *s = encrypt(s, passphrase)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the cipher with the key derived from the given passphrase and salt.
+fn get_cipher(passphrase: &str, salt: &[u8]) -> ChaCha20Poly1305 {
+    let mut key = Key::default();
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, KDF_ITERATIONS, &mut key);
+
+    ChaCha20Poly1305::new(&key)
+}
+
+/// Returns the passphrase to encrypt and decrypt configuration values.
+///
+/// The passphrase is taken from the `PROPOSER_PASSPHRASE` environment variable.
+/// If it is not set, it is looked up in the OS keychain (`security` on macOS, `secret-tool` on Linux)
+/// under the service `proposer` and otherwise prompted for.
+pub fn get_passphrase() -> Result<String, SecretError> {
+    if let Some(passphrase) = PASSPHRASE.get() {
+        return Ok(passphrase.clone());
+    }
+
+    let passphrase = match std::env::var(PASSPHRASE_VAR) {
+        Ok(passphrase) => passphrase,
+        Err(_) => match get_keychain_passphrase() {
+            Some(passphrase) => passphrase,
+            None => inquire::Password::new("Passphrase to decrypt configuration values:")
+                .without_confirmation()
+                .prompt()?,
+        },
+    };
+
+    Ok(PASSPHRASE.get_or_init(|| passphrase).clone())
+}
+
+/// Returns the passphrase stored in the OS keychain if available.
+fn get_keychain_passphrase() -> Option<String> {
+    let (tool, args) = get_keychain_command();
+    let output = process::Command::new(tool).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let passphrase = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!passphrase.is_empty()).then_some(passphrase)
+}
+
+/// Returns the tool and arguments to read the passphrase from the OS keychain.
+fn get_keychain_command() -> (&'static str, Vec<&'static str>) {
+    if cfg!(target_os = "macos") {
+        (
+            "security",
+            vec!["find-generic-password", "-s", KEYCHAIN_SERVICE, "-w"],
+        )
+    } else {
+        ("secret-tool", vec!["lookup", "service", KEYCHAIN_SERVICE])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let encrypted = encrypt("https://hooks.example.com/abc", "secret").unwrap();
+        assert!(encrypted.starts_with(ENCRYPTED_PREFIX));
+        assert!(contains_encrypted(&encrypted));
+        assert_eq!(
+            decrypt(&encrypted, "secret").unwrap(),
+            "https://hooks.example.com/abc"
+        );
+        assert!(matches!(
+            decrypt(&encrypted, "wrong"),
+            Err(SecretError::Decrypt)
+        ));
+        assert!(matches!(
+            decrypt("enc:v1:AAAA", "secret"),
+            Err(SecretError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_values() {
+        let encrypted = encrypt("token", "secret").unwrap();
+        let mut value = json!({"name": "plain", "nested": {"list": [encrypted]}});
+        let pointers = decrypt_values(&mut value, "secret").unwrap();
+        assert_eq!(
+            value,
+            json!({"name": "plain", "nested": {"list": ["token"]}})
+        );
+        assert_eq!(pointers, vec!["/nested/list/0"]);
+
+        encrypt_values(&mut value, &pointers, "secret").unwrap();
+        assert_eq!(value["name"], "plain");
+        let encrypted = value["nested"]["list"][0].as_str().unwrap();
+        assert!(encrypted.starts_with(ENCRYPTED_PREFIX));
+        assert_eq!(decrypt(encrypted, "secret").unwrap(), "token");
+    }
+}
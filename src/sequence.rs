@@ -0,0 +1,207 @@
+use crate::block::BlockProvider;
+use crate::errors::SequenceError;
+use crate::http::get_json;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::Value;
+use url::Url;
+
+const ACCOUNTS_ENDPOINT: &str = "cosmos/auth/v1beta1/accounts/";
+
+/// The Tendermint RPC endpoint, which returns the transactions in the mempool of the node.
+const RPC_UNCONFIRMED_TXS_ENDPOINT: &str = "unconfirmed_txs";
+
+/// The maximum number of mempool transactions, which are checked for transactions of the signer.
+const MAX_UNCONFIRMED_TXS: u64 = 100;
+
+/// Contains the committed state of an account, which is relevant for signing the next transaction.
+#[derive(Debug, PartialEq)]
+pub struct AccountState {
+    /// The number of the account, which is part of the signed data of its transactions.
+    pub account_number: u64,
+    /// The sequence, which the next transaction of the account has to use.
+    pub sequence: u64,
+    /// The public key of the account, which is only known after it has sent a transaction.
+    pub pub_key: Option<Vec<u8>>,
+}
+
+/// Queries the committed sequence and public key of the given account.
+pub async fn get_account_state(
+    base_url: &Url,
+    address: &str,
+) -> Result<AccountState, SequenceError> {
+    let response: Value = get_json(base_url.join(ACCOUNTS_ENDPOINT)?.join(address)?).await?;
+
+    // NOTE: Ethereum accounts on Evmos embed the base account, while plain base accounts don't
+    let account = match response["account"]["base_account"].is_object() {
+        true => &response["account"]["base_account"],
+        false => &response["account"],
+    };
+
+    let account_number = account["account_number"]
+        .as_str()
+        .ok_or(SequenceError::MissingAccountNumber(address.to_string()))?
+        .parse()?;
+    let sequence = account["sequence"]
+        .as_str()
+        .ok_or(SequenceError::MissingSequence(address.to_string()))?
+        .parse()?;
+    let pub_key = account["pub_key"]["key"]
+        .as_str()
+        .map(|key| STANDARD.decode(key))
+        .transpose()?;
+
+    Ok(AccountState {
+        account_number,
+        sequence,
+        pub_key,
+    })
+}
+
+/// Returns the number of transactions in the mempool of the node behind the given Tendermint RPC,
+/// which are signed with the given public key.
+///
+/// The mempool transactions are only available as encoded bytes, which contain the public key
+/// of the signer in the signer infos, so that they are matched without decoding the transactions.
+pub async fn count_pending_txs(rpc_url: &Url, pub_key: &[u8]) -> Result<usize, SequenceError> {
+    let mut url = rpc_url.join(RPC_UNCONFIRMED_TXS_ENDPOINT)?;
+    url.set_query(Some(&format!("limit={}", MAX_UNCONFIRMED_TXS)));
+    let response: Value = get_json(url).await?;
+
+    let mut pending = 0;
+    for tx in response["result"]["txs"].as_array().into_iter().flatten() {
+        let bytes = STANDARD.decode(tx.as_str().unwrap_or_default())?;
+        if bytes.windows(pub_key.len()).any(|window| window == pub_key) {
+            pending += 1;
+        }
+    }
+
+    Ok(pending)
+}
+
+/// Checks whether another transaction of the given account is in flight, which would make
+/// the submission fail with a sequence mismatch.
+///
+/// Returns a description of the conflict, if the mempool contains transactions from the same key.
+pub async fn check_sequence_conflict(
+    provider: &BlockProvider,
+    address: &str,
+) -> Result<Option<String>, SequenceError> {
+    let state = get_account_state(&provider.rest, address).await?;
+
+    // NOTE: without a public key, the account has not signed any transaction yet
+    let (Some(pub_key), Some(rpc)) = (state.pub_key, &provider.rpc) else {
+        return Ok(None);
+    };
+
+    match count_pending_txs(rpc, &pub_key).await? {
+        0 => Ok(None),
+        pending => Ok(Some(format!(
+            "{} has {} pending transaction(s) in the mempool; the committed sequence is {}, \
+            but the submission will need sequence {} or wait until the pending transactions are included",
+            address,
+            pending,
+            state.sequence,
+            state.sequence + pending as u64
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const TEST_ADDRESS: &str = "evmos1hafptm4zxy7y4fj6j7m6fj5n89v2zjy5l7ltae";
+
+    /// The base64 encoded public key of the test account.
+    const TEST_PUB_KEY: &str = "AiDOvyusPZRg9sw8ZDDmOjvchTOqgIw5eS/1eeASIj0U";
+
+    /// Sets up a mock server, which returns the test account and the given mempool transactions.
+    async fn setup_mock_api(txs: Vec<String>) -> MockServer {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{}{}", ACCOUNTS_ENDPOINT, TEST_ADDRESS)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "account": {
+                    "@type": "/ethermint.types.v1.EthAccount",
+                    "base_account": {
+                        "address": TEST_ADDRESS,
+                        "pub_key": {
+                            "@type": "/ethermint.crypto.v1.ethsecp256k1.PubKey",
+                            "key": TEST_PUB_KEY
+                        },
+                        "account_number": "7",
+                        "sequence": "12"
+                    },
+                    "code_hash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{}", RPC_UNCONFIRMED_TXS_ENDPOINT)))
+            .and(query_param("limit", "100"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": -1,
+                "result": {
+                    "n_txs": txs.len().to_string(),
+                    "txs": txs
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    /// Returns the base64 encoded bytes of a transaction signed with the given public key.
+    fn get_tx(pub_key: &str) -> String {
+        let mut bytes = b"\x0a\x05body\x12".to_vec();
+        bytes.extend(STANDARD.decode(pub_key).unwrap());
+        bytes.extend(b"\x18\x0c");
+
+        STANDARD.encode(bytes)
+    }
+
+    fn get_provider(mock_server: &MockServer) -> BlockProvider {
+        let url = Url::from_str(&mock_server.uri()).unwrap();
+        BlockProvider {
+            rest: url.clone(),
+            rpc: Some(url),
+            cache: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_account_state() {
+        let mock_server = setup_mock_api(vec![]).await;
+        let state = get_account_state(&get_provider(&mock_server).rest, TEST_ADDRESS)
+            .await
+            .expect("failed to get account state");
+
+        assert_eq!(state.account_number, 7);
+        assert_eq!(state.sequence, 12);
+        assert_eq!(state.pub_key, Some(STANDARD.decode(TEST_PUB_KEY).unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_check_sequence_conflict() {
+        let mock_server = setup_mock_api(vec![get_tx("AAAA")]).await;
+        let conflict = check_sequence_conflict(&get_provider(&mock_server), TEST_ADDRESS)
+            .await
+            .expect("failed to check sequence");
+        assert!(conflict.is_none());
+
+        let mock_server = setup_mock_api(vec![get_tx(TEST_PUB_KEY), get_tx("AAAA")]).await;
+        let conflict = check_sequence_conflict(&get_provider(&mock_server), TEST_ADDRESS)
+            .await
+            .expect("failed to check sequence")
+            .expect("expected a conflict");
+        assert!(conflict.contains("1 pending transaction(s)"));
+        assert!(conflict.contains("sequence 13"));
+    }
+}
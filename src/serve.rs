@@ -0,0 +1,261 @@
+use crate::{
+    block::{get_block_time_estimate, get_rpc_url, round_to_nearest_500, BlockTimeEstimate},
+    command::{render_command, Signer},
+    context::Context,
+    errors::{BlockError, PrepareError, ProposalError, ServeError},
+    evmosd::ClientConfig,
+    helper::UpgradeHelper,
+    network::Network,
+    proposal::render_proposal,
+};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// The default address, on which the API server listens.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8080";
+
+/// Contains the context and the options, which are shared by all requests to the API server.
+#[derive(Clone)]
+struct ServerState {
+    /// The context, which provides the chain configurations and clients of the networks.
+    ctx: Arc<Context>,
+    /// Whether the templates are rendered in strict mode.
+    strict: bool,
+}
+
+/// The request to estimate the upgrade height for the given time.
+#[derive(Deserialize)]
+struct EstimateHeightRequest {
+    /// The network to estimate the height on.
+    network: Network,
+    /// The planned time of the upgrade.
+    upgrade_time: DateTime<Utc>,
+}
+
+/// The estimated upgrade height and the block time used for the estimation.
+#[derive(Serialize)]
+struct EstimateHeightResponse {
+    /// The estimated height rounded to the nearest 500.
+    height: u64,
+    /// The measured block time.
+    block_time_estimate: BlockTimeEstimate,
+}
+
+/// The request to render the command to submit a proposal.
+#[derive(Deserialize)]
+struct CommandRequest {
+    /// The upgrade configuration.
+    helper: UpgradeHelper,
+    /// The rendered proposal description.
+    description: String,
+    /// The name of the key, which submits the proposal.
+    key: String,
+    /// The keyring backend, which contains the key.
+    #[serde(default = "default_keyring_backend")]
+    keyring_backend: String,
+    /// The address of the account that pays the fees from its fee allowance to the key.
+    #[serde(default)]
+    fee_granter: Option<String>,
+}
+
+/// Returns the default keyring backend of the Evmos CLI.
+fn default_keyring_backend() -> String {
+    "os".to_string()
+}
+
+/// The error returned by the API, which is serialized as `{"error": "..."}`.
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({"error": self.1}))).into_response()
+    }
+}
+
+impl From<BlockError> for ApiError {
+    fn from(e: BlockError) -> Self {
+        ApiError(StatusCode::BAD_GATEWAY, e.to_string())
+    }
+}
+
+impl From<ProposalError> for ApiError {
+    fn from(e: ProposalError) -> Self {
+        ApiError(StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+    }
+}
+
+impl From<PrepareError> for ApiError {
+    fn from(e: PrepareError) -> Self {
+        let status = match e {
+            PrepareError::Template(_) | PrepareError::RenderCommand(_) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            _ => StatusCode::BAD_GATEWAY,
+        };
+
+        ApiError(status, e.to_string())
+    }
+}
+
+/// Returns the router of the API server, which exposes the height estimation
+/// and the rendering of the proposal and the command.
+fn get_router(ctx: Arc<Context>, strict: bool) -> Router {
+    Router::new()
+        .route("/health", get(|| async { Json(json!({"status": "ok"})) }))
+        .route("/estimate-height", post(estimate_height))
+        .route("/proposal", post(proposal))
+        .route("/command", post(command))
+        .with_state(ServerState { ctx, strict })
+}
+
+/// Runs the API server with the given context on the given address until the process is stopped.
+pub async fn serve(ctx: Arc<Context>, address: SocketAddr, strict: bool) -> Result<(), ServeError> {
+    let server =
+        axum::Server::try_bind(&address)?.serve(get_router(ctx, strict).into_make_service());
+    println!("Listening on http://{}", server.local_addr());
+
+    Ok(server.await?)
+}
+
+/// Estimates the upgrade height for the requested time from the current block time.
+async fn estimate_height(
+    State(state): State<ServerState>,
+    Json(request): Json<EstimateHeightRequest>,
+) -> Result<Json<EstimateHeightResponse>, ApiError> {
+    let provider = state.ctx.block_provider(request.network)?;
+    let block_time_estimate = get_block_time_estimate(&provider).await?;
+    if request.upgrade_time <= block_time_estimate.latest_time {
+        return Err(ApiError(
+            StatusCode::BAD_REQUEST,
+            format!("upgrade time {} is in the past", request.upgrade_time),
+        ));
+    }
+
+    Ok(Json(EstimateHeightResponse {
+        height: round_to_nearest_500(block_time_estimate.height_at(request.upgrade_time)),
+        block_time_estimate,
+    }))
+}
+
+/// Renders the proposal description for the given upgrade configuration.
+async fn proposal(
+    State(state): State<ServerState>,
+    Json(helper): Json<UpgradeHelper>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let chain_config = state
+        .ctx
+        .chain_config(helper.network)
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let proposal = render_proposal(&helper, &chain_config, state.strict)?;
+
+    Ok(Json(json!({"proposal": proposal})))
+}
+
+/// Renders the command to submit the proposal with the given description.
+async fn command(
+    State(state): State<ServerState>,
+    Json(request): Json<CommandRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let helper = request.helper;
+    let chain_config = state
+        .ctx
+        .chain_config(helper.network)
+        .map_err(|e| ApiError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let client_config = ClientConfig {
+        chain_id: helper.chain_id.clone(),
+        keyring_backend: request.keyring_backend,
+        output: "text".to_string(),
+        node: chain_config
+            .tm_rpc
+            .unwrap_or_else(|| get_rpc_url(helper.network)),
+        broadcast_mode: "sync".to_string(),
+    };
+
+    let command = render_command(
+        &state.ctx,
+        &helper,
+        &request.description,
+        &Signer {
+            client_config: &client_config,
+            key: &request.key,
+            fee_granter: request.fee_granter.as_deref(),
+        },
+        state.strict,
+    )
+    .await?;
+
+    Ok(Json(json!({"command": command})))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Starts the API server on a random port and returns its address.
+    fn start_server() -> SocketAddr {
+        let ctx = Context::for_tests(std::env::temp_dir().join("proposer-serve-cache"), &[]);
+        let server = axum::Server::bind(&"127.0.0.1:0".parse().unwrap())
+            .serve(get_router(Arc::new(ctx), true).into_make_service());
+        let address = server.local_addr();
+        tokio::spawn(server);
+
+        address
+    }
+
+    #[tokio::test]
+    async fn test_health() {
+        let address = start_server();
+
+        let res = reqwest::get(format!("http://{}/health", address))
+            .await
+            .expect("failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_proposal() {
+        let address = start_server();
+        let helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Testnet,
+            "v15.0.0",
+            "v16.0.0-rc4",
+            Utc::now(),
+            19_000_000,
+            "- Add precompile",
+        );
+
+        let client = reqwest::Client::new();
+        let res = client
+            .post(format!("http://{}/proposal", address))
+            .json(&helper)
+            .send()
+            .await
+            .expect("failed to send request");
+        assert_eq!(res.status(), reqwest::StatusCode::OK);
+        let body: serde_json::Value = res.json().await.expect("failed to parse response");
+        assert!(body["proposal"]
+            .as_str()
+            .expect("expected proposal string")
+            .contains("- Add precompile"));
+
+        let res = client
+            .post(format!("http://{}/proposal", address))
+            .json(&json!({"network": "Testnet"}))
+            .send()
+            .await
+            .expect("failed to send request");
+        assert!(res.status().is_client_error());
+    }
+}
@@ -0,0 +1,195 @@
+use crate::errors::SignatureError;
+use crate::http::get_bytes;
+use octocrab::models::repos::Asset;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// The name of the checksum file in the release assets.
+pub const CHECKSUMS_FILE: &str = "checksums.txt";
+
+/// Describes how the signature of the checksum file is verified before trusting its contents.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "lowercase")]
+pub enum ChecksumSignature {
+    /// The checksum file is signed with GPG and verified against the given public key file.
+    /// The detached signature is expected in the `checksums.txt.sig` asset.
+    Gpg {
+        /// The path to the (ASCII-armored or binary) public key of the release signer.
+        public_key: PathBuf,
+    },
+    /// The checksum file is signed keyless with cosign. The signature and certificate are expected
+    /// in the `checksums.txt.sig` and `checksums.txt.pem` assets.
+    Cosign {
+        /// The expected identity in the signing certificate, e.g. the release workflow.
+        certificate_identity: String,
+        /// The expected OIDC issuer of the signing certificate.
+        certificate_oidc_issuer: String,
+    },
+}
+
+impl ChecksumSignature {
+    /// Returns the names of the release assets that are required for the verification.
+    fn get_required_assets(&self) -> Vec<String> {
+        match self {
+            ChecksumSignature::Gpg { .. } => vec![format!("{}.sig", CHECKSUMS_FILE)],
+            ChecksumSignature::Cosign { .. } => vec![
+                format!("{}.sig", CHECKSUMS_FILE),
+                format!("{}.pem", CHECKSUMS_FILE),
+            ],
+        }
+    }
+}
+
+/// Verifies the signature of the given checksum file contents using the configured method.
+/// The signature files are downloaded from the release assets into a temporary directory.
+pub async fn verify_checksums(
+    signature: &ChecksumSignature,
+    checksums: &str,
+    assets: &[Asset],
+) -> Result<(), SignatureError> {
+    let mut downloads = Vec::new();
+    for name in signature.get_required_assets() {
+        let asset = assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .ok_or(SignatureError::MissingAsset(name.clone()))?;
+        downloads.push((name, asset.browser_download_url.clone()));
+    }
+
+    let dir = std::env::temp_dir().join(format!("proposer-signature-{}", process::id()));
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(CHECKSUMS_FILE), checksums)?;
+    for (name, url) in downloads {
+        fs::write(dir.join(name), get_bytes(url).await?)?;
+    }
+
+    let res = run_verification(signature, &dir);
+    fs::remove_dir_all(&dir)?;
+
+    res
+}
+
+/// Runs the verification tool for the given method on the files in the given directory.
+fn run_verification(signature: &ChecksumSignature, dir: &Path) -> Result<(), SignatureError> {
+    if let ChecksumSignature::Gpg { public_key } = signature {
+        let gnupg_home = dir.join("gnupg");
+        fs::create_dir_all(&gnupg_home)?;
+        run_tool(
+            "gpg",
+            &[
+                "--homedir".to_string(),
+                gnupg_home.display().to_string(),
+                "--batch".to_string(),
+                "--import".to_string(),
+                public_key.display().to_string(),
+            ],
+        )?;
+    }
+
+    let (tool, args) = get_verify_command(signature, dir);
+    run_tool(tool, &args)
+}
+
+/// Returns the tool and arguments to verify the checksum file in the given directory.
+fn get_verify_command(signature: &ChecksumSignature, dir: &Path) -> (&'static str, Vec<String>) {
+    let file = |name: &str| dir.join(name).display().to_string();
+    let checksums = file(CHECKSUMS_FILE);
+    let sig = file(&format!("{}.sig", CHECKSUMS_FILE));
+
+    match signature {
+        ChecksumSignature::Gpg { .. } => (
+            "gpg",
+            vec![
+                "--homedir".to_string(),
+                file("gnupg"),
+                "--batch".to_string(),
+                "--verify".to_string(),
+                sig,
+                checksums,
+            ],
+        ),
+        ChecksumSignature::Cosign {
+            certificate_identity,
+            certificate_oidc_issuer,
+        } => (
+            "cosign",
+            vec![
+                "verify-blob".to_string(),
+                "--certificate".to_string(),
+                file(&format!("{}.pem", CHECKSUMS_FILE)),
+                "--signature".to_string(),
+                sig,
+                "--certificate-identity".to_string(),
+                certificate_identity.clone(),
+                "--certificate-oidc-issuer".to_string(),
+                certificate_oidc_issuer.clone(),
+                checksums,
+            ],
+        ),
+    }
+}
+
+/// Runs the given tool and returns an error containing its output if it does not succeed.
+fn run_tool(tool: &str, args: &[String]) -> Result<(), SignatureError> {
+    let output = process::Command::new(tool).args(args).output()?;
+    if !output.status.success() {
+        return Err(SignatureError::Verification(
+            tool.to_string(),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use octocrab::models::repos::Release;
+
+    #[test]
+    fn test_get_verify_command() {
+        let signature = ChecksumSignature::Cosign {
+            certificate_identity:
+                "https://github.com/evmos/evmos/.github/workflows/release.yml@refs/tags/v14.0.0"
+                    .to_string(),
+            certificate_oidc_issuer: "https://token.actions.githubusercontent.com".to_string(),
+        };
+
+        let (tool, args) = get_verify_command(&signature, Path::new("/tmp/sig"));
+        assert_eq!(tool, "cosign");
+        assert_eq!(args[0], "verify-blob");
+        assert_eq!(args[2], "/tmp/sig/checksums.txt.pem");
+        assert_eq!(args[4], "/tmp/sig/checksums.txt.sig");
+        assert_eq!(args.last().unwrap(), "/tmp/sig/checksums.txt");
+
+        let signature = ChecksumSignature::Gpg {
+            public_key: PathBuf::from("/keys/evmos.asc"),
+        };
+        let (tool, args) = get_verify_command(&signature, Path::new("/tmp/sig"));
+        assert_eq!(tool, "gpg");
+        assert_eq!(
+            args[3..],
+            [
+                "--verify",
+                "/tmp/sig/checksums.txt.sig",
+                "/tmp/sig/checksums.txt"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify_checksums_missing_signature() {
+        let release: Release = serde_json::from_str(include_str!("testdata/release.json")).unwrap();
+        let signature = ChecksumSignature::Gpg {
+            public_key: PathBuf::from("/keys/evmos.asc"),
+        };
+
+        let res = verify_checksums(&signature, "", &release.assets).await;
+        assert!(
+            matches!(res, Err(SignatureError::MissingAsset(name)) if name == "checksums.txt.sig")
+        );
+    }
+}
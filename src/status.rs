@@ -0,0 +1,360 @@
+use crate::block::{get_latest_block, BlockProvider};
+use crate::errors::{GovError, StatusError};
+use crate::gov::{GovClient, Proposal, ProposalStatus};
+use crate::http::get_json;
+use crate::network::{format_coin, Network};
+use chrono::{DateTime, Duration, Utc};
+use num_format::ToFormattedString;
+use serde::Deserialize;
+use url::Url;
+
+/// The REST endpoint for querying the currently scheduled upgrade plan.
+const CURRENT_PLAN_ENDPOINT: &str = "/cosmos/upgrade/v1beta1/current_plan";
+
+/// The maximum number of proposals, which are listed per network in the overview.
+const MAX_OVERVIEW_PROPOSALS: usize = 5;
+
+/// The number of spaces between the columns of the overview.
+const COLUMN_GAP: usize = 4;
+
+/// Represents the response of the current plan query, which contains no plan if no upgrade is scheduled.
+#[derive(Deserialize)]
+struct CurrentPlanResponse {
+    plan: Option<CurrentPlan>,
+}
+
+/// Represents the relevant information of the scheduled upgrade plan.
+#[derive(Deserialize)]
+struct CurrentPlan {
+    name: String,
+    height: String,
+}
+
+/// Returns the governance status of the network, containing the governance parameters
+/// and the proposals in the voting period with their current tally and remaining voting time.
+pub async fn get_status(
+    client: &dyn GovClient,
+    network: Network,
+    now: DateTime<Utc>,
+) -> Result<String, GovError> {
+    let params = client.params().await?;
+    let mut lines = vec![
+        format!("Network:        {}", network),
+        format!("Voting period:  {} hours", params.voting_period.num_hours()),
+        format!(
+            "Min deposit:    {}",
+            params
+                .min_deposit
+                .iter()
+                .map(|coin| format_coin(&coin.amount, &coin.denom, network))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        format!("Quorum:         {}", params.quorum),
+        format!("Threshold:      {}", params.threshold),
+        String::new(),
+    ];
+
+    let proposals = client.proposals(Some(ProposalStatus::VotingPeriod)).await?;
+    if proposals.is_empty() {
+        lines.push("No proposals in voting period".to_string());
+        return Ok(lines.join("\n"));
+    }
+
+    lines.push("Proposals in voting period:".to_string());
+    for proposal in proposals {
+        let proposal_id = proposal.id.parse::<u64>().unwrap_or_default();
+        let tally = client.tally(proposal_id).await?;
+        let time_left = proposal.voting_end_time.map_or("-".to_string(), |end| {
+            format!(
+                "{} at {}",
+                format_time_left(end - now),
+                end.format("%Y-%m-%d %H:%M UTC")
+            )
+        });
+
+        lines.push(format!(
+            "#{} {} (ends in {}) - Yes: {} | No: {} | Veto: {} | Abstain: {}",
+            proposal.id,
+            proposal.get_title(),
+            time_left,
+            tally.yes_count,
+            tally.no_count,
+            tally.no_with_veto_count,
+            tally.abstain_count,
+        ));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Returns the name and height of the upgrade plan, which is currently scheduled on the network, if any.
+pub async fn get_current_plan(base_url: &Url) -> Result<Option<(String, u64)>, StatusError> {
+    let response: CurrentPlanResponse = get_json(base_url.join(CURRENT_PLAN_ENDPOINT)?).await?;
+
+    Ok(response.plan.map(|plan| {
+        let height = plan.height.parse::<u64>().unwrap_or_default();
+        (plan.name, height)
+    }))
+}
+
+/// Returns the lines of the overview for the given network, containing the latest block,
+/// the scheduled upgrade plan and the newest proposals in deposit or voting period.
+///
+/// Failed queries are shown as unavailable, so that the overview is still printed for the other networks.
+pub async fn get_overview(
+    client: &dyn GovClient,
+    provider: &BlockProvider,
+    network: Network,
+) -> Vec<String> {
+    let mut lines = vec![format!("{}", network), String::new()];
+
+    lines.push(match get_latest_block(provider).await {
+        Ok(block) => format!(
+            "Latest block:  {} ({})",
+            block.height.to_formatted_string(&num_format::Locale::en),
+            block.time.format("%Y-%m-%d %H:%M UTC")
+        ),
+        Err(e) => format!("Latest block:  unavailable ({})", e),
+    });
+
+    lines.push(match get_current_plan(&provider.rest).await {
+        Ok(Some((name, height))) => format!(
+            "Upgrade plan:  {} at {}",
+            name,
+            height.to_formatted_string(&num_format::Locale::en)
+        ),
+        Ok(None) => "Upgrade plan:  none scheduled".to_string(),
+        Err(e) => format!("Upgrade plan:  unavailable ({})", e),
+    });
+
+    lines.push(String::new());
+    match get_open_proposals(client).await {
+        Ok(proposals) if proposals.is_empty() => {
+            lines.push("No proposals in deposit or voting period".to_string())
+        }
+        Ok(proposals) => {
+            lines.push("Newest open proposals:".to_string());
+            for proposal in proposals.iter().take(MAX_OVERVIEW_PROPOSALS) {
+                lines.push(format!(
+                    "#{} {} ({})",
+                    proposal.id,
+                    proposal.get_title(),
+                    get_status_name(&proposal.status)
+                ));
+            }
+        }
+        Err(e) => lines.push(format!("Proposals unavailable ({})", e)),
+    }
+
+    lines
+}
+
+/// Returns the proposals in deposit or voting period, sorted from newest to oldest.
+async fn get_open_proposals(client: &dyn GovClient) -> Result<Vec<Proposal>, GovError> {
+    let mut proposals = client.proposals(Some(ProposalStatus::VotingPeriod)).await?;
+    proposals.extend(
+        client
+            .proposals(Some(ProposalStatus::DepositPeriod))
+            .await?,
+    );
+    proposals
+        .sort_by_key(|proposal| std::cmp::Reverse(proposal.id.parse::<u64>().unwrap_or_default()));
+
+    Ok(proposals)
+}
+
+/// Returns the short name of the given proposal status, e.g. `voting` for `PROPOSAL_STATUS_VOTING_PERIOD`.
+pub fn get_status_name(status: &str) -> String {
+    status
+        .trim_start_matches("PROPOSAL_STATUS_")
+        .trim_end_matches("_PERIOD")
+        .to_lowercase()
+}
+
+/// Renders the given columns of lines side by side.
+pub fn render_columns(columns: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|column| {
+            column
+                .iter()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    let n_lines = columns.iter().map(Vec::len).max().unwrap_or_default();
+
+    (0..n_lines)
+        .map(|idx| {
+            columns
+                .iter()
+                .zip(widths.iter())
+                .map(|(column, width)| {
+                    let line = column.get(idx).map_or("", String::as_str);
+                    format!("{:<width$}", line, width = width + COLUMN_GAP)
+                })
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats the given remaining time in days, hours and minutes.
+pub fn format_time_left(time_left: Duration) -> String {
+    if time_left <= Duration::zero() {
+        return "0m".to_string();
+    }
+
+    let days = time_left.num_days();
+    let hours = time_left.num_hours() % 24;
+    let minutes = time_left.num_minutes() % 60;
+
+    match days {
+        0 => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h {}m", days, hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gov::RestGovClient;
+    use chrono::TimeZone;
+    use serde_json::{json, Value};
+    use std::str::FromStr;
+    use url::Url;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Sets up a mock server to return the governance parameters and one proposal in voting period.
+    async fn setup_mock_api() -> MockServer {
+        let mut proposal: Value =
+            serde_json::from_str(include_str!("testdata/proposal_upgrade.json"))
+                .expect("failed to parse proposal JSON");
+        proposal["proposal"]["status"] = json!("PROPOSAL_STATUS_VOTING_PERIOD");
+
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/gov/v1/params/voting"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "params": {
+                    "min_deposit": [{"denom": "aevmos", "amount": "1000000000000000000000"}],
+                    "voting_period": "432000s",
+                    "quorum": "0.334000000000000000",
+                    "threshold": "0.500000000000000000"
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/cosmos/gov/v1/proposals/"))
+            .and(query_param(
+                "proposal_status",
+                "PROPOSAL_STATUS_VOTING_PERIOD",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "proposals": [proposal["proposal"]]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path("/cosmos/gov/v1/proposals/252/tally"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "tally": {
+                    "yes_count": "100",
+                    "abstain_count": "2",
+                    "no_count": "1",
+                    "no_with_veto_count": "0"
+                }
+            })))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    #[tokio::test]
+    async fn test_get_status() {
+        let mock_server = setup_mock_api().await;
+        let client = RestGovClient::new(
+            Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri"),
+        );
+
+        let now = Utc.with_ymd_and_hms(2024, 1, 11, 12, 0, 0).unwrap();
+        let status = get_status(&client, Network::Mainnet, now)
+            .await
+            .expect("failed to get status");
+
+        assert!(status.contains("Voting period:  120 hours"));
+        assert!(status.contains("Min deposit:    1000 EVMOS"));
+        assert!(status.contains(
+            "#252 Evmos Mainnet v16.0.0 Upgrade (ends in 2d 3h 4m at 2024-01-13 15:04 UTC) - Yes: 100 | No: 1 | Veto: 0 | Abstain: 2"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_overview() {
+        let mock_server = setup_mock_api().await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/gov/v1/proposals/"))
+            .and(query_param(
+                "proposal_status",
+                "PROPOSAL_STATUS_DEPOSIT_PERIOD",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"proposals": []})))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/upgrade/v1beta1/current_plan"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "plan": {"name": "v16.0.0", "height": "18798000", "info": ""}
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/cosmos/base/tendermint/v1beta1/blocks/latest"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string(include_str!("testdata/block_mainnet_18798834.json")),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let base_url =
+            Url::from_str(mock_server.uri().as_str()).expect("failed to parse mock server uri");
+        let client = RestGovClient::new(base_url.clone());
+        let provider = BlockProvider {
+            rest: base_url,
+            rpc: None,
+            cache: None,
+        };
+
+        let overview = get_overview(&client, &provider, Network::Mainnet).await;
+        assert_eq!(overview[0], "Mainnet");
+        assert!(overview[2].starts_with("Latest block:  18,798,834 ("));
+        assert_eq!(overview[3], "Upgrade plan:  v16.0.0 at 18,798,000");
+        assert_eq!(overview[6], "#252 Evmos Mainnet v16.0.0 Upgrade (voting)");
+    }
+
+    #[test]
+    fn test_render_columns() {
+        let columns = vec![
+            vec!["Testnet".to_string(), "a".to_string()],
+            vec!["Mainnet".to_string()],
+        ];
+        assert_eq!(render_columns(&columns), "Testnet    Mainnet\na");
+    }
+
+    #[test]
+    fn test_format_time_left() {
+        assert_eq!(format_time_left(Duration::minutes(90)), "1h 30m");
+        assert_eq!(format_time_left(Duration::hours(50)), "2d 2h 0m");
+        assert_eq!(format_time_left(Duration::minutes(-5)), "0m");
+    }
+}
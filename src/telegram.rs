@@ -0,0 +1,567 @@
+use crate::context::Context;
+use crate::errors::{BlockError, HelperError, TelegramError};
+use crate::helper::{from_file, get_estimated_upgrade_time, UpgradeHelper};
+use crate::notifier::{self, Notification, Severity};
+use crate::status::{format_time_left, get_status};
+use crate::watch::FileWatcher;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use std::path::PathBuf;
+use url::Url;
+
+/// The environment variable containing the token of the Telegram bot.
+const TOKEN_VAR: &str = "TELEGRAM_BOT_TOKEN";
+
+/// The base URL of the Telegram Bot API.
+const API_URL: &str = "https://api.telegram.org";
+
+/// The number of seconds, for which the query of new messages is held open by the Telegram API.
+/// This has to be shorter than the timeout of the shared HTTP client.
+const POLL_TIMEOUT_SECONDS: u64 = 25;
+
+/// The interval in which the upgrade time is re-estimated to check for reached milestones.
+const CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// The message listing the available chat commands.
+const HELP: &str = "Available commands:\n\
+                    /estimate - re-estimate the time of reaching the upgrade height\n\
+                    /status - show the governance status of the network\n\
+                    /help - show this message";
+
+/// Represents the response of the Telegram Bot API.
+#[derive(Deserialize)]
+struct ApiResponse<T> {
+    ok: bool,
+    result: Option<T>,
+    description: Option<String>,
+}
+
+/// Represents an incoming update of the Telegram Bot API.
+#[derive(Debug, Deserialize)]
+pub struct Update {
+    /// The ID of the update, which is used to acknowledge the received updates.
+    pub update_id: i64,
+    /// The received message, if the update contains one.
+    pub message: Option<Message>,
+}
+
+/// Represents a received chat message.
+#[derive(Debug, Deserialize)]
+pub struct Message {
+    /// The chat, in which the message was sent.
+    pub chat: Chat,
+    /// The user, who sent the message.
+    pub from: Option<User>,
+    /// The text of the message.
+    pub text: Option<String>,
+}
+
+/// Represents a Telegram chat.
+#[derive(Debug, Deserialize)]
+pub struct Chat {
+    pub id: i64,
+}
+
+/// Represents a Telegram user.
+#[derive(Debug, Deserialize)]
+pub struct User {
+    pub id: i64,
+}
+
+/// The client to send requests to the Telegram Bot API.
+pub struct TelegramClient {
+    /// The base URL of the bot, which contains the bot token.
+    base_url: Url,
+    /// The HTTP client to send the requests with.
+    client: Client,
+}
+
+impl TelegramClient {
+    /// Creates a new client for the bot with the given base URL.
+    pub fn new(base_url: Url, client: Client) -> TelegramClient {
+        TelegramClient { base_url, client }
+    }
+
+    /// Creates the client for the bot with the token from the environment,
+    /// which sends the requests with the given HTTP client.
+    pub fn from_env(client: Client) -> Result<TelegramClient, TelegramError> {
+        let token = std::env::var(TOKEN_VAR).map_err(|_| TelegramError::MissingToken(TOKEN_VAR))?;
+        let base_url = Url::parse(&format!("{}/bot{}/", API_URL, token))?;
+
+        Ok(TelegramClient::new(base_url, client))
+    }
+
+    /// Returns the updates starting with the given offset.
+    /// The request is held open until new updates are available or the poll timeout is reached.
+    pub async fn get_updates(&self, offset: i64) -> Result<Vec<Update>, TelegramError> {
+        self.call(
+            "getUpdates",
+            json!({"offset": offset, "timeout": POLL_TIMEOUT_SECONDS, "allowed_updates": ["message"]}),
+        )
+        .await
+    }
+
+    /// Sends the given text to the given chat.
+    pub async fn send_message(&self, chat_id: i64, text: &str) -> Result<(), TelegramError> {
+        self.call::<serde_json::Value>("sendMessage", json!({"chat_id": chat_id, "text": text}))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Calls the given method of the Bot API with the given parameters.
+    /// The URL is removed from failed requests, because it contains the bot token.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, TelegramError> {
+        let response: ApiResponse<T> = self
+            .client
+            .post(self.base_url.join(method)?)
+            .json(&params)
+            .send()
+            .await
+            .map_err(|e| TelegramError::Request(e.without_url()))?
+            .json()
+            .await
+            .map_err(|e| TelegramError::Request(e.without_url()))?;
+
+        match (response.ok, response.result) {
+            (true, Some(result)) => Ok(result),
+            _ => Err(TelegramError::Api(
+                response
+                    .description
+                    .unwrap_or_else(|| format!("{} failed", method)),
+            )),
+        }
+    }
+}
+
+/// The chat commands, which are understood by the bot.
+#[derive(Debug, PartialEq)]
+enum BotCommand {
+    Estimate,
+    Status,
+    Help,
+}
+
+/// Parses the chat command from the given message text. Commands addressed to a
+/// bot in group chats (e.g. `/status@proposer_bot`) are supported as well.
+fn parse_command(text: &str) -> Option<BotCommand> {
+    let command = text.split_whitespace().next()?.strip_prefix('/')?;
+    match command.split('@').next()? {
+        "estimate" => Some(BotCommand::Estimate),
+        "status" => Some(BotCommand::Status),
+        "help" | "start" => Some(BotCommand::Help),
+        _ => None,
+    }
+}
+
+/// The milestones of the upgrade, for which alerts are sent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Milestone {
+    DayBefore,
+    HourBefore,
+    TenMinutesBefore,
+    HeightReached,
+}
+
+impl Milestone {
+    /// Returns the time before the upgrade, at which the milestone is reached.
+    fn offset(self) -> Duration {
+        match self {
+            Milestone::DayBefore => Duration::hours(24),
+            Milestone::HourBefore => Duration::hours(1),
+            Milestone::TenMinutesBefore => Duration::minutes(10),
+            Milestone::HeightReached => Duration::zero(),
+        }
+    }
+}
+
+/// Returns the milestones, which were reached and not alerted yet, in chronological order.
+/// If no estimated time is given, the upgrade height was already reached.
+fn get_due_milestones(
+    estimated_time: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    sent: &[Milestone],
+) -> Vec<Milestone> {
+    [
+        Milestone::DayBefore,
+        Milestone::HourBefore,
+        Milestone::TenMinutesBefore,
+        Milestone::HeightReached,
+    ]
+    .into_iter()
+    .filter(|milestone| !sent.contains(milestone))
+    .filter(|milestone| estimated_time.is_none_or(|time| time - milestone.offset() <= now))
+    .collect()
+}
+
+/// Returns the alert for the given milestone of the upgrade.
+fn get_alert(helper: &UpgradeHelper, milestone: Milestone) -> String {
+    let upgrade = format!(
+        "{} upgrade to {} at height {}",
+        helper.network, helper.target_version, helper.upgrade_height
+    );
+
+    match milestone {
+        Milestone::HeightReached => format!("The upgrade height of the {} was reached.", upgrade),
+        _ => format!(
+            "The {} is expected in about {}.",
+            upgrade,
+            format_time_left(milestone.offset())
+        ),
+    }
+}
+
+/// The Telegram bot, which lets authorized team members follow the upgrade of a configuration.
+pub struct Bot<'a> {
+    /// The context, which provides the chain configurations and clients of the networks.
+    ctx: &'a Context,
+    /// The client to send requests to the Telegram Bot API.
+    client: TelegramClient,
+    /// The path to the configuration of the upgrade.
+    config_path: PathBuf,
+    /// The configuration of the upgrade.
+    helper: UpgradeHelper,
+    /// The IDs of the users, which are allowed to use the bot.
+    allowed_users: Vec<i64>,
+    /// The chat, to which the milestone alerts are sent.
+    alert_chat: Option<i64>,
+    /// The milestones, which were already alerted.
+    sent: Vec<Milestone>,
+    /// Whether the last estimation failed, so that failures are only notified once.
+    failing: bool,
+}
+
+impl<'a> Bot<'a> {
+    /// Creates a new bot for the upgrade configuration at the given path.
+    pub fn new(
+        ctx: &'a Context,
+        client: TelegramClient,
+        config_path: PathBuf,
+        allowed_users: Vec<i64>,
+        alert_chat: Option<i64>,
+    ) -> Result<Bot<'a>, HelperError> {
+        Ok(Bot {
+            ctx,
+            client,
+            helper: from_file(&config_path)?,
+            config_path,
+            allowed_users,
+            alert_chat,
+            sent: Vec::new(),
+            failing: false,
+        })
+    }
+
+    /// Runs the bot until the process is stopped. The configuration is reloaded when it changes.
+    pub async fn run(mut self) -> Result<(), TelegramError> {
+        let mut watcher = FileWatcher::new(vec![self.config_path.clone()]);
+        let mut check = tokio::time::interval(CHECK_INTERVAL);
+        let mut offset = 0;
+
+        // NOTE: milestones that were reached before starting the bot are not alerted
+        if let Some(estimated_time) = self.estimate().await {
+            self.sent = get_due_milestones(estimated_time, Utc::now(), &[]);
+        }
+
+        loop {
+            tokio::select! {
+                updates = self.client.get_updates(offset) => match updates {
+                    Ok(updates) => {
+                        for update in updates {
+                            offset = update.update_id + 1;
+                            if let Some(message) = update.message {
+                                self.handle_message(message).await;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        println!("WARNING: failed to get updates: {}", e);
+                        tokio::time::sleep(std::time::Duration::from_secs(POLL_TIMEOUT_SECONDS)).await;
+                    }
+                },
+                changed = watcher.wait_for_change() => {
+                    changed?;
+                    self.reload().await;
+                },
+                _ = check.tick() => self.check_milestones().await,
+            }
+        }
+    }
+
+    /// Handles a received message and replies to the chat, in which it was sent.
+    async fn handle_message(&self, message: Message) {
+        let Some(command) = message.text.as_deref().and_then(parse_command) else {
+            return;
+        };
+
+        let is_authorized = message
+            .from
+            .is_some_and(|user| self.allowed_users.contains(&user.id));
+        let reply = match (is_authorized, command) {
+            (false, _) => "You are not authorized to use this bot.".to_string(),
+            (true, BotCommand::Help) => HELP.to_string(),
+            (true, BotCommand::Estimate) => self.get_estimate_reply().await,
+            (true, BotCommand::Status) => self.get_status_reply().await,
+        };
+
+        if let Err(e) = self.client.send_message(message.chat.id, &reply).await {
+            println!("WARNING: failed to send reply: {}", e);
+        }
+    }
+
+    /// Returns the current estimate of the upgrade time compared to the scheduled time.
+    async fn get_estimate_reply(&self) -> String {
+        match get_estimated_upgrade_time(self.ctx, &self.helper).await {
+            Ok(estimated_time) => get_estimate_message(&self.helper, estimated_time),
+            Err(e) => format!("Failed to estimate the upgrade time: {}", e),
+        }
+    }
+
+    /// Returns the governance status of the network of the upgrade.
+    async fn get_status_reply(&self) -> String {
+        let status = match self.ctx.gov_client(self.helper.network) {
+            Ok(client) => get_status(client.as_ref(), self.helper.network, Utc::now()).await,
+            Err(e) => Err(e),
+        };
+
+        match (status, self.helper.proposal_id) {
+            (Ok(status), Some(proposal_id)) => format!("{}\n\n{}", proposal_id, status),
+            (Ok(status), None) => status,
+            (Err(e), _) => format!("Failed to query the governance status: {}", e),
+        }
+    }
+
+    /// Returns the estimated upgrade time, which is `Some(None)` if the upgrade height was already reached.
+    /// If the estimation fails, a warning is printed and `None` is returned.
+    async fn estimate(&self) -> Option<Option<DateTime<Utc>>> {
+        match get_estimated_upgrade_time(self.ctx, &self.helper).await {
+            Ok(estimated_time) => Some(Some(estimated_time)),
+            Err(BlockError::PastHeight(..)) => Some(None),
+            Err(e) => {
+                println!("WARNING: failed to estimate the upgrade time: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Re-estimates the upgrade time and sends an alert for the latest reached milestone.
+    /// The alerts and the first of consecutive estimation failures are also sent to the notification channels.
+    async fn check_milestones(&mut self) {
+        let Some(estimated_time) = self.estimate().await else {
+            if !self.failing {
+                self.failing = true;
+                notifier::notify(Notification::new(
+                    Severity::Warning,
+                    "Estimation failed",
+                    &format!(
+                        "Failed to estimate the time of the {} upgrade to {}.",
+                        self.helper.network, self.helper.target_version
+                    ),
+                ))
+                .await;
+            }
+            return;
+        };
+        self.failing = false;
+
+        let due = get_due_milestones(estimated_time, Utc::now(), &self.sent);
+        if let Some(milestone) = due.last() {
+            let alert = get_alert(&self.helper, *milestone);
+            self.alert(&alert).await;
+            notifier::notify(Notification::new(
+                Severity::Info,
+                "Upgrade milestone",
+                &alert,
+            ))
+            .await;
+        }
+        self.sent.extend(due);
+    }
+
+    /// Reloads the changed configuration and resets the alerted milestones.
+    async fn reload(&mut self) {
+        match from_file(&self.config_path) {
+            Ok(helper) => {
+                self.helper = helper;
+                self.sent.clear();
+                self.alert(&format!(
+                    "The configuration was updated: {} upgrade to {} at height {}.",
+                    self.helper.network, self.helper.target_version, self.helper.upgrade_height
+                ))
+                .await;
+            }
+            Err(e) => println!("WARNING: failed to reload configuration: {}", e),
+        }
+    }
+
+    /// Sends the given alert to the alert chat, if one is configured.
+    async fn alert(&self, text: &str) {
+        let Some(chat_id) = self.alert_chat else {
+            return;
+        };
+
+        if let Err(e) = self.client.send_message(chat_id, text).await {
+            println!("WARNING: failed to send alert: {}", e);
+        }
+    }
+}
+
+/// Returns the message comparing the estimated upgrade time with the scheduled time.
+fn get_estimate_message(helper: &UpgradeHelper, estimated_time: DateTime<Utc>) -> String {
+    let drift = estimated_time - helper.upgrade_time;
+    let sign = if drift < Duration::zero() { "-" } else { "+" };
+
+    format!(
+        "The {} upgrade to {} at height {} is estimated for {} (scheduled: {}, drift: {}{}).",
+        helper.network,
+        helper.target_version,
+        helper.upgrade_height,
+        estimated_time.format("%a, %b %d %Y, %H:%M UTC"),
+        helper.upgrade_time.format("%a, %b %d %Y, %H:%M UTC"),
+        sign,
+        format_time_left(drift.abs())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+    use chrono::TimeZone;
+    use wiremock::{
+        matchers::{body_partial_json, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(parse_command("/estimate"), Some(BotCommand::Estimate));
+        assert_eq!(
+            parse_command("/status@proposer_bot now"),
+            Some(BotCommand::Status)
+        );
+        assert_eq!(parse_command("/start"), Some(BotCommand::Help));
+        assert_eq!(parse_command("/unknown"), None);
+        assert_eq!(parse_command("status"), None);
+    }
+
+    #[test]
+    fn test_get_due_milestones() {
+        let upgrade_time = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+
+        assert!(
+            get_due_milestones(Some(upgrade_time), upgrade_time - Duration::days(2), &[])
+                .is_empty()
+        );
+        assert_eq!(
+            get_due_milestones(
+                Some(upgrade_time),
+                upgrade_time - Duration::minutes(30),
+                &[Milestone::DayBefore]
+            ),
+            vec![Milestone::HourBefore]
+        );
+        assert_eq!(
+            get_due_milestones(None, upgrade_time, &[Milestone::DayBefore]),
+            vec![
+                Milestone::HourBefore,
+                Milestone::TenMinutesBefore,
+                Milestone::HeightReached
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_estimate_message() {
+        let upgrade_time = Utc.with_ymd_and_hms(2024, 1, 17, 16, 0, 0).unwrap();
+        let helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v15.0.0",
+            "v16.0.0",
+            upgrade_time,
+            19_000_000,
+            "",
+        );
+
+        assert_eq!(
+            get_estimate_message(&helper, upgrade_time - Duration::minutes(25)),
+            "The Mainnet upgrade to v16.0.0 at height 19000000 is estimated for Wed, Jan 17 2024, 15:35 UTC \
+             (scheduled: Wed, Jan 17 2024, 16:00 UTC, drift: -0h 25m)."
+        );
+        assert_eq!(
+            get_alert(&helper, Milestone::HourBefore),
+            "The Mainnet upgrade to v16.0.0 at height 19000000 is expected in about 1h 0m."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/botTOKEN/getUpdates"))
+            .and(body_partial_json(json!({"offset": 5})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ok": true,
+                "result": [{
+                    "update_id": 5,
+                    "message": {"chat": {"id": -100}, "from": {"id": 42}, "text": "/status"}
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/botTOKEN/sendMessage"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+                "ok": false,
+                "description": "Forbidden: bot was blocked by the user"
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let client = TelegramClient::new(
+            Url::parse(&format!("{}/botTOKEN/", mock_server.uri())).unwrap(),
+            Client::new(),
+        );
+
+        let updates = client.get_updates(5).await.expect("failed to get updates");
+        assert_eq!(updates.len(), 1);
+        let message = updates[0].message.as_ref().expect("expected message");
+        assert_eq!(message.chat.id, -100);
+        assert_eq!(message.text.as_deref(), Some("/status"));
+
+        let res = client.send_message(-100, "hello").await;
+        assert!(
+            matches!(res, Err(TelegramError::Api(ref description)) if description.contains("blocked")),
+            "expected API error, got: {:?}",
+            res
+        );
+    }
+
+    #[tokio::test]
+    async fn test_client_hides_token() {
+        // NOTE: binding to port 0 reserves a free port, which refuses connections after dropping the listener
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let client = TelegramClient::new(
+            Url::parse(&format!("http://127.0.0.1:{}/botSECRET/", port)).unwrap(),
+            Client::new(),
+        );
+
+        let err = client
+            .get_updates(0)
+            .await
+            .expect_err("expected the request to fail");
+        assert!(matches!(err, TelegramError::Request(_)));
+        assert!(!err.to_string().contains("SECRET"), "token leaked: {}", err);
+        assert!(!format!("{:?}", err).contains("SECRET"));
+    }
+}
@@ -0,0 +1,267 @@
+use crate::chain::{get_config_dir, get_network_key};
+use crate::errors::TelemetryError;
+use crate::http::get_client;
+use crate::network::Network;
+use crate::secret;
+use chrono::{DateTime, Utc};
+use reqwest::header::CONTENT_TYPE;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// The name of the file inside of the proposer configuration directory,
+/// which enables the export of usage metrics. Without this file, no metrics are sent.
+const TELEMETRY_FILE: &str = "telemetry.toml";
+
+/// Contains the configuration of the opt-in metrics export.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryConfig {
+    /// The URL, to which the metrics are sent, e.g. the base URL of the Prometheus Pushgateway.
+    pub endpoint: String,
+    /// The format, in which the metrics are sent.
+    #[serde(default)]
+    pub format: TelemetryFormat,
+    /// The job name, under which the metrics are grouped in the Pushgateway.
+    #[serde(default = "default_job")]
+    pub job: String,
+}
+
+/// The formats, in which the metrics can be exported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TelemetryFormat {
+    /// Each event is sent as a JSON object in a POST request.
+    #[default]
+    Json,
+    /// Each event is pushed as gauges in the text format of the Prometheus Pushgateway.
+    Pushgateway,
+}
+
+/// Returns the default job name of the pushed metrics.
+fn default_job() -> String {
+    "proposer".to_string()
+}
+
+/// The anonymous usage events, which are recorded. They contain no information about the user.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    /// A proposal was generated for the given network.
+    ProposalGenerated { network: Network },
+    /// An upgrade was executed on the given network and deviated from the estimated time
+    /// by the given number of minutes.
+    UpgradeExecuted {
+        network: Network,
+        prediction_error_minutes: i64,
+    },
+}
+
+impl Event {
+    /// Returns the network of the event.
+    fn network(&self) -> Network {
+        match self {
+            Event::ProposalGenerated { network } => *network,
+            Event::UpgradeExecuted { network, .. } => *network,
+        }
+    }
+}
+
+/// Returns the telemetry configuration from the given file, if it exists.
+/// Encrypted values, e.g. an endpoint containing credentials, are decrypted with the configured passphrase.
+fn load_config(path: &Path) -> Result<Option<TelemetryConfig>, TelemetryError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(path)?;
+    if secret::contains_encrypted(&contents) {
+        let mut value: serde_json::Value = toml::from_str(&contents)?;
+        secret::decrypt_values(&mut value, &secret::get_passphrase()?)?;
+
+        return serde_json::from_value(value)
+            .map(Some)
+            .map_err(TelemetryError::Decrypted);
+    }
+
+    Ok(Some(toml::from_str(&contents)?))
+}
+
+/// Returns the URL, the body and the content type of the request exporting the given event.
+fn get_request(
+    config: &TelemetryConfig,
+    event: &Event,
+    timestamp: DateTime<Utc>,
+) -> Result<(String, String, &'static str), TelemetryError> {
+    match config.format {
+        TelemetryFormat::Json => {
+            let mut body = serde_json::to_value(event)?;
+            body["timestamp"] = serde_json::json!(timestamp);
+
+            Ok((
+                config.endpoint.clone(),
+                body.to_string(),
+                "application/json",
+            ))
+        }
+        TelemetryFormat::Pushgateway => {
+            let url = format!(
+                "{}/metrics/job/{}/network/{}",
+                config.endpoint.trim_end_matches('/'),
+                config.job,
+                get_network_key(event.network())
+            );
+            let body = match event {
+                Event::ProposalGenerated { .. } => format!(
+                    "proposer_last_proposal_generated_timestamp_seconds {}\n",
+                    timestamp.timestamp()
+                ),
+                Event::UpgradeExecuted {
+                    prediction_error_minutes,
+                    ..
+                } => format!(
+                    "proposer_last_prediction_error_minutes {}\n",
+                    prediction_error_minutes
+                ),
+            };
+
+            Ok((url, body, "text/plain; version=0.0.4"))
+        }
+    }
+}
+
+/// Sends the given event to the configured endpoint.
+/// The URL is removed from failed requests, because the endpoint may contain credentials.
+async fn send_event(config: &TelemetryConfig, event: &Event) -> Result<(), TelemetryError> {
+    let (url, body, content_type) = get_request(config, event, Utc::now())?;
+    let response = get_client()?
+        .post(url)
+        .header(CONTENT_TYPE, content_type)
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| TelemetryError::Request(e.without_url()))?;
+
+    if !response.status().is_success() {
+        return Err(TelemetryError::Status(response.status().as_u16()));
+    }
+
+    Ok(())
+}
+
+/// Records the given usage event if the metrics export is enabled in the configuration directory.
+///
+/// Failures are only printed as warnings, so that the metrics export never interrupts the workflow.
+pub async fn record(event: Event) {
+    let result = match load_config(&get_config_dir().join(TELEMETRY_FILE)) {
+        Ok(Some(config)) => send_event(&config, &event).await,
+        Ok(None) => Ok(()),
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        println!("WARNING: failed to export usage metrics: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use test_dir::{DirBuilder, TestDir};
+    use wiremock::{
+        matchers::{body_string, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[test]
+    fn test_load_encrypted_config() {
+        std::env::set_var("PROPOSER_PASSPHRASE", "test-passphrase");
+        let endpoint = secret::encrypt("https://metrics.example.com", "test-passphrase").unwrap();
+        let test_dir = TestDir::temp();
+        let path = test_dir.path(TELEMETRY_FILE);
+        fs::write(&path, format!("endpoint = \"{}\"\n", endpoint)).unwrap();
+
+        let config = load_config(&path)
+            .expect("failed to load telemetry configuration")
+            .expect("expected telemetry configuration");
+        assert_eq!(config.endpoint, "https://metrics.example.com");
+    }
+
+    #[test]
+    fn test_get_request() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 11, 12, 0, 0).unwrap();
+        let mut config: TelemetryConfig =
+            toml::from_str(r#"endpoint = "https://metrics.example.com""#).unwrap();
+        assert_eq!(config.format, TelemetryFormat::Json);
+
+        let (url, body, _) = get_request(
+            &config,
+            &Event::ProposalGenerated {
+                network: Network::Testnet,
+            },
+            timestamp,
+        )
+        .unwrap();
+        assert_eq!(url, "https://metrics.example.com");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            serde_json::json!({
+                "event": "proposal_generated",
+                "network": "Testnet",
+                "timestamp": "2024-01-11T12:00:00Z"
+            })
+        );
+
+        config.format = TelemetryFormat::Pushgateway;
+        let (url, body, _) = get_request(
+            &config,
+            &Event::UpgradeExecuted {
+                network: Network::Mainnet,
+                prediction_error_minutes: -12,
+            },
+            timestamp,
+        )
+        .unwrap();
+        assert_eq!(
+            url,
+            "https://metrics.example.com/metrics/job/proposer/network/mainnet"
+        );
+        assert_eq!(body, "proposer_last_prediction_error_minutes -12\n");
+    }
+
+    #[tokio::test]
+    async fn test_send_event() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/metrics/job/proposer/network/local-node"))
+            .and(body_string("proposer_last_prediction_error_minutes 5\n"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let config = TelemetryConfig {
+            endpoint: mock_server.uri(),
+            format: TelemetryFormat::Pushgateway,
+            job: default_job(),
+        };
+        send_event(
+            &config,
+            &Event::UpgradeExecuted {
+                network: Network::LocalNode,
+                prediction_error_minutes: 5,
+            },
+        )
+        .await
+        .expect("failed to send event");
+
+        let res = send_event(
+            &config,
+            &Event::ProposalGenerated {
+                network: Network::Mainnet,
+            },
+        )
+        .await;
+        assert!(matches!(res, Err(TelemetryError::Status(404))));
+    }
+}
@@ -0,0 +1,371 @@
+use crate::errors::TemplateError;
+use handlebars::{no_escape, Handlebars};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The block helpers, which change the context for the placeholders inside of the block.
+const CONTEXT_HELPERS: [&str; 2] = ["each", "with"];
+
+/// The directory containing the partial templates, which are shared across the generated artifacts,
+/// e.g. the footer. A partial is included with `{{> name}}`, where the name is the file name without extension.
+pub const PARTIALS_DIR: &str = "src/templates/partials";
+
+/// Contains the comparison of the placeholders used in a template with the provided data.
+#[derive(Debug, PartialEq)]
+pub struct TemplateReport {
+    /// The name of the template.
+    pub template: String,
+    /// The placeholders, which are used in the template but not contained in the data.
+    pub missing: Vec<String>,
+    /// The fields of the data, which are not used in the template.
+    pub unused: Vec<String>,
+    /// The fields of the data, which can be used in the template.
+    pub available: Vec<String>,
+}
+
+impl TemplateReport {
+    /// Returns whether all placeholders of the template are contained in the data.
+    pub fn is_valid(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+impl fmt::Display for TemplateReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "template '{}' uses {} unknown placeholder(s):",
+            self.template,
+            self.missing.len()
+        )?;
+        for placeholder in self.missing.iter() {
+            writeln!(f, "  - {}", placeholder)?;
+        }
+        write!(f, "available placeholders: {}", self.available.join(", "))?;
+        if !self.unused.is_empty() {
+            write!(f, "\nunused data: {}", self.unused.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the Handlebars registry with the given template file registered.
+///
+/// In strict mode, rendering fails for placeholders that are not contained in the data.
+/// Otherwise, they are rendered as empty values.
+pub fn get_handlebars<'a>(
+    name: &str,
+    path: &str,
+    strict: bool,
+    escape: bool,
+) -> Result<Handlebars<'a>, TemplateError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.set_strict_mode(strict);
+    if !escape {
+        handlebars.register_escape_fn(no_escape);
+    }
+    register_partials(&mut handlebars, Path::new(PARTIALS_DIR))?;
+    handlebars
+        .register_template_file(name, path)
+        .map_err(Box::new)?;
+
+    Ok(handlebars)
+}
+
+/// Registers the partial templates (`.hbs` files) in the given directory with the given registry.
+pub fn register_partials(handlebars: &mut Handlebars, dir: &Path) -> Result<(), TemplateError> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = get_partial_name(&path) else {
+            continue;
+        };
+        handlebars
+            .register_partial(&name, fs::read_to_string(&path)?)
+            .map_err(Box::new)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the name of the partial defined in the given file, if it is a template.
+fn get_partial_name(path: &Path) -> Option<String> {
+    if path.extension()? != "hbs" {
+        return None;
+    }
+
+    Some(path.file_stem()?.to_string_lossy().to_string())
+}
+
+/// Replaces the partials included in the given template source with their sources from the given directory,
+/// so that the placeholders used in the partials are checked as well. Unknown partials are left untouched.
+pub fn expand_partials(source: &str, dir: &Path) -> String {
+    let include = Regex::new(r"\{\{~?>\s*([\w-]+)\s*~?\}\}").expect("invalid regex");
+    include
+        .replace_all(source, |captures: &regex::Captures| {
+            fs::read_to_string(dir.join(format!("{}.hbs", &captures[1])))
+                .unwrap_or_else(|_| captures[0].to_string())
+        })
+        .to_string()
+}
+
+/// Renders the template with the given name after checking that all used placeholders are provided.
+///
+/// In strict mode, a report of the missing placeholders is returned as an error before rendering.
+/// Otherwise, the report is printed as a warning and the template is rendered regardless.
+pub fn render(
+    handlebars: &Handlebars,
+    name: &str,
+    path: &str,
+    data: &Value,
+) -> Result<String, TemplateError> {
+    let source = expand_partials(&fs::read_to_string(path)?, Path::new(PARTIALS_DIR));
+    let report = check_template(name, &source, data);
+    if !report.is_valid() {
+        match handlebars.strict_mode() {
+            true => return Err(TemplateError::MissingPlaceholders(report)),
+            false => println!("WARNING: {}", report),
+        }
+    }
+
+    Ok(handlebars.render(name, data)?)
+}
+
+/// Returns the line and column in the template, at which the given error occurred, if it is known.
+pub fn get_error_location(error: &TemplateError) -> Option<(usize, usize)> {
+    match error {
+        TemplateError::Register(e) => e.line_no.zip(e.column_no),
+        TemplateError::Render(e) => e.line_no.zip(e.column_no),
+        _ => None,
+    }
+}
+
+/// Compares the placeholders used in the given template source with the given data.
+pub fn check_template(name: &str, source: &str, data: &Value) -> TemplateReport {
+    let placeholders = get_placeholders(source);
+    let available: Vec<String> = data
+        .as_object()
+        .map(|fields| fields.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let missing = placeholders
+        .iter()
+        .filter(|placeholder| !is_provided(placeholder, data))
+        .cloned()
+        .collect();
+    let used: BTreeSet<&str> = placeholders
+        .iter()
+        .filter_map(|placeholder| placeholder.split('.').next())
+        .collect();
+    let unused = available
+        .iter()
+        .filter(|field| !used.contains(field.as_str()))
+        .cloned()
+        .collect();
+
+    TemplateReport {
+        template: name.to_string(),
+        missing,
+        unused,
+        available,
+    }
+}
+
+/// Returns the placeholders, which are used in the root context of the given template.
+///
+/// Placeholders inside of `each` and `with` blocks refer to the iterated items,
+/// so that only the argument of the block itself is returned.
+pub fn get_placeholders(source: &str) -> BTreeSet<String> {
+    let expression = Regex::new(r"\{\{\{?~?\s*(.*?)\s*~?\}?\}\}").expect("invalid regex");
+    let mut placeholders = BTreeSet::new();
+    let mut blocks: Vec<bool> = Vec::new();
+
+    for captures in expression.captures_iter(source) {
+        let content = &captures[1];
+        let in_root_context = !blocks.iter().any(|changes_context| *changes_context);
+
+        // NOTE: partials are checked by expanding them before
+        if content.starts_with('!') || content.starts_with('>') {
+            continue;
+        }
+        if content.starts_with('/') {
+            blocks.pop();
+            continue;
+        }
+
+        let (is_block, tokens) = match content.strip_prefix('#') {
+            Some(block) => (true, block.split_whitespace().collect::<Vec<_>>()),
+            None => (false, content.split_whitespace().collect::<Vec<_>>()),
+        };
+        let Some((first, params)) = tokens.split_first() else {
+            continue;
+        };
+
+        // NOTE: single tokens are placeholders, otherwise the first token is the name of a helper
+        let paths: Vec<&str> = match (is_block, params.is_empty()) {
+            // NOTE: chained blocks like `else if` contain the name of the helper after `else`
+            (false, _) if *first == "else" => params.iter().skip(1).copied().collect(),
+            (false, true) => vec![*first],
+            _ => params.to_vec(),
+        };
+        if is_block {
+            blocks.push(CONTEXT_HELPERS.contains(first));
+        }
+
+        if in_root_context {
+            placeholders.extend(paths.into_iter().filter_map(get_root_path));
+        }
+    }
+
+    placeholders
+}
+
+/// Returns the path of the given parameter relative to the root context or `None`
+/// if the parameter is a literal, a hash argument or refers to the current item.
+fn get_root_path(param: &str) -> Option<String> {
+    let param = param.trim_matches(|c| c == '(' || c == ')');
+    let is_literal = param.starts_with(['"', '\''])
+        || param.parse::<f64>().is_ok()
+        || ["true", "false", "null", "undefined"].contains(&param);
+    if is_literal || param.contains('=') || param.starts_with('@') || param == "this" {
+        return None;
+    }
+
+    Some(param.strip_prefix("this.").unwrap_or(param).to_string())
+}
+
+/// Checks if the given placeholder path is contained in the data. Fields of values,
+/// that are not objects (e.g. a `null` for an optional section), are not checked.
+fn is_provided(placeholder: &str, data: &Value) -> bool {
+    let mut value = data;
+    for segment in placeholder.split('.') {
+        match value {
+            Value::Object(fields) => match fields.get(segment) {
+                Some(field) => value = field,
+                None => return false,
+            },
+            _ => return true,
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use test_dir::{DirBuilder, TestDir};
+
+    #[test]
+    fn test_get_error_location() {
+        let mut handlebars = Handlebars::new();
+        let error = handlebars
+            .register_template_string("test", "# Title\n\n{{#if name}}\n{{name}}\n")
+            .map_err(Box::new)
+            .expect_err("expected unclosed block to fail");
+        assert_eq!(
+            get_error_location(&TemplateError::Register(error)),
+            Some((5, 1))
+        );
+
+        handlebars.set_strict_mode(true);
+        handlebars
+            .register_template_string("test", "# Title\n\n{{name}}\n")
+            .unwrap();
+        let error = handlebars.render("test", &json!({})).unwrap_err();
+        assert_eq!(
+            get_error_location(&TemplateError::Render(error)),
+            Some((3, 1))
+        );
+    }
+
+    #[test]
+    fn test_get_placeholders() {
+        let source = "{{! comment }}# {{name}}\n\
+                      {{#if block_time}}{{block_time.seconds_per_block}}{{/if}}\n\
+                      {{#each upgrade_times}}| {{this.timezone}} | {{time}} |{{/each}}\n\
+                      {{#unless downtime}}none{{else}}{{{downtime.expected}}}{{/unless}}\n\
+                      {{#if a}}a{{else if b}}b{{/if}}";
+
+        assert_eq!(
+            get_placeholders(source).into_iter().collect::<Vec<_>>(),
+            vec![
+                "a",
+                "b",
+                "block_time",
+                "block_time.seconds_per_block",
+                "downtime",
+                "downtime.expected",
+                "name",
+                "upgrade_times",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_template() {
+        let source = "{{name}} at {{height}} ({{block_time.latest}}) by {{autor}}";
+        let data = json!({
+            "author": "Evmos Core Team",
+            "block_time": null,
+            "height": 100,
+            "name": "v16.0.0",
+        });
+
+        let report = check_template("proposal", source, &data);
+        assert!(!report.is_valid());
+        assert_eq!(report.missing, vec!["autor"]);
+        assert_eq!(report.unused, vec!["author"]);
+        assert_eq!(
+            report.to_string(),
+            "template 'proposal' uses 1 unknown placeholder(s):\n  \
+             - autor\n\
+             available placeholders: author, block_time, height, name\n\
+             unused data: author"
+        );
+
+        let report = check_template("proposal", "{{name}}", &json!({"name": "v16.0.0"}));
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn test_partials() {
+        let test_dir = TestDir::temp();
+        fs::write(test_dir.path("footer.hbs"), "Tracked in {{issue}}.").unwrap();
+        fs::write(test_dir.path("notes.md"), "{{unrelated}}").unwrap();
+
+        let source = "# {{name}}\n\n{{> footer}}\n{{> unknown}}";
+        let expanded = expand_partials(source, test_dir.root());
+        assert_eq!(
+            expanded,
+            "# {{name}}\n\nTracked in {{issue}}.\n{{> unknown}}"
+        );
+        assert_eq!(
+            get_placeholders(&expanded).into_iter().collect::<Vec<_>>(),
+            vec!["issue", "name"]
+        );
+
+        let mut handlebars = Handlebars::new();
+        register_partials(&mut handlebars, test_dir.root()).unwrap();
+        assert!(handlebars.has_template("footer"));
+        assert!(!handlebars.has_template("notes"));
+        handlebars
+            .register_template_string("test", "# {{name}}\n\n{{> footer}}")
+            .unwrap();
+        assert_eq!(
+            handlebars
+                .render("test", &json!({"name": "v16.0.0", "issue": "#3"}))
+                .unwrap(),
+            "# v16.0.0\n\nTracked in #3."
+        );
+    }
+}
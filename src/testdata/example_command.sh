@@ -1,3 +1,4 @@
+# Fees: 0.00000001 tEVMOS
 evmosd tx gov submit-legacy-proposal software-upgrade v14.0.0 \
 --title "Evmos Testnet v14.0.0 Upgrade" \
 --upgrade-height 60 \
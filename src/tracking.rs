@@ -0,0 +1,231 @@
+use crate::errors::TrackingError;
+use crate::manifest::hash_file;
+use octocrab::Octocrab;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// The maximum size of a file in bytes, whose contents are included in the comment on the tracking issue.
+/// Larger files are only listed with their hash to stay within the size limit of GitHub comments.
+const MAX_INLINE_SIZE: usize = 20_000;
+
+/// Describes the repository, in which the upgrades are tracked with one issue per version.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TrackingRepo {
+    /// The owner of the repository.
+    pub owner: String,
+    /// The name of the repository.
+    pub repo: String,
+    /// The labels, which are added to created tracking issues.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// The number of the milestone, which created tracking issues are associated with (optional).
+    #[serde(default)]
+    pub milestone: Option<u64>,
+}
+
+/// Represents the issue, in which the upgrade to a version is tracked.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct TrackingIssue {
+    /// The number of the issue.
+    pub number: u64,
+    /// The link to the issue.
+    pub url: String,
+}
+
+/// Returns the title of the tracking issue for the given version, e.g. `v16.0.0 upgrade`.
+pub fn get_issue_title(version: &str) -> String {
+    format!("{} upgrade", version)
+}
+
+/// Returns the tracking issue from the given issues of the repository, if any.
+/// Pull requests are returned by the issues API as well and are skipped.
+fn find_issue(issues: &Value, title: &str) -> Option<TrackingIssue> {
+    issues
+        .as_array()?
+        .iter()
+        .filter(|issue| issue["pull_request"].is_null())
+        .find(|issue| issue["title"].as_str() == Some(title))
+        .and_then(|issue| {
+            Some(TrackingIssue {
+                number: issue["number"].as_u64()?,
+                url: issue["html_url"].as_str()?.to_string(),
+            })
+        })
+}
+
+/// Looks up the tracking issue of the given version in the given repository
+/// and creates it, if it does not exist yet.
+pub async fn get_or_create_issue(
+    instance: &Octocrab,
+    repo: &TrackingRepo,
+    version: &str,
+) -> Result<TrackingIssue, TrackingError> {
+    let route = format!("/repos/{}/{}/issues", repo.owner, repo.repo);
+    let title = get_issue_title(version);
+
+    let issues: Value = instance
+        .get(
+            &route,
+            Some(&json!({"state": "all", "per_page": 100, "sort": "created"})),
+        )
+        .await?;
+    if let Some(issue) = find_issue(&issues, &title) {
+        return Ok(issue);
+    }
+
+    let issue: Value = instance
+        .post(
+            &route,
+            Some(&json!({
+                "title": title,
+                "body": format!("Tracks the preparation of the {} upgrade.", version),
+                "labels": repo.labels,
+                "milestone": repo.milestone,
+            })),
+        )
+        .await?;
+
+    find_issue(&json!([issue]), &title).ok_or(TrackingError::MissingIssue(title))
+}
+
+/// Renders the comment, which lists the given generated files with their hashes
+/// and includes their contents in collapsible sections.
+pub fn render_artifacts_comment(dir: &Path, files: &[String]) -> Result<String, TrackingError> {
+    let mut comment = "Generated artifacts:\n".to_string();
+    for file in files {
+        let path = dir.join(file);
+        comment.push_str(&format!(
+            "\n- `{}` (sha256 `{}`)\n",
+            file,
+            hash_file(&path)?
+        ));
+
+        let contents = std::fs::read_to_string(&path)?;
+        if contents.len() <= MAX_INLINE_SIZE {
+            comment.push_str(&format!(
+                "\n<details><summary>{}</summary>\n\n```\n{}\n```\n\n</details>\n",
+                file,
+                contents.trim_end()
+            ));
+        }
+    }
+
+    Ok(comment)
+}
+
+/// Posts the given generated files as a comment on the given tracking issue.
+pub async fn post_artifacts(
+    instance: &Octocrab,
+    repo: &TrackingRepo,
+    issue: &TrackingIssue,
+    files: &[String],
+) -> Result<(), TrackingError> {
+    let body = render_artifacts_comment(Path::new("."), files)?;
+    let _: Value = instance
+        .post(
+            format!(
+                "/repos/{}/{}/issues/{}/comments",
+                repo.owner, repo.repo, issue.number
+            ),
+            Some(&json!({ "body": body })),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use test_dir::{DirBuilder, TestDir};
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn get_repo() -> TrackingRepo {
+        TrackingRepo {
+            owner: "evmos".to_string(),
+            repo: "upgrades".to_string(),
+            labels: vec!["upgrade".to_string()],
+            milestone: None,
+        }
+    }
+
+    fn setup_octocrab(uri: &str) -> Octocrab {
+        Octocrab::builder().base_uri(uri).unwrap().build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_or_create_issue() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/evmos/upgrades/issues"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {
+                    "number": 3,
+                    "title": "v16.0.0 upgrade",
+                    "html_url": "https://github.com/evmos/upgrades/pull/3",
+                    "pull_request": {"url": "https://api.github.com/repos/evmos/upgrades/pulls/3"}
+                },
+                {
+                    "number": 2,
+                    "title": "v16.0.0 upgrade",
+                    "html_url": "https://github.com/evmos/upgrades/issues/2"
+                }
+            ])))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/evmos/upgrades/issues"))
+            .and(body_partial_json(
+                json!({"title": "v17.0.0 upgrade", "labels": ["upgrade"]}),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "number": 4,
+                "title": "v17.0.0 upgrade",
+                "html_url": "https://github.com/evmos/upgrades/issues/4"
+            })))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        let instance = setup_octocrab(&mock_server.uri());
+        let existing = get_or_create_issue(&instance, &get_repo(), "v16.0.0")
+            .await
+            .expect("failed to get issue");
+        assert_eq!(existing.number, 2);
+
+        let created = get_or_create_issue(&instance, &get_repo(), "v17.0.0")
+            .await
+            .expect("failed to create issue");
+        assert_eq!(
+            created,
+            TrackingIssue {
+                number: 4,
+                url: "https://github.com/evmos/upgrades/issues/4".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_render_artifacts_comment() {
+        let test_dir = TestDir::temp();
+        fs::write(test_dir.root().join("proposal.md"), "# Description\n").unwrap();
+        fs::write(
+            test_dir.root().join("large.json"),
+            "x".repeat(MAX_INLINE_SIZE + 1),
+        )
+        .unwrap();
+
+        let comment = render_artifacts_comment(
+            test_dir.root(),
+            &["proposal.md".to_string(), "large.json".to_string()],
+        )
+        .unwrap();
+        assert!(comment.contains("<summary>proposal.md</summary>\n\n```\n# Description\n```"));
+        assert!(comment.contains("- `large.json` (sha256 `"));
+        assert!(!comment.contains("<summary>large.json</summary>"));
+    }
+}
@@ -0,0 +1,372 @@
+use crate::account::{Account, PubKey, ETH_SECP256K1_PUB_KEY};
+use crate::errors::MessageError;
+use crate::gov::MSG_SOFTWARE_UPGRADE;
+use crate::grpc::{MsgSoftwareUpgrade, Plan, ProtoCoin};
+use crate::messages::{get_message_type, V1Proposal};
+use crate::sequence::AccountState;
+use prost::Message;
+use prost_types::Any;
+use serde_json::{json, Value};
+
+/// The type URL of the message to submit a gov v1 proposal.
+pub const MSG_SUBMIT_PROPOSAL: &str = "/cosmos.gov.v1.MsgSubmitProposal";
+
+/// The default gas limit of the exported unsigned transaction, since the gas cannot be simulated
+/// without a signer.
+pub const DEFAULT_GAS_LIMIT: u64 = 2_000_000;
+
+/// The sign mode, in which the signer signs the serialized transaction body and auth info.
+const SIGN_MODE_DIRECT: i32 = 1;
+
+/// Contains the fee that is paid for the submission.
+pub struct Fee<'a> {
+    pub amount: &'a str,
+    pub denom: &'a str,
+    pub gas_limit: u64,
+    /// The account that pays the fee from its fee allowance to the signer.
+    pub granter: Option<&'a str>,
+}
+
+/// Builds the unsigned transaction to submit the given proposal in the Proto JSON format,
+/// which is also returned by `tx ... --generate-only`. The Amino JSON format is not supported.
+///
+/// The transaction contains no signer infos and signatures, so that it can be built
+/// without access to the local keyring.
+pub fn build_unsigned_tx(
+    proposal: &V1Proposal,
+    proposer: &str,
+    fee_amount: &str,
+    fee_denom: &str,
+    gas_limit: u64,
+    fee_granter: Option<&str>,
+) -> Result<Value, MessageError> {
+    Ok(json!({
+        "body": {
+            "messages": [{
+                "@type": MSG_SUBMIT_PROPOSAL,
+                "messages": proposal.messages,
+                "initial_deposit": proposal.deposit_coins()?,
+                "proposer": proposer,
+                "metadata": proposal.metadata,
+                "title": proposal.title,
+                "summary": proposal.summary,
+            }],
+            "memo": "",
+            "timeout_height": "0",
+            "extension_options": [],
+            "non_critical_extension_options": [],
+        },
+        "auth_info": {
+            "signer_infos": [],
+            "fee": {
+                "amount": [{"denom": fee_denom, "amount": fee_amount}],
+                "gas_limit": gas_limit.to_string(),
+                "payer": "",
+                "granter": fee_granter.unwrap_or_default(),
+            },
+        },
+        "signatures": [],
+    }))
+}
+
+/// Builds and signs the transaction to submit the given proposal with the given account,
+/// whose account number and sequence are given in the account state.
+/// Returns the protobuf encoded transaction, which can be broadcast directly.
+///
+/// Only the software upgrade message can be encoded, since the other proposal messages
+/// are only available in their JSON representation.
+pub fn build_signed_tx(
+    proposal: &V1Proposal,
+    account: &Account,
+    state: &AccountState,
+    chain_id: &str,
+    fee: &Fee,
+) -> Result<Vec<u8>, MessageError> {
+    let msg = MsgSubmitProposal {
+        messages: proposal
+            .messages
+            .iter()
+            .map(encode_message)
+            .collect::<Result<_, _>>()?,
+        initial_deposit: proposal
+            .deposit_coins()?
+            .into_iter()
+            .map(|coin| ProtoCoin {
+                denom: coin.denom,
+                amount: coin.amount,
+            })
+            .collect(),
+        proposer: account.address.clone(),
+        metadata: proposal.metadata.clone(),
+        title: proposal.title.clone(),
+        summary: proposal.summary.clone(),
+    };
+    let body_bytes = TxBody {
+        messages: vec![Any {
+            type_url: MSG_SUBMIT_PROPOSAL.to_string(),
+            value: msg.encode_to_vec(),
+        }],
+        memo: String::new(),
+    }
+    .encode_to_vec();
+    let auth_info_bytes = AuthInfo {
+        signer_infos: vec![SignerInfo {
+            public_key: Some(Any {
+                type_url: ETH_SECP256K1_PUB_KEY.to_string(),
+                value: PubKey {
+                    key: account.public_key(),
+                }
+                .encode_to_vec(),
+            }),
+            mode_info: Some(ModeInfo {
+                single: Some(Single {
+                    mode: SIGN_MODE_DIRECT,
+                }),
+            }),
+            sequence: state.sequence,
+        }],
+        fee: Some(ProtoFee {
+            amount: vec![ProtoCoin {
+                denom: fee.denom.to_string(),
+                amount: fee.amount.to_string(),
+            }],
+            gas_limit: fee.gas_limit,
+            payer: String::new(),
+            granter: fee.granter.unwrap_or_default().to_string(),
+        }),
+    }
+    .encode_to_vec();
+
+    let sign_doc = SignDoc {
+        body_bytes: body_bytes.clone(),
+        auth_info_bytes: auth_info_bytes.clone(),
+        chain_id: chain_id.to_string(),
+        account_number: state.account_number,
+    };
+    let signature = account.sign(&sign_doc.encode_to_vec())?;
+
+    Ok(TxRaw {
+        body_bytes,
+        auth_info_bytes,
+        signatures: vec![signature],
+    }
+    .encode_to_vec())
+}
+
+/// Encodes the given proposal message from its JSON representation.
+fn encode_message(message: &Value) -> Result<Any, MessageError> {
+    match get_message_type(message) {
+        Some(MSG_SOFTWARE_UPGRADE) => {
+            let plan = &message["plan"];
+            let msg = MsgSoftwareUpgrade {
+                authority: message["authority"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                plan: Some(Plan {
+                    name: plan["name"].as_str().unwrap_or_default().to_string(),
+                    height: plan["height"]
+                        .as_str()
+                        .and_then(|height| height.parse().ok())
+                        .ok_or_else(|| MessageError::Encode("plan height".to_string()))?,
+                    info: plan["info"].as_str().unwrap_or_default().to_string(),
+                }),
+            };
+
+            Ok(Any {
+                type_url: MSG_SOFTWARE_UPGRADE.to_string(),
+                value: msg.encode_to_vec(),
+            })
+        }
+        msg_type => Err(MessageError::Encode(
+            msg_type.unwrap_or("untyped message").to_string(),
+        )),
+    }
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct MsgSubmitProposal {
+    #[prost(message, repeated, tag = "1")]
+    messages: Vec<Any>,
+    #[prost(message, repeated, tag = "2")]
+    initial_deposit: Vec<ProtoCoin>,
+    #[prost(string, tag = "3")]
+    proposer: String,
+    #[prost(string, tag = "4")]
+    metadata: String,
+    #[prost(string, tag = "5")]
+    title: String,
+    #[prost(string, tag = "6")]
+    summary: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct TxBody {
+    #[prost(message, repeated, tag = "1")]
+    messages: Vec<Any>,
+    #[prost(string, tag = "2")]
+    memo: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct AuthInfo {
+    #[prost(message, repeated, tag = "1")]
+    signer_infos: Vec<SignerInfo>,
+    #[prost(message, optional, tag = "2")]
+    fee: Option<ProtoFee>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct SignerInfo {
+    #[prost(message, optional, tag = "1")]
+    public_key: Option<Any>,
+    #[prost(message, optional, tag = "2")]
+    mode_info: Option<ModeInfo>,
+    #[prost(uint64, tag = "3")]
+    sequence: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ModeInfo {
+    #[prost(message, optional, tag = "1")]
+    single: Option<Single>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct Single {
+    #[prost(int32, tag = "1")]
+    mode: i32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ProtoFee {
+    #[prost(message, repeated, tag = "1")]
+    amount: Vec<ProtoCoin>,
+    #[prost(uint64, tag = "2")]
+    gas_limit: u64,
+    #[prost(string, tag = "3")]
+    payer: String,
+    #[prost(string, tag = "4")]
+    granter: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct SignDoc {
+    #[prost(bytes = "vec", tag = "1")]
+    body_bytes: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    auth_info_bytes: Vec<u8>,
+    #[prost(string, tag = "3")]
+    chain_id: String,
+    #[prost(uint64, tag = "4")]
+    account_number: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct TxRaw {
+    #[prost(bytes = "vec", tag = "1")]
+    body_bytes: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    auth_info_bytes: Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    signatures: Vec<Vec<u8>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_unsigned_tx() {
+        let proposal = V1Proposal {
+            messages: vec![json!({"@type": MSG_SOFTWARE_UPGRADE, "authority": "evmos1gov"})],
+            metadata: "https://commonwealth.im/evmos/discussion/1".to_string(),
+            deposit: "10000000000aevmos".to_string(),
+            title: "Evmos Mainnet v16.0.0 Upgrade".to_string(),
+            summary: "# Description".to_string(),
+        };
+
+        let tx = build_unsigned_tx(
+            &proposal,
+            "evmos1proposer",
+            "10000000000",
+            "aevmos",
+            DEFAULT_GAS_LIMIT,
+            Some("evmos1granter"),
+        )
+        .expect("failed to build unsigned transaction");
+
+        let msg = &tx["body"]["messages"][0];
+        assert_eq!(msg["@type"], MSG_SUBMIT_PROPOSAL);
+        assert_eq!(msg["proposer"], "evmos1proposer");
+        assert_eq!(msg["messages"][0]["@type"], MSG_SOFTWARE_UPGRADE);
+        assert_eq!(msg["title"], "Evmos Mainnet v16.0.0 Upgrade");
+        assert_eq!(
+            msg["initial_deposit"],
+            json!([{"denom": "aevmos", "amount": "10000000000"}])
+        );
+        assert_eq!(
+            tx["auth_info"]["fee"],
+            json!({
+                "amount": [{"denom": "aevmos", "amount": "10000000000"}],
+                "gas_limit": "2000000",
+                "payer": "",
+                "granter": "evmos1granter",
+            })
+        );
+        assert_eq!(tx["signatures"], json!([]));
+    }
+
+    #[test]
+    fn test_build_signed_tx() {
+        let account = Account::from_private_key(&"ab".repeat(32), "evmos").unwrap();
+        let mut proposal = V1Proposal {
+            messages: vec![json!({
+                "@type": MSG_SOFTWARE_UPGRADE,
+                "authority": "evmos10d07y265gmmuvt4z0w9aw880jnsr700jcrztvm",
+                "plan": {"name": "v16.0.0", "height": "1234", "info": "{}"},
+            })],
+            metadata: "https://commonwealth.im/evmos/discussion/1".to_string(),
+            deposit: "10000000000aevmos".to_string(),
+            title: "Evmos Mainnet v16.0.0 Upgrade".to_string(),
+            summary: "# Description".to_string(),
+        };
+        let state = AccountState {
+            account_number: 7,
+            sequence: 12,
+            pub_key: None,
+        };
+        let fee = Fee {
+            amount: "10000000000",
+            denom: "aevmos",
+            gas_limit: DEFAULT_GAS_LIMIT,
+            granter: None,
+        };
+
+        let tx_bytes = build_signed_tx(&proposal, &account, &state, "evmos_9001-2", &fee)
+            .expect("failed to build signed transaction");
+        let tx = TxRaw::decode(tx_bytes.as_slice()).expect("failed to decode transaction");
+        assert_eq!(tx.signatures.len(), 1);
+        assert_eq!(tx.signatures[0].len(), 65);
+
+        let body = TxBody::decode(tx.body_bytes.as_slice()).unwrap();
+        assert_eq!(body.messages[0].type_url, MSG_SUBMIT_PROPOSAL);
+        let msg = MsgSubmitProposal::decode(body.messages[0].value.as_slice()).unwrap();
+        assert_eq!(msg.proposer, account.address);
+        assert_eq!(msg.initial_deposit[0].amount, "10000000000");
+        let upgrade = MsgSoftwareUpgrade::decode(msg.messages[0].value.as_slice()).unwrap();
+        assert_eq!(upgrade.plan.unwrap().height, 1234);
+
+        let auth_info = AuthInfo::decode(tx.auth_info_bytes.as_slice()).unwrap();
+        assert_eq!(auth_info.signer_infos[0].sequence, 12);
+        assert_eq!(auth_info.fee.unwrap().gas_limit, DEFAULT_GAS_LIMIT);
+
+        proposal
+            .messages
+            .push(json!({"@type": "/cosmos.bank.v1beta1.MsgSend"}));
+        assert!(matches!(
+            build_signed_tx(&proposal, &account, &state, "evmos_9001-2", &fee),
+            Err(MessageError::Encode(_))
+        ));
+    }
+}
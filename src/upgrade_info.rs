@@ -0,0 +1,236 @@
+use crate::chain::ChainConfig;
+use crate::errors::UploadError;
+use crate::helper::UpgradeHelper;
+use crate::upload::upload_upgrade_info;
+use crate::utils::write_content_to_file;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// The length of the upgrade info, above which the info is considered too large for the upgrade plan.
+/// Longer values bloat the proposal and are truncated or rejected by some explorers and wallets.
+pub const MAX_INFO_LENGTH: usize = 2048;
+
+/// The length of a single binary URL, above which some clients fail to download the binary.
+pub const MAX_URL_LENGTH: usize = 512;
+
+/// The contents of the `info` field of the upgrade plan.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PlanInfo {
+    /// The binaries JSON with the checksums of the released binaries,
+    /// which Cosmovisor uses to download the new binary automatically.
+    #[default]
+    Binaries,
+    /// The URL of a hosted upgrade info file.
+    Url { url: String },
+    /// The link to the release notes of the target version.
+    ReleaseNotes,
+    /// A custom text.
+    Custom { text: String },
+}
+
+impl PlanInfo {
+    /// Returns whether Cosmovisor can download the new binary automatically with the plan info,
+    /// which requires the binaries JSON or a URL to a hosted upgrade info file.
+    pub fn supports_auto_download(&self) -> bool {
+        matches!(self, PlanInfo::Binaries | PlanInfo::Url { .. })
+    }
+
+    /// Returns the description of the plan info contents, which is rendered into the proposal.
+    pub fn describe(&self, target_version: &str) -> String {
+        match self {
+            PlanInfo::Binaries => "the released binaries with their checksums, so that Cosmovisor can download the new binary automatically".to_string(),
+            PlanInfo::Url { url } => format!("a link to the upgrade info hosted at {}", url),
+            PlanInfo::ReleaseNotes => format!(
+                "a link to the [release notes]({})",
+                get_release_notes_url(target_version)
+            ),
+            PlanInfo::Custom { text } => format!("the following text: `{}`", text),
+        }
+    }
+}
+
+/// Returns the URL of the release notes of the given version on GitHub.
+pub fn get_release_notes_url(version: &str) -> String {
+    format!("https://github.com/evmos/evmos/releases/tag/{}", version)
+}
+
+/// Returns the name of the file containing the full upgrade info, which can be hosted
+/// and linked from the upgrade plan instead of the binaries JSON.
+pub fn get_upgrade_info_file_name(helper: &UpgradeHelper) -> String {
+    format!("upgrade-info-{}.json", helper.target_version)
+}
+
+/// Checks the size of the given upgrade info and returns a warning for each exceeded threshold.
+pub fn check_upgrade_info(info: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if info.len() > MAX_INFO_LENGTH {
+        warnings.push(format!(
+            "upgrade info has {} characters, which exceeds the recommended maximum of {}",
+            info.len(),
+            MAX_INFO_LENGTH
+        ));
+    }
+
+    let binaries = serde_json::from_str::<Value>(info)
+        .ok()
+        .and_then(|info| info["binaries"].as_object().cloned())
+        .unwrap_or_default();
+    for (platform, url) in binaries.iter() {
+        let length = url.as_str().map_or(0, str::len);
+        if length > MAX_URL_LENGTH {
+            warnings.push(format!(
+                "URL of the {} binary has {} characters, which exceeds the recommended maximum of {}",
+                platform, length, MAX_URL_LENGTH
+            ));
+        }
+    }
+
+    warnings
+}
+
+/// Returns the shortened upgrade info, which links to the hosted upgrade info file.
+pub fn get_shortened_info(helper: &UpgradeHelper, info: &str, base_url: &str) -> String {
+    get_info_url(
+        &format!(
+            "{}/{}",
+            base_url.trim_end_matches('/'),
+            get_upgrade_info_file_name(helper)
+        ),
+        info,
+    )
+}
+
+/// Returns the given URL of the hosted upgrade info with the checksum of its contents,
+/// so that Cosmovisor verifies the downloaded file.
+fn get_info_url(url: &str, info: &str) -> String {
+    format!(
+        "{}?checksum=sha256:{}",
+        url,
+        hex::encode(Sha256::digest(info.as_bytes()))
+    )
+}
+
+/// Returns the upgrade info to use in the upgrade plan after checking its size.
+///
+/// If a host for the upgrade info is configured, the full info is uploaded and the plan links to it.
+/// Otherwise, if the given info exceeds the size thresholds and a URL to host the upgrade info is configured,
+/// the full info is written to the upgrade info file and the shortened info linking to it is returned.
+/// If neither is configured, the exceeded thresholds are printed as warnings and the full info is returned.
+pub async fn get_upgrade_info(
+    helper: &UpgradeHelper,
+    info: String,
+    chain_config: &ChainConfig,
+) -> Result<String, UploadError> {
+    if let Some(host) = &chain_config.upgrade_info_host {
+        let file_name = get_upgrade_info_file_name(helper);
+        write_content_to_file(&info, &file_name)?;
+        let url = upload_upgrade_info(host, Path::new(&file_name), &helper.target_version).await?;
+        println!("Uploaded upgrade info to {}", url);
+
+        return Ok(get_info_url(&url, &info));
+    }
+
+    let warnings = check_upgrade_info(&info);
+    if warnings.is_empty() {
+        return Ok(info);
+    }
+
+    let Some(base_url) = chain_config.upgrade_info_url.as_deref() else {
+        for warning in warnings {
+            println!("WARNING: {}", warning);
+        }
+        println!("WARNING: configure `upgrade_info_url` or `upgrade_info_host` in the chain configuration to link to a hosted upgrade info file instead");
+        return Ok(info);
+    };
+
+    let file_name = get_upgrade_info_file_name(helper);
+    write_content_to_file(&info, &file_name)?;
+    let shortened = get_shortened_info(helper, &info, base_url);
+    println!(
+        "Upgrade info exceeds the size limits; wrote the full info to {}, which has to be hosted at {}",
+        file_name,
+        shortened.split('?').next().unwrap_or_default()
+    );
+
+    Ok(shortened)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Network;
+    use chrono::Utc;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_parse_plan_info() {
+        let plan_info: PlanInfo =
+            serde_json::from_value(json!({"kind": "url", "url": "https://example.com/info.json"}))
+                .expect("failed to parse plan info");
+        assert_eq!(
+            plan_info,
+            PlanInfo::Url {
+                url: "https://example.com/info.json".to_string()
+            }
+        );
+        assert!(plan_info.supports_auto_download());
+
+        let plan_info: PlanInfo = serde_json::from_value(json!({"kind": "release_notes"}))
+            .expect("failed to parse plan info");
+        assert!(!plan_info.supports_auto_download());
+        assert_eq!(
+            plan_info.describe("v16.0.0"),
+            "a link to the [release notes](https://github.com/evmos/evmos/releases/tag/v16.0.0)"
+        );
+    }
+
+    /// Returns the upgrade info with the given length of the binary URLs.
+    fn get_info(url_length: usize) -> String {
+        let url = format!("https://example.com/{}", "a".repeat(url_length - 20));
+        json!({"binaries": {"linux/amd64": url, "linux/arm64": url, "darwin/amd64": url, "darwin/arm64": url}})
+            .to_string()
+    }
+
+    #[test]
+    fn test_check_upgrade_info() {
+        assert!(check_upgrade_info(&get_info(200)).is_empty());
+
+        let warnings = check_upgrade_info(&get_info(600));
+        assert_eq!(warnings.len(), 5);
+        assert!(warnings[0].starts_with("upgrade info has 2"));
+        assert_eq!(
+            warnings[1],
+            "URL of the darwin/amd64 binary has 600 characters, which exceeds the recommended maximum of 512"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_shortened_info() {
+        let helper = UpgradeHelper::new(
+            PathBuf::from("./.evmosd"),
+            Network::Mainnet,
+            "v15.0.0",
+            "v16.0.0",
+            Utc::now(),
+            19_000_000,
+            "",
+        );
+
+        assert_eq!(
+            get_shortened_info(&helper, "{}", "https://example.com/upgrades/"),
+            "https://example.com/upgrades/upgrade-info-v16.0.0.json\
+             ?checksum=sha256:44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+        );
+        assert_eq!(
+            get_upgrade_info(&helper, get_info(200), &ChainConfig::default())
+                .await
+                .expect("failed to get upgrade info"),
+            get_info(200)
+        );
+    }
+}
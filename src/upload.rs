@@ -0,0 +1,244 @@
+use crate::errors::UploadError;
+use crate::http::get_client;
+use octocrab::Octocrab;
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process;
+
+/// The environment variable containing the GitHub token, which is used to create gists and upload release assets.
+pub const GITHUB_TOKEN_VAR: &str = "GITHUB_TOKEN";
+
+/// Describes where the upgrade info file is uploaded to, so that the upgrade plan can link to it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum UpgradeInfoHost {
+    /// The file is uploaded to the given S3 bucket with the AWS CLI (`aws s3 cp`),
+    /// which uses the credentials configured for the CLI. The bucket has to allow public reads.
+    S3 {
+        /// The name of the bucket.
+        bucket: String,
+        /// The prefix of the object key, e.g. `upgrades/`.
+        #[serde(default)]
+        prefix: String,
+    },
+    /// The file is uploaded as a secret gist of the user owning the GitHub token.
+    Gist,
+    /// The file is uploaded as an asset of the release of the target version in the given repository.
+    ReleaseAsset {
+        /// The owner of the repository.
+        owner: String,
+        /// The name of the repository.
+        repo: String,
+    },
+}
+
+/// Uploads the upgrade info file at the given path to the given host and returns its public URL.
+pub async fn upload_upgrade_info(
+    host: &UpgradeInfoHost,
+    path: &Path,
+    version: &str,
+) -> Result<String, UploadError> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let contents = std::fs::read_to_string(path)?;
+
+    match host {
+        UpgradeInfoHost::S3 { bucket, prefix } => {
+            let (tool, args, url) = get_s3_command(bucket, prefix, path, &name);
+            run_tool(tool, &args)?;
+            Ok(url)
+        }
+        UpgradeInfoHost::Gist => {
+            let gist = get_github_client()?
+                .gists()
+                .create()
+                .description(format!("Upgrade info for {}", version))
+                .public(false)
+                .file(&name, contents)
+                .send()
+                .await?;
+
+            gist.files
+                .get(&name)
+                .map(|file| file.raw_url.to_string())
+                .ok_or(UploadError::MissingUrl(name))
+        }
+        UpgradeInfoHost::ReleaseAsset { owner, repo } => {
+            let release = get_github_client()?
+                .repos(owner, repo)
+                .releases()
+                .get_by_tag(version)
+                .await?;
+
+            upload_release_asset(&release.upload_url, &get_github_token()?, &name, contents).await
+        }
+    }
+}
+
+/// Returns the tool and arguments to upload the file at the given path to the S3 bucket
+/// together with the public URL of the uploaded object.
+fn get_s3_command(
+    bucket: &str,
+    prefix: &str,
+    path: &Path,
+    name: &str,
+) -> (&'static str, Vec<String>, String) {
+    let key = format!("{}{}", prefix, name);
+
+    (
+        "aws",
+        vec![
+            "s3".to_string(),
+            "cp".to_string(),
+            path.display().to_string(),
+            format!("s3://{}/{}", bucket, key),
+            "--content-type".to_string(),
+            "application/json".to_string(),
+        ],
+        format!("https://{}.s3.amazonaws.com/{}", bucket, key),
+    )
+}
+
+/// Uploads the given contents as a release asset with the given name using the upload URL of the release,
+/// e.g. `https://uploads.github.com/repos/evmos/evmos/releases/1/assets{?name,label}`.
+/// Returns the download URL of the uploaded asset.
+async fn upload_release_asset(
+    upload_url: &str,
+    token: &str,
+    name: &str,
+    contents: String,
+) -> Result<String, UploadError> {
+    let url = upload_url.split('{').next().unwrap_or(upload_url);
+    let response = get_client()?
+        .post(url)
+        .query(&[("name", name)])
+        .header(AUTHORIZATION, format!("Bearer {}", token))
+        .header(ACCEPT, "application/vnd.github+json")
+        .header(CONTENT_TYPE, "application/json")
+        .body(contents)
+        .send()
+        .await?;
+
+    let status = response.status();
+    let body: serde_json::Value = response.json().await?;
+    if !status.is_success() {
+        return Err(UploadError::Upload(
+            "GitHub".to_string(),
+            body["message"].as_str().unwrap_or_default().to_string(),
+        ));
+    }
+
+    body["browser_download_url"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or(UploadError::MissingUrl(name.to_string()))
+}
+
+/// Returns the GitHub token from the environment.
+fn get_github_token() -> Result<String, UploadError> {
+    std::env::var(GITHUB_TOKEN_VAR).map_err(|_| UploadError::MissingToken(GITHUB_TOKEN_VAR))
+}
+
+/// Returns a GitHub client, which is authenticated with the token from the environment.
+fn get_github_client() -> Result<Octocrab, UploadError> {
+    Ok(Octocrab::builder()
+        .personal_token(get_github_token()?)
+        .build()?)
+}
+
+/// Runs the given tool and returns an error containing its output if it does not succeed.
+fn run_tool(tool: &str, args: &[String]) -> Result<(), UploadError> {
+    let output = process::Command::new(tool).args(args).output()?;
+    if !output.status.success() {
+        return Err(UploadError::Upload(
+            tool.to_string(),
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::{
+        matchers::{header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    #[test]
+    fn test_get_s3_command() {
+        let (tool, args, url) = get_s3_command(
+            "evmos-upgrades",
+            "mainnet/",
+            Path::new("upgrade-info-v16.0.0.json"),
+            "upgrade-info-v16.0.0.json",
+        );
+        assert_eq!(tool, "aws");
+        assert_eq!(
+            args[2..4],
+            [
+                "upgrade-info-v16.0.0.json",
+                "s3://evmos-upgrades/mainnet/upgrade-info-v16.0.0.json"
+            ]
+        );
+        assert_eq!(
+            url,
+            "https://evmos-upgrades.s3.amazonaws.com/mainnet/upgrade-info-v16.0.0.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_release_asset() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/evmos/evmos/releases/1/assets"))
+            .and(query_param("name", "upgrade-info-v16.0.0.json"))
+            .and(header("authorization", "Bearer TOKEN"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "browser_download_url": "https://github.com/evmos/evmos/releases/download/v16.0.0/upgrade-info-v16.0.0.json"
+            })))
+            .mount(&mock_server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/evmos/evmos/releases/2/assets"))
+            .respond_with(
+                ResponseTemplate::new(422).set_body_json(json!({"message": "Validation Failed"})),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let upload_url = format!(
+            "{}/repos/evmos/evmos/releases/1/assets{{?name,label}}",
+            mock_server.uri()
+        );
+        let url = upload_release_asset(
+            &upload_url,
+            "TOKEN",
+            "upgrade-info-v16.0.0.json",
+            "{}".to_string(),
+        )
+        .await
+        .expect("failed to upload release asset");
+        assert_eq!(
+            url,
+            "https://github.com/evmos/evmos/releases/download/v16.0.0/upgrade-info-v16.0.0.json"
+        );
+
+        let res = upload_release_asset(
+            &upload_url.replace("/1/", "/2/"),
+            "TOKEN",
+            "upgrade-info-v16.0.0.json",
+            "{}".to_string(),
+        )
+        .await;
+        assert!(
+            matches!(res, Err(UploadError::Upload(_, ref message)) if message == "Validation Failed")
+        );
+    }
+}
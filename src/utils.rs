@@ -1,9 +1,70 @@
+use crate::errors::DeadlineError;
+use crate::profile;
+use std::future::Future;
+use std::path::Path;
+use std::time::Duration;
+
+/// The environment variable containing the deadline of a single step in seconds,
+/// e.g. querying the release or creating the summary.
+const STEP_TIMEOUT_VAR: &str = "PROPOSER_STEP_TIMEOUT";
+
+/// The default deadline of a single step in seconds.
+const DEFAULT_STEP_TIMEOUT_SECONDS: u64 = 300;
+
 /// Wrapper method to write the proposal contents to a file.
 pub fn write_content_to_file(
     proposal: &str,
     proposal_file_name: &str,
 ) -> Result<(), std::io::Error> {
-    std::fs::write(proposal_file_name, proposal)
+    write_atomically(Path::new(proposal_file_name), proposal.as_bytes())
+}
+
+/// Writes the contents to a temporary file next to the given path, which is then renamed.
+/// This ensures that no partially written files are left behind if the process is interrupted.
+pub fn write_atomically(path: &Path, contents: &[u8]) -> Result<(), std::io::Error> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    std::fs::write(&tmp_path, contents)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+/// Returns the deadline of a single step, which can be configured in the environment.
+pub fn get_step_deadline() -> Duration {
+    let seconds = std::env::var(STEP_TIMEOUT_VAR)
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .unwrap_or(DEFAULT_STEP_TIMEOUT_SECONDS);
+
+    Duration::from_secs(seconds)
+}
+
+/// Runs the given future and returns an error if it does not finish before the step deadline.
+pub async fn with_deadline<T>(
+    step: &'static str,
+    future: impl Future<Output = T>,
+) -> Result<T, DeadlineError> {
+    with_timeout(step, get_step_deadline(), future).await
+}
+
+/// Runs the given future and returns an error if it does not finish within the given duration.
+async fn with_timeout<T>(
+    step: &'static str,
+    deadline: Duration,
+    future: impl Future<Output = T>,
+) -> Result<T, DeadlineError> {
+    profile::timed(step, tokio::time::timeout(deadline, future))
+        .await
+        .map_err(|_| DeadlineError(step, deadline.as_secs()))
+}
+
+/// Parses a duration in the protobuf JSON format (e.g. `1209600s`).
+/// Returns None if the given string is not a valid duration.
+pub fn parse_proto_duration(duration: &str) -> Option<chrono::Duration> {
+    let seconds = duration.strip_suffix('s')?.parse::<f64>().ok()?;
+
+    Some(chrono::Duration::milliseconds((seconds * 1000.0) as i64))
 }
 
 #[cfg(test)]
@@ -30,4 +91,46 @@ mod tests {
         // Clean up
         std::fs::remove_file(proposal_file_name).expect("failed to remove file after test");
     }
+
+    #[test]
+    fn test_write_atomically() {
+        let test_dir = test_dir::TestDir::temp();
+        let path = test_dir::DirBuilder::path(&test_dir, "proposal.md");
+
+        utils::write_atomically(&path, b"test").expect("failed to write file");
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "test");
+        assert!(!path.with_file_name("proposal.md.tmp").exists());
+    }
+
+    #[tokio::test]
+    async fn test_with_deadline() {
+        let res = utils::with_deadline("test step", async { 1 }).await;
+        assert_eq!(res.unwrap(), 1);
+
+        let res = utils::with_timeout(
+            "test step",
+            std::time::Duration::from_millis(10),
+            std::future::pending::<()>(),
+        )
+        .await;
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Step 'test step' did not finish within 0 seconds"
+        );
+    }
+
+    #[test]
+    fn test_parse_proto_duration() {
+        assert_eq!(
+            utils::parse_proto_duration("432000s"),
+            Some(chrono::Duration::days(5))
+        );
+        assert_eq!(
+            utils::parse_proto_duration("1.5s"),
+            Some(chrono::Duration::milliseconds(1500))
+        );
+        assert_eq!(utils::parse_proto_duration("432000"), None);
+        assert_eq!(utils::parse_proto_duration("abcs"), None);
+    }
 }
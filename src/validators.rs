@@ -0,0 +1,334 @@
+use crate::errors::ValidatorsError;
+use crate::http::get_json;
+use crate::network::Network;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use url::Url;
+
+/// The REST endpoint for querying the validators of the staking module.
+const VALIDATORS_ENDPOINT: &str = "cosmos/staking/v1beta1/validators";
+
+/// The maximum number of validators to query, which covers the whole active set.
+const VALIDATORS_LIMIT: &str = "1000";
+
+/// The header of the exported CSV file.
+const CSV_HEADER: &str =
+    "moniker,operator_address,voting_power,voting_power_share,website,security_contact";
+
+/// Represents the response from the query for the validators of the staking module.
+#[derive(Serialize, Deserialize)]
+struct ValidatorsResponse {
+    validators: Vec<ValidatorResponse>,
+}
+
+/// Represents a validator as returned from the staking module.
+#[derive(Serialize, Deserialize)]
+struct ValidatorResponse {
+    operator_address: String,
+    tokens: String,
+    description: Description,
+}
+
+/// Represents the on-chain description of a validator.
+#[derive(Serialize, Deserialize)]
+struct Description {
+    #[serde(default)]
+    moniker: String,
+    #[serde(default)]
+    website: String,
+    #[serde(default)]
+    security_contact: String,
+}
+
+/// Contains the information of a bonded validator that is relevant for the upgrade outreach.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Validator {
+    /// The moniker of the validator.
+    pub moniker: String,
+    /// The operator address of the validator.
+    pub operator_address: String,
+    /// The bonded tokens of the validator, which determine its voting power.
+    pub voting_power: u128,
+    /// The share of the total bonded voting power in percent.
+    pub share: f64,
+    /// The website of the validator.
+    pub website: String,
+    /// The security contact of the validator.
+    pub security_contact: String,
+}
+
+/// Returns the bonded validators of the network, sorted by their voting power in descending order.
+pub async fn get_validators(base_url: &Url) -> Result<Vec<Validator>, ValidatorsError> {
+    let mut url = base_url.join(VALIDATORS_ENDPOINT)?;
+    url.query_pairs_mut()
+        .append_pair("status", "BOND_STATUS_BONDED")
+        .append_pair("pagination.limit", VALIDATORS_LIMIT);
+
+    let response: ValidatorsResponse = get_json(url).await?;
+
+    let mut validators = response
+        .validators
+        .into_iter()
+        .map(|validator| {
+            Ok((
+                validator.tokens.parse::<u128>()?,
+                validator.operator_address,
+                validator.description,
+            ))
+        })
+        .collect::<Result<Vec<_>, ValidatorsError>>()?;
+    validators.sort_by_key(|(tokens, _, _)| std::cmp::Reverse(*tokens));
+
+    let total: u128 = validators.iter().map(|(tokens, _, _)| tokens).sum();
+
+    Ok(validators
+        .into_iter()
+        .map(|(tokens, operator_address, description)| Validator {
+            moniker: description.moniker,
+            operator_address,
+            voting_power: tokens,
+            share: match total {
+                0 => 0.0,
+                _ => tokens as f64 / total as f64 * 100.0,
+            },
+            website: description.website,
+            security_contact: description.security_contact,
+        })
+        .collect())
+}
+
+/// Returns the CSV representation of the given validators.
+pub fn to_csv(validators: &[Validator]) -> String {
+    let mut csv = format!("{}\n", CSV_HEADER);
+    for validator in validators {
+        csv.push_str(&format!(
+            "{},{},{},{:.4},{},{}\n",
+            escape_csv_field(&validator.moniker),
+            escape_csv_field(&validator.operator_address),
+            validator.voting_power,
+            validator.share,
+            escape_csv_field(&validator.website),
+            escape_csv_field(&validator.security_contact),
+        ));
+    }
+
+    csv
+}
+
+/// Returns the default name of the exported CSV file for the given network.
+pub fn get_csv_file_name(network: Network) -> String {
+    format!("validators-{}.csv", network)
+}
+
+/// Returns the validators that have not voted, which are identified by the account address
+/// that corresponds to their operator address. The order of the given validators is preserved.
+pub fn get_missing_voters(
+    validators: &[Validator],
+    voters: &[String],
+    prefix: &str,
+) -> Result<Vec<Validator>, ValidatorsError> {
+    let voters: HashSet<&str> = voters.iter().map(String::as_str).collect();
+
+    let mut missing = Vec::new();
+    for validator in validators {
+        let account_address = get_account_address(&validator.operator_address, prefix)?;
+        if !voters.contains(account_address.as_str()) {
+            missing.push(validator.clone());
+        }
+    }
+
+    Ok(missing)
+}
+
+/// Renders the list of the given number of validators with the highest voting power,
+/// which have not voted on the proposal, to be used for targeted reminders.
+pub fn render_nag_list(proposal_id: u64, missing: &[Validator], top: usize) -> String {
+    let missing_share: f64 = missing.iter().map(|validator| validator.share).sum();
+
+    let mut out = format!(
+        "{} validators have not voted on proposal {} (combined voting power: {:.2}%)\n",
+        missing.len(),
+        proposal_id,
+        missing_share,
+    );
+    if missing.is_empty() {
+        return out;
+    }
+
+    let listed = &missing[..top.min(missing.len())];
+    out.push_str(&format!(
+        "\nTop {} by voting power (combined: {:.2}%):\n",
+        listed.len(),
+        listed.iter().map(|validator| validator.share).sum::<f64>(),
+    ));
+    for (i, validator) in listed.iter().enumerate() {
+        let contact = [&validator.security_contact, &validator.website]
+            .into_iter()
+            .find(|contact| !contact.is_empty())
+            .map_or("no contact".to_string(), |contact| contact.to_string());
+
+        out.push_str(&format!(
+            "{:>3}. {} ({:.2}%) - {}\n",
+            i + 1,
+            validator.moniker,
+            validator.share,
+            contact,
+        ));
+    }
+
+    out
+}
+
+/// Returns the account address of the validator with the given operator address,
+/// which is used to cast the votes of the validator.
+fn get_account_address(operator_address: &str, prefix: &str) -> Result<String, ValidatorsError> {
+    let (_, data, variant) = bech32::decode(operator_address)?;
+
+    Ok(bech32::encode(prefix, data, variant)?)
+}
+
+/// Escapes the given field for the use in a CSV file, which is necessary because
+/// the on-chain descriptions can contain arbitrary text.
+pub fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bech32::{ToBase32, Variant};
+    use serde_json::json;
+    use std::str::FromStr;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_get_validators() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{}", VALIDATORS_ENDPOINT)))
+            .and(query_param("status", "BOND_STATUS_BONDED"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "validators": [
+                    {
+                        "operator_address": "evmosvaloper1small",
+                        "tokens": "250",
+                        "description": {
+                            "moniker": "Small, Validator",
+                            "identity": "",
+                            "website": "",
+                            "security_contact": "",
+                            "details": ""
+                        }
+                    },
+                    {
+                        "operator_address": "evmosvaloper1large",
+                        "tokens": "750",
+                        "description": {
+                            "moniker": "Large",
+                            "website": "https://large.example.com",
+                            "security_contact": "security@large.example.com"
+                        }
+                    }
+                ],
+                "pagination": {"next_key": null, "total": "2"}
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let base_url = Url::from_str(&mock_server.uri()).expect("failed to parse mock server uri");
+        let validators = get_validators(&base_url)
+            .await
+            .expect("failed to get validators");
+
+        assert_eq!(validators.len(), 2, "expected two validators");
+        assert_eq!(
+            validators[0].moniker, "Large",
+            "expected sorting by voting power"
+        );
+        assert_eq!(validators[0].share, 75.0, "expected a different share");
+        assert_eq!(validators[1].share, 25.0, "expected a different share");
+
+        let csv = to_csv(&validators);
+        assert_eq!(
+            csv,
+            format!(
+                "{}\n{}\n{}\n",
+                CSV_HEADER,
+                "Large,evmosvaloper1large,750,75.0000,https://large.example.com,security@large.example.com",
+                "\"Small, Validator\",evmosvaloper1small,250,25.0000,,",
+            ),
+            "expected different CSV output"
+        );
+    }
+
+    /// Returns a validator with the given moniker, operator address and voting power share.
+    fn get_validator(moniker: &str, operator_address: &str, share: f64) -> Validator {
+        Validator {
+            moniker: moniker.to_string(),
+            operator_address: operator_address.to_string(),
+            voting_power: 0,
+            share,
+            website: "".to_string(),
+            security_contact: format!("security@{}.example.com", moniker),
+        }
+    }
+
+    #[test]
+    fn test_get_account_address() {
+        // NOTE: the operator address of a validator has the same bytes as its account address
+        let operator_address =
+            bech32::encode("evmosvaloper", [0u8; 20].to_base32(), Variant::Bech32).unwrap();
+        let account_address =
+            bech32::encode("evmos", [0u8; 20].to_base32(), Variant::Bech32).unwrap();
+
+        assert_eq!(
+            get_account_address(&operator_address, "evmos").expect("failed to convert address"),
+            account_address,
+        );
+        assert!(get_account_address("invalid", "evmos").is_err());
+    }
+
+    #[test]
+    fn test_get_missing_voters_and_render() {
+        let operator_addresses: Vec<String> = (1u8..=3)
+            .map(|i| bech32::encode("evmosvaloper", [i; 20].to_base32(), Variant::Bech32).unwrap())
+            .collect();
+        let validators = vec![
+            get_validator("alpha", &operator_addresses[0], 50.0),
+            get_validator("beta", &operator_addresses[1], 30.0),
+            get_validator("gamma", &operator_addresses[2], 20.0),
+        ];
+        let voters = vec![bech32::encode("evmos", [2u8; 20].to_base32(), Variant::Bech32).unwrap()];
+
+        let missing = get_missing_voters(&validators, &voters, "evmos")
+            .expect("failed to get missing voters");
+        assert_eq!(
+            missing
+                .iter()
+                .map(|v| v.moniker.as_str())
+                .collect::<Vec<_>>(),
+            vec!["alpha", "gamma"],
+            "expected the validators without votes"
+        );
+
+        assert_eq!(
+            render_nag_list(252, &missing, 1),
+            "2 validators have not voted on proposal 252 (combined voting power: 70.00%)\n\n\
+             Top 1 by voting power (combined: 50.00%):\n  \
+             1. alpha (50.00%) - security@alpha.example.com\n",
+            "expected a different nag list"
+        );
+    }
+
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("plain"), "plain");
+        assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
@@ -1,5 +1,126 @@
+use crate::errors::ValidationError;
 use crate::network::Network;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Contains the optional checks of the version bumps, which are enforced when generating a proposal.
+/// All checks are disabled per default.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VersionPolicy {
+    /// Testnet upgrades must target a release candidate of the next patch, minor or major version
+    /// after the previous version, or a later release candidate of the same version.
+    #[serde(default)]
+    pub testnet_rc_next_minor: bool,
+    /// Patch releases must not change the major or minor version of the previous version.
+    #[serde(default)]
+    pub patch_keeps_minor: bool,
+    /// Mainnet target versions must have at least one release candidate released before.
+    #[serde(default)]
+    pub mainnet_requires_rc: bool,
+}
+
+/// Represents the parts of a version in the format `vX.Y.Z(-rcN)`.
+///
+/// Versions are ordered by their major, minor and patch versions and the release candidate,
+/// where a release is ordered after all of its release candidates.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub rc: Option<u64>,
+}
+
+impl Version {
+    /// Returns the key, by which the versions are ordered.
+    fn sort_key(&self) -> (u64, u64, u64, bool, u64) {
+        (
+            self.major,
+            self.minor,
+            self.patch,
+            self.rc.is_none(),
+            self.rc.unwrap_or_default(),
+        )
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Parses the given version in the format `vX.Y.Z(-rcN)`.
+pub fn parse_version(version: &str) -> Option<Version> {
+    let captures = Regex::new(r"^v(\d+)\.(\d+)\.(\d+)(?:-rc(\d+))?$")
+        .unwrap()
+        .captures(version)?;
+
+    Some(Version {
+        major: captures[1].parse().ok()?,
+        minor: captures[2].parse().ok()?,
+        patch: captures[3].parse().ok()?,
+        rc: captures.get(4).and_then(|rc| rc.as_str().parse().ok()),
+    })
+}
+
+/// Checks the bump from the given previous version to the given target version on the given network
+/// against the given policy. The check for released release candidates is not covered here,
+/// because it requires querying the repository.
+pub fn check_version_policy(
+    policy: &VersionPolicy,
+    network: Network,
+    previous_version: &str,
+    target_version: &str,
+) -> Result<(), ValidationError> {
+    let (Some(previous), Some(target)) = (
+        parse_version(previous_version),
+        parse_version(target_version),
+    ) else {
+        return Ok(());
+    };
+    let violation = |reason: &str| {
+        Err(ValidationError::VersionPolicy(
+            previous_version.to_string(),
+            target_version.to_string(),
+            reason.to_string(),
+        ))
+    };
+
+    if policy.patch_keeps_minor
+        && target.patch > 0
+        && (target.major, target.minor) != (previous.major, previous.minor)
+    {
+        return violation("patch releases cannot change the major or minor version");
+    }
+
+    if policy.testnet_rc_next_minor && network == Network::Testnet {
+        // NOTE: a later release candidate of the same version is allowed, but not an earlier one
+        let same_version = (target.major, target.minor, target.patch)
+            == (previous.major, previous.minor, previous.patch)
+            && previous.rc.is_some()
+            && target > previous;
+        let next_patch = (target.major, target.minor, target.patch)
+            == (previous.major, previous.minor, previous.patch + 1)
+            && previous.rc.is_none();
+        let next_version = target.patch == 0
+            && ((target.major, target.minor) == (previous.major, previous.minor + 1)
+                || (target.major, target.minor) == (previous.major + 1, 0));
+        if target.rc.is_none() || !(same_version || next_patch || next_version) {
+            return violation(
+                "testnet release candidates must target the next patch, minor or major version",
+            );
+        }
+    }
+
+    Ok(())
+}
 
 /// Returns a boolean value if the defined version fulfills the semantic
 /// versioning requirements.
@@ -28,18 +149,85 @@ mod tests {
     use super::*;
     use crate::network;
 
+    #[test]
+    fn test_check_version_policy() {
+        let policy = VersionPolicy {
+            testnet_rc_next_minor: true,
+            patch_keeps_minor: true,
+            mainnet_requires_rc: false,
+        };
+
+        assert!(check_version_policy(&policy, Network::Testnet, "v15.0.0", "v16.0.0-rc1").is_ok());
+        assert!(check_version_policy(&policy, Network::Testnet, "v15.0.0", "v15.1.0-rc1").is_ok());
+        assert!(
+            check_version_policy(&policy, Network::Testnet, "v16.0.0-rc1", "v16.0.0-rc2").is_ok()
+        );
+        assert!(
+            check_version_policy(&policy, Network::Testnet, "v16.0.0", "v16.0.1-rc1").is_ok(),
+            "expected release candidate of the next patch version to be accepted"
+        );
+        assert!(
+            check_version_policy(&policy, Network::Testnet, "v16.0.0-rc2", "v16.0.0-rc1").is_err(),
+            "expected earlier release candidate to be rejected"
+        );
+        assert!(
+            check_version_policy(&policy, Network::Testnet, "v16.0.0-rc1", "v16.0.0-rc1").is_err(),
+            "expected same release candidate to be rejected"
+        );
+        assert!(
+            check_version_policy(&policy, Network::Testnet, "v16.0.0", "v16.0.2-rc1").is_err(),
+            "expected skipped patch version to be rejected"
+        );
+        assert!(
+            check_version_policy(&policy, Network::Testnet, "v15.0.0", "v17.0.0-rc1").is_err(),
+            "expected skipped major version to be rejected"
+        );
+        assert!(
+            check_version_policy(&policy, Network::Testnet, "v15.0.0", "v16.0.0").is_err(),
+            "expected testnet target without release candidate to be rejected"
+        );
+
+        assert!(check_version_policy(&policy, Network::Mainnet, "v15.0.0", "v15.0.1").is_ok());
+        assert!(
+            check_version_policy(&policy, Network::Mainnet, "v15.0.0", "v16.0.1").is_err(),
+            "expected patch release changing the major version to be rejected"
+        );
+        assert!(check_version_policy(
+            &VersionPolicy::default(),
+            Network::Testnet,
+            "v15.0.0",
+            "v17.0.1"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_version_order() {
+        let version = |version: &str| parse_version(version).unwrap();
+
+        assert!(version("v16.0.0-rc2") > version("v16.0.0-rc1"));
+        assert!(version("v16.0.0") > version("v16.0.0-rc10"));
+        assert!(version("v16.0.1-rc1") > version("v16.0.0"));
+        assert!(version("v16.1.0-rc1") > version("v16.0.9"));
+        assert!(version("v17.0.0-rc1") > version("v16.10.0"));
+        assert_eq!(version("v16.0.0-rc1"), version("v16.0.0-rc1"));
+    }
+
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_is_valid_version_pass() {
         assert_eq!(is_valid_version("v14.0.0"), true);
         assert_eq!(is_valid_version("v14.0.0-rc1"), true);
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_is_valid_version_fail() {
         assert_eq!(is_valid_version("v14.0."), false);
         assert_eq!(is_valid_version("v.0.1"), false);
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_is_valid_target_version_local_node_pass() {
         assert_eq!(
@@ -48,6 +236,7 @@ mod tests {
         );
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_is_valid_target_version_local_node_fail() {
         assert_eq!(
@@ -56,6 +245,7 @@ mod tests {
         );
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_is_valid_target_version_testnet_pass() {
         assert_eq!(
@@ -64,6 +254,7 @@ mod tests {
         );
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_is_valid_target_version_testnet_fail() {
         assert_eq!(
@@ -72,6 +263,7 @@ mod tests {
         );
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_is_valid_target_version_mainnet_pass() {
         assert_eq!(
@@ -80,6 +272,7 @@ mod tests {
         );
     }
 
+    #[allow(clippy::bool_assert_comparison)]
     #[test]
     fn test_is_valid_target_version_mainnet_fail() {
         assert_eq!(
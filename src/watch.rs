@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
+
+/// The interval in which the watched files are checked for modifications.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watches a set of files for modifications by polling their modification times.
+pub struct FileWatcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl FileWatcher {
+    /// Creates a new watcher for the given files.
+    /// Files that don't exist yet are watched for their creation.
+    pub fn new(paths: Vec<PathBuf>) -> FileWatcher {
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let modified = get_modified(&path);
+                (path, modified)
+            })
+            .collect();
+
+        FileWatcher { files }
+    }
+
+    /// Waits until one of the watched files is modified and returns its path.
+    pub async fn wait_for_change(&mut self) -> io::Result<PathBuf> {
+        loop {
+            if let Some(path) = self.check_for_change() {
+                return Ok(path);
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Checks if any of the watched files was modified since the last check
+    /// and returns the path of the first modified file.
+    fn check_for_change(&mut self) -> Option<PathBuf> {
+        let mut changed = None;
+        for (path, last_modified) in self.files.iter_mut() {
+            let modified = get_modified(path);
+            if modified != *last_modified {
+                *last_modified = modified;
+                changed.get_or_insert(path.clone());
+            }
+        }
+
+        changed
+    }
+}
+
+/// Returns the modification time of the given file or None if it does not exist.
+fn get_modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_dir::{DirBuilder, FileType::EmptyFile, TestDir};
+
+    #[test]
+    fn test_check_for_change() {
+        let test_dir = TestDir::temp()
+            .create("config.json", EmptyFile)
+            .create("template.hbs", EmptyFile);
+        let config = test_dir.path("config.json");
+        let template = test_dir.path("template.hbs");
+
+        let mut watcher = FileWatcher::new(vec![config.clone(), template.clone()]);
+        assert_eq!(watcher.check_for_change(), None, "expected no change");
+
+        // NOTE: the modification time is set explicitly because the file system resolution
+        // might not register a change within the runtime of the test.
+        let file = fs::File::options()
+            .write(true)
+            .open(&template)
+            .expect("failed to open template");
+        file.set_modified(SystemTime::now() + Duration::from_secs(10))
+            .expect("failed to set modification time");
+
+        assert_eq!(watcher.check_for_change(), Some(template));
+        assert_eq!(
+            watcher.check_for_change(),
+            None,
+            "expected no further change"
+        );
+    }
+
+    #[test]
+    fn test_check_for_change_created_file() {
+        let test_dir = TestDir::temp();
+        let config = test_dir.path("config.json");
+
+        let mut watcher = FileWatcher::new(vec![config.clone()]);
+        assert_eq!(watcher.check_for_change(), None, "expected no change");
+
+        fs::write(&config, "{}").expect("failed to write config");
+        assert_eq!(watcher.check_for_change(), Some(config));
+    }
+}